@@ -111,7 +111,7 @@ export const TitleBar = memo(function TitleBar({
 
       <span className="text-sm font-medium text-[var(--text-secondary)] ml-1 select-none" style={dragRegion}>
         {showInstanceDetails && selectedInstance
-          ? `Instances / ${selectedInstance.name}`
+          ? `Instances / ${selectedInstance.display_name ?? selectedInstance.name}`
           : activeTab === "browse"
           ? `Addons / ${browseSubTabLabels[browseSubTab]}`
           : tabLabels[activeTab]}
@@ -151,6 +151,8 @@ export function CurseforgeModsTab({ selectedInstance, instances, onSetSelectedIn
         downloadUrl: file.downloadUrl,
         filename: file.fileName,
         targetFolder: "mods",
+        modId: file.modId,
+        fileId: file.id,
       })
       setInstalledFiles(prev => new Set(prev).add(file.fileName))
     } catch (error) {
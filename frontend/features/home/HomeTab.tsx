@@ -251,7 +251,7 @@ export function HomeTab({
                   {tooltipInstance === instance && (
                     <div className="absolute bottom-full left-0 right-0 mb-2 z-50 pointer-events-none">
                       <div className="bg-[var(--bg-secondary)] rounded-md p-3 mx-2 border border-[var(--border-default)]">
-                        <div className="text-sm font-medium text-[var(--text-primary)] mb-1.5">{instance.name}</div>
+                        <div className="text-sm font-medium text-[var(--text-primary)] mb-1.5">{instance.display_name ?? instance.name}</div>
                         <div className="space-y-1 text-xs text-[var(--text-muted)]">
                           <div className="flex justify-between gap-4">
                             <span>Version</span>
@@ -287,7 +287,7 @@ export function HomeTab({
                     )}
                   </div>
                   <div className={`flex-1 min-w-0 ${isRunning || isLaunching ? 'pr-12' : 'group-hover:pr-12'}`}>
-                    <div className="text-sm font-medium text-[var(--text-primary)] truncate leading-tight">{instance.name}</div>
+                    <div className="text-sm font-medium text-[var(--text-primary)] truncate leading-tight">{instance.display_name ?? instance.name}</div>
                     <div className="text-xs text-[var(--text-muted)] truncate leading-tight mt-0.5">
                       {instance.last_played && formatDate(instance.last_played)}
                     </div>
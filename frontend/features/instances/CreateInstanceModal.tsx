@@ -3,7 +3,7 @@ import { invoke } from "@tauri-apps/api/core"
 import { open } from '@tauri-apps/plugin-dialog'
 import { X, Loader2, AlertCircle, FileDown, Check } from "lucide-react"
 import { AlertModal } from "../../components/ui/ConfirmModal"
-import type { FabricVersion, NeoForgeVersion, ForgeVersion, Instance } from "../../types"
+import type { FabricVersion, NeoForgeVersion, ForgeVersion, Instance, PackShareInstallSummary } from "../../types"
 
 interface MinecraftVersion {
   id: string
@@ -11,6 +11,8 @@ interface MinecraftVersion {
   url: string
   time: string
   releaseTime: string
+  is_latest_release: boolean
+  is_latest_snapshot: boolean
 }
 
 interface CreateInstanceModalProps {
@@ -270,7 +272,7 @@ export function CreateInstanceModal({ versions, instances, onClose, onSuccess, o
     try {
       const selected = await open({
         multiple: false,
-        filters: [{ name: "Instance Files", extensions: ['mrpack', 'zip'] }]
+        filters: [{ name: "Instance Files", extensions: ['mrpack', 'zip', 'json'] }]
       })
 
       if (!selected) return
@@ -279,6 +281,11 @@ export function CreateInstanceModal({ versions, instances, onClose, onSuccess, o
       
       handleClose()
 
+      if (filePath.toLowerCase().endsWith(".json")) {
+        await importShareFile(filePath)
+        return
+      }
+
       let extractedName = ""
       try {
         extractedName = await invoke<string>("get_modpack_name_from_file", { filePath })
@@ -304,6 +311,31 @@ export function CreateInstanceModal({ versions, instances, onClose, onSuccess, o
     }
   }
 
+  const importShareFile = async (filePath: string) => {
+    const sharedName = filePath.split(/[/\\]/).pop()?.replace(/(\.share)?\.json$/i, '') || "Shared Instance"
+
+    let finalName = sharedName
+    let counter = 1
+    while (instances.some(i => i.name.toLowerCase() === finalName.toLowerCase())) {
+      finalName = `${sharedName} (${counter++})`
+    }
+
+    setIsCreating(true)
+    onStartCreating(finalName)
+    const summary = await invoke<PackShareInstallSummary>("install_from_share", { manifestPath: filePath, instanceName: finalName })
+    onSuccess()
+
+    const missing = [...summary.failed, ...summary.unlisted_mods]
+    if (missing.length > 0) {
+      setAlertModal({
+        isOpen: true,
+        title: "Some mods are missing",
+        message: `Installed ${summary.installed} mod(s). Add these by hand: ${missing.join(", ")}`,
+        type: "warning"
+      })
+    }
+  }
+
   const handleCreateInstance = async () => {
     if (!newInstanceName.trim() || instanceExists) return
 
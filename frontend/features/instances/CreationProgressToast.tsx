@@ -1,6 +1,8 @@
 import { useState, useEffect, useRef } from "react"
 import { Loader2, CheckCircle, XCircle } from "lucide-react"
 import { listen } from "@tauri-apps/api/event"
+import { invoke } from "@tauri-apps/api/core"
+import type { ProgressEvent } from "../../types"
 
 interface CreationProgressToastProps {
   instanceName: string
@@ -8,13 +10,6 @@ interface CreationProgressToastProps {
   onDismiss: () => void
 }
 
-interface ProgressPayload {
-  instance: string
-  progress: number
-  stage?: string
-  current_file?: string
-}
-
 export function CreationProgressToast({
   instanceName,
   onError,
@@ -45,25 +40,33 @@ export function CreationProgressToast({
   useEffect(() => {
     const setupListeners = async () => {
       try {
-        const handleProgress = (e: { payload: ProgressPayload }) => {
-          if (e.payload.instance !== instanceName) return
+        const trackedKinds: ProgressEvent["kind"][] = ["duplicate", "create_instance", "modpack_install"]
+        // Events name the instance folder, which can differ from the name that was typed
+        const folderName = await invoke<string>("get_instance_folder_name", { name: instanceName }).catch(() => instanceName)
+
+        const handleProgress = (e: { payload: ProgressEvent }) => {
+          if (e.payload.instance !== folderName || !trackedKinds.includes(e.payload.kind)) return
+          if (isCompletingRef.current) return
           setHasReceivedProgress(true)
           lastProgressTimeRef.current = Date.now()
-          setProgress(e.payload.progress)
+          if (e.payload.error) {
+            isCompletingRef.current = true
+            setStatus("error")
+            setStage(e.payload.error)
+            onError()
+            return
+          }
+          if (e.payload.percent !== null) setProgress(e.payload.percent)
           if (e.payload.stage) setStage(e.payload.stage)
-          if (e.payload.progress >= 100 && !isCompletingRef.current) {
+          if ((e.payload.percent ?? 0) >= 100) {
             isCompletingRef.current = true
             setStatus("success")
           }
         }
 
-        const [u1, u2, u3] = await Promise.all([
-          listen<ProgressPayload>("duplication-progress", handleProgress),
-          listen<ProgressPayload>("creation-progress", handleProgress),
-          listen<ProgressPayload>("modpack-install-progress", handleProgress),
-        ])
+        const unlisten = await listen<ProgressEvent>("operation-progress", handleProgress)
 
-        unlistenFunctionsRef.current = [u1, u2, u3]
+        unlistenFunctionsRef.current = [unlisten]
 
         const watchdog = setInterval(() => {
           if (!isCompletingRef.current && Date.now() - lastProgressTimeRef.current > 60000) {
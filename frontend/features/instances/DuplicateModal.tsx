@@ -0,0 +1,127 @@
+import { useState, useEffect } from "react"
+import { X, Copy } from "lucide-react"
+import type { DuplicateOptions, DuplicateSkip } from "../../types"
+
+interface DuplicateModalProps {
+  instanceName: string
+  onDuplicate: (options: DuplicateOptions) => void
+  onClose: () => void
+}
+
+const SKIP_OPTIONS: { key: keyof DuplicateSkip; label: string; description: string }[] = [
+  { key: "saves", label: "Worlds", description: "Copy the saves folder" },
+  { key: "logs", label: "Logs", description: "Copy game logs" },
+  { key: "screenshots", label: "Screenshots", description: "Copy taken screenshots" },
+  { key: "crash_reports", label: "Crash Reports", description: "Copy crash reports" },
+]
+
+export function DuplicateModal({ instanceName, onDuplicate, onClose }: DuplicateModalProps) {
+  const [isClosing, setIsClosing] = useState(false)
+  const [skip, setSkip] = useState<DuplicateSkip>({ saves: false, logs: true, screenshots: true, crash_reports: true })
+  const [linked, setLinked] = useState(false)
+
+  useEffect(() => {
+    const main = document.querySelector('main')
+    if (main) {
+      const prev = main.style.overflowY
+      main.style.overflowY = 'hidden'
+      return () => { main.style.overflowY = prev }
+    }
+  }, [])
+
+  const handleClose = () => {
+    setIsClosing(true)
+    setTimeout(() => {
+      setIsClosing(false)
+      onClose()
+    }, 150)
+  }
+
+  const handleDuplicate = () => {
+    onDuplicate({ linked, skip })
+    handleClose()
+  }
+
+  const checkboxClass = "w-4 h-4 rounded border-2 border-gray-500 bg-transparent checked:bg-[var(--accent-primary)] checked:border-[var(--accent-primary)] focus:ring-2 focus:ring-[var(--accent-primary)] focus:ring-offset-0 transition-all cursor-pointer flex-shrink-0"
+
+  return (
+    <div
+      className={`fixed inset-0 bg-black/60 backdrop-blur-sm flex items-center justify-center z-50 p-4 modal-backdrop ${isClosing ? 'closing' : ''}`}
+      onClick={handleClose}
+    >
+      <div
+        className={`blur-border bg-[var(--bg-secondary)] rounded w-full max-w-md shadow-2xl modal-content ${isClosing ? 'closing' : ''}`}
+        onClick={(e) => e.stopPropagation()}
+        style={{ pointerEvents: 'auto' }}
+      >
+        <div className="flex items-center justify-between px-6 pt-6 pb-5">
+          <div>
+            <h2 className="text-xl font-semibold text-[var(--text-primary)] tracking-tight">Duplicate Instance</h2>
+            <p className="text-sm text-[var(--text-muted)] mt-0.5">{instanceName}</p>
+          </div>
+          <button
+            onClick={handleClose}
+            className="p-1.5 hover:bg-[var(--bg-hover-strong)] rounded transition-colors text-[var(--text-muted)] hover:text-[var(--text-primary)] cursor-pointer"
+          >
+            <X size={18} strokeWidth={2} />
+          </button>
+        </div>
+
+        <div className="px-6 pb-4 space-y-4">
+          <div>
+            <label className="block text-sm font-medium text-[var(--text-primary)] mb-2.5">Include</label>
+            <div className="bg-[var(--bg-tertiary)] rounded p-4 space-y-3">
+              {SKIP_OPTIONS.map(({ key, label, description }) => (
+                <label key={key} className="flex items-center gap-3 cursor-pointer group">
+                  <input
+                    type="checkbox"
+                    checked={!skip[key]}
+                    onChange={(e) => setSkip({ ...skip, [key]: !e.target.checked })}
+                    className={checkboxClass}
+                  />
+                  <div className="flex-1">
+                    <span className="text-sm font-medium text-[var(--text-primary)]">{label}</span>
+                    <p className="text-xs text-[var(--text-muted)] mt-0.5">{description}</p>
+                  </div>
+                </label>
+              ))}
+            </div>
+          </div>
+
+          <div className="bg-[var(--bg-tertiary)] rounded p-4">
+            <label className="flex items-center gap-3 cursor-pointer group">
+              <input
+                type="checkbox"
+                checked={linked}
+                onChange={(e) => setLinked(e.target.checked)}
+                className={checkboxClass}
+              />
+              <div className="flex-1">
+                <span className="text-sm font-medium text-[var(--text-primary)]">Share Mods and Packs</span>
+                <p className="text-xs text-[var(--text-muted)] mt-0.5">
+                  Hardlink mods, resource packs and shader packs instead of copying them. Much faster and uses no extra disk space
+                </p>
+              </div>
+            </label>
+          </div>
+        </div>
+
+        <div className="flex items-center justify-end gap-3 px-6 pb-6 pt-3">
+          <button
+            onClick={handleClose}
+            className="px-5 py-3 bg-[var(--bg-tertiary)] hover:bg-[var(--bg-hover-strong)] text-[var(--text-primary)] rounded font-medium text-sm transition-colors cursor-pointer"
+          >
+            Cancel
+          </button>
+          <button
+            onClick={handleDuplicate}
+            className="px-5 py-3 bg-[var(--accent-primary)] hover:bg-[var(--accent-hover)] text-white rounded font-medium text-sm flex items-center gap-2 transition-colors cursor-pointer"
+          >
+            <Copy size={16} strokeWidth={2} />
+            <span>Duplicate</span>
+          </button>
+        </div>
+      </div>
+    </div>
+  )
+}
@@ -1,8 +1,9 @@
 import { useState, useEffect } from "react"
 import { invoke } from "@tauri-apps/api/core"
-import { save } from '@tauri-apps/plugin-dialog'
+import { open, save } from '@tauri-apps/plugin-dialog'
 import { X, Download } from "lucide-react"
 import { AlertModal } from "../../components/ui/ConfirmModal"
+import type { PackShare } from "../../types"
 
 interface ExportModalProps {
   instanceName: string
@@ -21,7 +22,7 @@ export function ExportModal({ instanceName, onClose }: ExportModalProps) {
       return () => { main.style.overflowY = prev }
     }
   }, [])
-  const [exportFormat, setExportFormat] = useState<'zip' | 'mrpack'>('mrpack')
+  const [exportFormat, setExportFormat] = useState<'zip' | 'mrpack' | 'folder' | 'share'>('mrpack')
   const [includeWorlds, setIncludeWorlds] = useState(true)
   const [includeResourcePacks, setIncludeResourcePacks] = useState(true)
   const [includeShaderPacks, setIncludeShaderPacks] = useState(true)
@@ -43,6 +44,15 @@ export function ExportModal({ instanceName, onClose }: ExportModalProps) {
   }
 
   const handleExport = async () => {
+    if (exportFormat === 'folder') {
+      await handleFolderExport();
+      return;
+    }
+    if (exportFormat === 'share') {
+      await handleShareExport();
+      return;
+    }
+
     try {
       const defaultExtension = exportFormat === 'mrpack' ? 'mrpack' : 'zip';
       const defaultFileName = `${instanceName}.${defaultExtension}`;
@@ -93,6 +103,85 @@ export function ExportModal({ instanceName, onClose }: ExportModalProps) {
     }
   };
 
+  const handleShareExport = async () => {
+    try {
+      const savePath = await save({
+        defaultPath: `${instanceName}.share.json`,
+        filters: [{ name: 'Pack Share', extensions: ['json'] }]
+      });
+
+      if (!savePath) return;
+
+      setIsExporting(true);
+
+      const share = await invoke<PackShare>("generate_pack_share", {
+        instanceName: instanceName,
+        outputPath: savePath,
+      });
+
+      const unlisted = share.unlisted_mods.length > 0
+        ? ` ${share.unlisted_mods.length} mod(s) not installed from Modrinth have to be added by hand: ${share.unlisted_mods.join(", ")}`
+        : "";
+
+      setAlertModal({
+        isOpen: true,
+        title: "Success",
+        message: `Share file with ${share.mods.length} mod(s) saved to ${savePath}.${unlisted}`,
+        type: share.unlisted_mods.length > 0 ? "warning" : "success"
+      });
+    } catch (error) {
+      console.error("Export error:", error);
+      setAlertModal({
+        isOpen: true,
+        title: "Error",
+        message: `Failed to create share file: ${error}`,
+        type: "danger"
+      });
+    } finally {
+      setIsExporting(false);
+    }
+  };
+
+  const handleFolderExport = async () => {
+    try {
+      const destination = await open({
+        directory: true,
+        multiple: false,
+        title: "Choose an empty folder",
+      });
+
+      if (!destination || Array.isArray(destination)) return;
+
+      setIsExporting(true);
+
+      const exportedTo = await invoke<string>("export_instance_plain", {
+        instanceName: instanceName,
+        destination: destination,
+      });
+
+      setAlertModal({
+        isOpen: true,
+        title: "Success",
+        message: `Instance exported successfully to ${exportedTo}`,
+        type: "success"
+      });
+
+      setTimeout(() => {
+        handleClose();
+      }, 1500);
+    } catch (error) {
+      console.error("Export error:", error);
+      setAlertModal({
+        isOpen: true,
+        title: "Error",
+        message: `Failed to export instance: ${error}`,
+        type: "danger"
+      });
+    } finally {
+      setIsExporting(false);
+    }
+  };
+
   return (
     <>
       <div 
@@ -156,9 +245,46 @@ export function ExportModal({ instanceName, onClose }: ExportModalProps) {
                     </p>
                   </div>
                 </label>
+
+                <label className="flex items-center gap-3 cursor-pointer group">
+                  <input
+                    type="radio"
+                    checked={exportFormat === 'folder'}
+                    onChange={() => setExportFormat('folder')}
+                    disabled={isExporting}
+                    className="w-4 h-4 text-[var(--accent-primary)] border-gray-500 focus:ring-[var(--accent-primary)] cursor-pointer disabled:cursor-not-allowed flex-shrink-0"
+                  />
+                  <div className="flex-1">
+                    <span className="text-sm font-medium text-[var(--text-primary)] group-hover:text-[var(--text-primary)] transition-colors">
+                      Plain Folder (.minecraft)
+                    </span>
+                    <p className="text-xs text-[var(--text-muted)] mt-0.5">
+                      Game files only, for server hosts and other launchers
+                    </p>
+                  </div>
+                </label>
+
+                <label className="flex items-center gap-3 cursor-pointer group">
+                  <input
+                    type="radio"
+                    checked={exportFormat === 'share'}
+                    onChange={() => setExportFormat('share')}
+                    disabled={isExporting}
+                    className="w-4 h-4 text-[var(--accent-primary)] border-gray-500 focus:ring-[var(--accent-primary)] cursor-pointer disabled:cursor-not-allowed flex-shrink-0"
+                  />
+                  <div className="flex-1">
+                    <span className="text-sm font-medium text-[var(--text-primary)] group-hover:text-[var(--text-primary)] transition-colors">
+                      Share File (.json)
+                    </span>
+                    <p className="text-xs text-[var(--text-muted)] mt-0.5">
+                      Small list of Modrinth mods friends can install from
+                    </p>
+                  </div>
+                </label>
               </div>
             </div>
 
+            {(exportFormat === 'zip' || exportFormat === 'mrpack') && (
             <div>
               <label className="block text-sm font-medium text-[var(--text-primary)] mb-2.5">Include in Export</label>
               <div className="bg-[var(--bg-tertiary)] rounded p-4 space-y-3">
@@ -253,6 +379,7 @@ export function ExportModal({ instanceName, onClose }: ExportModalProps) {
                 </label>
               </div>
             </div>
+            )}
 
           </div>
 
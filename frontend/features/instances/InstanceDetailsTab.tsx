@@ -1,9 +1,9 @@
 import { useState, useEffect, useMemo } from "react"
-import { Play, FolderOpen, Package, Loader2, ExternalLink, Globe, Settings, Trash2, RefreshCw, Search, X, Image, Palette } from "lucide-react"
+import { Play, FolderOpen, Package, Loader2, ExternalLink, Globe, Settings, Trash2, RefreshCw, Search, X, Image, Palette, Pin, Lock, Zap } from "lucide-react"
 import { invoke } from "@tauri-apps/api/core"
 import { ConfirmModal, AlertModal } from "../../components/ui/ConfirmModal"
 import { InstanceSettingsModal } from "./InstanceSettingsModal"
-import type { Instance, ModFileWithMetadata, ModrinthVersion, ModrinthFile } from "../../types"
+import type { Instance, InstanceWarmup, ModFileWithMetadata, ModrinthVersion, ModrinthFile } from "../../types"
 
 type InstalledMod = ModFileWithMetadata
 
@@ -59,6 +59,7 @@ export function InstanceDetailsTab({
   const [isLoadingMods, setIsLoadingMods] = useState(true)
   const [isLoadingWorlds, setIsLoadingWorlds] = useState(true)
   const [instanceIcon, setInstanceIcon] = useState<string | null>(null)
+  const [coverImage, setCoverImage] = useState<string | null>(null)
   const [isSettingsOpen, setIsSettingsOpen] = useState(false)
   const [availableUpdates, setAvailableUpdates] = useState<ModUpdate[]>([])
   const [isCheckingUpdates, setIsCheckingUpdates] = useState(false)
@@ -66,7 +67,9 @@ export function InstanceDetailsTab({
   const [modSearchQuery, setModSearchQuery] = useState("")
   const [worldSearchQuery, setWorldSearchQuery] = useState("")
   const [launchingWorld, setLaunchingWorld] = useState<string | null>(null)
+  const [isWarming, setIsWarming] = useState(false)
   const [resourcePacks, setResourcePacks] = useState<ModFileWithMetadata[]>([])
+  const [enforcedPacks, setEnforcedPacks] = useState<string[]>(instance.enforced_resource_packs ?? [])
   const [shaderPacks, setShaderPacks] = useState<ModFileWithMetadata[]>([])
   const [isLoadingResourcePacks, setIsLoadingResourcePacks] = useState(true)
   const [isLoadingShaderPacks, setIsLoadingShaderPacks] = useState(true)
@@ -107,6 +110,12 @@ export function InstanceDetailsTab({
       console.error("Failed to load instance icon:", error)
       setInstanceIcon(null)
     }
+    try {
+      setCoverImage(instance.cover_image ? await invoke<string | null>("get_instance_cover", { instanceName: instance.name }) : null)
+    } catch (error) {
+      console.error("Failed to load cover image:", error)
+      setCoverImage(null)
+    }
   }
 
   const loadWorlds = async () => {
@@ -210,15 +219,18 @@ export function InstanceDetailsTab({
     }
   }
 
+  const pinnedFilenames = new Set(installedMods.filter(m => m.pinned).map(m => m.filename))
+  const bulkUpdates = availableUpdates.filter(u => !pinnedFilenames.has(u.filename))
+
   const updateAllMods = async () => {
-    if (availableUpdates.length === 0) return
+    if (bulkUpdates.length === 0) return
 
     setIsUpdatingMods(true)
 
-    for (const update of availableUpdates) {
+    for (const update of bulkUpdates) {
       try {
-        await invoke("download_mod", { instanceName: instance.name, downloadUrl: update.latestVersion.downloadUrl, filename: update.latestVersion.filename })
-        if (update.filename !== update.latestVersion.filename) {
+        const replaced = await invoke<string[]>("download_mod", { instanceName: instance.name, downloadUrl: update.latestVersion.downloadUrl, filename: update.latestVersion.filename, projectId: update.projectId, versionId: update.latestVersion.id, allowServerOnly: true })
+        if (update.filename !== update.latestVersion.filename && !replaced.includes(update.filename)) {
           await invoke("delete_mod", { instanceName: instance.name, filename: update.filename }).catch(err =>
             console.error(`Failed to delete old version ${update.filename}:`, err)
           )
@@ -229,15 +241,15 @@ export function InstanceDetailsTab({
     }
 
     setIsUpdatingMods(false)
-    setAvailableUpdates([])
+    setAvailableUpdates(prev => prev.filter(u => pinnedFilenames.has(u.filename)))
 
     await loadInstalledMods()
   }
 
   const updateSingleMod = async (update: ModUpdate) => {
     try {
-      await invoke("download_mod", { instanceName: instance.name, downloadUrl: update.latestVersion.downloadUrl, filename: update.latestVersion.filename })
-      if (update.filename !== update.latestVersion.filename) {
+      const replaced = await invoke<string[]>("download_mod", { instanceName: instance.name, downloadUrl: update.latestVersion.downloadUrl, filename: update.latestVersion.filename, projectId: update.projectId, versionId: update.latestVersion.id, allowServerOnly: true })
+      if (update.filename !== update.latestVersion.filename && !replaced.includes(update.filename)) {
         await invoke("delete_mod", { instanceName: instance.name, filename: update.filename }).catch(err =>
           console.error(`Failed to delete old version ${update.filename}:`, err)
         )
@@ -342,6 +354,20 @@ export function InstanceDetailsTab({
     }
   }
 
+  const handleWarmInstance = async () => {
+    setIsWarming(true)
+    try {
+      const result = await invoke<InstanceWarmup>("warm_instance", { instanceName: instance.name })
+      if (result.warnings.length > 0) {
+        setAlertModal({ isOpen: true, title: "Prepared With Warnings", message: result.warnings.join("\n"), type: "warning" })
+      }
+    } catch (error) {
+      setAlertModal({ isOpen: true, title: "Error", message: `Failed to prepare launch: ${String(error)}`, type: "danger" })
+    } finally {
+      setIsWarming(false)
+    }
+  }
+
   const handleDeleteMod = async (filename: string) => {
     try {
       await invoke("delete_mod", { instanceName: instance.name, filename })
@@ -403,6 +429,31 @@ export function InstanceDetailsTab({
     }
   }
 
+  const handleTogglePinned = async (mod: InstalledMod) => {
+    setInstalledMods(prev => prev.map(m => m.filename === mod.filename ? { ...m, pinned: !m.pinned } : m))
+    try {
+      await invoke("set_mod_pinned", { instanceName: instance.name, filename: mod.filename, pinned: !mod.pinned })
+    } catch (error) {
+      setInstalledMods(prev => prev.map(m => m.filename === mod.filename ? { ...m, pinned: !m.pinned } : m))
+      console.error("Failed to pin mod:", error)
+      setAlertModal({ isOpen: true, title: "Error", message: `Failed to ${mod.pinned ? 'unpin' : 'pin'} mod: ${String(error)}`, type: "danger" })
+    }
+  }
+
+  const handleToggleEnforcedPack = async (filename: string) => {
+    const previous = enforcedPacks
+    const next = previous.includes(filename) ? previous.filter(p => p !== filename) : [...previous, filename]
+    setEnforcedPacks(next)
+    try {
+      await invoke("set_enforced_resource_packs", { instanceName: instance.name, packs: next })
+      onInstanceUpdated()
+    } catch (error) {
+      setEnforcedPacks(previous)
+      console.error("Failed to update enforced resource packs:", error)
+      setAlertModal({ isOpen: true, title: "Error", message: `Failed to update resource pack: ${String(error)}`, type: "danger" })
+    }
+  }
+
   const handleOpenWorldsFolder = async () => {
     try {
       await invoke("open_worlds_folder", { instanceName: instance.name })
@@ -498,7 +549,10 @@ export function InstanceDetailsTab({
     <>
           <div className="flex flex-col h-full overflow-hidden">
 
-        <div className="flex-shrink-0 px-8 pt-8 pb-6">
+        <div
+          className="flex-shrink-0 px-8 pt-8 pb-6 bg-cover bg-center"
+          style={coverImage ? { backgroundImage: `linear-gradient(to bottom, transparent, var(--bg-secondary)), url(${coverImage})` } : undefined}
+        >
           <div className="max-w-7xl mx-auto">
           <div className="flex items-center gap-4">
             <div className="flex-shrink-0">
@@ -517,7 +571,7 @@ export function InstanceDetailsTab({
               <div className="flex items-center justify-between">
                 <div className="flex-1 min-w-0">
                   <div className="flex items-center gap-3">
-                    <h1 className="text-2xl font-semibold text-[var(--text-primary)] tracking-tight leading-tight">{instance.name}</h1>
+                    <h1 className="text-2xl font-semibold text-[var(--text-primary)] tracking-tight leading-tight">{instance.display_name ?? instance.name}</h1>
                     {(instance.total_playtime_seconds ?? 0) > 0 && (
                       <span className="px-2 py-0.5 bg-[var(--bg-tertiary)] text-[var(--text-muted)] text-xs rounded">
                         {formatPlaytime(instance.total_playtime_seconds ?? 0)}
@@ -550,6 +604,14 @@ export function InstanceDetailsTab({
                       <><Play size={20} fill="currentColor" strokeWidth={0} /><span>Play</span></>
                     )}
                   </button>
+                  <button
+                    onClick={handleWarmInstance}
+                    disabled={isWarming || isLaunching || isRunning}
+                    className="px-4 py-2.5 bg-[var(--bg-tertiary)] hover:bg-[var(--bg-hover-strong)] text-[var(--text-primary)] rounded-md font-medium text-sm flex items-center gap-2 transition-all cursor-pointer disabled:opacity-50 disabled:cursor-not-allowed"
+                    title="Prepare launch: verify files and extract natives so Play starts right away"
+                  >
+                    {isWarming ? <Loader2 size={18} className="animate-spin" /> : <Zap size={18} strokeWidth={2.5} />}
+                  </button>
                   <button onClick={handleOpenFolder} className="px-4 py-2.5 bg-[var(--bg-tertiary)] hover:bg-[var(--bg-hover-strong)] text-[var(--text-primary)] rounded-md font-medium text-sm flex items-center gap-2 transition-all cursor-pointer">
                     <FolderOpen size={18} strokeWidth={2.5} /><span>Open Folder</span>
                   </button>
@@ -592,9 +654,9 @@ export function InstanceDetailsTab({
                     </div>
                     <div className="flex items-center gap-2">
                       {(instance.loader === "fabric" || instance.loader === "neoforge") && modsWithProjectId > 0 && (
-                        availableUpdates.length > 0 ? (
+                        bulkUpdates.length > 0 ? (
                           <button onClick={updateAllMods} disabled={isUpdatingMods} className="flex items-center gap-1.5 px-2 py-0.5 bg-[var(--accent-primary)] hover:bg-[var(--accent-hover)] disabled:opacity-50 disabled:cursor-not-allowed text-white rounded text-xs font-medium transition-colors cursor-pointer">
-                            {isUpdatingMods ? <><Loader2 size={14} className="animate-spin" /><span>Updating...</span></> : <><RefreshCw size={14} /><span>Update All ({availableUpdates.length})</span></>}
+                            {isUpdatingMods ? <><Loader2 size={14} className="animate-spin" /><span>Updating...</span></> : <><RefreshCw size={14} /><span>Update All ({bulkUpdates.length})</span></>}
                           </button>
                         ) : (
                           <button onClick={checkForUpdates} disabled={isCheckingUpdates} className="flex items-center gap-1.5 px-2 py-0.5 bg-[var(--bg-tertiary)] hover:bg-[var(--bg-hover-strong)] disabled:opacity-50 text-[var(--text-muted)] hover:text-[var(--text-primary)] rounded text-xs transition-colors cursor-pointer">
@@ -653,6 +715,9 @@ export function InstanceDetailsTab({
                                   <p className="text-sm text-[var(--text-muted)] mt-0.5">{formatFileSize(mod.size)}</p>
                                 </div>
                                 <div className="flex items-center gap-3">
+                                  <button onClick={() => handleTogglePinned(mod)} title={mod.pinned ? "Pinned: skipped by Update All" : "Pin to skip in Update All"} className={`p-1 rounded transition-all cursor-pointer ${mod.pinned ? 'text-[var(--accent-primary)]' : 'text-[var(--text-muted)] hover:text-[var(--text-primary)]'}`}>
+                                    <Pin size={18} fill={mod.pinned ? 'currentColor' : 'none'} />
+                                  </button>
                                   <button onClick={() => handleToggleMod(mod)} className={`w-6 h-6 rounded border-2 flex items-center justify-center transition-all cursor-pointer ${mod.disabled ? 'bg-[var(--bg-hover-strong)] border-[var(--text-muted)]' : 'bg-[#16a34a] border-[#16a34a]'}`}>
                                     <svg width="16" height="16" viewBox="0 0 12 12" fill="none" xmlns="http://www.w3.org/2000/svg">
                                       <path d="M10 3L4.5 8.5L2 6" stroke={mod.disabled ? '#7d8590' : '#0f1115'} strokeWidth="2" strokeLinecap="round" strokeLinejoin="round" />
@@ -796,6 +861,9 @@ export function InstanceDetailsTab({
                                 <p className="text-sm text-[var(--text-muted)] mt-0.5">{formatFileSize(pack.size)}</p>
                               </div>
                               <div className="flex flex-col items-center gap-1 self-center">
+                                <button onClick={() => handleToggleEnforcedPack(pack.filename)} title={enforcedPacks.includes(pack.filename) ? "Always enabled when launching" : "Always enable when launching"} className={`p-1.5 rounded-md transition-all cursor-pointer ${enforcedPacks.includes(pack.filename) ? 'text-[var(--accent-primary)]' : 'text-[var(--text-muted)] hover:text-[var(--text-primary)]'}`}>
+                                  <Lock size={20} />
+                                </button>
                                 <button onClick={() => handleDeleteResourcePack(pack.filename)} className="p-1.5 hover:bg-red-500/10 text-[var(--text-muted)] hover:text-red-400 rounded-md transition-all cursor-pointer">
                                   <Trash2 size={20} />
                                 </button>
@@ -2,7 +2,7 @@ import { useState, useRef, useEffect } from "react"
 import { X, Trash2, Camera, ImagePlus, Loader2, Check, Cpu } from "lucide-react"
 import { invoke } from "@tauri-apps/api/core"
 import { ConfirmModal, AlertModal } from "../../components/ui/ConfirmModal"
-import type { Instance, FabricVersion, NeoForgeVersion, ForgeVersion, LauncherSettings } from "../../types"
+import type { Instance, FabricVersion, NeoForgeVersion, ForgeVersion, InstanceLaunchOptions } from "../../types"
 
 interface SystemInfo {
   total_memory_mb: number
@@ -34,11 +34,15 @@ function InnerModal({
   onInstanceRenamed,
 }: InstanceSettingsModalProps) {
   const [isDeleting, setIsDeleting] = useState(false)
-  const [newName, setNewName] = useState(instance.name)
+  const [newName, setNewName] = useState(instance.display_name ?? instance.name)
   const [renameError, setRenameError] = useState<string | null>(null)
   const [isRenamingInstance, setIsRenamingInstance] = useState(false)
   const [isUploadingIcon, setIsUploadingIcon] = useState(false)
   const [localIcon, setLocalIcon] = useState<string | null>(instanceIcon)
+  const [accentColor, setAccentColor] = useState<string | null>(instance.accent_color ?? null)
+  const [hasCover, setHasCover] = useState(!!instance.cover_image)
+  const [isUploadingCover, setIsUploadingCover] = useState(false)
+  const coverInputRef = useRef<HTMLInputElement>(null)
   const [isClosing, setIsClosing] = useState(false)
   const fileInputRef = useRef<HTMLInputElement>(null)
 
@@ -143,10 +147,10 @@ function InnerModal({
 
   const loadInstanceRamSettings = async () => {
     try {
-      const settings = await invoke<LauncherSettings | null>("get_instance_settings", { instanceName: instance.name })
-      if (settings?.memory_mb) {
+      const options = await invoke<InstanceLaunchOptions | null>("get_instance_launch_options", { instanceName: instance.name })
+      if (options?.memory_mb) {
         setUseCustomRam(true)
-        setInstanceMemoryMb(settings.memory_mb)
+        setInstanceMemoryMb(options.memory_mb)
       } else {
         setUseCustomRam(false)
         setInstanceMemoryMb(2048)
@@ -169,19 +173,13 @@ function InnerModal({
 
   const handleSaveRam = async (memoryMb: number, enabled: boolean) => {
     try {
-      if (enabled) {
-        const currentSettings = await invoke<LauncherSettings | null>("get_instance_settings", { instanceName: instance.name })
-        const newSettings: LauncherSettings = {
-          memory_mb: memoryMb,
-          java_path: currentSettings?.java_path ?? null,
-          language: currentSettings?.language,
-          auto_navigate_to_console: currentSettings?.auto_navigate_to_console ?? true,
-        }
-        await invoke("save_instance_settings", { instanceName: instance.name, settings: newSettings })
-      } else {
-        await invoke("save_instance_settings", { instanceName: instance.name, settings: null })
-      }
+      const currentOptions = await invoke<InstanceLaunchOptions | null>("get_instance_launch_options", { instanceName: instance.name })
+      const newOptions: InstanceLaunchOptions = { ...currentOptions, memory_mb: enabled ? memoryMb : null }
+      const warnings = await invoke<string[]>("save_instance_launch_options", { instanceName: instance.name, options: newOptions })
       onInstanceUpdated()
+      if (warnings.length > 0) {
+        setAlertModal({ isOpen: true, title: "Warning", message: warnings.join("\n\n"), type: "warning" })
+      }
     } catch (error) {
       console.error("Failed to save RAM settings:", error)
       setAlertModal({ isOpen: true, title: "An error occurred", message: `Failed to save RAM settings: ${String(error)}`, type: "danger" })
@@ -337,7 +335,7 @@ function InnerModal({
     if (!file) return
 
     if (!file.type.startsWith('image/')) {
-      setAlertModal({ isOpen: true, title: "Invalid File", message: "Please select an image file (PNG, JPEG, or WebP)", type: "danger" })
+      setAlertModal({ isOpen: true, title: "Invalid File", message: "Please select an image file (PNG, JPEG, WebP, GIF, or SVG)", type: "danger" })
       return
     }
 
@@ -394,6 +392,58 @@ function InnerModal({
     })
   }
 
+  const handleAccentColorChange = async (color: string | null) => {
+    const previous = accentColor
+    setAccentColor(color)
+    try {
+      await invoke("set_instance_accent_color", { instanceName: instance.name, color })
+      onInstanceUpdated()
+    } catch (error) {
+      setAccentColor(previous)
+      console.error("Failed to set accent color:", error)
+      setAlertModal({ isOpen: true, title: "An error occurred", message: `Failed to set accent color: ${String(error)}`, type: "danger" })
+    }
+  }
+
+  const handleCoverChange = async (event: React.ChangeEvent<HTMLInputElement>) => {
+    const file = event.target.files?.[0]
+    if (event.target) event.target.value = ''
+    if (!file) return
+
+    if (file.size > 8 * 1024 * 1024) {
+      setAlertModal({ isOpen: true, title: "File Too Large", message: "Image must be smaller than 8MB", type: "danger" })
+      return
+    }
+
+    setIsUploadingCover(true)
+    const reader = new FileReader()
+    reader.onload = async () => {
+      try {
+        const base64 = (reader.result as string).split(',')[1]
+        await invoke("set_instance_cover", { instanceName: instance.name, imageData: base64 })
+        setHasCover(true)
+        onInstanceUpdated()
+      } catch (error) {
+        console.error("Failed to set cover image:", error)
+        setAlertModal({ isOpen: true, title: "An error occurred", message: `Failed to set cover image: ${String(error)}`, type: "danger" })
+      } finally {
+        setIsUploadingCover(false)
+      }
+    }
+    reader.readAsDataURL(file)
+  }
+
+  const handleRemoveCover = async () => {
+    try {
+      await invoke("remove_instance_cover", { instanceName: instance.name })
+      setHasCover(false)
+      onInstanceUpdated()
+    } catch (error) {
+      console.error("Failed to remove cover image:", error)
+      setAlertModal({ isOpen: true, title: "An error occurred", message: `Failed to remove cover image: ${String(error)}`, type: "danger" })
+    }
+  }
+
   const handleClose = () => {
     setIsClosing(true)
     setTimeout(() => { setIsClosing(false); onClose() }, 150)
@@ -401,16 +451,16 @@ function InnerModal({
 
   const handleRename = async (trimmedName: string) => {
     if (!trimmedName) { setRenameError("Instance name cannot be empty"); return }
-    if (trimmedName === instance.name) { setRenameError(null); return }
+    if (trimmedName === (instance.display_name ?? instance.name)) { setRenameError(null); return }
     setIsRenamingInstance(true)
     try {
-      await invoke("rename_instance", { oldName: instance.name, newName: trimmedName })
+      const newFolder = await invoke<string>("rename_instance", { oldName: instance.name, newName: trimmedName })
       setRenameError(null)
-      onInstanceRenamed?.(instance.name, trimmedName)
+      onInstanceRenamed?.(instance.name, newFolder)
       onClose()
     } catch (error) {
       setRenameError(error as string)
-      setNewName(instance.name)
+      setNewName(instance.display_name ?? instance.name)
     } finally {
       setIsRenamingInstance(false)
     }
@@ -420,7 +470,7 @@ function InnerModal({
     setConfirmModal({
       isOpen: true,
       title: "Delete Instance",
-      message: `Are you sure you want to delete "${instance.name}"?\n\nThis action cannot be undone.`,
+      message: `Are you sure you want to delete "${instance.display_name ?? instance.name}"?\n\nThis action cannot be undone.`,
       type: "danger",
       onConfirm: async () => {
         setIsDeleting(true)
@@ -502,7 +552,7 @@ function InnerModal({
                     type="text"
                     value={newName}
                     onChange={(e) => { setNewName(e.target.value); setRenameError(null) }}
-                    onBlur={(e) => { const trimmed = e.target.value.trim(); if (trimmed && trimmed !== instance.name) handleRename(trimmed) }}
+                    onBlur={(e) => { const trimmed = e.target.value.trim(); if (trimmed && trimmed !== (instance.display_name ?? instance.name)) handleRename(trimmed) }}
                     onKeyDown={(e) => { if (e.key === 'Enter') e.currentTarget.blur() }}
                     className="w-full bg-[var(--bg-tertiary)] rounded px-4 py-3.5 pr-10 text-sm text-[var(--text-primary)] placeholder-gray-500 focus:outline-none transition-all"
                     placeholder="Enter instance name"
@@ -517,6 +567,41 @@ function InnerModal({
                 {renameError && <p className="text-xs text-red-400 mt-2">{renameError}</p>}
               </div>
 
+              {/* Accent Color */}
+              <div>
+                <label className="block text-sm font-medium text-[var(--text-primary)] mb-2.5">Accent Color</label>
+                <div className="flex items-center gap-4">
+                  <input
+                    type="color"
+                    value={accentColor ?? "#808080"}
+                    onChange={(e) => setAccentColor(e.target.value)}
+                    onBlur={(e) => { if (e.target.value !== (instance.accent_color ?? null)) handleAccentColorChange(e.target.value) }}
+                    className="w-12 h-12 flex-shrink-0 rounded bg-[var(--bg-tertiary)] cursor-pointer"
+                  />
+                  {accentColor && (
+                    <button onClick={() => handleAccentColorChange(null)} className="px-4 py-3.5 bg-[var(--bg-tertiary)] hover:bg-[var(--bg-hover-strong)] text-[var(--text-primary)] rounded text-sm font-medium transition-all cursor-pointer">
+                      Clear Color
+                    </button>
+                  )}
+                </div>
+              </div>
+
+              {/* Cover Image */}
+              <div>
+                <label className="block text-sm font-medium text-[var(--text-primary)] mb-2.5">Cover Image</label>
+                <div className="flex items-center gap-4">
+                  <input ref={coverInputRef} type="file" accept="image/*" onChange={handleCoverChange} className="hidden" />
+                  <button onClick={() => coverInputRef.current?.click()} disabled={isUploadingCover} className="px-4 py-3.5 bg-[var(--bg-tertiary)] hover:bg-[var(--bg-hover-strong)] text-[var(--text-primary)] rounded text-sm font-medium transition-all disabled:opacity-50 cursor-pointer">
+                    {isUploadingCover ? "Uploading..." : hasCover ? "Change Cover" : "Upload Cover"}
+                  </button>
+                  {hasCover && (
+                    <button onClick={handleRemoveCover} disabled={isUploadingCover} className="px-4 py-3.5 bg-[var(--bg-tertiary)] hover:bg-red-500/10 text-[var(--text-primary)] hover:text-red-400 rounded text-sm font-medium transition-all disabled:opacity-50 cursor-pointer">
+                      Remove Cover
+                    </button>
+                  )}
+                </div>
+              </div>
+
               {/* Minecraft Version */}
               <div>
                 <label className="block text-sm font-medium text-[var(--text-primary)] mb-2.5">Minecraft Version</label>
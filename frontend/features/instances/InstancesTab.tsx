@@ -1,9 +1,10 @@
-import { Package, Plus, Search, FolderOpen, Copy, Trash2, ChevronDown, Play, FileArchive, ChevronUp, FolderPlus, FolderSymlink, FolderX } from "lucide-react"
+import { Package, Plus, Search, FolderOpen, Copy, Trash2, ChevronDown, Play, FileArchive, ChevronUp, FolderPlus, FolderSymlink, FolderX, AlertTriangle, CopyPlus } from "lucide-react"
 import { useState, useEffect, useRef, useMemo } from "react"
 import { invoke } from "@tauri-apps/api/core"
-import type { Instance } from "../../types"
+import type { DuplicateOptions, Instance } from "../../types"
 import { ContextMenu } from "../../components/ui/ContextMenu"
 import { ExportModal } from "./ExportModal"
+import { DuplicateModal } from "./DuplicateModal"
 import { storeGet, storeSet } from "../../lib/store"
 
 type SortOption = "recently-played" | "name-asc" | "name-desc"
@@ -28,7 +29,7 @@ interface InstancesTabProps {
   onCreateNew: () => void
   onShowDetails: (instance: Instance) => void
   onOpenFolder?: (instance: Instance) => void
-  onDuplicateInstance?: (instance: Instance) => void
+  onDuplicateInstance?: (instance: Instance, options?: DuplicateOptions) => void
   onDeleteInstance?: (instanceName: string) => void
   onKillInstance?: (instance: Instance) => void
 }
@@ -52,6 +53,7 @@ export function InstancesTab({
   const [instanceIcons, setInstanceIcons] = useState<Record<string, string | null>>({})
   const [sortBy, setSortBy] = useState<SortOption>("recently-played")
   const [exportModalInstance, setExportModalInstance] = useState<Instance | null>(null)
+  const [duplicateModalInstance, setDuplicateModalInstance] = useState<Instance | null>(null)
 
   const [groups, setGroups] = useState<Record<string, string[]>>({})
   const [collapsed, setCollapsed] = useState<Record<string, boolean>>({})
@@ -279,6 +281,7 @@ export function InstancesTab({
         onClick={() => { onSetSelectedInstance(instance); onShowDetails(instance) }}
         onContextMenu={(e) => handleContextMenu(e, instance)}
         className="bg-[var(--bg-tertiary)] rounded-md flex items-center hover:bg-[var(--bg-hover)] transition-all cursor-pointer group relative overflow-hidden"
+        style={instance.accent_color ? { boxShadow: `inset 3px 0 0 ${instance.accent_color}` } : undefined}
       >
         <div className="relative flex-shrink-0">
           {icon ? (
@@ -291,11 +294,16 @@ export function InstancesTab({
         </div>
 
         <div className={`py-2 pr-2 pl-4 flex-1 min-w-0 ${isRunning || isLaunching ? 'pr-12' : 'group-hover:pr-12'}`}>
-          <div className="text-base font-medium text-[var(--text-primary)] truncate leading-tight">{instance.name}</div>
+          <div className="text-base font-medium text-[var(--text-primary)] truncate leading-tight">{instance.display_name ?? instance.name}</div>
           <div className="flex items-center gap-2 text-sm text-[var(--text-muted)] mt-0.5">
             <span>{getMinecraftVersion(instance)}</span>
             <span className="text-[var(--text-muted)]">•</span>
             {getLoaderBadge(instance)}
+            {instance.last_crash && (
+              <span title={`Crashed last time${instance.last_crash.reason ? `: ${instance.last_crash.reason}` : ""}`} className="text-[#eab308]">
+                <AlertTriangle size={14} />
+              </span>
+            )}
           </div>
         </div>
 
@@ -456,6 +464,7 @@ export function InstancesTab({
             { label: "Open", icon: <Package size={16} />, onClick: () => { onSetSelectedInstance(contextMenu.instance); onShowDetails(contextMenu.instance) } },
             { label: "Open Folder", icon: <FolderOpen size={16} />, onClick: () => onOpenFolder?.(contextMenu.instance) },
             { label: "Duplicate", icon: <Copy size={16} />, onClick: () => onDuplicateInstance?.(contextMenu.instance) },
+            { label: "Duplicate with Options...", icon: <CopyPlus size={16} />, onClick: () => setDuplicateModalInstance(contextMenu.instance) },
             { label: "Export", icon: <FileArchive size={16} />, onClick: () => setExportModalInstance(contextMenu.instance) },
             { separator: true as const },
             {
@@ -564,6 +573,14 @@ export function InstancesTab({
           onClose={() => setExportModalInstance(null)}
         />
       )}
+
+      {duplicateModalInstance && (
+        <DuplicateModal
+          instanceName={duplicateModalInstance.display_name ?? duplicateModalInstance.name}
+          onDuplicate={(options) => onDuplicateInstance?.(duplicateModalInstance, options)}
+          onClose={() => setDuplicateModalInstance(null)}
+        />
+      )}
     </>
   )
 }
\ No newline at end of file
@@ -1,6 +1,8 @@
 import { useState, useEffect, useRef, useCallback } from "react"
 import { invoke } from "@tauri-apps/api/core"
 import { Search, Download, Loader2, Package, ChevronDown, Check } from "lucide-react"
+import { ConfirmModal } from "../../components/ui/ConfirmModal"
+import { WorldPickerModal } from "./WorldPickerModal"
 import type { Instance, ModrinthSearchResult, ModrinthProject, ModrinthVersion, ModFile } from "../../types"
 
 interface ModsSelectorProps {
@@ -89,7 +91,7 @@ export function ModsSelector({ instances, selectedInstance, onSetSelectedInstanc
           </div>
         )}
         <div className="text-left min-w-0">
-          <div className="font-semibold text-[var(--text-primary)] whitespace-nowrap leading-tight">{selectedInstance.name}</div>
+          <div className="font-semibold text-[var(--text-primary)] whitespace-nowrap leading-tight">{selectedInstance.display_name ?? selectedInstance.name}</div>
           <div className="flex items-center gap-1 text-xs leading-tight mt-0.5">
             <span className="text-[var(--text-muted)]">{getMinecraftVersion(selectedInstance)}</span>
             <span className="text-[#3a3f4b]">•</span>
@@ -123,7 +125,7 @@ export function ModsSelector({ instances, selectedInstance, onSetSelectedInstanc
                     </div>
                   )}
                   <div className="flex-1 min-w-0">
-                    <div className="font-semibold text-[var(--text-primary)] truncate">{instance.name}</div>
+                    <div className="font-semibold text-[var(--text-primary)] truncate">{instance.display_name ?? instance.name}</div>
                     <div className="flex items-center gap-1 text-xs">
                       <span>{getMinecraftVersion(instance)}</span>
                       <span>•</span>
@@ -165,6 +167,8 @@ export function ModsTab({ selectedInstance, instances, onSetSelectedInstance, so
   const [isLoadingVersions, setIsLoadingVersions] = useState(false)
   const [downloadingMods, setDownloadingMods] = useState<Set<string>>(new Set())
   const [installedModFiles, setInstalledModFiles] = useState<Set<string>>(new Set())
+  const [serverOnlyVersion, setServerOnlyVersion] = useState<ModrinthVersion | null>(null)
+  const [datapackVersion, setDatapackVersion] = useState<ModrinthVersion | null>(null)
   const sentinelRef = useRef<HTMLDivElement>(null)
   const searchTimeoutRef = useRef<NodeJS.Timeout | null>(null)
   const offsetRef = useRef(0)
@@ -284,7 +288,8 @@ export function ModsTab({ selectedInstance, instances, onSetSelectedInstance, so
     try {
       const versions = await invoke<ModrinthVersion[]>("get_mod_versions", {
         idOrSlug: mod.project_id,
-        loaders: [selectedInstance.loader],
+        // Search results list a project's loaders among its categories
+        loaders: mod.categories.includes("datapack") ? [selectedInstance.loader, "datapack"] : [selectedInstance.loader],
         gameVersions: [getMinecraftVersion(selectedInstance)],
       })
       setModVersions(versions)
@@ -298,14 +303,32 @@ export function ModsTab({ selectedInstance, instances, onSetSelectedInstance, so
   const isModInstalled = (version: ModrinthVersion): boolean =>
     version.files.some(file => installedModFiles.has(file.filename))
 
-  const handleDownloadMod = async (version: ModrinthVersion) => {
+  const isServerOnly = (mod: ModrinthProject | null): boolean =>
+    !!mod && mod.client_side === "unsupported" && mod.server_side !== "unsupported"
+
+  // Modrinth lists data packs as mods whose versions are built for the "datapack" loader
+  const isDatapackVersion = (version: ModrinthVersion, filename: string): boolean =>
+    version.loaders.includes("datapack") && !version.loaders.includes(selectedInstance?.loader ?? "") && !filename.endsWith(".jar")
+
+  const handleDownloadMod = async (version: ModrinthVersion, allowServerOnly = false, world?: string) => {
     if (!selectedInstance || (selectedInstance.loader !== "fabric" && selectedInstance.loader !== "neoforge")) return
     const primaryFile = version.files.find(f => f.primary) || version.files[0]
     if (!primaryFile) return
+    const isDatapack = isDatapackVersion(version, primaryFile.filename)
+    if (isDatapack && !world) {
+      setDatapackVersion(version)
+      return
+    }
+    if (!isDatapack && !allowServerOnly && isServerOnly(selectedMod)) {
+      setServerOnlyVersion(version)
+      return
+    }
     setDownloadingMods(prev => new Set(prev).add(version.id))
     try {
-      await invoke<string>("download_mod", {
+      await invoke<string[]>("download_mod", {
         instanceName: selectedInstance.name, downloadUrl: primaryFile.url, filename: primaryFile.filename,
+        projectId: version.project_id, versionId: version.id, allowServerOnly,
+        projectType: isDatapack ? "datapack" : selectedMod?.project_type, world,
       })
       setInstalledModFiles(prev => new Set(prev).add(primaryFile.filename))
     } catch (error) {
@@ -406,6 +429,7 @@ export function ModsTab({ selectedInstance, instances, onSetSelectedInstance, so
                   {formatDownloads(selectedMod.downloads)}
                 </span>
                 <span className="bg-[var(--bg-secondary)] px-2 py-1 rounded text-[var(--text-muted)]">{selectedMod.follows.toLocaleString()} followers</span>
+                {isServerOnly(selectedMod) && <span className="bg-yellow-500/10 px-2 py-1 rounded text-yellow-400">Server-side only</span>}
               </div>
               <div className="pt-1">
                 <h3 className="font-semibold text-sm text-[var(--text-primary)] mb-3">Versions</h3>
@@ -442,6 +466,23 @@ export function ModsTab({ selectedInstance, instances, onSetSelectedInstance, so
             </div>
           )}
         </div>
+      <ConfirmModal
+        isOpen={serverOnlyVersion !== null}
+        title="Server-side mod"
+        message={`${selectedMod?.title ?? "This mod"} only runs on servers and does nothing in a client instance. Install it anyway?`}
+        confirmText="Install"
+        type="warning"
+        onConfirm={() => { const version = serverOnlyVersion; setServerOnlyVersion(null); if (version) handleDownloadMod(version, true) }}
+        onCancel={() => setServerOnlyVersion(null)}
+      />
+      {datapackVersion && selectedInstance && (
+        <WorldPickerModal
+          instanceName={selectedInstance.name}
+          packTitle={selectedMod?.title ?? "This project"}
+          onPick={(world) => handleDownloadMod(datapackVersion, false, world)}
+          onClose={() => setDatapackVersion(null)}
+        />
+      )}
     </div>
   )
 }
\ No newline at end of file
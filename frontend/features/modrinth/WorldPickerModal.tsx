@@ -0,0 +1,102 @@
+import { useState, useEffect } from "react"
+import { invoke } from "@tauri-apps/api/core"
+import { X, Globe, Loader2 } from "lucide-react"
+
+interface World {
+  name: string
+  folder_name: string
+  icon?: string
+}
+
+interface WorldPickerModalProps {
+  instanceName: string
+  packTitle: string
+  onPick: (worldFolder: string) => void
+  onClose: () => void
+}
+
+export function WorldPickerModal({ instanceName, packTitle, onPick, onClose }: WorldPickerModalProps) {
+  const [isClosing, setIsClosing] = useState(false)
+  const [worlds, setWorlds] = useState<World[]>([])
+  const [isLoading, setIsLoading] = useState(true)
+
+  useEffect(() => {
+    invoke<World[]>("get_instance_worlds", { instanceName })
+      .then(setWorlds)
+      .catch((error) => console.error("Failed to load worlds:", error))
+      .finally(() => setIsLoading(false))
+  }, [instanceName])
+
+  const handleClose = () => {
+    setIsClosing(true)
+    setTimeout(() => {
+      setIsClosing(false)
+      onClose()
+    }, 150)
+  }
+
+  const handlePick = (worldFolder: string) => {
+    onPick(worldFolder)
+    handleClose()
+  }
+
+  return (
+    <div
+      className={`fixed inset-0 bg-black/60 backdrop-blur-sm flex items-center justify-center z-50 p-4 modal-backdrop ${isClosing ? 'closing' : ''}`}
+      onClick={handleClose}
+    >
+      <div
+        className={`blur-border bg-[var(--bg-secondary)] rounded w-full max-w-md shadow-2xl modal-content ${isClosing ? 'closing' : ''}`}
+        onClick={(e) => e.stopPropagation()}
+        style={{ pointerEvents: 'auto' }}
+      >
+        <div className="flex items-center justify-between px-6 pt-6 pb-5">
+          <div>
+            <h2 className="text-xl font-semibold text-[var(--text-primary)] tracking-tight">Choose a World</h2>
+            <p className="text-sm text-[var(--text-muted)] mt-0.5">{packTitle} is a data pack and is installed into one world</p>
+          </div>
+          <button
+            onClick={handleClose}
+            className="p-1.5 hover:bg-[var(--bg-hover-strong)] rounded transition-colors text-[var(--text-muted)] hover:text-[var(--text-primary)] cursor-pointer"
+          >
+            <X size={18} strokeWidth={2} />
+          </button>
+        </div>
+
+        <div className="px-6 pb-6">
+          <div className="bg-[var(--bg-tertiary)] rounded p-2 max-h-[50vh] overflow-y-auto">
+            {isLoading ? (
+              <div className="py-6"><Loader2 size={20} className="animate-spin text-[var(--accent-primary)] mx-auto" /></div>
+            ) : worlds.length === 0 ? (
+              <p className="text-sm text-[var(--text-muted)] text-center py-6 px-4">
+                This instance has no worlds yet. Create one in the game first, then install the data pack.
+              </p>
+            ) : (
+              worlds.map((world) => (
+                <button
+                  key={world.folder_name}
+                  onClick={() => handlePick(world.folder_name)}
+                  className="w-full flex items-center gap-3 px-3 py-2 rounded text-left hover:bg-[var(--bg-hover)] transition-colors cursor-pointer"
+                >
+                  {world.icon ? (
+                    <img src={world.icon} alt={world.name} className="w-8 h-8 rounded object-cover flex-shrink-0" />
+                  ) : (
+                    <div className="w-8 h-8 flex items-center justify-center flex-shrink-0">
+                      <Globe size={22} className="text-[var(--text-muted)]" strokeWidth={1.5} />
+                    </div>
+                  )}
+                  <div className="flex-1 min-w-0">
+                    <div className="text-sm font-medium text-[var(--text-primary)] truncate">{world.name}</div>
+                    {world.name !== world.folder_name && (
+                      <div className="text-xs text-[var(--text-muted)] truncate">{world.folder_name}</div>
+                    )}
+                  </div>
+                </button>
+              ))
+            )}
+          </div>
+        </div>
+      </div>
+    </div>
+  )
+}
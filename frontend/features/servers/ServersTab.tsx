@@ -3,7 +3,7 @@ import { useState, useEffect, useRef, useCallback } from "react"
 import { invoke } from "@tauri-apps/api/core"
 import { CreateServerModal } from "./CreateServerModal"
 import { ConfirmModal } from "../../components/ui/ConfirmModal"
-import type { ServerInfo, McSrvStatResponse } from "../../types"
+import type { ServerInfo, McSrvStatResponse, ServerCompatibilityReport } from "../../types"
 
 interface ServersTabProps {
   runningInstances: Set<string>
@@ -88,7 +88,7 @@ export function ServersTab({ runningInstances }: ServersTabProps) {
         version: data.version || data.protocol?.name,
         motd: data.motd?.clean?.join("\n"),
         motd_html: data.motd?.html,
-        favicon: data.icon ? `https://api.mcsrvstat.us/icon/${fullAddress}` : undefined,
+        favicon: data.icon,
         last_checked: Date.now(),
         ping,
       }
@@ -315,6 +315,22 @@ export function ServersTab({ runningInstances }: ServersTabProps) {
     }
     setLaunchingServer(server.name)
     try {
+      const report = await invoke<ServerCompatibilityReport>("check_server_compatibility", {
+        serverName: server.name,
+        instanceName: null,
+      }).catch(() => null)
+
+      if (report && (report.mismatches.length > 0 || report.missing_mods.length > 0)) {
+        const problems = [...report.mismatches]
+        if (report.missing_mods.length > 0) {
+          problems.push(`Missing mods: ${report.missing_mods.join(", ")}`)
+        }
+        if (!confirm(`${report.instance_name} may not be able to join ${server.name}:\n\n${problems.join("\n")}\n\nLaunch anyway?`)) {
+          setLaunchingServer(null)
+          return
+        }
+      }
+
       await invoke("launch_server", {
         serverAddress: server.address,
         serverPort: server.port,
@@ -1,8 +1,8 @@
 import { useState, useEffect, useRef, useCallback } from "react"
 import { invoke } from "@tauri-apps/api/core"
-import { Loader2, Coffee, Cpu, ImagePlus, FolderOpen, X, Check, ChevronDown, Info, Terminal, Paintbrush, Trash2, Play } from "lucide-react"
+import { Loader2, Coffee, Cpu, ImagePlus, FolderOpen, X, Check, ChevronDown, Info, Terminal, Paintbrush, Trash2, Play, Webhook, Eraser } from "lucide-react"
 import { AlertModal } from "../../components/ui/ConfirmModal"
-import type { LauncherSettings } from "../../types"
+import type { AssetCleanupMode, AssetCleanupResult, AutomationSettings, DetectedJava, LauncherSettings } from "../../types"
 import { storeSet } from "../../lib/store"
 
 interface SystemInfo {
@@ -109,6 +109,161 @@ function TrashSection({ onAlert }: { onAlert: (alert: any) => void }) {
   )
 }
 
+const ASSET_CLEANUP_MODES: { value: AssetCleanupMode; label: string }[] = [
+  { value: "off", label: "Off" },
+  { value: "weekly", label: "Weekly" },
+  { value: "after_change", label: "After Uninstalling a Version" },
+]
+
+function AssetCleanupSection({ mode, onChange, onCleaned, onAlert }: {
+  mode: AssetCleanupMode
+  onChange: (mode: AssetCleanupMode) => void
+  onCleaned: () => void
+  onAlert: (alert: any) => void
+}) {
+  const [running, setRunning] = useState(false)
+
+  const handleCleanNow = async () => {
+    setRunning(true)
+    try {
+      const result = await invoke<AssetCleanupResult>("run_asset_cleanup")
+      const freedMb = (result.freed_bytes / (1024 * 1024)).toFixed(1)
+      onAlert({
+        isOpen: true,
+        title: "Assets Cleaned",
+        message: result.removed_files === 0 ? "No unused assets found" : `Removed ${result.removed_files} unused files (${freedMb} MB)`,
+        type: "success",
+      })
+      onCleaned()
+    } catch (e) {
+      onAlert({ isOpen: true, title: "Error", message: `Asset cleanup failed: ${e}`, type: "danger" })
+    }
+    setRunning(false)
+  }
+
+  return (
+    <div className="space-y-2">
+      <div className="flex items-center gap-2 text-[var(--text-primary)]">
+        <Eraser size={16} className="text-[var(--accent-primary)]" />
+        <span className="font-medium text-sm">Asset Cleanup</span>
+      </div>
+      <div className="bg-[var(--bg-elevated)] rounded p-3 space-y-3">
+        <p className="text-xs text-[var(--text-muted)]">
+          Deletes game assets no installed version uses anymore, in the background.
+        </p>
+        <div className="flex items-center justify-between gap-3">
+          <div className="flex gap-1">
+            {ASSET_CLEANUP_MODES.map(({ value, label }) => (
+              <button
+                key={value}
+                onClick={() => onChange(value)}
+                className={`px-2.5 py-1 rounded text-xs font-medium transition-colors cursor-pointer ${
+                  mode === value
+                    ? "bg-[var(--accent-primary)] text-white"
+                    : "bg-[var(--bg-hover)] text-[var(--text-muted)] hover:text-[var(--text-primary)]"
+                }`}
+              >
+                {label}
+              </button>
+            ))}
+          </div>
+          <button
+            onClick={handleCleanNow}
+            disabled={running}
+            className="flex-shrink-0 px-2.5 py-1 bg-[var(--bg-hover)] rounded text-xs font-medium text-[var(--text-primary)] cursor-pointer disabled:opacity-50 disabled:cursor-not-allowed flex items-center gap-1.5 transition-colors"
+          >
+            {running ? <Loader2 size={12} className="animate-spin" /> : <Eraser size={12} />}
+            Clean Now
+          </button>
+        </div>
+      </div>
+    </div>
+  )
+}
+
+function AutomationSection({ automation, onSave, onAlert }: {
+  automation: AutomationSettings
+  onSave: (automation: AutomationSettings) => void
+  onAlert: (alert: any) => void
+}) {
+  const [webhookUrl, setWebhookUrl] = useState(automation.webhook_url ?? "")
+  const [socketAddress, setSocketAddress] = useState(automation.socket_address ?? "")
+  const [testing, setTesting] = useState(false)
+
+  const draft = (): AutomationSettings => ({
+    ...automation,
+    webhook_url: webhookUrl.trim() || null,
+    socket_address: socketAddress.trim() || null,
+  })
+
+  const handleBlur = () => {
+    const next = draft()
+    if (next.webhook_url !== automation.webhook_url || next.socket_address !== automation.socket_address) {
+      onSave(next)
+    }
+  }
+
+  const handleTest = async () => {
+    setTesting(true)
+    try {
+      await invoke("test_automation", { automation: draft() })
+      onAlert({ isOpen: true, title: "Test Event Sent", message: "The test event was delivered.", type: "success" })
+    } catch (e) {
+      onAlert({ isOpen: true, title: "Test Failed", message: `${e}`, type: "danger" })
+    }
+    setTesting(false)
+  }
+
+  return (
+    <div className="space-y-2">
+      <div className="flex items-center gap-2 text-[var(--text-primary)]">
+        <Webhook size={16} className="text-[var(--accent-primary)]" />
+        <span className="font-medium text-sm">Automation</span>
+      </div>
+      <div className="bg-[var(--bg-elevated)] rounded p-3 space-y-3">
+        <div className="flex items-center justify-between">
+          <div>
+            <span className="text-sm font-medium text-[var(--text-primary)]">Send Launcher Events</span>
+            <p className="text-xs text-[var(--text-muted)]">Launch, exit and install events for OBS, Home Assistant or stream overlays</p>
+          </div>
+          <button
+            onClick={() => onSave({ ...draft(), enabled: !automation.enabled })}
+            className={`relative inline-flex h-6 w-11 items-center rounded-full transition-colors cursor-pointer flex-shrink-0 ml-3 ${automation.enabled ? 'bg-[var(--accent-primary)]' : 'bg-[var(--bg-hover)]'}`}
+          >
+            <span className={`inline-block h-4 w-4 transform rounded-full bg-white transition-transform ${automation.enabled ? 'translate-x-6' : 'translate-x-1'}`} />
+          </button>
+        </div>
+        <input
+          type="text"
+          value={webhookUrl}
+          onChange={(e) => setWebhookUrl(e.target.value)}
+          onBlur={handleBlur}
+          placeholder="Webhook URL, e.g. http://homeassistant.local:8123/api/webhook/minecraft"
+          className="w-full px-3 py-2 bg-[var(--bg-hover)] rounded text-xs text-[var(--text-primary)] placeholder-[var(--text-muted)] focus:outline-none"
+        />
+        <div className="flex items-center gap-2">
+          <input
+            type="text"
+            value={socketAddress}
+            onChange={(e) => setSocketAddress(e.target.value)}
+            onBlur={handleBlur}
+            placeholder="Local socket, e.g. 127.0.0.1:4455 or /tmp/octane.sock"
+            className="flex-1 px-3 py-2 bg-[var(--bg-hover)] rounded text-xs text-[var(--text-primary)] placeholder-[var(--text-muted)] focus:outline-none font-mono"
+          />
+          <button
+            onClick={handleTest}
+            disabled={testing || (!webhookUrl.trim() && !socketAddress.trim())}
+            className="flex-shrink-0 px-2.5 py-2 bg-[var(--bg-hover)] disabled:opacity-50 rounded text-xs font-medium text-[var(--text-primary)] cursor-pointer disabled:cursor-not-allowed flex items-center gap-1.5 transition-colors"
+          >
+            {testing ? <Loader2 size={12} className="animate-spin" /> : <Play size={12} />}
+            Test
+          </button>
+        </div>
+      </div>
+    </div>
+  )
+}
+
 export function SettingsModal({
   isOpen,
   settings,
@@ -140,6 +295,7 @@ export function SettingsModal({
   const [isTabDropdownOpen, setIsTabDropdownOpen] = useState(false)
   const tabDropdownRef = useRef<HTMLDivElement>(null)
   const saveTimeoutRef = useRef<NodeJS.Timeout | undefined>(undefined)
+  const shownWarningsRef = useRef("")
 
   useEffect(() => {
     if (isOpen) {
@@ -243,8 +399,8 @@ export function SettingsModal({
   const loadJavaInstallations = async () => {
     setIsLoadingJava(true)
     try {
-      const installations = await invoke<string[]>("detect_java_installations")
-      setJavaInstallations(installations)
+      const installations = await invoke<DetectedJava[]>("detect_java_installations")
+      setJavaInstallations(installations.map((java) => java.path))
     } catch (error) {
       console.error("Failed to detect Java installations:", error)
     } finally {
@@ -254,9 +410,15 @@ export function SettingsModal({
 
   const handleSettingChange = async (newSettings: LauncherSettings) => {
     try {
-      await invoke("save_settings", { settings: newSettings })
+      const warnings = await invoke<string[]>("save_settings", { settings: newSettings })
       await storeSet('octane_theme', newSettings.theme ?? 'octane')
       onSettingsChange(newSettings)
+      // Every change saves, so a warning is only shown again once it changes
+      const warningText = warnings.join("\n\n")
+      if (warningText && warningText !== shownWarningsRef.current) {
+        setAlertModal({ isOpen: true, title: "Warning", message: warningText, type: "warning" })
+      }
+      shownWarningsRef.current = warningText
     } catch (error) {
       console.error("Failed to save settings:", error)
       setAlertModal({ isOpen: true, title: "An error occurred", message: "Failed to save settings" + `: ${error}`, type: "danger" })
@@ -588,6 +750,13 @@ export function SettingsModal({
               </div>
             </div>
 
+            {/* Automation */}
+            <AutomationSection
+              automation={settings.automation ?? { enabled: false, webhook_url: null, socket_address: null }}
+              onSave={(automation) => handleSettingChange({ ...settings, automation })}
+              onAlert={setAlertModal}
+            />
+
             {/* Game Directory */}
             <div className="space-y-2">
               <div className="flex items-center gap-2 text-[var(--text-primary)]">
@@ -651,6 +820,14 @@ export function SettingsModal({
               </div>
             </div>
 
+            {/* Asset Cleanup */}
+            <AssetCleanupSection
+              mode={settings.asset_cleanup ?? "off"}
+              onChange={(asset_cleanup) => handleSettingChange({ ...settings, asset_cleanup })}
+              onCleaned={loadStorageUsage}
+              onAlert={setAlertModal}
+            />
+
             {/* Trash */}
             <TrashSection onAlert={setAlertModal} />
 
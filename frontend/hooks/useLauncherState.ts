@@ -2,7 +2,7 @@ import { useState, useEffect, useRef, useCallback } from "react"
 import { invoke } from "@tauri-apps/api/core"
 import { listen } from "@tauri-apps/api/event"
 import { getCurrentWindow } from "@tauri-apps/api/window"
-import type { Instance, LauncherSettings, ConsoleLog, AccountInfo, UpdateInfo } from "../types"
+import type { Instance, LauncherSettings, ConsoleLog, AccountInfo, UpdateInfo, DuplicateOptions } from "../types"
 import type { CSSProperties } from "react"
 import { storeGet, storeSet } from "../lib/store"
 
@@ -158,6 +158,7 @@ export function useLauncherState() {
         setTimeout(() => splash.remove(), 500)
       }
       checkForUpdates()
+      invoke("frontend_ready").catch(() => {})
     }
     initializeApp()
   }, [])
@@ -195,10 +196,20 @@ export function useLauncherState() {
       setRunningInstances((prev) => new Set(prev).add(event.payload.instance))
     })
 
+    const unlistenSkippedFiles = listen<{ instance: string, files: string[] }>("modpack-files-skipped", (event) => {
+      setAlertModal({
+        isOpen: true,
+        title: "Some mods were not installed",
+        message: `${event.payload.instance} is missing ${event.payload.files.length} mod(s) from the modpack, download them manually:\n\n${event.payload.files.join("\n")}`,
+        type: "warning",
+      })
+    })
+
     return () => {
       unlistenConsole.then((fn) => fn())
       unlistenExit.then((fn) => fn())
       unlistenServerLaunch.then((fn) => fn())
+      unlistenSkippedFiles.then((fn) => fn())
     }
   }, [isReady])
 
@@ -382,7 +393,7 @@ export function useLauncherState() {
     })
   }, [selectedInstance, instances, loadInstances])
 
-  const handleDuplicateInstance = useCallback(async (instance: Instance) => {
+  const handleDuplicateInstance = useCallback(async (instance: Instance, options: DuplicateOptions = {}) => {
     let baseName = instance.name
     let counter = 1
     let newName = `${baseName} (Copy)`
@@ -395,6 +406,8 @@ export function useLauncherState() {
       await invoke("duplicate_instance", {
         instanceName: instance.name,
         newName,
+        linked: options.linked ?? false,
+        skip: options.skip ?? null,
         appHandle: appWindow,
       })
       await loadInstances()
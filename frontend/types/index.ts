@@ -11,15 +11,101 @@ export interface AuthResponse {
 }
 
 export interface Instance {
+  id: string
   name: string
+  display_name?: string | null
   version: string
   loader: string | null
   loader_version: string | null
   created_at: string
   last_played: string | null
-  settings_override: LauncherSettings | null
+  settings_override?: LauncherSettings | null
+  launch_options?: InstanceLaunchOptions | null
   icon_path: string | null
+  accent_color?: string | null
+  cover_image?: string | null
   total_playtime_seconds?: number
+  modpack?: ModpackInfo | null
+  track_latest_snapshot?: boolean
+  enforced_resource_packs?: string[]
+  last_crash?: LastCrash | null
+}
+
+export interface DuplicateSkip {
+  saves: boolean
+  logs: boolean
+  screenshots: boolean
+  crash_reports: boolean
+}
+
+export interface DuplicateOptions {
+  linked?: boolean
+  skip?: DuplicateSkip
+}
+
+export interface InstanceWarmup {
+  warnings: string[]
+}
+
+export interface LastCrash {
+  crashed_at: string
+  exit_code: number | null
+  crash_report: string | null
+  reason: string | null
+}
+
+export interface InstanceLaunchOptions {
+  memory_mb?: number | null
+  java_path?: string | null
+  jvm_args?: string[] | null
+  env_vars?: Record<string, string> | null
+  window_width?: number | null
+  window_height?: number | null
+  wrapper?: string | null
+}
+
+export interface ModpackInfo {
+  source: "modrinth" | "curseforge" | "file"
+  name: string
+  summary: string | null
+  icon_url: string | null
+  project_url: string | null
+  project_id: string | null
+  version_id: string | null
+  version_name: string | null
+}
+
+export interface ModpackUpdateSummary {
+  version_name: string
+  added: number
+  updated: number
+  removed: number
+  unchanged: number
+}
+
+export interface SharedMod {
+  project_id: string
+  version_id: string
+  filename: string
+  enabled: boolean
+}
+
+export interface PackShare {
+  format_version: number
+  name: string
+  minecraft_version: string
+  loader: string | null
+  loader_version: string | null
+  mods: SharedMod[]
+  options: string | null
+  unlisted_mods: string[]
+}
+
+export interface PackShareInstallSummary {
+  instance_name: string
+  installed: number
+  failed: string[]
+  unlisted_mods: string[]
 }
 
 export interface FabricVersion {
@@ -39,13 +125,57 @@ export interface ForgeVersion {
   full_version: string
 }
 
+export interface DetectedJava {
+  path: string
+  major_version: number
+  full_version: string
+  architecture: string
+  vendor: string
+  is_64bit: boolean
+}
+
 export interface LauncherSettings {
+  settings_version?: number
   memory_mb: number
   java_path: string | null
   language?: string
   auto_navigate_to_console?: boolean
   theme?: string
   default_tab?: string
+  window_width?: number | null
+  window_height?: number | null
+  jvm_args?: string[]
+  authlib_injector_enabled?: boolean
+  restricted_mode?: RestrictedMode | null
+  automation?: AutomationSettings
+  asset_cleanup?: AssetCleanupMode
+}
+
+export type AssetCleanupMode = "off" | "weekly" | "after_change"
+
+export interface AssetCleanupResult {
+  removed_files: number
+  freed_bytes: number
+}
+
+export interface AutomationSettings {
+  enabled: boolean
+  webhook_url: string | null
+  socket_address: string | null
+}
+
+export type SettingsCategory = "launcher" | "templates" | "servers"
+
+export interface SettingsImportSummary {
+  launcher_settings: boolean
+  templates: number
+  servers: number
+}
+
+export interface RestrictedMode {
+  pin_hash: string
+  salt: string
+  blocked_terms: string[]
 }
 
 export interface MinecraftOptions {
@@ -68,6 +198,21 @@ export interface MinecraftOptions {
   keybinds: Record<string, string> | null
 }
 
+export interface InstanceTemplate {
+  id: string
+  name: string
+  description: string | null
+  created_at: string
+  launcher_settings: LauncherSettings | null
+  minecraft_options: MinecraftOptions | null
+  icon: string | null
+}
+
+export interface KeybindConflict {
+  key: string
+  actions: string[]
+}
+
 export interface ModrinthSearchResult {
   hits: ModrinthProject[]
   offset: number
@@ -149,8 +294,11 @@ export interface ModrinthProjectDetails {
     created: string
     ordering: number
   }>
+  environment?: ModEnvironment | null
 }
 
+export type ModEnvironment = "client_only" | "server_only" | "both"
+
 export interface ModrinthVersion {
   id: string
   project_id: string
@@ -180,6 +328,19 @@ export interface ModrinthFile {
   file_type: string | null
 }
 
+export interface GithubRelease {
+  tag_name: string
+  name: string | null
+  html_url: string
+  assets: GithubReleaseAsset[]
+}
+
+export interface GithubReleaseAsset {
+  name: string
+  size: number
+  browser_download_url: string
+}
+
 export interface ModrinthDependency {
   version_id: string | null
   project_id: string | null
@@ -210,6 +371,7 @@ export interface ModFileWithMetadata {
   downloads: number | null
   disabled: boolean
   current_version_id: string | null
+  pinned: boolean
 }
 
 export interface AccountInfo {
@@ -218,6 +380,23 @@ export interface AccountInfo {
   is_active: boolean
   added_at: string
   last_used: string | null
+  auth_server?: string | null
+  xbox_profile?: XboxProfile | null
+}
+
+export interface AccountStatus {
+  uuid: string
+  valid: boolean
+  token_expiry: string | null
+  expires_in_seconds: number | null
+  needs_reauth: boolean
+  error: string | null
+}
+
+export interface XboxProfile {
+  gamertag: string
+  xuid: string
+  picture_url: string | null
 }
 
 export interface UpdateInfo {
@@ -244,6 +423,19 @@ export interface ServerInfo {
   favicon?: string
   last_checked?: number
   ping?: number
+  group?: string
+  preferred_instance?: string
+  modpack_project_id?: string
+}
+
+export interface ServerCompatibilityReport {
+  instance_name: string
+  instance_version: string
+  server_version: string | null
+  online: boolean
+  mismatches: string[]
+  missing_mods: string[]
+  mods_checked: boolean
 }
 
 export interface McSrvStatResponse {
@@ -406,4 +598,47 @@ export interface CurseforgeFileDependency {
 export interface CurseforgeFileHash {
   value: string
   algo: number
-}
\ No newline at end of file
+}
+export type OperationKind =
+  | "create_instance"
+  | "modpack_install"
+  | "export"
+  | "copy_data"
+  | "duplicate"
+  | "version_update"
+  | "data_migration"
+
+export interface ProgressEvent {
+  operation_id: string
+  kind: OperationKind
+  instance: string | null
+  percent: number | null
+  stage: string | null
+  detail: string | null
+  error: string | null
+}
+
+export interface ActiveOperation {
+  id: string
+  kind: OperationKind
+  instance: string | null
+  percent: number | null
+  stage: string | null
+  started_at: string
+}
+
+export interface TaskRecord {
+  kind: string
+  instance: string | null
+  started_at: string
+  finished_at: string
+  outcome: "succeeded" | "failed"
+  error?: string
+}
+
+export interface LauncherProfile {
+  name: string
+  is_default: boolean
+  active: boolean
+  instance_count: number
+}
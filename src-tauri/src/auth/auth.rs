@@ -13,6 +13,7 @@ const REDIRECT_URL: &str = "http://localhost:3160/auth";
 const SERVER_ADDRESS: &str = "127.0.0.1:3160";
 const XBOX_AUTHENTICATE_URL: &str = "https://user.auth.xboxlive.com/user/authenticate";
 const XSTS_AUTHORIZE_URL: &str = "https://xsts.auth.xboxlive.com/xsts/authorize";
+const XBOX_PROFILE_URL: &str = "https://profile.xboxlive.com/users/batch/profile/settings";
 const MINECRAFT_LOGIN_URL: &str = "https://api.minecraftservices.com/launcher/login";
 const MINECRAFT_PROFILE_URL: &str = "https://api.minecraftservices.com/minecraft/profile";
 const AUTH_SUCCESS_HTML: &str = include_str!("../../../auth.html");
@@ -150,16 +151,17 @@ impl Authenticator {
         })
     }
 
-    pub async fn obtain_xsts(
+    async fn request_xsts(
         &self,
         xbl_token: &str,
-    ) -> Result<(TokenWithExpiry, String), Box<dyn std::error::Error>> {
+        relying_party: &str,
+    ) -> Result<XstsAuthResponse, Box<dyn std::error::Error>> {
         let request = XstsAuthRequest {
             properties: XstsAuthProperties {
                 sandbox_id: "RETAIL",
                 user_tokens: &[xbl_token],
             },
-            relying_party: "rp://api.minecraftservices.com/",
+            relying_party,
             token_type: "JWT",
         };
 
@@ -175,7 +177,14 @@ impl Authenticator {
             return Err(error_text.into());
         }
 
-        let xsts_response: XstsAuthResponse = response.json().await?;
+        Ok(response.json().await?)
+    }
+
+    pub async fn obtain_xsts(
+        &self,
+        xbl_token: &str,
+    ) -> Result<(TokenWithExpiry, String), Box<dyn std::error::Error>> {
+        let xsts_response = self.request_xsts(xbl_token, "rp://api.minecraftservices.com/").await?;
         let skew = Utc::now() - xsts_response.issue_instant;
 
         let userhash = xsts_response
@@ -195,6 +204,44 @@ impl Authenticator {
         ))
     }
 
+    /// Gamertag and profile picture. The Minecraft XSTS token doesn't carry them, so a second
+    /// token for the Xbox Live relying party is requested.
+    pub async fn get_xbox_profile(&self, xbl_token: &str) -> Result<XboxProfile, Box<dyn std::error::Error>> {
+        let xsts_response = self.request_xsts(xbl_token, "http://xboxlive.com").await?;
+        let claims = xsts_response.display_claims.xui.first().ok_or("Missing Xbox claims")?;
+
+        let userhash = claims.get("uhs").ok_or("Missing userhash")?;
+        let xuid = claims.get("xid").ok_or("Missing Xbox user ID")?.clone();
+        let claimed_gamertag = claims.get("gtg").cloned();
+
+        let response = self
+            .http_client
+            .post(XBOX_PROFILE_URL)
+            .header("Authorization", format!("XBL3.0 x={};{}", userhash, xsts_response.token))
+            .header("x-xbl-contract-version", "2")
+            .json(&serde_json::json!({
+                "userIds": [xuid],
+                "settings": ["Gamertag", "GameDisplayPicRaw"]
+            }))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(error_text.into());
+        }
+
+        let profile: XboxProfileResponse = response.json().await?;
+        let settings = profile.profile_users.into_iter().next().map(|u| u.settings).unwrap_or_default();
+        let setting = |id: &str| settings.iter().find(|s| s.id == id).map(|s| s.value.clone());
+
+        Ok(XboxProfile {
+            gamertag: setting("Gamertag").or(claimed_gamertag).ok_or("Missing gamertag")?,
+            xuid,
+            picture_url: setting("GameDisplayPicRaw"),
+        })
+    }
+
     pub async fn authenticate_minecraft(
         &self,
         xsts_token: &str,
@@ -269,6 +316,9 @@ impl Authenticator {
         let (xsts_token, userhash) = self.obtain_xsts(&xbl_token.token).await?;
         let mc_token = self.authenticate_minecraft(&xsts_token.token, &userhash).await?;
         let profile = self.get_minecraft_profile(&mc_token.token).await?;
+        let xbox_profile = self.get_xbox_profile(&xbl_token.token).await
+            .map_err(|e| eprintln!("Failed to load Xbox profile: {}", e))
+            .ok();
 
         Ok(AuthResponse {
             access_token: mc_token.token.to_string(),
@@ -276,6 +326,7 @@ impl Authenticator {
             token_expiry: mc_token.expiry,
             username: profile.name.to_string(),
             uuid: profile.id.to_string(),
+            xbox_profile,
         })
     }
 
@@ -300,6 +351,9 @@ impl Authenticator {
         let (xsts_token, userhash) = self.obtain_xsts(&xbl_token.token).await?;
         let mc_token = self.authenticate_minecraft(&xsts_token.token, &userhash).await?;
         let profile = self.get_minecraft_profile(&mc_token.token).await?;
+        let xbox_profile = self.get_xbox_profile(&xbl_token.token).await
+            .map_err(|e| eprintln!("Failed to load Xbox profile: {}", e))
+            .ok();
 
         Ok(AuthResponse {
             access_token: mc_token.token.to_string(),
@@ -307,6 +361,7 @@ impl Authenticator {
             token_expiry: mc_token.expiry,
             username: profile.name.to_string(),
             uuid: profile.id.to_string(),
+            xbox_profile,
         })
     }
 }
\ No newline at end of file
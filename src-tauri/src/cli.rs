@@ -0,0 +1,144 @@
+use crate::services::instance::InstanceManager;
+use tauri::{AppHandle, Manager};
+
+/// Command line options understood by the launcher executable.
+///
+/// - `--launch <instance>`: launch the instance with the active account on startup
+/// - `--install-modpack <file>`: install a `.mrpack`/`.zip` modpack, named by `--name` or the file stem
+/// - `--list-instances`: print installed instances and exit without opening a window
+/// - `--headless`: keep the window hidden and exit once the requested actions finish
+#[derive(Debug, Default, Clone)]
+pub struct CliArgs {
+    pub launch: Option<String>,
+    pub install_modpack: Option<String>,
+    pub instance_name: Option<String>,
+    pub list_instances: bool,
+    pub headless: bool,
+}
+
+impl CliArgs {
+    fn has_actions(&self) -> bool {
+        self.launch.is_some() || self.install_modpack.is_some()
+    }
+}
+
+pub fn parse_args() -> CliArgs {
+    parse_from(std::env::args().skip(1))
+}
+
+fn parse_from(args: impl IntoIterator<Item = String>) -> CliArgs {
+    let mut parsed = CliArgs::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value.to_string())),
+            _ => (arg, None),
+        };
+
+        match flag.as_str() {
+            "--launch" => parsed.launch = inline_value.or_else(|| args.next()),
+            "--install-modpack" => parsed.install_modpack = inline_value.or_else(|| args.next()),
+            "--name" => parsed.instance_name = inline_value.or_else(|| args.next()),
+            "--list-instances" => parsed.list_instances = true,
+            "--headless" => parsed.headless = true,
+            _ => {}
+        }
+    }
+
+    parsed
+}
+
+/// Prints installed instances as tab-separated `name  version  loader  last played` lines.
+pub fn print_instances() {
+    match InstanceManager::get_all() {
+        Ok(instances) => {
+            for instance in instances {
+                println!(
+                    "{}\t{}\t{}\t{}",
+                    instance.name,
+                    instance.version,
+                    instance.loader.as_deref().unwrap_or("vanilla"),
+                    instance.last_played.as_deref().unwrap_or("never"),
+                );
+            }
+        }
+        Err(e) => {
+            eprintln!("Failed to list instances: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Runs the actions requested on the command line once the app is set up.
+pub fn run_startup_actions(app: &AppHandle, args: CliArgs) {
+    if args.headless {
+        if let Some(window) = app.get_webview_window("main") {
+            let _ = window.hide();
+        }
+    }
+
+    if !args.has_actions() {
+        if args.headless {
+            app.exit(0);
+        }
+        return;
+    }
+
+    let app_handle = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let success = run_actions(&app_handle, &args).await;
+
+        if args.headless {
+            app_handle.exit(if success { 0 } else { 1 });
+        }
+    });
+}
+
+async fn run_actions(app_handle: &AppHandle, args: &CliArgs) -> bool {
+    if let Some(file_path) = args.install_modpack.clone() {
+        let instance_name = args.instance_name.clone().or_else(|| {
+            std::path::Path::new(&file_path)
+                .file_stem()
+                .map(|s| s.to_string_lossy().to_string())
+        });
+
+        let Some(instance_name) = instance_name else {
+            eprintln!("Could not determine an instance name for {}", file_path);
+            return false;
+        };
+
+        match crate::commands::install_modpack_from_file(file_path, instance_name.clone(), None, app_handle.clone()).await {
+            Ok(()) => println!("Installed modpack as '{}'", instance_name),
+            Err(e) => {
+                eprintln!("Failed to install modpack: {}", e);
+                return false;
+            }
+        }
+    }
+
+    if let Some(instance_name) = args.launch.clone() {
+        if let Err(e) = crate::commands::launch_instance_with_active_account(instance_name.clone(), app_handle.clone()).await {
+            eprintln!("Failed to launch {}: {}", instance_name, e);
+            return false;
+        }
+
+        if !args.headless {
+            if let Some(window) = app_handle.get_webview_window("main") {
+                let _ = window.minimize();
+            }
+            return true;
+        }
+
+        // Headless runs stay alive until the game exits so playtime and status are recorded
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(2)).await;
+            let running = crate::commands::instances::is_running(&instance_name).unwrap_or(false);
+            if !running {
+                break;
+            }
+        }
+    }
+
+    true
+}
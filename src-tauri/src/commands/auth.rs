@@ -1,6 +1,6 @@
 use crate::auth::Authenticator;
 use crate::services::accounts::AccountManager;
-use crate::models::{AppConfig, AuthResponse, AccountInfo};
+use crate::models::{AppConfig, AuthResponse, AccountInfo, AccountStatus};
 use tauri::Manager;
 
 fn make_authenticator(client_id: &str) -> Result<Authenticator, String> {
@@ -32,6 +32,8 @@ pub async fn get_active_account() -> Result<Option<AccountInfo>, String> {
             is_active: true,
             added_at: account.added_at,
             last_used: account.last_used,
+            auth_server: account.auth_server,
+            xbox_profile: account.xbox_profile,
         }))
 }
 
@@ -45,6 +47,7 @@ pub async fn switch_account(uuid: String) -> Result<(), String> {
 #[tauri::command]
 pub async fn remove_account(uuid: String) -> Result<(), String> {
     crate::commands::validation::validate_uuid(&uuid)?;
+    crate::services::restrictions::RestrictionManager::ensure_allowed("Removing accounts")?;
     AccountManager::remove_account(&uuid)
         .map_err(|e| e.to_string())
 }
@@ -85,6 +88,9 @@ pub async fn microsoft_login_and_store(app_handle: tauri::AppHandle) -> Result<A
             .map_err(|e| e.to_string())?;
     }
 
+    AccountManager::set_xbox_profile(&auth_response.uuid, auth_response.xbox_profile.clone())
+        .map_err(|e| e.to_string())?;
+
     AccountManager::get_all_accounts()
         .map_err(|e| e.to_string())?
         .into_iter()
@@ -92,6 +98,41 @@ pub async fn microsoft_login_and_store(app_handle: tauri::AppHandle) -> Result<A
         .ok_or_else(|| "Account not found".to_string())
 }
 
+/// Signs in to a Yggdrasil-compatible auth server such as ely.by or a Blessing Skin site.
+/// `server_url` may be the API root or the site's address, which usually points at the API.
+#[tauri::command]
+pub async fn third_party_login_and_store(
+    server_url: String,
+    username: String,
+    password: String,
+    app_handle: tauri::AppHandle,
+) -> Result<AccountInfo, String> {
+    use crate::services::authlib_injector::AuthlibInjector;
+
+    if !AuthlibInjector::is_enabled() {
+        return Err("Third-party auth servers are disabled, enable them in the advanced settings".to_string());
+    }
+
+    let client = crate::utils::http::client_from(&app_handle);
+    let api_root = AuthlibInjector::resolve_api_root(&client, &server_url)
+        .await
+        .map_err(|e| format!("Could not reach auth server: {}", e))?;
+
+    let session = AuthlibInjector::authenticate(&client, &api_root, username.trim(), &password)
+        .await
+        .map_err(|e| e.to_string())?;
+    let uuid = session.uuid.clone();
+
+    AccountManager::add_third_party_account(session, &api_root)
+        .map_err(|e| e.to_string())?;
+
+    AccountManager::get_all_accounts()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|acc| acc.uuid == uuid)
+        .ok_or_else(|| "Account not found".to_string())
+}
+
 #[tauri::command]
 pub async fn get_launch_token(app_handle: tauri::AppHandle) -> Result<String, String> {
     let config = app_handle.state::<AppConfig>();
@@ -112,4 +153,72 @@ pub async fn refresh_account_token(uuid: String, app_handle: tauri::AppHandle) -
         .await
         .map_err(|e| e.to_string())?;
     Ok(())
+}
+
+/// Checks whether the account can still be used, refreshing its token when it has expired.
+#[tauri::command]
+pub async fn get_account_status(uuid: String, app_handle: tauri::AppHandle) -> Result<AccountStatus, String> {
+    crate::commands::validation::validate_uuid(&uuid)?;
+    let config = app_handle.state::<AppConfig>();
+
+    let account = AccountManager::get_account(&uuid)
+        .map_err(|e| e.to_string())?
+        .ok_or("Account not found")?;
+
+    let result = AccountManager::get_valid_token(&uuid, &config.microsoft_client_id)
+        .await
+        .map_err(|e| e.to_string());
+
+    // Re-read, the check may have refreshed the token
+    let account = AccountManager::get_account(&uuid)
+        .map_err(|e| e.to_string())?
+        .unwrap_or(account);
+
+    let token_expiry = account.auth_server.is_none().then_some(account.token_expiry);
+
+    Ok(AccountStatus {
+        uuid,
+        valid: result.is_ok(),
+        token_expiry: token_expiry.map(|expiry| expiry.to_rfc3339()),
+        expires_in_seconds: token_expiry.map(|expiry| (expiry - chrono::Utc::now()).num_seconds().max(0)),
+        needs_reauth: result.is_err(),
+        error: result.err(),
+    })
+}
+
+/// Runs the Microsoft sign-in for an existing account and updates it in place. Signing in
+/// with a different Microsoft account is rejected instead of adding a second entry.
+#[tauri::command]
+pub async fn reauthenticate_account(uuid: String, app_handle: tauri::AppHandle) -> Result<AccountInfo, String> {
+    crate::commands::validation::validate_uuid(&uuid)?;
+
+    let account = AccountManager::get_account(&uuid)
+        .map_err(|e| e.to_string())?
+        .ok_or("Account not found")?;
+
+    if let Some(auth_server) = &account.auth_server {
+        return Err(format!("Sign in to {} again with your username and password", auth_server));
+    }
+
+    let config = app_handle.state::<AppConfig>();
+    let auth_response = make_authenticator(&config.microsoft_client_id)?
+        .authenticate()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if auth_response.uuid != uuid {
+        return Err(format!(
+            "Signed in as {}, but this account is {}. Use the Microsoft account that owns it.",
+            auth_response.username, account.username
+        ));
+    }
+
+    AccountManager::reauthenticate(&auth_response)
+        .map_err(|e| e.to_string())?;
+
+    AccountManager::get_all_accounts()
+        .map_err(|e| e.to_string())?
+        .into_iter()
+        .find(|acc| acc.uuid == uuid)
+        .ok_or_else(|| "Account not found".to_string())
 }
\ No newline at end of file
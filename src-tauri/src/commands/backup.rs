@@ -0,0 +1,571 @@
+use crate::commands::validation::{sanitize_instance_name, validate_server_address};
+use crate::models::{
+    AccountInfo, AutomationSettings, InstanceTemplate, LauncherSettings, ServerInfo, SettingsCategory,
+    SettingsImportSummary, WorldBackupInfo, WorldBackupPolicy,
+};
+use crate::services::accounts::AccountManager;
+use crate::services::instance::InstanceManager;
+use crate::services::servers::ServerManager;
+use crate::services::settings::SettingsManager;
+use crate::services::templates::TemplateManager;
+use crate::services::world_backup::WorldBackupManager;
+use crate::utils::*;
+use serde::{Deserialize, Serialize};
+use std::io::{Read, Write};
+use std::path::Path;
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+const BACKUP_FORMAT_VERSION: u32 = 1;
+const SETTINGS_EXPORT_FORMAT_VERSION: u32 = 1;
+const BACKUP_MANIFEST: &str = "octane-backup.json";
+const ACCOUNTS_ENTRY: &str = "accounts.json";
+
+// Top-level launcher files and folders carried over by a backup
+const BACKUP_FILES: &[&str] = &["settings.json", "servers.json", "bg.png", "master_options.txt"];
+const BACKUP_DIRS: &[&str] = &["templates", "recent_skins"];
+
+/// Backup entries that belong to the active launcher profile rather than the launcher dir.
+const PROFILE_FILES: &[&str] = &["settings.json"];
+
+fn backup_root(name: &str) -> std::path::PathBuf {
+    if PROFILE_FILES.contains(&name) {
+        get_profile_dir()
+    } else {
+        get_launcher_dir()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BackupManifest {
+    format_version: u32,
+    created_at: String,
+    app_version: String,
+    includes_instances: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LauncherRestoreSummary {
+    pub restored_files: Vec<String>,
+    pub restored_instances: Vec<String>,
+    pub skipped_instances: Vec<String>,
+    /// Accounts from the backup that are not signed in on this machine.
+    pub accounts_to_sign_in: Vec<AccountInfo>,
+}
+
+#[tauri::command]
+pub async fn export_launcher_data(
+    output_path: String,
+    include_instances: Option<bool>,
+) -> Result<(), String> {
+    let include_instances = include_instances.unwrap_or(false);
+    let started_at = chrono::Utc::now().to_rfc3339();
+
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        write_backup(Path::new(&output_path), include_instances)
+    })
+    .await
+    .map_err(|e| format!("Backup task failed: {}", e))
+    .and_then(|r| r);
+
+    crate::services::task_history::record_result("launcher_backup", None, started_at, &result);
+    result
+}
+
+#[tauri::command]
+pub async fn import_launcher_data(
+    path: String,
+    server_manager: tauri::State<'_, crate::services::servers::ServerManager>,
+) -> Result<LauncherRestoreSummary, String> {
+    crate::services::restrictions::RestrictionManager::ensure_allowed("Restoring a launcher backup")?;
+    let started_at = chrono::Utc::now().to_rfc3339();
+    let result = tauri::async_runtime::spawn_blocking(move || restore_backup(Path::new(&path)))
+        .await
+        .map_err(|e| format!("Restore task failed: {}", e))
+        .and_then(|r| r);
+
+    crate::services::task_history::record_result("launcher_restore", None, started_at, &result);
+    let summary = result?;
+
+    server_manager.reload().await;
+    Ok(summary)
+}
+
+/// Shareable configuration written by `export_settings`. Categories that weren't exported
+/// are left out.
+#[derive(Debug, Serialize, Deserialize)]
+struct SettingsExport {
+    format_version: u32,
+    created_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    launcher_settings: Option<LauncherSettings>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    templates: Option<Vec<InstanceTemplate>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    servers: Option<Vec<ServerInfo>>,
+}
+
+/// Clears what only makes sense on this machine: the Java path, restricted mode, settings
+/// tied to local instances, and network and sign-in switches an imported file must not turn on.
+fn shareable_settings(mut settings: LauncherSettings) -> LauncherSettings {
+    settings.java_path = None;
+    settings.restricted_mode = None;
+    settings.allow_any_https_host = false;
+    settings.http_proxy = None;
+    settings.authlib_injector_enabled = false;
+    settings.options_sync_instances.clear();
+    settings.world_backup_policies.clear();
+    settings.default_template_id = None;
+    settings.automation = AutomationSettings::default();
+    settings
+}
+
+/// Writes the chosen parts of the configuration to a JSON file that can be imported on
+/// another machine.
+#[tauri::command]
+pub async fn export_settings(
+    categories: Vec<SettingsCategory>,
+    output_path: String,
+    server_manager: tauri::State<'_, ServerManager>,
+) -> Result<(), String> {
+    if categories.is_empty() {
+        return Err("Choose at least one category to export".to_string());
+    }
+
+    let mut export = SettingsExport {
+        format_version: SETTINGS_EXPORT_FORMAT_VERSION,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        launcher_settings: None,
+        templates: None,
+        servers: None,
+    };
+
+    if categories.contains(&SettingsCategory::Launcher) {
+        let settings = SettingsManager::load().map_err(|e| e.to_string())?;
+        export.launcher_settings = Some(shareable_settings(settings));
+    }
+
+    if categories.contains(&SettingsCategory::Templates) {
+        let mut templates = TemplateManager::get_all().map_err(|e| e.to_string())?;
+        for template in templates.iter_mut() {
+            template.launcher_settings = template.launcher_settings.take().map(shareable_settings);
+        }
+        export.templates = Some(templates);
+    }
+
+    if categories.contains(&SettingsCategory::Servers) {
+        let servers = server_manager.list().await?
+            .into_iter()
+            .map(|server| ServerInfo {
+                status: "unknown".to_string(),
+                players_online: None,
+                players_max: None,
+                last_checked: None,
+                preferred_instance: None,
+                ..server
+            })
+            .collect();
+        export.servers = Some(servers);
+    }
+
+    let json = serde_json::to_string_pretty(&export).map_err(|e| e.to_string())?;
+    std::fs::write(&output_path, json)
+        .map_err(|e| format!("Failed to write settings export: {}", e))
+}
+
+/// Imports the chosen categories from a file written by `export_settings`. Launcher settings
+/// replace the current ones except for machine-specific values, templates with the same id
+/// are overwritten and servers are added unless their address is already listed.
+#[tauri::command]
+pub async fn import_settings(
+    file_path: String,
+    categories: Vec<SettingsCategory>,
+    server_manager: tauri::State<'_, ServerManager>,
+) -> Result<SettingsImportSummary, String> {
+    crate::services::restrictions::RestrictionManager::ensure_allowed("Importing settings")?;
+
+    let content = std::fs::read_to_string(&file_path)
+        .map_err(|e| format!("Failed to read settings export: {}", e))?;
+    let export: SettingsExport = serde_json::from_str(&content)
+        .map_err(|e| format!("Not a settings export: {}", e))?;
+
+    if export.format_version > SETTINGS_EXPORT_FORMAT_VERSION {
+        return Err("This settings export was made by a newer launcher version".to_string());
+    }
+
+    let mut summary = SettingsImportSummary::default();
+
+    if let Some(imported) = export.launcher_settings.filter(|_| categories.contains(&SettingsCategory::Launcher)) {
+        crate::commands::validation::validate_memory_allocation(imported.memory_mb as u64)?;
+        crate::commands::validation::validate_trusted_hosts(&imported.trusted_download_hosts)?;
+        crate::commands::validation::validate_env_vars(&imported.env_vars)?;
+        crate::commands::validation::validate_jvm_args(&imported.jvm_args)?;
+        crate::commands::validation::validate_window_size(imported.window_width, imported.window_height)?;
+
+        SettingsManager::update(|current| {
+            let local = current.clone();
+            *current = LauncherSettings {
+                settings_version: local.settings_version,
+                java_path: local.java_path,
+                restricted_mode: local.restricted_mode,
+                allow_any_https_host: local.allow_any_https_host,
+                http_proxy: local.http_proxy,
+                authlib_injector_enabled: local.authlib_injector_enabled,
+                options_sync_instances: local.options_sync_instances,
+                world_backup_policies: local.world_backup_policies,
+                default_template_id: local.default_template_id,
+                automation: local.automation,
+                ..imported
+            };
+            Ok(())
+        })
+        .map_err(|e| e.to_string())?;
+        summary.launcher_settings = true;
+    }
+
+    if let Some(templates) = export.templates.filter(|_| categories.contains(&SettingsCategory::Templates)) {
+        for mut template in templates {
+            template.launcher_settings = template.launcher_settings.take().map(shareable_settings);
+            if let Err(e) = TemplateManager::validate_imported(&mut template).and_then(|_| TemplateManager::save(&template)) {
+                eprintln!("Skipping template '{}': {}", template.name, e);
+                continue;
+            }
+            summary.templates += 1;
+        }
+    }
+
+    if let Some(servers) = export.servers.filter(|_| categories.contains(&SettingsCategory::Servers)) {
+        server_manager.update(|list| {
+            for server in servers {
+                if validate_server_address(&server.address).is_err() || server.port == 0 {
+                    continue;
+                }
+
+                let favicon = server.favicon.as_deref()
+                    .and_then(|f| f.strip_prefix("data:image/png;base64,"))
+                    .and_then(ServerManager::favicon_from_base64);
+                let server = ServerInfo {
+                    status: "unknown".to_string(),
+                    players_online: None,
+                    players_max: None,
+                    favicon,
+                    last_checked: None,
+                    preferred_instance: None,
+                    ..server
+                };
+
+                if crate::commands::servers::merge_server(list, server)? {
+                    summary.servers += 1;
+                }
+            }
+            Ok(())
+        })
+        .await?;
+    }
+
+    Ok(summary)
+}
+
+#[tauri::command]
+pub async fn get_world_backup_policy(instance_name: String) -> Result<Option<WorldBackupPolicy>, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let instance_id = InstanceManager::instance_id(&safe_name).map_err(|e| e.to_string())?;
+    let settings = SettingsManager::load().map_err(|e| e.to_string())?;
+    Ok(settings.world_backup_policies.get(&instance_id).cloned())
+}
+
+/// Sets or (with `None`) removes the scheduled world backup policy of an instance.
+#[tauri::command]
+pub async fn set_world_backup_policy(
+    instance_name: String,
+    policy: Option<WorldBackupPolicy>,
+) -> Result<(), String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    if let Some(ref p) = policy {
+        if !(5..=10080).contains(&p.interval_minutes) {
+            return Err("Backup interval must be between 5 minutes and 7 days".to_string());
+        }
+        if !(1..=100).contains(&p.retention_count) {
+            return Err("Keep between 1 and 100 backups".to_string());
+        }
+    }
+
+    let instance_id = InstanceManager::instance_id(&safe_name).map_err(|e| e.to_string())?;
+
+    SettingsManager::update(|settings| {
+        match policy {
+            Some(p) => {
+                settings.world_backup_policies.insert(instance_id, p);
+            }
+            None => {
+                settings.world_backup_policies.remove(&instance_id);
+            }
+        }
+        Ok(())
+    })
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn list_world_backups(instance_name: String) -> Result<Vec<WorldBackupInfo>, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    Ok(WorldBackupManager::list_backups(&safe_name))
+}
+
+/// Backs up the instance's worlds right away, applying its retention policy if it has one.
+/// Returns the backup's file name.
+#[tauri::command]
+pub async fn backup_worlds_now(instance_name: String, app_handle: tauri::AppHandle) -> Result<String, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    if !get_instance_dir(&safe_name).join("instance.json").exists() {
+        return Err(format!("Instance '{}' does not exist", safe_name));
+    }
+
+    let instance_id = InstanceManager::instance_id(&safe_name).map_err(|e| e.to_string())?;
+    let retention = SettingsManager::load()
+        .ok()
+        .and_then(|s| s.world_backup_policies.get(&instance_id).map(|p| p.retention_count));
+
+    crate::services::world_backup::run_backup(&app_handle, safe_name, retention).await
+}
+
+fn write_backup(output_path: &Path, include_instances: bool) -> Result<(), String> {
+    let launcher_dir = get_launcher_dir();
+
+    if let Some(parent) = output_path.parent() {
+        if !parent.exists() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create output directory: {}", e))?;
+        }
+    }
+
+    let file = std::fs::File::create(output_path)
+        .map_err(|e| format!("Failed to create backup file: {}", e))?;
+
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default()
+        .compression_method(CompressionMethod::Deflated)
+        .large_file(true);
+
+    let manifest = BackupManifest {
+        format_version: BACKUP_FORMAT_VERSION,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        includes_instances: include_instances,
+    };
+    write_json_entry(&mut zip, BACKUP_MANIFEST, &manifest, options)?;
+
+    // Only account metadata is exported, tokens never leave the machine
+    let accounts = AccountManager::get_all_accounts().unwrap_or_default();
+    write_json_entry(&mut zip, ACCOUNTS_ENTRY, &accounts, options)?;
+
+    for name in BACKUP_FILES {
+        let path = backup_root(name).join(name);
+        if path.is_file() {
+            add_file(&mut zip, &path, name, options)?;
+        }
+    }
+
+    for name in BACKUP_DIRS {
+        let path = launcher_dir.join(name);
+        if path.is_dir() {
+            add_dir(&mut zip, &path, name, options)?;
+        }
+    }
+
+    if include_instances {
+        let instances_dir = get_instances_dir();
+        if instances_dir.is_dir() {
+            add_dir(&mut zip, &instances_dir, "instances", options)?;
+        }
+    }
+
+    zip.finish()
+        .map_err(|e| format!("Failed to finalize backup: {}", e))?;
+
+    Ok(())
+}
+
+fn restore_backup(path: &Path) -> Result<LauncherRestoreSummary, String> {
+    let file = std::fs::File::open(path)
+        .map_err(|e| format!("Failed to open backup: {}", e))?;
+    let mut archive = ZipArchive::new(file)
+        .map_err(|e| format!("Invalid backup archive: {}", e))?;
+
+    let manifest: BackupManifest = {
+        let mut entry = archive.by_name(BACKUP_MANIFEST)
+            .map_err(|_| "Not a launcher backup: manifest missing".to_string())?;
+        let mut content = String::new();
+        entry.read_to_string(&mut content)
+            .map_err(|e| format!("Failed to read backup manifest: {}", e))?;
+        serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid backup manifest: {}", e))?
+    };
+
+    if manifest.format_version > BACKUP_FORMAT_VERSION {
+        return Err(format!(
+            "Backup format {} is newer than this launcher supports",
+            manifest.format_version
+        ));
+    }
+
+    let instances_dir = get_instances_dir();
+    std::fs::create_dir_all(&instances_dir)
+        .map_err(|e| format!("Failed to create instances directory: {}", e))?;
+
+    // Existing instances are never overwritten by a restore
+    let mut restored_instances = std::collections::BTreeSet::new();
+    let mut skipped_instances = std::collections::BTreeSet::new();
+    let mut restored_files = Vec::new();
+    let mut backup_accounts: Vec<AccountInfo> = Vec::new();
+
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i)
+            .map_err(|e| format!("Failed to read backup entry: {}", e))?;
+
+        let relative = match entry.enclosed_name() {
+            Some(p) => p.to_path_buf(),
+            None => continue,
+        };
+        let entry_name = relative.to_string_lossy().replace('\\', "/");
+
+        if entry_name == BACKUP_MANIFEST {
+            continue;
+        }
+
+        if entry_name == ACCOUNTS_ENTRY {
+            let mut content = String::new();
+            entry.read_to_string(&mut content)
+                .map_err(|e| format!("Failed to read accounts: {}", e))?;
+            backup_accounts = serde_json::from_str(&content).unwrap_or_default();
+            continue;
+        }
+
+        let mut components = entry_name.split('/');
+        let top = components.next().unwrap_or_default();
+
+        let dest = if top == "instances" {
+            let Some(instance_name) = components.next().filter(|n| !n.is_empty()) else {
+                continue;
+            };
+
+            if skipped_instances.contains(instance_name) {
+                continue;
+            }
+            if !restored_instances.contains(instance_name) {
+                if instances_dir.join(instance_name).exists() {
+                    skipped_instances.insert(instance_name.to_string());
+                    continue;
+                }
+                restored_instances.insert(instance_name.to_string());
+            }
+
+            match relative.strip_prefix("instances") {
+                Ok(rest) => instances_dir.join(rest),
+                Err(_) => continue,
+            }
+        } else if BACKUP_FILES.contains(&top) || BACKUP_DIRS.contains(&top) {
+            if !restored_files.iter().any(|f| f == top) {
+                restored_files.push(top.to_string());
+            }
+            backup_root(top).join(&relative)
+        } else {
+            continue;
+        };
+
+        if entry.is_dir() {
+            std::fs::create_dir_all(&dest)
+                .map_err(|e| format!("Failed to create {}: {}", dest.display(), e))?;
+            continue;
+        }
+
+        if let Some(parent) = dest.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+
+        let mut out = crate::utils::create_file_unlinked(&dest)
+            .map_err(|e| format!("Failed to write {}: {}", dest.display(), e))?;
+        std::io::copy(&mut entry, &mut out)
+            .map_err(|e| format!("Failed to write {}: {}", dest.display(), e))?;
+    }
+
+    let accounts_to_sign_in = backup_accounts
+        .into_iter()
+        .filter(|acc| !AccountManager::account_exists(&acc.uuid).unwrap_or(false))
+        .map(|acc| AccountInfo { is_active: false, ..acc })
+        .collect();
+
+    Ok(LauncherRestoreSummary {
+        restored_files,
+        restored_instances: restored_instances.into_iter().collect(),
+        skipped_instances: skipped_instances.into_iter().collect(),
+        accounts_to_sign_in,
+    })
+}
+
+fn write_json_entry<T: Serialize>(
+    zip: &mut ZipWriter<std::fs::File>,
+    name: &str,
+    value: &T,
+    options: SimpleFileOptions,
+) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(value)
+        .map_err(|e| format!("Failed to serialize {}: {}", name, e))?;
+
+    zip.start_file(name, options)
+        .map_err(|e| format!("Failed to add {} to backup: {}", name, e))?;
+    zip.write_all(json.as_bytes())
+        .map_err(|e| format!("Failed to write {}: {}", name, e))?;
+
+    Ok(())
+}
+
+fn add_file(
+    zip: &mut ZipWriter<std::fs::File>,
+    path: &Path,
+    zip_path: &str,
+    options: SimpleFileOptions,
+) -> Result<(), String> {
+    let mut file = std::fs::File::open(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+
+    zip.start_file(zip_path, options)
+        .map_err(|e| format!("Failed to add {} to backup: {}", zip_path, e))?;
+
+    std::io::copy(&mut file, zip)
+        .map_err(|e| format!("Failed to write {} to backup: {}", zip_path, e))?;
+
+    Ok(())
+}
+
+pub(crate) fn add_dir(
+    zip: &mut ZipWriter<std::fs::File>,
+    dir: &Path,
+    zip_prefix: &str,
+    options: SimpleFileOptions,
+) -> Result<(), String> {
+    let entries = std::fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read {}: {}", dir.display(), e))?;
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name();
+        let zip_path = format!("{}/{}", zip_prefix, name.to_string_lossy());
+
+        // Extracted natives are regenerated on launch
+        if name == "natives" {
+            continue;
+        }
+
+        if path.is_file() {
+            add_file(zip, &path, &zip_path, options)?;
+        } else if path.is_dir() {
+            zip.add_directory(format!("{}/", zip_path), options)
+                .map_err(|e| format!("Failed to add directory to backup: {}", e))?;
+            add_dir(zip, &path, &zip_path, options)?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,245 @@
+use crate::commands::validation::sanitize_instance_name;
+use crate::models::Instance;
+use crate::services::accounts::AccountManager;
+use crate::services::instance::InstanceManager;
+use crate::services::settings::SettingsManager;
+use crate::utils::*;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+const REDACTED: &str = "[REDACTED]";
+
+/// Only the tail of large logs is included, the crash is almost always near the end.
+const MAX_LOG_BYTES: usize = 2 * 1024 * 1024;
+
+/// Writes a zip with a system report, the instance's latest.log and newest crash report,
+/// instance.json and the launcher settings. Account names, UUIDs, tokens, proxy credentials,
+/// environment variable values and the user's home folder are scrubbed from every file.
+#[tauri::command]
+pub async fn save_debug_report(instance_name: Option<String>, output_path: String) -> Result<(), String> {
+    let safe_name = instance_name.as_deref().map(sanitize_instance_name).transpose()?;
+
+    tauri::async_runtime::spawn_blocking(move || write_debug_report(safe_name.as_deref(), Path::new(&output_path)))
+        .await
+        .map_err(|e| format!("Debug report task failed: {}", e))?
+}
+
+fn write_debug_report(instance_name: Option<&str>, output_path: &Path) -> Result<(), String> {
+    let redactor = Redactor::new();
+    let mut entries: Vec<(String, String)> = Vec::new();
+
+    let instance = match instance_name {
+        Some(name) => {
+            let instance_dir = get_instance_dir(name);
+            let content = std::fs::read_to_string(instance_dir.join("instance.json"))
+                .map_err(|_| format!("Instance '{}' does not exist", name))?;
+            let instance: Instance = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+
+            let mut value = serde_json::to_value(&instance).map_err(|e| e.to_string())?;
+            for key in ["settings_override", "launch_options"] {
+                if let Some(settings) = value.get_mut(key) {
+                    scrub_settings(settings);
+                }
+            }
+            entries.push(("instance.json".to_string(), to_pretty(&value)));
+
+            if let Some(log) = read_tail(&instance_dir.join("logs").join("latest.log")) {
+                entries.push(("latest.log".to_string(), log));
+            }
+
+            if let Some(report) = newest_crash_report(&instance_dir) {
+                let file_name = report.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+                if let Some(content) = read_tail(&report) {
+                    entries.push((format!("crash-reports/{}", file_name), content));
+                }
+            }
+
+            Some(instance)
+        }
+        None => None,
+    };
+
+    let settings = SettingsManager::load().unwrap_or_default();
+    let mut settings_value = serde_json::to_value(&settings).map_err(|e| e.to_string())?;
+    scrub_settings(&mut settings_value);
+    entries.push(("settings.json".to_string(), to_pretty(&settings_value)));
+
+    entries.insert(0, ("report.txt".to_string(), system_report(instance_name, instance.as_ref())));
+
+    if let Some(parent) = output_path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create output directory: {}", e))?;
+    }
+
+    let file = std::fs::File::create(output_path)
+        .map_err(|e| format!("Failed to create debug report: {}", e))?;
+    let mut zip = ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    for (name, content) in entries {
+        zip.start_file(name.as_str(), options)
+            .map_err(|e| format!("Failed to add {} to report: {}", name, e))?;
+        zip.write_all(redactor.redact(&content).as_bytes())
+            .map_err(|e| format!("Failed to write {}: {}", name, e))?;
+    }
+
+    zip.finish()
+        .map_err(|e| format!("Failed to finalize debug report: {}", e))?;
+
+    Ok(())
+}
+
+fn system_report(instance_name: Option<&str>, instance: Option<&Instance>) -> String {
+    let info = crate::commands::instances::collect_system_info();
+
+    let mut report = String::new();
+    report.push_str(&format!("Launcher version: {}\n", info.launcher_version));
+    report.push_str(&format!(
+        "OS: {} ({})\n",
+        info.os_version.or(info.os_name).unwrap_or_else(|| std::env::consts::OS.to_string()),
+        info.arch
+    ));
+    if let Some(kernel) = &info.kernel_version {
+        report.push_str(&format!("Kernel: {}\n", kernel));
+    }
+    report.push_str(&format!(
+        "CPU: {} ({} cores, {} threads)\n",
+        info.cpu_model.as_deref().unwrap_or("unknown"),
+        info.cpu_cores.map(|c| c.to_string()).unwrap_or_else(|| "?".to_string()),
+        info.cpu_threads
+    ));
+    for gpu in &info.gpus {
+        report.push_str(&format!(
+            "GPU: {}{} (driver {})\n",
+            gpu.vendor.as_deref().map(|v| format!("{} ", v)).unwrap_or_default(),
+            gpu.name,
+            gpu.driver_version.as_deref().unwrap_or("unknown")
+        ));
+    }
+    if info.gpus.is_empty() {
+        report.push_str("GPU: not detected\n");
+    }
+    report.push_str(&format!("Total memory: {} MB\n", info.total_memory_mb));
+    report.push_str(&format!("Available memory: {} MB\n", info.available_memory_mb));
+    report.push_str(&format!("Generated at: {}\n", chrono::Utc::now().to_rfc3339()));
+
+    if let (Some(name), Some(instance)) = (instance_name, instance) {
+        report.push_str(&format!("\nInstance: {}\n", name));
+        report.push_str(&format!("Version: {}\n", instance.version));
+        report.push_str(&format!(
+            "Loader: {} {}\n",
+            instance.loader.as_deref().unwrap_or("vanilla"),
+            instance.loader_version.as_deref().unwrap_or("")
+        ));
+
+        let mods_dir = get_instance_dir(name).join("mods");
+        let (enabled, disabled) = std::fs::read_dir(&mods_dir)
+            .map(|entries| {
+                entries.flatten().fold((0, 0), |(enabled, disabled), e| {
+                    let file_name = e.file_name().to_string_lossy().to_string();
+                    if file_name.ends_with(".jar") {
+                        (enabled + 1, disabled)
+                    } else if file_name.ends_with(".jar.disabled") {
+                        (enabled, disabled + 1)
+                    } else {
+                        (enabled, disabled)
+                    }
+                })
+            })
+            .unwrap_or((0, 0));
+        report.push_str(&format!("Mods: {} enabled, {} disabled\n", enabled, disabled));
+
+        if let Some(hint) = crate::services::crash_analysis::analyze_instance_crash(
+            &get_instance_dir(name),
+            std::time::SystemTime::UNIX_EPOCH,
+        ) {
+            report.push_str(&format!("\nDetected problem: {}\n{}\n{}\n", hint.title, hint.explanation, hint.suggestion));
+        }
+    }
+
+    report
+}
+
+/// Removes values that can identify the user or leak credentials.
+fn scrub_settings(settings: &mut serde_json::Value) {
+    if let Some(proxy) = settings.get_mut("http_proxy").filter(|p| !p.is_null()) {
+        *proxy = serde_json::Value::String(REDACTED.to_string());
+    }
+
+    if let Some(restricted) = settings.get_mut("restricted_mode").filter(|r| !r.is_null()) {
+        *restricted = serde_json::Value::String(REDACTED.to_string());
+    }
+
+    if let Some(env_vars) = settings.get_mut("env_vars").and_then(|v| v.as_object_mut()) {
+        for value in env_vars.values_mut() {
+            *value = serde_json::Value::String(REDACTED.to_string());
+        }
+    }
+}
+
+fn to_pretty(value: &serde_json::Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_default()
+}
+
+fn read_tail(path: &Path) -> Option<String> {
+    let bytes = std::fs::read(path).ok()?;
+    let start = bytes.len().saturating_sub(MAX_LOG_BYTES);
+    Some(String::from_utf8_lossy(&bytes[start..]).into_owned())
+}
+
+fn newest_crash_report(instance_dir: &Path) -> Option<PathBuf> {
+    std::fs::read_dir(instance_dir.join("crash-reports"))
+        .ok()?
+        .flatten()
+        .filter_map(|e| Some((e.metadata().ok()?.modified().ok()?, e.path())))
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path)
+}
+
+/// Replaces account names and UUIDs, the home folder path and session tokens in text.
+struct Redactor {
+    replacements: Vec<(String, &'static str)>,
+}
+
+impl Redactor {
+    fn new() -> Self {
+        let mut replacements = Vec::new();
+
+        for account in AccountManager::get_all_accounts().unwrap_or_default() {
+            let simple_uuid = account.uuid.replace('-', "");
+            replacements.push((account.uuid.clone(), "[UUID]"));
+            replacements.push((simple_uuid, "[UUID]"));
+            // Very short names would also match ordinary words
+            if account.username.len() >= 3 {
+                replacements.push((account.username, "[USERNAME]"));
+            }
+        }
+
+        if let Some(home) = dirs::home_dir() {
+            let home = home.to_string_lossy().to_string();
+            replacements.push((home.replace('\\', "/"), "~"));
+            replacements.push((home.replace('\\', "\\\\"), "~"));
+            replacements.push((home, "~"));
+        }
+
+        // Longest first so a path is replaced before a username it contains
+        replacements.retain(|(from, _)| !from.is_empty());
+        replacements.sort_by_key(|r| std::cmp::Reverse(r.0.len()));
+
+        Self { replacements }
+    }
+
+    fn redact(&self, text: &str) -> String {
+        let mut result = text.to_string();
+        for (from, to) in &self.replacements {
+            result = result.replace(from.as_str(), to);
+        }
+
+        result
+            .lines()
+            .map(|line| InstanceManager::scrub_access_token(line, ""))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
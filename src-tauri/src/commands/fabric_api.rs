@@ -0,0 +1,124 @@
+use crate::commands::mods::invalidate_mod_cache;
+use crate::commands::modpacks::extract_minecraft_version_from_instance;
+use crate::commands::validation::{sanitize_instance_name, sanitize_mod_filename, validate_download_url};
+use crate::models::Instance;
+use crate::utils::get_instance_dir;
+use crate::utils::mod_metadata::read_jar_mod_info;
+use crate::utils::modrinth::ModrinthClient;
+use std::path::Path;
+use tauri::Emitter;
+
+const FABRIC_API_PROJECT_ID: &str = "P7dR8mSH";
+const FABRIC_API_MOD_ID: &str = "fabric-api";
+
+/// Whether the instance uses Fabric but has no Fabric API jar (enabled or disabled) in its mods folder.
+#[tauri::command]
+pub async fn is_fabric_api_missing(instance_name: String) -> Result<bool, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let instance = load_instance(&safe_name)?;
+
+    Ok(is_fabric(&instance) && !has_fabric_api(&get_instance_dir(&safe_name).join("mods")))
+}
+
+/// Installs the newest Fabric API release for the instance's game version.
+/// Returns the filename of the installed jar.
+#[tauri::command]
+pub async fn install_fabric_api(instance_name: String) -> Result<String, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let instance = load_instance(&safe_name)?;
+
+    if !is_fabric(&instance) {
+        return Err("Fabric API can only be installed into Fabric instances".to_string());
+    }
+
+    let game_version = extract_minecraft_version_from_instance(&instance.version);
+    let filename = install_fabric_api_into(&get_instance_dir(&safe_name).join("mods"), &game_version).await?;
+    invalidate_mod_cache(&safe_name);
+
+    Ok(filename)
+}
+
+/// Emits `fabric-api-missing` so the frontend can offer to install it, e.g. after importing a modpack.
+pub(crate) fn notify_if_fabric_api_missing(app_handle: &tauri::AppHandle, instance_name: &str) {
+    let Ok(instance) = load_instance(instance_name) else {
+        return;
+    };
+
+    if !is_fabric(&instance) || has_fabric_api(&get_instance_dir(instance_name).join("mods")) {
+        return;
+    }
+
+    let _ = app_handle.emit("fabric-api-missing", serde_json::json!({
+        "instance": instance_name,
+        "game_version": extract_minecraft_version_from_instance(&instance.version)
+    }));
+}
+
+pub(crate) async fn install_fabric_api_into(mods_dir: &Path, game_version: &str) -> Result<String, String> {
+    if has_fabric_api(mods_dir) {
+        return Err("Fabric API is already installed".to_string());
+    }
+
+    let client = ModrinthClient::new().map_err(|e| e.to_string())?;
+    let versions = client
+        .get_project_versions(
+            FABRIC_API_PROJECT_ID,
+            Some(vec!["fabric".to_string()]),
+            Some(vec![game_version.to_string()]),
+        )
+        .await
+        .map_err(|e| format!("Failed to fetch Fabric API versions: {}", e))?;
+
+    // Prefer a stable release, but fall back to betas for fresh game versions
+    let version = versions
+        .iter()
+        .find(|v| v.version_type == "release")
+        .or_else(|| versions.first())
+        .ok_or_else(|| format!("No Fabric API release found for Minecraft {}", game_version))?;
+
+    let file = version
+        .files
+        .iter()
+        .find(|f| f.primary)
+        .or_else(|| version.files.first())
+        .ok_or("Fabric API version has no files")?;
+
+    let filename = sanitize_mod_filename(&file.filename)?;
+    validate_download_url(&file.url)?;
+
+    std::fs::create_dir_all(mods_dir).map_err(|e| e.to_string())?;
+    client
+        .download_mod_file(&file.url, &mods_dir.join(&filename))
+        .await
+        .map_err(|e| format!("Failed to download Fabric API: {}", e))?;
+
+    Ok(filename)
+}
+
+fn load_instance(instance_name: &str) -> Result<Instance, String> {
+    let instance_json = get_instance_dir(instance_name).join("instance.json");
+    let content = std::fs::read_to_string(&instance_json)
+        .map_err(|_| format!("Instance '{}' does not exist", instance_name))?;
+    serde_json::from_str(&content).map_err(|e| e.to_string())
+}
+
+fn is_fabric(instance: &Instance) -> bool {
+    instance.loader.as_deref() == Some("fabric")
+}
+
+/// Checks filenames first and falls back to the mod id in the jar, since packs often rename jars.
+fn has_fabric_api(mods_dir: &Path) -> bool {
+    let Ok(entries) = std::fs::read_dir(mods_dir) else {
+        return false;
+    };
+
+    entries.flatten().any(|entry| {
+        let name = entry.file_name().to_string_lossy().to_lowercase();
+        if !(name.ends_with(".jar") || name.ends_with(".jar.disabled")) {
+            return false;
+        }
+
+        name.starts_with(FABRIC_API_MOD_ID)
+            || read_jar_mod_info(&entry.path()).is_some_and(|info| info.id == FABRIC_API_MOD_ID)
+    })
+}
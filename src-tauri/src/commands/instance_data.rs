@@ -0,0 +1,409 @@
+use crate::commands::mods::invalidate_mod_cache;
+use crate::commands::validation::sanitize_instance_name;
+use crate::models::{ConfigRollbackSummary, ConfigSnapshotInfo, Instance};
+use crate::services::config_snapshot::ConfigSnapshotManager;
+use crate::utils::get_instance_dir;
+use crate::utils::mod_metadata::read_jar_mod_info;
+use crate::services::operations::{OperationKind, ProgressEvent};
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+const COPYABLE_CATEGORIES: &[&str] = &["saves", "config", "mods", "resourcepacks", "options.txt"];
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct CopyDataSummary {
+    pub copied: usize,
+    pub skipped: usize,
+    pub renamed: usize,
+    pub overwritten: usize,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ConflictMode {
+    Skip,
+    Overwrite,
+    Rename,
+}
+
+/// Copies worlds, configs, mods, resource packs or `options.txt` from one instance to another.
+///
+/// `on_conflict` decides what happens when an entry already exists in the target:
+/// `skip` (default), `overwrite` or `rename` (keeps both, e.g. `World (1)`).
+#[tauri::command]
+pub async fn copy_instance_data(
+    source_instance: String,
+    target_instance: String,
+    categories: Vec<String>,
+    on_conflict: Option<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<CopyDataSummary, String> {
+    let safe_source = sanitize_instance_name(&source_instance)?;
+    let safe_target = sanitize_instance_name(&target_instance)?;
+    let _lock = crate::services::instance_lock::lock(&app_handle, &safe_target, "data copy")?;
+    let operation = crate::services::operations::track(&app_handle, OperationKind::CopyData, Some(&safe_target));
+
+    if safe_source == safe_target {
+        return Err("Source and target instance cannot be the same".to_string());
+    }
+
+    if let Some(category) = categories.iter().find(|c| !COPYABLE_CATEGORIES.contains(&c.as_str())) {
+        return Err(format!("Unknown data category '{}'", category));
+    }
+
+    let mode = match on_conflict.as_deref() {
+        None | Some("skip") => ConflictMode::Skip,
+        Some("overwrite") => ConflictMode::Overwrite,
+        Some("rename") => ConflictMode::Rename,
+        Some(other) => return Err(format!("Unknown conflict mode '{}'", other)),
+    };
+
+    let source_dir = get_instance_dir(&safe_source);
+    let target_dir = get_instance_dir(&safe_target);
+
+    for (name, dir) in [(&safe_source, &source_dir), (&safe_target, &target_dir)] {
+        if !dir.join("instance.json").exists() {
+            return Err(format!("Instance '{}' does not exist", name));
+        }
+    }
+
+    if crate::commands::instances::is_running(&safe_target)? {
+        return Err("Close the target instance before copying data into it".to_string());
+    }
+
+    // Each top-level entry (a world, a mod jar, a config file or folder) is copied as one unit
+    let mut entries: Vec<(PathBuf, PathBuf)> = Vec::new();
+    for category in &categories {
+        let source_path = source_dir.join(category);
+        let target_path = target_dir.join(category);
+
+        if !source_path.exists() {
+            continue;
+        }
+
+        if source_path.is_file() {
+            entries.push((source_path, target_path));
+            continue;
+        }
+
+        for entry in std::fs::read_dir(&source_path).map_err(|e| e.to_string())?.flatten() {
+            entries.push((entry.path(), target_path.join(entry.file_name())));
+        }
+    }
+
+    let total = entries.len().max(1);
+    let mut summary = CopyDataSummary::default();
+
+    for (index, (from, to)) in entries.iter().enumerate() {
+        let entry_name = from.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+
+        ProgressEvent::new(OperationKind::CopyData, Some(&safe_target))
+            .percent((index * 100 / total) as u32)
+            .stage(format!("Copying {}...", entry_name))
+            .emit(&app_handle);
+
+        let destination = if to.exists() {
+            match mode {
+                ConflictMode::Skip => {
+                    summary.skipped += 1;
+                    continue;
+                }
+                ConflictMode::Overwrite => {
+                    remove_path(to).map_err(|e| format!("Failed to replace {}: {}", entry_name, e))?;
+                    summary.overwritten += 1;
+                    to.clone()
+                }
+                ConflictMode::Rename => {
+                    summary.renamed += 1;
+                    unique_destination(to)
+                }
+            }
+        } else {
+            to.clone()
+        };
+
+        copy_path(from, &destination).map_err(|e| format!("Failed to copy {}: {}", entry_name, e))?;
+        summary.copied += 1;
+    }
+
+    if categories.iter().any(|c| c == "mods") {
+        invalidate_mod_cache(&safe_target);
+    }
+
+    ProgressEvent::new(OperationKind::CopyData, Some(&safe_target))
+        .percent(100)
+        .stage("Copy complete")
+        .emit(&app_handle);
+
+    operation.complete();
+    Ok(summary)
+}
+
+/// Saves a copy of the instance's `config/` folder that can be restored later with
+/// `rollback_instance_config`. Returns the snapshot id.
+#[tauri::command]
+pub async fn snapshot_instance_config(instance_name: String, label: Option<String>) -> Result<String, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    if !get_instance_dir(&safe_name).join("instance.json").exists() {
+        return Err(format!("Instance '{}' does not exist", safe_name));
+    }
+
+    let label = label.map(|l| l.trim().to_string()).filter(|l| !l.is_empty());
+    if label.as_ref().is_some_and(|l| l.len() > 100) {
+        return Err("Snapshot label is too long (max 100 characters)".to_string());
+    }
+
+    tauri::async_runtime::spawn_blocking(move || {
+        ConfigSnapshotManager::create(&safe_name, label)
+            .map(|snapshot| snapshot.id)
+            .map_err(|e| format!("Failed to snapshot config: {}", e))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+pub async fn list_config_snapshots(instance_name: String) -> Result<Vec<ConfigSnapshotInfo>, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    Ok(ConfigSnapshotManager::list(&safe_name))
+}
+
+#[tauri::command]
+pub async fn delete_config_snapshot(snapshot_id: String) -> Result<(), String> {
+    ConfigSnapshotManager::delete(&snapshot_id).map_err(|e| e.to_string())
+}
+
+/// Restores the instance's `config/` folder to the state captured in the snapshot.
+#[tauri::command]
+pub async fn rollback_instance_config(snapshot_id: String) -> Result<ConfigRollbackSummary, String> {
+    let snapshot = ConfigSnapshotManager::get(&snapshot_id).map_err(|e| e.to_string())?;
+    let safe_name = sanitize_instance_name(&snapshot.instance)?;
+
+    if !get_instance_dir(&safe_name).join("instance.json").exists() {
+        return Err(format!("Instance '{}' no longer exists", safe_name));
+    }
+
+    if crate::commands::instances::is_running(&safe_name)? {
+        return Err("Close the game before rolling back its config".to_string());
+    }
+
+    tauri::async_runtime::spawn_blocking(move || {
+        ConfigSnapshotManager::rollback(&snapshot).map_err(|e| format!("Failed to roll back config: {}", e))
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DiffMod {
+    /// Mod id from the jar metadata, or the filename when the jar has none.
+    pub id: String,
+    pub name: Option<String>,
+    pub version: Option<String>,
+    pub filename: String,
+    pub disabled: bool,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ModVersionChange {
+    pub id: String,
+    pub name: Option<String>,
+    pub version_a: Option<String>,
+    pub version_b: Option<String>,
+    pub filename_a: String,
+    pub filename_b: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct InstanceDiff {
+    pub version_a: String,
+    pub version_b: String,
+    pub loader_a: Option<String>,
+    pub loader_b: Option<String>,
+    pub mods_only_in_a: Vec<DiffMod>,
+    pub mods_only_in_b: Vec<DiffMod>,
+    pub mod_version_changes: Vec<ModVersionChange>,
+    /// Paths relative to the instance, e.g. `config/sodium-options.json` or `options.txt`.
+    pub configs_only_in_a: Vec<String>,
+    pub configs_only_in_b: Vec<String>,
+    pub configs_changed: Vec<String>,
+}
+
+/// Compares the mods (by mod id) and config files of two instances.
+#[tauri::command]
+pub async fn diff_instances(instance_a: String, instance_b: String) -> Result<InstanceDiff, String> {
+    let safe_a = sanitize_instance_name(&instance_a)?;
+    let safe_b = sanitize_instance_name(&instance_b)?;
+
+    tauri::async_runtime::spawn_blocking(move || {
+        let dir_a = get_instance_dir(&safe_a);
+        let dir_b = get_instance_dir(&safe_b);
+        let meta_a = load_instance(&safe_a, &dir_a)?;
+        let meta_b = load_instance(&safe_b, &dir_b)?;
+
+        let mods_a = collect_mods(&dir_a.join("mods"));
+        let mut mods_b = collect_mods(&dir_b.join("mods"));
+
+        let mut mods_only_in_a = Vec::new();
+        let mut mod_version_changes = Vec::new();
+
+        for (id, mod_a) in mods_a {
+            match mods_b.remove(&id) {
+                None => mods_only_in_a.push(mod_a),
+                Some(mod_b) => {
+                    if mod_a.version != mod_b.version || (mod_a.version.is_none() && mod_a.filename != mod_b.filename) {
+                        mod_version_changes.push(ModVersionChange {
+                            id,
+                            name: mod_a.name.or(mod_b.name),
+                            version_a: mod_a.version,
+                            version_b: mod_b.version,
+                            filename_a: mod_a.filename,
+                            filename_b: mod_b.filename,
+                        });
+                    }
+                }
+            }
+        }
+
+        let configs_a = collect_configs(&dir_a);
+        let configs_b = collect_configs(&dir_b);
+
+        let configs_changed = configs_a
+            .intersection(&configs_b)
+            .filter(|relative| files_differ(&dir_a.join(relative), &dir_b.join(relative)))
+            .cloned()
+            .collect();
+
+        Ok(InstanceDiff {
+            version_a: meta_a.version,
+            version_b: meta_b.version,
+            loader_a: meta_a.loader,
+            loader_b: meta_b.loader,
+            mods_only_in_a,
+            mods_only_in_b: mods_b.into_values().collect(),
+            mod_version_changes,
+            configs_only_in_a: configs_a.difference(&configs_b).cloned().collect(),
+            configs_only_in_b: configs_b.difference(&configs_a).cloned().collect(),
+            configs_changed,
+        })
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+fn load_instance(name: &str, instance_dir: &Path) -> Result<Instance, String> {
+    let content = std::fs::read_to_string(instance_dir.join("instance.json"))
+        .map_err(|_| format!("Instance '{}' does not exist", name))?;
+    serde_json::from_str(&content).map_err(|e| e.to_string())
+}
+
+fn collect_mods(mods_dir: &Path) -> BTreeMap<String, DiffMod> {
+    let mut mods = BTreeMap::new();
+
+    let Ok(entries) = std::fs::read_dir(mods_dir) else {
+        return mods;
+    };
+
+    for entry in entries.flatten() {
+        let filename = entry.file_name().to_string_lossy().to_string();
+        let disabled = filename.ends_with(".jar.disabled");
+        if !filename.ends_with(".jar") && !disabled {
+            continue;
+        }
+
+        let info = read_jar_mod_info(&entry.path());
+        let id = info.as_ref().map(|i| i.id.clone()).unwrap_or_else(|| filename.trim_end_matches(".disabled").to_string());
+
+        mods.insert(id.clone(), DiffMod {
+            id,
+            name: info.as_ref().and_then(|i| i.name.clone()),
+            version: info.and_then(|i| i.version),
+            filename,
+            disabled,
+        });
+    }
+
+    mods
+}
+
+/// Relative paths of every file under `config/`, plus `options.txt`.
+fn collect_configs(instance_dir: &Path) -> BTreeSet<String> {
+    let mut configs = BTreeSet::new();
+
+    if instance_dir.join("options.txt").is_file() {
+        configs.insert("options.txt".to_string());
+    }
+
+    let mut pending = vec![instance_dir.join("config")];
+    while let Some(dir) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if let Ok(relative) = path.strip_prefix(instance_dir) {
+                configs.insert(relative.to_string_lossy().replace('\\', "/"));
+            }
+        }
+    }
+
+    configs
+}
+
+fn files_differ(a: &Path, b: &Path) -> bool {
+    match (std::fs::metadata(a), std::fs::metadata(b)) {
+        (Ok(meta_a), Ok(meta_b)) if meta_a.len() != meta_b.len() => true,
+        (Ok(_), Ok(_)) => std::fs::read(a).ok() != std::fs::read(b).ok(),
+        _ => true,
+    }
+}
+
+/// `World` -> `World (1)`, `mod.jar` -> `mod (1).jar`, keeping `.jar.disabled` style suffixes intact.
+fn unique_destination(path: &Path) -> PathBuf {
+    let parent = path.parent().unwrap_or(Path::new(""));
+    let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+
+    let (stem, suffix) = if path.is_dir() {
+        (file_name.as_str(), "")
+    } else {
+        match file_name.find('.') {
+            Some(pos) if pos > 0 => file_name.split_at(pos),
+            _ => (file_name.as_str(), ""),
+        }
+    };
+
+    let mut counter = 1;
+    loop {
+        let candidate = parent.join(format!("{} ({}){}", stem, counter, suffix));
+        if !candidate.exists() {
+            return candidate;
+        }
+        counter += 1;
+    }
+}
+
+fn remove_path(path: &Path) -> std::io::Result<()> {
+    if path.is_dir() {
+        std::fs::remove_dir_all(path)
+    } else {
+        std::fs::remove_file(path)
+    }
+}
+
+fn copy_path(from: &Path, to: &Path) -> std::io::Result<()> {
+    if let Some(parent) = to.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+
+    if from.is_dir() {
+        std::fs::create_dir_all(to)?;
+        for entry in std::fs::read_dir(from)?.flatten() {
+            copy_path(&entry.path(), &to.join(entry.file_name()))?;
+        }
+        Ok(())
+    } else {
+        crate::utils::copy_file_unlinked(from, to).map(|_| ())
+    }
+}
@@ -1,9 +1,14 @@
 use crate::commands::validation::sanitize_instance_name;
 use crate::models::Instance;
 use crate::utils::*;
+use crate::services::mod_index::{ModIndex, ModProvenance};
+use crate::services::operations::{OperationKind, ProgressEvent};
 use std::io::Write;
+use std::path::{Path, PathBuf};
 use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
 
+const DEFAULT_COMPRESSION_LEVEL: u8 = 6;
+
 #[tauri::command]
 pub async fn export_instance(
     instance_name: String,
@@ -14,14 +19,30 @@ pub async fn export_instance(
     include_shader_packs: bool,
     include_mods: bool,
     include_config: bool,
+    compression_level: Option<u8>,
+    exclude_patterns: Option<Vec<String>>,
+    app_handle: tauri::AppHandle,
 ) -> Result<(), String> {
     let safe_name = sanitize_instance_name(&instance_name)?;
+    let operation = crate::services::operations::track(&app_handle, OperationKind::Export, Some(&safe_name));
     let instance_dir = get_instance_dir(&safe_name);
 
     if !instance_dir.exists() {
         return Err(format!("Instance '{}' does not exist", safe_name));
     }
 
+    let compression_level = compression_level.unwrap_or(DEFAULT_COMPRESSION_LEVEL);
+    if compression_level > 9 {
+        return Err("Compression level must be between 0 and 9".to_string());
+    }
+
+    let excludes: Vec<String> = exclude_patterns
+        .unwrap_or_default()
+        .into_iter()
+        .map(|p| p.trim().replace('\\', "/"))
+        .filter(|p| !p.is_empty())
+        .collect();
+
     let output_path_obj = std::path::Path::new(&output_path);
     if let Some(parent) = output_path_obj.parent() {
         if !parent.exists() {
@@ -30,129 +51,427 @@ pub async fn export_instance(
         }
     }
 
-    let file = std::fs::File::create(&output_path)
-        .map_err(|e| format!("Failed to create output file: {}", e))?;
-
-    let mut zip = ZipWriter::new(file);
-    let options = SimpleFileOptions::default()
-        .compression_method(CompressionMethod::Deflated)
-        .unix_permissions(0o755);
-
-    if export_format == "mrpack" {
-        export_as_mrpack(
-            &mut zip,
-            &safe_name,
-            &instance_dir,
-            options,
-            include_worlds,
-            include_resource_packs,
-            include_shader_packs,
-            include_mods,
-            include_config,
-        )?;
-    } else {
-        export_as_zip(
-            &mut zip,
-            &instance_dir,
-            options,
-            include_worlds,
-            include_resource_packs,
-            include_shader_packs,
-            include_mods,
-            include_config,
-        )?;
-    }
+    let selection = ExportSelection {
+        include_worlds,
+        include_resource_packs,
+        include_shader_packs,
+        include_mods,
+        include_config,
+    };
+
+    // Zipping multi-GB instances is blocking work, keep it off the async runtime
+    tauri::async_runtime::spawn_blocking(move || {
+        ProgressEvent::new(OperationKind::Export, Some(&safe_name))
+            .percent(0)
+            .stage("Collecting files...")
+            .emit(&app_handle);
+
+        let mut plan = ExportPlan::new(&instance_dir, excludes);
+        let manifest = if export_format == "mrpack" {
+            Some(plan_mrpack(&mut plan, &safe_name, &instance_dir, &selection)?)
+        } else {
+            plan_zip(&mut plan, &instance_dir, &selection);
+            None
+        };
+
+        let file = std::fs::File::create(&output_path)
+            .map_err(|e| format!("Failed to create output file: {}", e))?;
+
+        let mut zip = ZipWriter::new(file);
+        let options = if compression_level == 0 {
+            SimpleFileOptions::default().compression_method(CompressionMethod::Stored)
+        } else {
+            SimpleFileOptions::default()
+                .compression_method(CompressionMethod::Deflated)
+                .compression_level(Some(compression_level as i64))
+        }
+        .unix_permissions(0o755)
+        .large_file(true);
+
+        write_entries(&mut zip, &plan.entries, options, &safe_name, &app_handle)?;
+
+        if let Some(manifest) = manifest {
+            let manifest_json = serde_json::to_string_pretty(&manifest)
+                .map_err(|e| format!("Failed to serialize manifest: {}", e))?;
+
+            zip.start_file("modrinth.index.json", options)
+                .map_err(|e| format!("Failed to create manifest file: {}", e))?;
+            zip.write_all(manifest_json.as_bytes())
+                .map_err(|e| format!("Failed to write manifest: {}", e))?;
+        }
+
+        zip.finish()
+            .map_err(|e| format!("Failed to finalize zip: {}", e))?;
+
+        ProgressEvent::new(OperationKind::Export, Some(&safe_name))
+            .percent(100)
+            .stage("Export complete!")
+            .emit(&app_handle);
 
-    zip.finish()
-        .map_err(|e| format!("Failed to finalize zip: {}", e))?;
+        Ok::<(), String>(())
+    })
+    .await
+    .map_err(|e| format!("Export task failed: {}", e))??;
 
+    operation.complete();
     Ok(())
 }
 
-fn export_as_zip(
-    zip: &mut ZipWriter<std::fs::File>,
-    instance_dir: &std::path::Path,
-    options: SimpleFileOptions,
+/// Files the launcher keeps next to the game files, left out of plain exports.
+const LAUNCHER_METADATA: &[&str] = &[
+    "instance.json",
+    "icon.png",
+    "icon.gif",
+    "cover.png",
+    "mods.index.json",
+    ".mod_cache.json",
+    ".resourcepack_cache.json",
+    ".shaderpack_cache.json",
+    ".modpack_files.json",
+    ".pending_install.json",
+    ".mod_bisect.json",
+    "natives",
+];
+
+/// Copies an instance into `destination` as a plain `.minecraft` folder, for server hosts and
+/// launchers that expect a raw game directory. Returns the destination path.
+#[tauri::command]
+pub async fn export_instance_plain(
+    instance_name: String,
+    destination: String,
+    app_handle: tauri::AppHandle,
+) -> Result<String, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let instance_dir = get_instance_dir(&safe_name);
+
+    if !instance_dir.exists() {
+        return Err(format!("Instance '{}' does not exist", safe_name));
+    }
+
+    let destination = PathBuf::from(destination.trim());
+    if destination.as_os_str().is_empty() {
+        return Err("Destination folder is required".to_string());
+    }
+
+    if destination.exists() {
+        let is_empty = std::fs::read_dir(&destination)
+            .map_err(|e| format!("Failed to read destination folder: {}", e))?
+            .next()
+            .is_none();
+        if !is_empty {
+            return Err("Destination folder must be empty".to_string());
+        }
+    }
+
+    let canonical_instance = instance_dir
+        .canonicalize()
+        .map_err(|e| format!("Failed to resolve instance folder: {}", e))?;
+    let destination_parent = destination
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .and_then(|p| p.canonicalize().ok());
+    if destination_parent.is_some_and(|p| p.starts_with(&canonical_instance)) {
+        return Err("Destination can't be inside the instance folder".to_string());
+    }
+
+    let operation = crate::services::operations::track(&app_handle, OperationKind::Export, Some(&safe_name));
+
+    let output = destination.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        ProgressEvent::new(OperationKind::Export, Some(&safe_name))
+            .percent(0)
+            .stage("Collecting files...")
+            .emit(&app_handle);
+
+        let mut plan = ExportPlan::new(&instance_dir, Vec::new());
+        plan_plain(&mut plan, &instance_dir);
+
+        std::fs::create_dir_all(&destination)
+            .map_err(|e| format!("Failed to create destination folder: {}", e))?;
+
+        copy_entries(&plan.entries, &destination, &safe_name, &app_handle)?;
+
+        ProgressEvent::new(OperationKind::Export, Some(&safe_name))
+            .percent(100)
+            .stage("Export complete!")
+            .emit(&app_handle);
+
+        Ok::<(), String>(())
+    })
+    .await
+    .map_err(|e| format!("Export task failed: {}", e))??;
+
+    operation.complete();
+    Ok(output.to_string_lossy().to_string())
+}
+
+struct ExportSelection {
     include_worlds: bool,
     include_resource_packs: bool,
     include_shader_packs: bool,
     include_mods: bool,
     include_config: bool,
-) -> Result<(), String> {
-    let instance_json = instance_dir.join("instance.json");
-    if instance_json.exists() {
-        add_file_to_zip(zip, &instance_json, "instance.json", options)?;
+}
+
+/// A file or directory (zip path ending in `/`) queued for the archive.
+struct ExportEntry {
+    source: PathBuf,
+    zip_path: String,
+}
+
+/// Collects archive entries up front so progress can be reported against a known total.
+struct ExportPlan<'a> {
+    instance_dir: &'a Path,
+    excludes: Vec<String>,
+    entries: Vec<ExportEntry>,
+}
+
+impl<'a> ExportPlan<'a> {
+    fn new(instance_dir: &'a Path, excludes: Vec<String>) -> Self {
+        Self {
+            instance_dir,
+            excludes,
+            entries: Vec::new(),
+        }
+    }
+
+    fn is_excluded(&self, path: &Path, is_dir: bool) -> bool {
+        let relative = match path.strip_prefix(self.instance_dir) {
+            Ok(rel) => rel.to_string_lossy().replace('\\', "/"),
+            Err(_) => return false,
+        };
+
+        self.excludes
+            .iter()
+            .any(|pattern| matches_exclusion(pattern, &relative, is_dir))
     }
 
-    let icon_path = instance_dir.join("icon.png");
-    if icon_path.exists() {
-        add_file_to_zip(zip, &icon_path, "icon.png", options)?;
+    fn add_file(&mut self, path: &Path, zip_path: &str) {
+        if !path.is_file() || self.is_excluded(path, false) {
+            return;
+        }
+
+        self.entries.push(ExportEntry {
+            source: path.to_path_buf(),
+            zip_path: zip_path.to_string(),
+        });
     }
 
-    if include_worlds {
-        let saves_dir = instance_dir.join("saves");
-        if saves_dir.exists() {
-            add_dir_to_zip(zip, &saves_dir, "saves", options)?;
+    fn add_dir(&mut self, dir_path: &Path, zip_prefix: &str) {
+        if !dir_path.is_dir() || self.is_excluded(dir_path, true) {
+            return;
+        }
+
+        let Ok(entries) = std::fs::read_dir(dir_path) else {
+            return;
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let zip_path = format!("{}/{}", zip_prefix, entry.file_name().to_string_lossy());
+
+            if path.is_file() {
+                self.add_file(&path, &zip_path);
+            } else if path.is_dir() && !self.is_excluded(&path, true) {
+                self.entries.push(ExportEntry {
+                    source: path.clone(),
+                    zip_path: format!("{}/", zip_path),
+                });
+                self.add_dir(&path, &zip_path);
+            }
         }
     }
+}
 
-    if include_resource_packs {
-        let resourcepacks_dir = instance_dir.join("resourcepacks");
-        if resourcepacks_dir.exists() {
-            add_dir_to_zip(zip, &resourcepacks_dir, "resourcepacks", options)?;
+/// Matches an exclusion rule against an instance-relative path.
+///
+/// `logs/` excludes any directory named `logs`, `*.bak` matches file names anywhere,
+/// and patterns containing `/` are matched against the full relative path.
+fn matches_exclusion(pattern: &str, relative: &str, is_dir: bool) -> bool {
+    if let Some(dir_pattern) = pattern.strip_suffix('/') {
+        if !is_dir {
+            return false;
+        }
+        if dir_pattern.contains('/') {
+            return glob_match(dir_pattern, relative);
         }
+        return relative
+            .rsplit('/')
+            .next()
+            .is_some_and(|name| glob_match(dir_pattern, name));
     }
 
-    if include_shader_packs {
-        let shaderpacks_dir = instance_dir.join("shaderpacks");
-        if shaderpacks_dir.exists() {
-            add_dir_to_zip(zip, &shaderpacks_dir, "shaderpacks", options)?;
+    if pattern.contains('/') {
+        return glob_match(pattern, relative);
+    }
+
+    relative
+        .rsplit('/')
+        .next()
+        .is_some_and(|name| glob_match(pattern, name))
+}
+
+/// Minimal glob supporting `*` and `?`, case-insensitive.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    let mut star: Option<usize> = None;
+    let mut star_t = 0;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == text[t]) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some(p);
+            star_t = t;
+            p += 1;
+        } else if let Some(s) = star {
+            p = s + 1;
+            star_t += 1;
+            t = star_t;
+        } else {
+            return false;
         }
     }
 
-    if include_mods {
-        let mods_dir = instance_dir.join("mods");
-        if mods_dir.exists() {
-            add_dir_to_zip(zip, &mods_dir, "mods", options)?;
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+fn write_entries(
+    zip: &mut ZipWriter<std::fs::File>,
+    entries: &[ExportEntry],
+    options: SimpleFileOptions,
+    instance_name: &str,
+    app_handle: &tauri::AppHandle,
+) -> Result<(), String> {
+    let total = entries.len().max(1);
+    let mut last_progress = 0;
+
+    for (idx, entry) in entries.iter().enumerate() {
+        if entry.zip_path.ends_with('/') {
+            zip.add_directory(entry.zip_path.as_str(), options)
+                .map_err(|e| format!("Failed to add directory to zip: {}", e))?;
+        } else {
+            add_file_to_zip(zip, &entry.source, &entry.zip_path, options)?;
+        }
+
+        let progress = ((idx + 1) * 99 / total) as u32;
+        if progress != last_progress {
+            last_progress = progress;
+            ProgressEvent::new(OperationKind::Export, Some(instance_name))
+                .percent(progress)
+                .stage(format!("Compressing files... ({}/{})", idx + 1, entries.len()))
+                .emit(app_handle);
         }
     }
 
-    if include_config {
-        let config_dir = instance_dir.join("config");
-        if config_dir.exists() {
-            add_dir_to_zip(zip, &config_dir, "config", options)?;
+    Ok(())
+}
+
+/// Queues everything in the instance folder except launcher metadata and partial downloads.
+fn plan_plain(plan: &mut ExportPlan, instance_dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(instance_dir) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        if LAUNCHER_METADATA.contains(&name.as_str()) {
+            continue;
         }
 
-        let options_txt = instance_dir.join("options.txt");
-        if options_txt.exists() {
-            add_file_to_zip(zip, &options_txt, "options.txt", options)?;
+        let path = entry.path();
+        if path.is_dir() {
+            plan.entries.push(ExportEntry {
+                source: path.clone(),
+                zip_path: format!("{}/", name),
+            });
+            plan.add_dir(&path, &name);
+        } else {
+            plan.add_file(&path, &name);
         }
+    }
+
+    plan.entries.retain(|entry| !entry.zip_path.trim_end_matches('/').ends_with(".part"));
+}
 
-        let optionsof_txt = instance_dir.join("optionsof.txt");
-        if optionsof_txt.exists() {
-            add_file_to_zip(zip, &optionsof_txt, "optionsof.txt", options)?;
+/// Copies planned entries below `destination`, using their archive paths as relative paths.
+fn copy_entries(
+    entries: &[ExportEntry],
+    destination: &Path,
+    instance_name: &str,
+    app_handle: &tauri::AppHandle,
+) -> Result<(), String> {
+    let total = entries.len().max(1);
+    let mut last_progress = 0;
+
+    for (idx, entry) in entries.iter().enumerate() {
+        let target = destination.join(entry.zip_path.trim_end_matches('/'));
+
+        if entry.zip_path.ends_with('/') {
+            std::fs::create_dir_all(&target)
+                .map_err(|e| format!("Failed to create {}: {}", entry.zip_path, e))?;
+        } else {
+            if let Some(parent) = target.parent() {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+            }
+            std::fs::copy(&entry.source, &target)
+                .map_err(|e| format!("Failed to copy {}: {}", entry.zip_path, e))?;
         }
 
-        let optionsshaders_txt = instance_dir.join("optionsshaders.txt");
-        if optionsshaders_txt.exists() {
-            add_file_to_zip(zip, &optionsshaders_txt, "optionsshaders.txt", options)?;
+        let progress = ((idx + 1) * 99 / total) as u32;
+        if progress != last_progress {
+            last_progress = progress;
+            ProgressEvent::new(OperationKind::Export, Some(instance_name))
+                .percent(progress)
+                .stage(format!("Copying files... ({}/{})", idx + 1, entries.len()))
+                .emit(app_handle);
         }
     }
 
     Ok(())
 }
 
-fn export_as_mrpack(
-    zip: &mut ZipWriter<std::fs::File>,
+fn plan_zip(plan: &mut ExportPlan, instance_dir: &Path, selection: &ExportSelection) {
+    plan.add_file(&instance_dir.join("instance.json"), "instance.json");
+    plan.add_file(&instance_dir.join("icon.png"), "icon.png");
+    plan.add_file(&instance_dir.join("icon.gif"), "icon.gif");
+    plan.add_file(&instance_dir.join("cover.png"), "cover.png");
+
+    if selection.include_worlds {
+        plan.add_dir(&instance_dir.join("saves"), "saves");
+    }
+
+    if selection.include_resource_packs {
+        plan.add_dir(&instance_dir.join("resourcepacks"), "resourcepacks");
+    }
+
+    if selection.include_shader_packs {
+        plan.add_dir(&instance_dir.join("shaderpacks"), "shaderpacks");
+    }
+
+    if selection.include_mods {
+        plan.add_dir(&instance_dir.join("mods"), "mods");
+    }
+
+    if selection.include_config {
+        plan.add_dir(&instance_dir.join("config"), "config");
+        plan.add_file(&instance_dir.join("options.txt"), "options.txt");
+        plan.add_file(&instance_dir.join("optionsof.txt"), "optionsof.txt");
+        plan.add_file(&instance_dir.join("optionsshaders.txt"), "optionsshaders.txt");
+    }
+}
+
+fn plan_mrpack(
+    plan: &mut ExportPlan,
     instance_name: &str,
-    instance_dir: &std::path::Path,
-    options: SimpleFileOptions,
-    include_worlds: bool,
-    include_resource_packs: bool,
-    include_shader_packs: bool,
-    include_mods: bool,
-    include_config: bool,
-) -> Result<(), String> {
+    instance_dir: &Path,
+    selection: &ExportSelection,
+) -> Result<serde_json::Value, String> {
     let instance_json_path = instance_dir.join("instance.json");
     let instance_content = std::fs::read_to_string(&instance_json_path)
         .map_err(|e| e.to_string())?;
@@ -194,70 +513,71 @@ fn export_as_mrpack(
         _ => {}
     }
 
-    if include_mods {
-        let mods_dir = instance_dir.join("mods");
-        if mods_dir.exists() {
-            add_dir_to_zip_with_prefix(zip, &mods_dir, "overrides/mods", options)?;
-        }
+    if selection.include_mods {
+        plan.add_dir(&instance_dir.join("mods"), "overrides/mods");
+
+        // Mods with a known download are listed in the manifest instead of bundled
+        let mod_index = ModIndex::load(instance_name);
+        let mut files = Vec::new();
+        plan.entries.retain(|entry| {
+            let Some(filename) = entry.zip_path.strip_prefix("overrides/mods/").filter(|f| f.ends_with(".jar") && !f.contains('/')) else {
+                return true;
+            };
+            // Re-hashed since the manifest promises exactly these bytes
+            match mod_index.provenance(filename).filter(|p| p.verify(&entry.source)).and_then(|p| mrpack_file_entry(p, filename)) {
+                Some(file) => {
+                    files.push(file);
+                    false
+                }
+                None => true,
+            }
+        });
+        manifest["files"] = serde_json::Value::Array(files);
     }
 
-    if include_worlds {
-        let saves_dir = instance_dir.join("saves");
-        if saves_dir.exists() {
-            add_dir_to_zip_with_prefix(zip, &saves_dir, "overrides/saves", options)?;
-        }
+    if selection.include_worlds {
+        plan.add_dir(&instance_dir.join("saves"), "overrides/saves");
     }
 
-    if include_resource_packs {
-        let resourcepacks_dir = instance_dir.join("resourcepacks");
-        if resourcepacks_dir.exists() {
-            add_dir_to_zip_with_prefix(zip, &resourcepacks_dir, "overrides/resourcepacks", options)?;
-        }
+    if selection.include_resource_packs {
+        plan.add_dir(&instance_dir.join("resourcepacks"), "overrides/resourcepacks");
     }
 
-    if include_shader_packs {
-        let shaderpacks_dir = instance_dir.join("shaderpacks");
-        if shaderpacks_dir.exists() {
-            add_dir_to_zip_with_prefix(zip, &shaderpacks_dir, "overrides/shaderpacks", options)?;
-        }
+    if selection.include_shader_packs {
+        plan.add_dir(&instance_dir.join("shaderpacks"), "overrides/shaderpacks");
     }
 
-    if include_config {
-        let config_dir = instance_dir.join("config");
-        if config_dir.exists() {
-            add_dir_to_zip_with_prefix(zip, &config_dir, "overrides/config", options)?;
-        }
+    if selection.include_config {
+        plan.add_dir(&instance_dir.join("config"), "overrides/config");
+        plan.add_file(&instance_dir.join("options.txt"), "overrides/options.txt");
+        plan.add_file(&instance_dir.join("optionsof.txt"), "overrides/optionsof.txt");
+        plan.add_file(&instance_dir.join("optionsshaders.txt"), "overrides/optionsshaders.txt");
+    }
 
-        let options_txt = instance_dir.join("options.txt");
-        if options_txt.exists() {
-            add_file_to_zip(zip, &options_txt, "overrides/options.txt", options)?;
-        }
+    plan.add_file(&instance_dir.join("icon.png"), "icon.png");
 
-        let optionsof_txt = instance_dir.join("optionsof.txt");
-        if optionsof_txt.exists() {
-            add_file_to_zip(zip, &optionsof_txt, "overrides/optionsof.txt", options)?;
-        }
+    Ok(manifest)
+}
 
-        let optionsshaders_txt = instance_dir.join("optionsshaders.txt");
-        if optionsshaders_txt.exists() {
-            add_file_to_zip(zip, &optionsshaders_txt, "overrides/optionsshaders.txt", options)?;
-        }
-    }
+/// Hosts the mrpack format accepts downloads from.
+const MRPACK_DOWNLOAD_HOSTS: &[&str] = &["cdn.modrinth.com", "github.com", "raw.githubusercontent.com", "gitlab.com"];
 
-    let icon_path = instance_dir.join("icon.png");
-    if icon_path.exists() {
-        add_file_to_zip(zip, &icon_path, "icon.png", options)?;
+fn mrpack_file_entry(provenance: &ModProvenance, filename: &str) -> Option<serde_json::Value> {
+    let download_url = provenance.download_url.as_deref()?;
+    let host = url::Url::parse(download_url).ok()?.host_str()?.to_ascii_lowercase();
+    if !MRPACK_DOWNLOAD_HOSTS.contains(&host.as_str()) {
+        return None;
     }
 
-    let manifest_json = serde_json::to_string_pretty(&manifest)
-        .map_err(|e| format!("Failed to serialize manifest: {}", e))?;
-
-    zip.start_file("modrinth.index.json", options)
-        .map_err(|e| format!("Failed to create manifest file: {}", e))?;
-    zip.write_all(manifest_json.as_bytes())
-        .map_err(|e| format!("Failed to write manifest: {}", e))?;
-
-    Ok(())
+    Some(serde_json::json!({
+        "path": format!("mods/{}", filename),
+        "hashes": {
+            "sha1": provenance.sha1,
+            "sha512": provenance.sha512,
+        },
+        "downloads": [download_url],
+        "fileSize": provenance.size,
+    }))
 }
 
 fn extract_minecraft_version(version_string: &str, loader: &str) -> String {
@@ -310,42 +630,3 @@ fn add_file_to_zip(
 
     Ok(())
 }
-
-fn add_dir_to_zip(
-    zip: &mut ZipWriter<std::fs::File>,
-    dir_path: &std::path::Path,
-    zip_prefix: &str,
-    options: SimpleFileOptions,
-) -> Result<(), String> {
-    let entries = std::fs::read_dir(dir_path)
-        .map_err(|e| format!("Failed to read directory: {}", e))?;
-
-    for entry in entries {
-        let entry = entry.map_err(|e| format!("Failed to read entry: {}", e))?;
-        let path = entry.path();
-        let name = entry.file_name();
-        let name_str = name.to_string_lossy();
-
-        let zip_path = format!("{}/{}", zip_prefix, name_str);
-
-        if path.is_file() {
-            add_file_to_zip(zip, &path, &zip_path, options)?;
-        } else if path.is_dir() {
-            zip.add_directory(&format!("{}/", zip_path), options)
-                .map_err(|e| format!("Failed to add directory to zip: {}", e))?;
-
-            add_dir_to_zip(zip, &path, &zip_path, options)?;
-        }
-    }
-
-    Ok(())
-}
-
-fn add_dir_to_zip_with_prefix(
-    zip: &mut ZipWriter<std::fs::File>,
-    dir_path: &std::path::Path,
-    zip_prefix: &str,
-    options: SimpleFileOptions,
-) -> Result<(), String> {
-    add_dir_to_zip(zip, dir_path, zip_prefix, options)
-}
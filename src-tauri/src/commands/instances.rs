@@ -2,22 +2,27 @@ use crate::services::instance::InstanceManager;
 use crate::services::installer::MinecraftInstaller;
 use crate::services::fabric::FabricInstaller;
 use crate::services::accounts::AccountManager;
-use crate::models::{AppConfig, Instance};
+use crate::models::{AppConfig, DuplicateSkip, Instance, InstanceWarmup};
 use crate::utils::*;
 use std::sync::Mutex;
-use crate::commands::validation::sanitize_instance_name;
+use crate::commands::validation::{instance_folder_name, sanitize_display_name, sanitize_instance_name, validate_hex_color};
+use crate::services::operations::{OperationKind, ProgressEvent};
 use tauri::{Emitter, Manager};
 use base64::{Engine as _, engine::general_purpose};
 
+/// `with_fabric_api` also installs the matching Fabric API release into new Fabric instances.
 #[tauri::command]
 pub async fn create_instance(
     instance_name: String,
     version: String,
     loader: Option<String>,
     loader_version: Option<String>,
+    with_fabric_api: Option<bool>,
     app_handle: tauri::AppHandle,
 ) -> Result<String, String> {
-    let safe_name = sanitize_instance_name(&instance_name)?;
+    let display_name = sanitize_display_name(&instance_name)?;
+    let safe_name = instance_folder_name(&display_name)?;
+    let operation = crate::services::operations::track(&app_handle, OperationKind::CreateInstance, Some(&safe_name));
     
     if !version.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '-') {
         return Err("Invalid version format".to_string());
@@ -35,47 +40,42 @@ pub async fn create_instance(
         }
     }
 
-    let _ = app_handle.emit("creation-progress", serde_json::json!({
-        "instance": safe_name,
-        "progress": 0,
-        "stage": "Starting instance creation..."
-    }));
+    ProgressEvent::new(OperationKind::CreateInstance, Some(&safe_name))
+        .percent(0)
+        .stage("Starting instance creation...")
+        .emit(&app_handle);
 
-    let _ = app_handle.emit("creation-progress", serde_json::json!({
-        "instance": safe_name,
-        "progress": 10,
-        "stage": format!("Checking Minecraft {}...", version)
-    }));
+    ProgressEvent::new(OperationKind::CreateInstance, Some(&safe_name))
+        .percent(10)
+        .stage(format!("Checking Minecraft {}...", version))
+        .emit(&app_handle);
 
     let meta_dir = get_meta_dir();
     let installer = MinecraftInstaller::new(meta_dir.clone())
         .map_err(|e| e.to_string())?;
     
-    let _ = app_handle.emit("creation-progress", serde_json::json!({
-        "instance": safe_name,
-        "progress": 20,
-        "stage": format!("Installing Minecraft {}...", version)
-    }));
+    ProgressEvent::new(OperationKind::CreateInstance, Some(&safe_name))
+        .percent(20)
+        .stage(format!("Installing Minecraft {}...", version))
+        .emit(&app_handle);
 
     installer
         .install_version(&version)
         .await
         .map_err(|e| e.to_string())?;
 
-    let _ = app_handle.emit("creation-progress", serde_json::json!({
-        "instance": safe_name,
-        "progress": 60,
-        "stage": "Minecraft version ready"
-    }));
+    ProgressEvent::new(OperationKind::CreateInstance, Some(&safe_name))
+        .percent(60)
+        .stage("Minecraft version ready")
+        .emit(&app_handle);
 
     let final_version = if let Some(loader_type) = &loader {
         if loader_type == "fabric" {
             if let Some(fabric_version) = &loader_version {
-                let _ = app_handle.emit("creation-progress", serde_json::json!({
-                    "instance": safe_name,
-                    "progress": 70,
-                    "stage": format!("Installing Fabric {}...", fabric_version)
-                }));
+                ProgressEvent::new(OperationKind::CreateInstance, Some(&safe_name))
+                    .percent(70)
+                    .stage(format!("Installing Fabric {}...", fabric_version))
+                    .emit(&app_handle);
 
                 let fabric_installer = FabricInstaller::new(meta_dir.clone())
                     .map_err(|e| e.to_string())?;
@@ -89,11 +89,10 @@ pub async fn create_instance(
             }
         } else if loader_type == "forge" {
             if let Some(forge_version) = &loader_version {
-                let _ = app_handle.emit("creation-progress", serde_json::json!({
-                    "instance": safe_name,
-                    "progress": 70,
-                    "stage": format!("Downloading Forge installer {}...", forge_version)
-                }));
+                ProgressEvent::new(OperationKind::CreateInstance, Some(&safe_name))
+                    .percent(70)
+                    .stage(format!("Downloading Forge installer {}...", forge_version))
+                    .emit(&app_handle);
 
                 let forge_installer = crate::services::forge::ForgeInstaller::new(meta_dir.clone())
                     .map_err(|e| e.to_string())?;
@@ -104,11 +103,10 @@ pub async fn create_instance(
                     for i in 0..20 {
                         tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
                         let progress = 75 + (i * 1).min(10);
-                        let _ = app_handle_clone.emit("creation-progress", serde_json::json!({
-                            "instance": safe_name_clone,
-                            "progress": progress,
-                            "stage": "Running Forge installer (this may take a minute)..."
-                        }));
+                        ProgressEvent::new(OperationKind::CreateInstance, Some(&safe_name_clone))
+                            .percent(progress)
+                            .stage("Running Forge installer (this may take a minute)...")
+                            .emit(&app_handle_clone);
                     }
                 });
                 
@@ -119,11 +117,10 @@ pub async fn create_instance(
                 
                 progress_task.abort();
                     
-                let _ = app_handle.emit("creation-progress", serde_json::json!({
-                    "instance": safe_name,
-                    "progress": 85,
-                    "stage": "Forge installation complete"
-                }));
+                ProgressEvent::new(OperationKind::CreateInstance, Some(&safe_name))
+                    .percent(85)
+                    .stage("Forge installation complete")
+                    .emit(&app_handle);
                 
                 version_id
             } else {
@@ -131,11 +128,10 @@ pub async fn create_instance(
             }
         } else if loader_type == "neoforge" {
             if let Some(neoforge_version) = &loader_version {
-                let _ = app_handle.emit("creation-progress", serde_json::json!({
-                    "instance": safe_name,
-                    "progress": 70,
-                    "stage": format!("Downloading NeoForge installer {}...", neoforge_version)
-                }));
+                ProgressEvent::new(OperationKind::CreateInstance, Some(&safe_name))
+                    .percent(70)
+                    .stage(format!("Downloading NeoForge installer {}...", neoforge_version))
+                    .emit(&app_handle);
 
                 let neoforge_installer = crate::services::neoforge::NeoForgeInstaller::new(meta_dir.clone())
                     .map_err(|e| e.to_string())?;
@@ -146,11 +142,10 @@ pub async fn create_instance(
                     for i in 0..20 {
                         tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
                         let progress = 75 + (i * 1).min(10);
-                        let _ = app_handle_clone.emit("creation-progress", serde_json::json!({
-                            "instance": safe_name_clone,
-                            "progress": progress,
-                            "stage": "Running NeoForge installer (this may take a minute)..."
-                        }));
+                        ProgressEvent::new(OperationKind::CreateInstance, Some(&safe_name_clone))
+                            .percent(progress)
+                            .stage("Running NeoForge installer (this may take a minute)...")
+                            .emit(&app_handle_clone);
                     }
                 });
                 
@@ -161,11 +156,10 @@ pub async fn create_instance(
                 
                 progress_task.abort();
                     
-                let _ = app_handle.emit("creation-progress", serde_json::json!({
-                    "instance": safe_name,
-                    "progress": 85,
-                    "stage": "NeoForge installation complete"
-                }));
+                ProgressEvent::new(OperationKind::CreateInstance, Some(&safe_name))
+                    .percent(85)
+                    .stage("NeoForge installation complete")
+                    .emit(&app_handle);
                 
                 version_id
             } else {
@@ -178,35 +172,73 @@ pub async fn create_instance(
         version.clone()
     };
 
-    let _ = app_handle.emit("creation-progress", serde_json::json!({
-        "instance": safe_name,
-        "progress": 90,
-        "stage": "Creating instance structure..."
-    }));
+    ProgressEvent::new(OperationKind::CreateInstance, Some(&safe_name))
+        .percent(90)
+        .stage("Creating instance structure...")
+        .emit(&app_handle);
 
     InstanceManager::create(&safe_name, &final_version, loader.clone(), loader_version.clone())
         .map_err(|e| e.to_string())?;
 
-    let _ = app_handle.emit("creation-progress", serde_json::json!({
-        "instance": safe_name,
-        "progress": 100,
-        "stage": "Instance created successfully!"
-    }));
+    crate::services::templates::TemplateManager::apply_default_template(&safe_name);
+
+    if loader.as_deref() == Some("fabric") && with_fabric_api.unwrap_or(false) {
+        ProgressEvent::new(OperationKind::CreateInstance, Some(&safe_name))
+            .percent(95)
+            .stage("Installing Fabric API...")
+            .emit(&app_handle);
+
+        // The instance is usable without it, so a failed download only gets logged
+        let mods_dir = get_instance_dir(&safe_name).join("mods");
+        if let Err(e) = crate::commands::fabric_api::install_fabric_api_into(&mods_dir, &version).await {
+            eprintln!("Failed to install Fabric API for {}: {}", safe_name, e);
+        }
+    }
+
+    InstanceManager::set_display_name(&safe_name, &display_name)
+        .map_err(|e| e.to_string())?;
+
+    ProgressEvent::new(OperationKind::CreateInstance, Some(&safe_name))
+        .percent(100)
+        .stage("Instance created successfully!")
+        .emit(&app_handle);
+
+    operation.complete();
+    Ok(format!("Successfully created instance '{}'", display_name))
+}
 
-    Ok(format!("Successfully created instance '{}'", safe_name))
+/// A game started by the launcher that hasn't exited yet.
+pub struct RunningProcess {
+    pub instance_name: String,
+    pub pid: u32,
+    /// Account UUID the instance was launched with.
+    pub account_uuid: String,
 }
 
 lazy_static::lazy_static! {
-    pub static ref RUNNING_PROCESSES: Mutex<std::collections::HashMap<String, u32>> = Mutex::new(std::collections::HashMap::new());
+    /// Running games, keyed by instance id.
+    pub static ref RUNNING_PROCESSES: Mutex<std::collections::HashMap<String, RunningProcess>> = Mutex::new(std::collections::HashMap::new());
+}
+
+/// Whether the game of the instance is running.
+pub fn is_running(instance_name: &str) -> Result<bool, String> {
+    // An instance that can't be loaded can't have been launched either
+    let Ok(instance_id) = InstanceManager::instance_id(instance_name) else {
+        return Ok(false);
+    };
+    let processes = RUNNING_PROCESSES.lock().map_err(|e| e.to_string())?;
+    Ok(processes.contains_key(&instance_id))
 }
 
 #[tauri::command]
 pub async fn kill_instance(instance_name: String) -> Result<(), String> {
     let safe_name = sanitize_instance_name(&instance_name)?;
+    let instance_id = InstanceManager::instance_id(&safe_name)
+        .map_err(|_| "Instance is not running".to_string())?;
     
     let pid = {
         let processes = RUNNING_PROCESSES.lock().map_err(|e| e.to_string())?;
-        processes.get(&safe_name).copied()
+        processes.get(&instance_id).map(|process| process.pid)
     };
     
     if let Some(pid) = pid {
@@ -232,7 +264,7 @@ pub async fn kill_instance(instance_name: String) -> Result<(), String> {
         }
         
         let mut processes = RUNNING_PROCESSES.lock().map_err(|e| e.to_string())?;
-        processes.remove(&safe_name);
+        processes.remove(&instance_id);
         
         Ok(())
     } else {
@@ -246,55 +278,176 @@ pub async fn get_instances() -> Result<Vec<Instance>, String> {
 }
 
 #[tauri::command]
-pub async fn delete_instance(instance_name: String, permanent: bool) -> Result<(), String> {
+pub async fn delete_instance(instance_name: String, permanent: bool, app_handle: tauri::AppHandle) -> Result<(), String> {
     let safe_name = sanitize_instance_name(&instance_name)?;
+    let _lock = crate::services::instance_lock::lock(&app_handle, &safe_name, "deletion")?;
     
     InstanceManager::delete(&safe_name, permanent)
         .map_err(|e| e.to_string())
 }
 
+/// Renames an instance. Only the display name changes, the folder stays where it is so
+/// everything referring to the instance keeps working. Returns the folder name.
 #[tauri::command]
-pub async fn rename_instance(old_name: String, new_name: String) -> Result<(), String> {
-    let safe_old_name = sanitize_instance_name(&old_name)?;
-    let safe_new_name = sanitize_instance_name(&new_name)?;
-    
-    if safe_old_name == safe_new_name {
-        return Ok(());
+pub async fn rename_instance(old_name: String, new_name: String) -> Result<String, String> {
+    let safe_name = sanitize_instance_name(&old_name)?;
+    let display_name = sanitize_display_name(&new_name)?;
+
+    if !get_instance_dir(&safe_name).exists() {
+        return Err(format!("Instance '{}' does not exist", safe_name));
     }
-    
-    let instances_dir = get_instances_dir();
-    let old_path = instances_dir.join(&safe_old_name);
-    let new_path = instances_dir.join(&safe_new_name);
-    
-    if !old_path.exists() {
-        return Err(format!("Instance '{}' does not exist", safe_old_name));
+
+    InstanceManager::set_display_name(&safe_name, &display_name)
+        .map_err(|e| e.to_string())?;
+
+    Ok(safe_name)
+}
+
+/// The folder an instance created with this display name is stored in.
+#[tauri::command]
+pub async fn get_instance_folder_name(name: String) -> Result<String, String> {
+    instance_folder_name(&sanitize_display_name(&name)?)
+}
+
+/// Brings a "Latest Snapshot" instance up to date before it launches. When that fails, e.g.
+/// while offline, the snapshot already installed is launched.
+pub(crate) async fn update_snapshot_before_launch(instance_name: &str, app_handle: &tauri::AppHandle) {
+    match InstanceManager::update_tracked_snapshot(instance_name).await {
+        Ok(Some(version)) => {
+            let _ = app_handle.emit("snapshot-instance-updated", serde_json::json!({
+                "instance": instance_name,
+                "version": version,
+            }));
+        }
+        Ok(None) => {}
+        Err(e) => eprintln!("Failed to update snapshot of '{}': {}", instance_name, e),
     }
-    
-    if new_path.exists() {
-        return Err(format!("Instance '{}' already exists", safe_new_name));
+}
+
+/// Turns the "Latest Snapshot" behaviour of a vanilla instance on or off.
+#[tauri::command]
+pub async fn set_instance_snapshot_tracking(instance_name: String, enabled: bool) -> Result<(), String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    let instance_json_path = get_instance_dir(&safe_name).join("instance.json");
+    let content = std::fs::read_to_string(&instance_json_path)
+        .map_err(|_| format!("Instance '{}' does not exist", safe_name))?;
+
+    let mut instance: Instance = serde_json::from_str(&content)
+        .map_err(|e| e.to_string())?;
+
+    if enabled && instance.loader.is_some() {
+        return Err("Only vanilla instances can follow the latest snapshot".to_string());
     }
-    
-    std::fs::rename(&old_path, &new_path)
+
+    instance.track_latest_snapshot = enabled;
+
+    crate::utils::write_json_atomic(&instance_json_path, &instance)
+        .map_err(|e| e.to_string())
+}
+
+/// Resets an instance to a fresh state, keeping the chosen categories. Removed files go to
+/// the trash. When mods are reset on an instance installed from a Modrinth modpack, the
+/// installed pack version is applied again, including its config overrides.
+#[tauri::command]
+pub async fn reset_instance(
+    instance_name: String,
+    keep: crate::models::InstanceResetKeep,
+    app_handle: tauri::AppHandle,
+) -> Result<crate::models::InstanceResetSummary, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let _lock = crate::services::instance_lock::lock(&app_handle, &safe_name, "reset")?;
+
+    if is_running(&safe_name)? {
+        return Err("Close the game before resetting the instance".to_string());
+    }
+
+    let instance_dir = get_instance_dir(&safe_name);
+
+    if crate::services::install_control::get(&safe_name).is_some()
+        || instance_dir.join(crate::commands::modpacks::PENDING_INSTALL_FILE).exists()
+    {
+        return Err("Finish or cancel the running modpack installation first".to_string());
+    }
+
+    let content = std::fs::read_to_string(instance_dir.join("instance.json"))
+        .map_err(|_| format!("Instance '{}' does not exist", safe_name))?;
+    let instance: Instance = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+
+    let removed = InstanceManager::reset_files(&safe_name, &keep)
+        .map_err(|e| format!("Failed to reset instance: {}", e))?;
+
+    crate::commands::mods::invalidate_mod_cache(&safe_name);
+
+    let modpack_version = instance.modpack
+        .filter(|m| m.source == "modrinth" && m.project_id.is_some())
+        .and_then(|m| m.version_id);
+
+    let modpack_reapplied = match modpack_version {
+        Some(version_id) if !keep.mods => {
+            crate::commands::modpacks::update_modpack_locked(&safe_name, &version_id, &app_handle).await?;
+            true
+        }
+        _ => false,
+    };
+
+    Ok(crate::models::InstanceResetSummary {
+        removed,
+        modpack_reapplied,
+    })
+}
+
+/// Does the pre-launch work of an instance without starting the game: refreshes the active
+/// account's token, verifies the game files and extracts natives, so the next Play starts
+/// right away.
+#[tauri::command]
+pub async fn warm_instance(instance_name: String, app_handle: tauri::AppHandle) -> Result<InstanceWarmup, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let _lock = crate::services::instance_lock::lock(&app_handle, &safe_name, "launch warm-up")?;
+
+    if is_running(&safe_name)? {
+        return Err("Close the game before warming up the instance".to_string());
+    }
+
+    let client_id = app_handle.state::<AppConfig>().microsoft_client_id.clone();
+    let mut warnings = Vec::new();
+
+    // Boxed errors aren't Send, so they are turned into strings before awaiting
+    let active = AccountManager::get_active_account().map_err(|e| e.to_string());
+    match active {
+        Ok(Some(account)) => {
+            let refreshed = AccountManager::get_valid_token(&account.uuid, &client_id).await.map_err(|e| e.to_string());
+            if let Err(e) = refreshed {
+                warnings.push(format!("Couldn't refresh the sign-in of {}: {}", account.username, e));
+            }
+        }
+        Ok(None) => warnings.push("No account is signed in".to_string()),
+        Err(e) => warnings.push(format!("Couldn't load accounts: {}", e)),
+    }
+
+    update_snapshot_before_launch(&safe_name, &app_handle).await;
+
+    let content = std::fs::read_to_string(get_instance_dir(&safe_name).join("instance.json"))
+        .map_err(|_| format!("Instance '{}' does not exist", safe_name))?;
+    let instance: Instance = serde_json::from_str(&content)
         .map_err(|e| e.to_string())?;
-    
-    let instance_json_path = new_path.join("instance.json");
-    if instance_json_path.exists() {
-        let content = std::fs::read_to_string(&instance_json_path)
-            .map_err(|e| e.to_string())?;
-        
-        let mut instance: Instance = serde_json::from_str(&content)
-            .map_err(|e| e.to_string())?;
-        
-        instance.name = safe_new_name.clone();
-        
-        let updated_json = serde_json::to_string_pretty(&instance)
-            .map_err(|e| e.to_string())?;
-        
-        std::fs::write(&instance_json_path, updated_json)
-            .map_err(|e| e.to_string())?;
+
+    // Re-downloads missing or corrupted game files, checked against their SHA-1
+    let verified = match MinecraftInstaller::new(get_meta_dir()) {
+        Ok(installer) => installer
+            .install_version(&instance_minecraft_version(&instance))
+            .await
+            .map_err(|e| e.to_string()),
+        Err(e) => Err(e.to_string()),
+    };
+    if let Err(e) = verified {
+        warnings.push(format!("Couldn't verify game files: {}", e));
     }
-    
-    Ok(())
+
+    InstanceManager::warm(&safe_name, &app_handle)
+        .map_err(|e| e.to_string())?;
+
+    Ok(InstanceWarmup { warnings })
 }
 
 #[tauri::command]
@@ -313,6 +466,8 @@ pub async fn launch_instance_with_active_account(
         .await
         .map_err(|e| e.to_string())?;
 
+    update_snapshot_before_launch(&safe_name, &app_handle).await;
+
     crate::services::instance::InstanceManager::launch(
         &safe_name,
         &active_account.username,
@@ -340,6 +495,8 @@ pub async fn launch_world(
         .await
         .map_err(|e| e.to_string())?;
 
+    update_snapshot_before_launch(&safe_name, &app_handle).await;
+
     crate::services::instance::InstanceManager::launch_with_world(
         &safe_name,
         &active_account.username,
@@ -369,6 +526,8 @@ pub async fn launch_instance(
         return Err("Invalid UUID format".to_string());
     }
     
+    update_snapshot_before_launch(&safe_name, &app_handle).await;
+
     InstanceManager::launch(&safe_name, &username, &uuid, &access_token, app_handle)
         .map_err(|e| e.to_string())
 }
@@ -386,26 +545,13 @@ pub async fn set_instance_icon(
         return Err(format!("Instance '{}' does not exist", safe_name));
     }
     
-    let image_bytes = general_purpose::STANDARD
-        .decode(&image_data)
-        .map_err(|e| e.to_string())?;
-    
-    if image_bytes.len() > 2 * 1024 * 1024 {
-        return Err("Image too large (max 2MB)".to_string());
-    }
-    
-    let format = image::guess_format(&image_bytes)
-        .map_err(|e| e.to_string())?;
-    
-    match format {
-        image::ImageFormat::Png | 
-        image::ImageFormat::Jpeg | 
-        image::ImageFormat::WebP => {},
-        _ => return Err("Unsupported image format. Use PNG, JPEG, or WebP".to_string()),
-    }
+    let image_bytes = decode_image_data(&image_data, 2)?;
     
-    let img = image::load_from_memory(&image_bytes)
-        .map_err(|e| e.to_string())?;
+    let img = if is_svg(&image_bytes) {
+        rasterize_svg(&image_bytes, 256)?
+    } else {
+        load_instance_image(&image_bytes)?
+    };
     
     let resized = img.resize_exact(256, 256, image::imageops::FilterType::Lanczos3);
     
@@ -413,6 +559,16 @@ pub async fn set_instance_icon(
     resized.save(&icon_path)
         .map_err(|e| e.to_string())?;
     
+    // The PNG is the first frame, used wherever the icon can't animate
+    let animated_path = instance_dir.join(ANIMATED_ICON_FILE);
+    if is_animated_gif(&image_bytes) {
+        std::fs::write(&animated_path, &image_bytes)
+            .map_err(|e| e.to_string())?;
+    } else if animated_path.exists() {
+        std::fs::remove_file(&animated_path)
+            .map_err(|e| e.to_string())?;
+    }
+    
     let instance_json = instance_dir.join("instance.json");
     let content = std::fs::read_to_string(&instance_json)
         .map_err(|e| e.to_string())?;
@@ -422,13 +578,96 @@ pub async fn set_instance_icon(
     
     instance.icon_path = Some("icon.png".to_string());
     
-    let updated_json = serde_json::to_string_pretty(&instance)
+    crate::utils::write_json_atomic(&instance_json, &instance)
         .map_err(|e| e.to_string())?;
     
-    std::fs::write(&instance_json, updated_json)
+    Ok(())
+}
+
+/// Original of an animated GIF icon, kept next to its first frame in `icon.png`.
+const ANIMATED_ICON_FILE: &str = "icon.gif";
+
+const UNSUPPORTED_IMAGE_FORMAT: &str = "Unsupported image format. Use PNG, JPEG, WebP, GIF, or SVG";
+
+/// Decodes base64 image data of at most `max_mb` megabytes.
+fn decode_image_data(image_data: &str, max_mb: usize) -> Result<Vec<u8>, String> {
+    let image_bytes = general_purpose::STANDARD
+        .decode(image_data)
         .map_err(|e| e.to_string())?;
     
-    Ok(())
+    if image_bytes.len() > max_mb * 1024 * 1024 {
+        return Err(format!("Image too large (max {}MB)", max_mb));
+    }
+    
+    Ok(image_bytes)
+}
+
+/// Loads a PNG, JPEG, WebP or GIF image, only the first frame of an animated GIF.
+fn load_instance_image(image_bytes: &[u8]) -> Result<image::DynamicImage, String> {
+    let format = image::guess_format(image_bytes)
+        .map_err(|_| UNSUPPORTED_IMAGE_FORMAT.to_string())?;
+    
+    match format {
+        image::ImageFormat::Png | 
+        image::ImageFormat::Jpeg | 
+        image::ImageFormat::WebP |
+        image::ImageFormat::Gif => {},
+        _ => return Err(UNSUPPORTED_IMAGE_FORMAT.to_string()),
+    }
+    
+    image::load_from_memory_with_format(image_bytes, format)
+        .map_err(|e| e.to_string())
+}
+
+fn is_svg(image_bytes: &[u8]) -> bool {
+    let head = String::from_utf8_lossy(&image_bytes[..image_bytes.len().min(1024)]);
+    let head = head.trim_start_matches('\u{feff}').trim_start();
+    head.starts_with("<svg")
+        || ((head.starts_with("<?xml") || head.starts_with("<!--") || head.starts_with("<!DOCTYPE")) && head.contains("<svg"))
+}
+
+fn is_animated_gif(image_bytes: &[u8]) -> bool {
+    use image::AnimationDecoder;
+    
+    image::codecs::gif::GifDecoder::new(std::io::Cursor::new(image_bytes))
+        .is_ok_and(|decoder| decoder.into_frames().take(2).count() > 1)
+}
+
+/// Renders an SVG centered on a transparent `size` x `size` canvas, keeping its aspect ratio.
+fn rasterize_svg(image_bytes: &[u8], size: u32) -> Result<image::DynamicImage, String> {
+    use resvg::{tiny_skia, usvg};
+    
+    let mut options = usvg::Options::default();
+    options.fontdb_mut().load_system_fonts();
+    
+    let tree = usvg::Tree::from_data(image_bytes, &options)
+        .map_err(|e| format!("Invalid SVG: {}", e))?;
+    
+    let svg_size = tree.size();
+    let scale = (size as f32 / svg_size.width()).min(size as f32 / svg_size.height());
+    let transform = tiny_skia::Transform::from_translate(
+        (size as f32 - svg_size.width() * scale) / 2.0,
+        (size as f32 - svg_size.height() * scale) / 2.0,
+    )
+    .pre_scale(scale, scale);
+    
+    let mut pixmap = tiny_skia::Pixmap::new(size, size)
+        .ok_or("Failed to allocate SVG canvas")?;
+    resvg::render(&tree, transform, &mut pixmap.as_mut());
+    
+    // tiny-skia works with premultiplied alpha, image expects straight alpha
+    let pixels = pixmap
+        .pixels()
+        .iter()
+        .flat_map(|pixel| {
+            let color = pixel.demultiply();
+            [color.red(), color.green(), color.blue(), color.alpha()]
+        })
+        .collect();
+    
+    image::RgbaImage::from_raw(size, size, pixels)
+        .map(image::DynamicImage::ImageRgba8)
+        .ok_or_else(|| "Failed to rasterize SVG".to_string())
 }
 
 #[tauri::command]
@@ -441,10 +680,12 @@ pub async fn remove_instance_icon(instance_name: String) -> Result<(), String> {
         return Err(format!("Instance '{}' does not exist", safe_name));
     }
     
-    let icon_path = instance_dir.join("icon.png");
-    if icon_path.exists() {
-        std::fs::remove_file(&icon_path)
-            .map_err(|e| e.to_string())?;
+    for file in ["icon.png", ANIMATED_ICON_FILE] {
+        let icon_path = instance_dir.join(file);
+        if icon_path.exists() {
+            std::fs::remove_file(&icon_path)
+                .map_err(|e| e.to_string())?;
+        }
     }
     
     let instance_json = instance_dir.join("instance.json");
@@ -456,10 +697,7 @@ pub async fn remove_instance_icon(instance_name: String) -> Result<(), String> {
     
     instance.icon_path = None;
     
-    let updated_json = serde_json::to_string_pretty(&instance)
-        .map_err(|e| e.to_string())?;
-    
-    std::fs::write(&instance_json, updated_json)
+    crate::utils::write_json_atomic(&instance_json, &instance)
         .map_err(|e| e.to_string())?;
     
     Ok(())
@@ -475,7 +713,10 @@ pub async fn get_instance_icon(instance_name: String) -> Result<Option<String>,
         return Err(format!("Instance '{}' does not exist", safe_name));
     }
     
-    let icon_path = instance_dir.join("icon.png");
+    let (icon_path, mime) = match instance_dir.join(ANIMATED_ICON_FILE) {
+        animated if animated.exists() => (animated, "image/gif"),
+        _ => (instance_dir.join("icon.png"), "image/png"),
+    };
     
     if !icon_path.exists() {
         return Ok(None);
@@ -496,17 +737,127 @@ pub async fn get_instance_icon(instance_name: String) -> Result<Option<String>,
     
     let base64_data = general_purpose::STANDARD.encode(&image_bytes);
     
-    Ok(Some(format!("data:image/png;base64,{}", base64_data)))
+    Ok(Some(format!("data:{};base64,{}", mime, base64_data)))
+}
+
+const COVER_IMAGE_FILE: &str = "cover.png";
+
+/// Sets the banner shown on the instance card, scaled down to fit 1280x720.
+#[tauri::command]
+pub async fn set_instance_cover(instance_name: String, image_data: String) -> Result<(), String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let instance_dir = get_instance_dir(&safe_name);
+
+    let instance_json = instance_dir.join("instance.json");
+    let content = std::fs::read_to_string(&instance_json)
+        .map_err(|_| format!("Instance '{}' does not exist", safe_name))?;
+
+    let mut instance: Instance = serde_json::from_str(&content)
+        .map_err(|e| e.to_string())?;
+
+    let img = load_instance_image(&decode_image_data(&image_data, 8)?)?;
+    let cover = if img.width() > 1280 || img.height() > 720 {
+        img.resize(1280, 720, image::imageops::FilterType::Lanczos3)
+    } else {
+        img
+    };
+
+    cover.save(instance_dir.join(COVER_IMAGE_FILE))
+        .map_err(|e| e.to_string())?;
+
+    instance.cover_image = Some(COVER_IMAGE_FILE.to_string());
+
+    crate::utils::write_json_atomic(&instance_json, &instance)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn remove_instance_cover(instance_name: String) -> Result<(), String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let instance_dir = get_instance_dir(&safe_name);
+
+    let instance_json = instance_dir.join("instance.json");
+    let content = std::fs::read_to_string(&instance_json)
+        .map_err(|_| format!("Instance '{}' does not exist", safe_name))?;
+
+    let mut instance: Instance = serde_json::from_str(&content)
+        .map_err(|e| e.to_string())?;
+
+    let cover_path = instance_dir.join(COVER_IMAGE_FILE);
+    if cover_path.exists() {
+        std::fs::remove_file(&cover_path)
+            .map_err(|e| e.to_string())?;
+    }
+
+    instance.cover_image = None;
+
+    crate::utils::write_json_atomic(&instance_json, &instance)
+        .map_err(|e| e.to_string())
+}
+
+/// The cover image as a data URL, `None` when the instance has none.
+#[tauri::command]
+pub async fn get_instance_cover(instance_name: String) -> Result<Option<String>, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let instance_dir = get_instance_dir(&safe_name);
+
+    if !instance_dir.exists() {
+        return Err(format!("Instance '{}' does not exist", safe_name));
+    }
+
+    let cover_path = instance_dir.join(COVER_IMAGE_FILE);
+    if !cover_path.is_file() {
+        return Ok(None);
+    }
+
+    let image_bytes = std::fs::read(&cover_path)
+        .map_err(|e| e.to_string())?;
+
+    Ok(Some(format!("data:image/png;base64,{}", general_purpose::STANDARD.encode(&image_bytes))))
+}
+
+/// Sets the `#rrggbb` color the instance card is tinted with, or clears it.
+#[tauri::command]
+pub async fn set_instance_accent_color(instance_name: String, color: Option<String>) -> Result<(), String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let color = color.as_deref().map(validate_hex_color).transpose()?;
+
+    let instance_json = get_instance_dir(&safe_name).join("instance.json");
+    let content = std::fs::read_to_string(&instance_json)
+        .map_err(|_| format!("Instance '{}' does not exist", safe_name))?;
+
+    let mut instance: Instance = serde_json::from_str(&content)
+        .map_err(|e| e.to_string())?;
+
+    instance.accent_color = color;
+
+    crate::utils::write_json_atomic(&instance_json, &instance)
+        .map_err(|e| e.to_string())
 }
 
+/// Folders whose archives a linked duplicate shares with its source through hardlinks.
+const LINKABLE_FOLDERS: &[&str] = &["mods", "resourcepacks", "shaderpacks"];
+/// Only these are linked, other files in the folders (e.g. shader settings `.txt` files) are
+/// edited in place by the game and get copied. Launcher writers into the folders go through
+/// `create_file_unlinked`/`copy_file_unlinked` or a rename, so they never modify a shared file.
+const LINKABLE_EXTENSIONS: &[&str] = &["jar", "zip"];
+
+/// Copies an instance under a new name, without the folders chosen in `skip`. With `linked`,
+/// mod and pack archives in `LINKABLE_FOLDERS` are hardlinked instead of copied, which takes
+/// no extra disk space; saves and configs are always copied.
 #[tauri::command]
 pub async fn duplicate_instance(
     instance_name: String,
     new_name: String,
+    linked: Option<bool>,
+    skip: Option<DuplicateSkip>,
     app_handle: tauri::AppHandle,
 ) -> Result<(), String> {
     let safe_old_name = sanitize_instance_name(&instance_name)?;
-    let safe_new_name = sanitize_instance_name(&new_name)?;
+    let display_name = sanitize_display_name(&new_name)?;
+    let safe_new_name = instance_folder_name(&display_name)?;
+    let _lock = crate::services::instance_lock::lock(&app_handle, &safe_old_name, "duplication")?;
+    let operation = crate::services::operations::track(&app_handle, OperationKind::Duplicate, Some(&safe_new_name));
     
     if safe_old_name == safe_new_name {
         return Err("Source and destination names cannot be the same".to_string());
@@ -524,13 +875,13 @@ pub async fn duplicate_instance(
         return Err(format!("Instance '{}' already exists", safe_new_name));
     }
     
-    let _ = app_handle.emit("duplication-progress", serde_json::json!({
-        "instance": safe_new_name,
-        "progress": 0,
-        "stage": "Calculating size..."
-    }));
+    ProgressEvent::new(OperationKind::Duplicate, Some(&safe_new_name))
+        .percent(0)
+        .stage("Calculating size...")
+        .emit(&app_handle);
     
-    let total_files = count_files(&source_path)
+    let skip_folders = skip.unwrap_or_default().folders();
+    let total_files = count_files(&source_path, &skip_folders)
         .map_err(|e| e.to_string())?;
     
     let copied_files = std::sync::Arc::new(std::sync::atomic::AtomicUsize::new(0));
@@ -539,16 +890,18 @@ pub async fn duplicate_instance(
         &dest_path,
         total_files,
         copied_files.clone(),
+        &skip_folders,
+        if linked.unwrap_or(false) { LINKABLE_FOLDERS } else { &[] },
+        false,
         &safe_new_name,
         &app_handle,
     )
     .map_err(|e| e.to_string())?;
     
-    let _ = app_handle.emit("duplication-progress", serde_json::json!({
-        "instance": safe_new_name,
-        "progress": 90,
-        "stage": "Updating metadata..."
-    }));
+    ProgressEvent::new(OperationKind::Duplicate, Some(&safe_new_name))
+        .percent(90)
+        .stage("Updating metadata...")
+        .emit(&app_handle);
     
     let instance_json_path = dest_path.join("instance.json");
     if instance_json_path.exists() {
@@ -558,27 +911,27 @@ pub async fn duplicate_instance(
         let mut instance: Instance = serde_json::from_str(&content)
             .map_err(|e| e.to_string())?;
         
+        instance.id = uuid::Uuid::new_v4().to_string();
         instance.name = safe_new_name.clone();
+        instance.display_name = (display_name != safe_new_name).then(|| display_name.clone());
         instance.created_at = chrono::Utc::now().to_rfc3339();
         instance.last_played = None;
         
-        let updated_json = serde_json::to_string_pretty(&instance)
-            .map_err(|e| e.to_string())?;
-        
-        std::fs::write(&instance_json_path, updated_json)
+        crate::utils::write_json_atomic(&instance_json_path, &instance)
             .map_err(|e| e.to_string())?;
     }
     
-    let _ = app_handle.emit("duplication-progress", serde_json::json!({
-        "instance": safe_new_name,
-        "progress": 100,
-        "stage": "Complete!"
-    }));
+    ProgressEvent::new(OperationKind::Duplicate, Some(&safe_new_name))
+        .percent(100)
+        .stage("Complete!")
+        .emit(&app_handle);
     
+    operation.complete();
     Ok(())
 }
 
-fn count_files(path: &std::path::Path) -> std::io::Result<usize> {
+/// Counts the files below `path`, leaving out the subfolders of `path` named in `skip_folders`.
+fn count_files(path: &std::path::Path, skip_folders: &[&str]) -> std::io::Result<usize> {
     use std::fs;
     
     let mut count = 0;
@@ -596,7 +949,10 @@ fn count_files(path: &std::path::Path) -> std::io::Result<usize> {
         }
         
         if entry_path.is_dir() {
-            count += count_files(&entry_path)?;
+            if skip_folders.iter().any(|folder| entry.file_name() == *folder) {
+                continue;
+            }
+            count += count_files(&entry_path, &[])?;
         } else {
             count += 1;
         }
@@ -605,11 +961,18 @@ fn count_files(path: &std::path::Path) -> std::io::Result<usize> {
     Ok(count)
 }
 
+/// Copies `src` into `dst`, leaving out the subfolders of `src` named in `skip_folders`.
+/// Archives with a `LINKABLE_EXTENSIONS` extension are hardlinked instead when `link` is set
+/// or inside the subfolders named in `link_folders`, falling back to a copy where linking
+/// fails, e.g. across filesystems.
 fn copy_dir_recursive_with_progress(
     src: &std::path::Path,
     dst: &std::path::Path,
     total_files: usize,
     copied_files: std::sync::Arc<std::sync::atomic::AtomicUsize>,
+    skip_folders: &[&str],
+    link_folders: &[&str],
+    link: bool,
     instance_name: &str,
     app_handle: &tauri::AppHandle,
 ) -> std::io::Result<()> {
@@ -627,7 +990,7 @@ fn copy_dir_recursive_with_progress(
         let dst_path = dst.join(entry.file_name());
         
         if file_type.is_dir() {
-            if entry.file_name() == "natives" {
+            if entry.file_name() == "natives" || skip_folders.iter().any(|folder| entry.file_name() == *folder) {
                 continue;
             }
             copy_dir_recursive_with_progress(
@@ -635,11 +998,22 @@ fn copy_dir_recursive_with_progress(
                 &dst_path,
                 total_files,
                 copied_files.clone(),
+                &[],
+                &[],
+                link || link_folders.iter().any(|folder| entry.file_name() == *folder),
                 instance_name,
                 app_handle,
             )?;
         } else if file_type.is_file() {
-            fs::copy(&src_path, &dst_path)?;
+            // `fs::copy` already clones the file on copy-on-write filesystems (APFS, Btrfs, XFS)
+            let linkable = link
+                && src_path.extension()
+                    .and_then(|ext| ext.to_str())
+                    .is_some_and(|ext| LINKABLE_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()));
+            let linked = linkable && fs::hard_link(&src_path, &dst_path).is_ok();
+            if !linked {
+                fs::copy(&src_path, &dst_path)?;
+            }
             
             let current = copied_files.fetch_add(1, Ordering::Relaxed) + 1;
             let progress = ((current as f64 / total_files as f64) * 85.0) as u32;
@@ -649,12 +1023,11 @@ fn copy_dir_recursive_with_progress(
                     .and_then(|n| n.to_str())
                     .unwrap_or("file");
                 
-                let _ = app_handle.emit("duplication-progress", serde_json::json!({
-                    "instance": instance_name,
-                    "progress": progress,
-                    "stage": format!("Copying files... ({}/{})", current, total_files),
-                    "current_file": file_name
-                }));
+                ProgressEvent::new(OperationKind::Duplicate, Some(instance_name))
+                    .percent(progress)
+                    .stage(format!("Copying files... ({}/{})", current, total_files))
+                    .detail(file_name)
+                    .emit(app_handle);
             }
         }
     }
@@ -680,6 +1053,46 @@ pub fn open_instance_folder(instance_name: String) -> Result<(), String> {
     open_folder(instance_dir).map_err(|e| e.to_string())
 }
 
+/// Instance subfolders the frontend may open with `open_instance_subfolder`.
+const OPENABLE_SUBFOLDERS: &[&str] = &[
+    "mods",
+    "config",
+    "saves",
+    "resourcepacks",
+    "shaderpacks",
+    "screenshots",
+    "logs",
+    "crash-reports",
+];
+
+#[tauri::command]
+pub fn open_instance_subfolder(instance_name: String, folder: String) -> Result<(), String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    if !OPENABLE_SUBFOLDERS.contains(&folder.as_str()) {
+        return Err(format!("Cannot open folder '{}'", folder));
+    }
+
+    let instance_dir = get_instance_dir(&safe_name);
+    if !instance_dir.exists() {
+        return Err(format!("Instance '{}' does not exist", safe_name));
+    }
+
+    let subfolder = instance_dir.join(&folder);
+    if !subfolder.exists() {
+        std::fs::create_dir_all(&subfolder)
+            .map_err(|e| e.to_string())?;
+    }
+
+    let canonical_instance = instance_dir.canonicalize().map_err(|e| e.to_string())?;
+    let canonical_subfolder = subfolder.canonicalize().map_err(|e| e.to_string())?;
+    if !canonical_subfolder.starts_with(&canonical_instance) || !canonical_subfolder.is_dir() {
+        return Err(format!("'{}' is not a folder inside the instance", folder));
+    }
+
+    open_folder(subfolder).map_err(|e| e.to_string())
+}
+
 use sysinfo::System;
 
 #[derive(serde::Serialize, serde::Deserialize)]
@@ -687,22 +1100,141 @@ pub struct SystemInfo {
     pub total_memory_mb: u64,
     pub available_memory_mb: u64,
     pub recommended_max_memory_mb: u64,
+    pub os_name: Option<String>,
+    pub os_version: Option<String>,
+    pub kernel_version: Option<String>,
+    pub arch: String,
+    pub cpu_model: Option<String>,
+    pub cpu_cores: Option<usize>,
+    pub cpu_threads: usize,
+    pub gpus: Vec<crate::services::system_info::GpuInfo>,
+    pub launcher_version: String,
 }
 
-#[tauri::command]
-pub async fn get_system_info() -> Result<SystemInfo, String> {
+pub(crate) fn collect_system_info() -> SystemInfo {
     let mut sys = System::new_all();
     sys.refresh_memory();
+    sys.refresh_cpu();
     
     let total_mb = sys.total_memory() / 1024 / 1024;
     let available_mb = sys.available_memory() / 1024 / 1024;
     let recommended_max_mb = total_mb * 80 / 100;
     
-    Ok(SystemInfo {
+    SystemInfo {
         total_memory_mb: total_mb,
         available_memory_mb: available_mb,
         recommended_max_memory_mb: recommended_max_mb,
-    })
+        os_name: System::name(),
+        os_version: System::long_os_version(),
+        kernel_version: System::kernel_version(),
+        arch: std::env::consts::ARCH.to_string(),
+        cpu_model: sys.cpus().first().map(|cpu| cpu.brand().trim().to_string()).filter(|b| !b.is_empty()),
+        cpu_cores: sys.physical_core_count(),
+        cpu_threads: sys.cpus().len(),
+        gpus: crate::services::system_info::detect_gpus(),
+        launcher_version: env!("CARGO_PKG_VERSION").to_string(),
+    }
+}
+
+#[tauri::command]
+pub async fn get_system_info() -> Result<SystemInfo, String> {
+    tauri::async_runtime::spawn_blocking(collect_system_info)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Downloads and installs still in progress, so the frontend can restore its progress UI after a reload.
+#[tauri::command]
+pub fn get_active_operations(
+    registry: tauri::State<'_, crate::services::operations::OperationRegistry>,
+) -> Vec<crate::services::operations::ActiveOperation> {
+    registry.active()
+}
+
+/// Finished installs, updates, exports and backups, newest first.
+#[tauri::command]
+pub async fn get_task_history() -> Vec<crate::services::task_history::TaskRecord> {
+    tauri::async_runtime::spawn_blocking(crate::services::task_history::list)
+        .await
+        .unwrap_or_default()
+}
+
+/// Folders in the instances directory without a usable `instance.json`, which can be
+/// registered with `adopt_instance`.
+#[tauri::command]
+pub fn get_unregistered_instances() -> Vec<String> {
+    InstanceManager::get_unregistered()
+}
+
+/// Registers a manually added instance folder and installs the game version it needs.
+/// `version` overrides the version detected from the folder.
+#[tauri::command]
+pub async fn adopt_instance(
+    folder: String,
+    version: Option<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<Instance, String> {
+    let safe_name = sanitize_instance_name(&folder)?;
+    let operation = crate::services::operations::track(&app_handle, OperationKind::CreateInstance, Some(&safe_name));
+
+    let instance = InstanceManager::adopt(&safe_name, version.as_deref())
+        .map_err(|e| e.to_string())?;
+
+    let meta_dir = get_meta_dir();
+    let version_json = meta_dir
+        .join("versions")
+        .join(&instance.version)
+        .join(format!("{}.json", instance.version));
+
+    if !version_json.exists() {
+        ProgressEvent::new(OperationKind::CreateInstance, Some(&safe_name))
+            .percent(20)
+            .stage(format!("Installing {}...", instance.version))
+            .emit(&app_handle);
+
+        let minecraft_version = crate::commands::modpacks::extract_minecraft_version_from_instance(&instance.version);
+        let loader_version = instance.loader_version.clone().unwrap_or_default();
+
+        match instance.loader.as_deref() {
+            Some("forge") => {
+                crate::services::forge::ForgeInstaller::new(meta_dir)
+                    .map_err(|e| e.to_string())?
+                    .install_forge(&loader_version)
+                    .await
+                    .map_err(|e| e.to_string())?;
+            }
+            Some("neoforge") => {
+                crate::services::neoforge::NeoForgeInstaller::new(meta_dir)
+                    .map_err(|e| e.to_string())?
+                    .install_neoforge(&loader_version)
+                    .await
+                    .map_err(|e| e.to_string())?;
+            }
+            loader => {
+                MinecraftInstaller::new(meta_dir.clone())
+                    .map_err(|e| e.to_string())?
+                    .install_version(&minecraft_version)
+                    .await
+                    .map_err(|e| e.to_string())?;
+
+                if loader == Some("fabric") {
+                    FabricInstaller::new(meta_dir)
+                        .map_err(|e| e.to_string())?
+                        .install_fabric(&minecraft_version, &loader_version)
+                        .await
+                        .map_err(|e| e.to_string())?;
+                }
+            }
+        }
+    }
+
+    ProgressEvent::new(OperationKind::CreateInstance, Some(&safe_name))
+        .percent(100)
+        .stage("Instance ready")
+        .emit(&app_handle);
+
+    operation.complete();
+    Ok(instance)
 }
 
 #[tauri::command]
@@ -742,8 +1274,9 @@ pub fn open_world_folder(instance_name: String, folder_name: String) -> Result<(
 }
 
 #[tauri::command]
-pub fn delete_world(instance_name: String, folder_name: String) -> Result<(), String> {
+pub fn delete_world(instance_name: String, folder_name: String, app_handle: tauri::AppHandle) -> Result<(), String> {
     let safe_name = sanitize_instance_name(&instance_name)?;
+    let _lock = crate::services::instance_lock::lock(&app_handle, &safe_name, "world deletion")?;
     
     if folder_name.contains("..") || folder_name.contains("/") || folder_name.contains("\\") {
         return Err("Invalid folder name".to_string());
@@ -865,75 +1398,89 @@ fn calculate_dir_size(path: &std::path::Path) -> std::io::Result<u64> {
     Ok(size)
 }
 
-#[tauri::command]
-pub async fn update_instance_fabric_loader(
-    instance_name: String,
-    fabric_version: String,
-) -> Result<(), String> {
-    let safe_name = sanitize_instance_name(&instance_name)?;
-    
-    if !fabric_version.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '-') {
-        return Err("Invalid fabric version format".to_string());
-    }
-    
-    let instance_dir = get_instance_dir(&safe_name);
-    
-    if !instance_dir.exists() {
-        return Err(format!("Instance '{}' does not exist", safe_name));
-    }
-    
-    let instance_json_path = instance_dir.join("instance.json");
-    let content = std::fs::read_to_string(&instance_json_path)
-        .map_err(|e| e.to_string())?;
-    
-    let mut instance: Instance = serde_json::from_str(&content)
-        .map_err(|e| e.to_string())?;
-    
-    if instance.loader != Some("fabric".to_string()) {
-        return Err("This instance is not using Fabric loader".to_string());
+/// The Minecraft version an installed loader version builds on, read from its version json.
+fn base_minecraft_version(version_id: &str) -> Option<String> {
+    let path = get_meta_dir().join("versions").join(version_id).join(format!("{}.json", version_id));
+    let content = std::fs::read_to_string(path).ok()?;
+    let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+    json.get("inheritsFrom").and_then(|v| v.as_str()).map(String::from)
+}
+
+/// Installs `version` of `loader` on top of `minecraft_version` and returns the version id
+/// to launch.
+pub(crate) async fn install_loader_version(loader: &str, minecraft_version: &str, version: &str) -> Result<String, String> {
+    let meta_dir = get_meta_dir();
+
+    match loader {
+        "fabric" => {
+            FabricInstaller::new(meta_dir)
+                .map_err(|e| e.to_string())?
+                .install_fabric(minecraft_version, version)
+                .await
+                .map_err(|e| e.to_string())
+        }
+        "quilt" => {
+            crate::services::quilt::QuiltInstaller::new(meta_dir)
+                .map_err(|e| e.to_string())?
+                .install_quilt(minecraft_version, version)
+                .await
+                .map_err(|e| e.to_string())
+        }
+        "neoforge" => {
+            crate::services::neoforge::NeoForgeInstaller::new(meta_dir)
+                .map_err(|e| e.to_string())?
+                .install_neoforge(version)
+                .await
+                .map_err(|e| e.to_string())
+        }
+        "forge" => {
+            crate::services::forge::ForgeInstaller::new(meta_dir)
+                .map_err(|e| e.to_string())?
+                .install_forge(version)
+                .await
+                .map_err(|e| e.to_string())
+        }
+        _ => Err(format!("Installing the '{}' loader is not supported", loader)),
     }
-    
-    let minecraft_version = if instance.version.contains("fabric-loader") {
-        let parts: Vec<&str> = instance.version.split('-').collect();
-        if let Some(mc_version) = parts.last() {
-            mc_version.to_string()
+}
+
+/// The Minecraft version an instance runs, whatever loader it uses.
+pub(crate) fn instance_minecraft_version(instance: &Instance) -> String {
+    // Fabric and Quilt ids end in the Minecraft version, for when the version json is missing
+    base_minecraft_version(&instance.version).unwrap_or_else(|| {
+        if instance.version.starts_with("fabric-loader-") || instance.version.starts_with("quilt-loader-") {
+            instance.version.rsplit('-').next().unwrap_or(&instance.version).to_string()
         } else {
-            return Err("Could not determine Minecraft version".to_string());
+            instance.version.clone()
         }
-    } else {
-        instance.version.clone()
-    };
-    
-    let meta_dir = get_meta_dir();
-    let fabric_installer = FabricInstaller::new(meta_dir)
-        .map_err(|e| e.to_string())?;
-    
-    let new_fabric_version_id = fabric_installer
-        .install_fabric(&minecraft_version, &fabric_version)
-        .await
-        .map_err(|e| e.to_string())?;
-    
-    instance.version = new_fabric_version_id;
-    instance.loader_version = Some(fabric_version);
-    
-    let updated_json = serde_json::to_string_pretty(&instance)
-        .map_err(|e| e.to_string())?;
-    
-    std::fs::write(&instance_json_path, updated_json)
-        .map_err(|e| e.to_string())?;
-    
+    })
+}
+
+/// Removes natives extracted for the previous version, which would otherwise be reused.
+pub(crate) fn clear_instance_natives(instance_dir: &std::path::Path) -> Result<(), String> {
+    let natives_dir = instance_dir.join("natives");
+    if natives_dir.exists() {
+        std::fs::remove_dir_all(&natives_dir)
+            .map_err(|e| format!("Failed to remove old natives: {}", e))?;
+    }
     Ok(())
 }
 
+/// Switches an instance to another version of the loader it already uses. `version` is the
+/// loader version for Fabric and Quilt, the NeoForge version for NeoForge and the full
+/// `<minecraft>-<forge>` version for Forge.
 #[tauri::command]
-pub async fn update_instance_neoforge_loader(
+pub async fn update_instance_loader(
     instance_name: String,
-    neoforge_version: String,
+    loader: String,
+    version: String,
+    app_handle: tauri::AppHandle,
 ) -> Result<(), String> {
     let safe_name = sanitize_instance_name(&instance_name)?;
+    let _lock = crate::services::instance_lock::lock(&app_handle, &safe_name, "loader update")?;
 
-    if !neoforge_version.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '-') {
-        return Err("Invalid NeoForge version format".to_string());
+    if version.is_empty() || !version.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '-' || c == '+') {
+        return Err("Invalid loader version format".to_string());
     }
 
     let instance_dir = get_instance_dir(&safe_name);
@@ -942,6 +1489,10 @@ pub async fn update_instance_neoforge_loader(
         return Err(format!("Instance '{}' does not exist", safe_name));
     }
 
+    if is_running(&safe_name)? {
+        return Err("Close the game before changing the loader version".to_string());
+    }
+
     let instance_json_path = instance_dir.join("instance.json");
     let content = std::fs::read_to_string(&instance_json_path)
         .map_err(|e| e.to_string())?;
@@ -949,48 +1500,54 @@ pub async fn update_instance_neoforge_loader(
     let mut instance: Instance = serde_json::from_str(&content)
         .map_err(|e| e.to_string())?;
 
-    if instance.loader != Some("neoforge".to_string()) {
-        return Err("This instance is not using NeoForge loader".to_string());
+    if instance.loader.as_deref() != Some(loader.as_str()) {
+        return Err(format!("This instance is not using the {} loader", loader));
     }
 
-    let meta_dir = get_meta_dir();
-    let neoforge_installer = crate::services::neoforge::NeoForgeInstaller::new(meta_dir)
-        .map_err(|e| e.to_string())?;
-
-    let new_version_id = neoforge_installer
-        .install_neoforge(&neoforge_version)
-        .await
-        .map_err(|e| e.to_string())?;
+    let minecraft_version = instance_minecraft_version(&instance);
+    let new_version_id = install_loader_version(&loader, &minecraft_version, &version).await?;
 
     instance.version = new_version_id;
-    instance.loader_version = Some(neoforge_version);
+    instance.loader_version = Some(version);
 
-    let updated_json = serde_json::to_string_pretty(&instance)
+    crate::utils::write_json_atomic(&instance_json_path, &instance)
         .map_err(|e| e.to_string())?;
 
-    std::fs::write(&instance_json_path, updated_json)
-        .map_err(|e| e.to_string())?;
+    clear_instance_natives(&instance_dir)
+}
 
-    Ok(())
+/// Mod loaders whose mods `loader` can run. Quilt runs Fabric mods, NeoForge is given the
+/// benefit of the doubt for Forge mods since early versions share their metadata.
+fn loadable_mod_loaders(loader: Option<&str>) -> &'static [&'static str] {
+    match loader {
+        Some("fabric") => &["fabric"],
+        Some("quilt") => &["quilt", "fabric"],
+        Some("forge") => &["forge"],
+        Some("neoforge") => &["neoforge", "forge"],
+        _ => &[],
+    }
 }
 
+/// Moves an instance to another mod loader (or to vanilla with `new_loader` "vanilla"),
+/// keeping its Minecraft version. Mods made for a loader the new one can't run are disabled
+/// and returned. `loader_version` follows `update_instance_loader` and is ignored for vanilla.
 #[tauri::command]
-pub async fn update_instance_forge_loader(
+pub async fn change_instance_loader(
     instance_name: String,
-    forge_full_version: String,
-) -> Result<(), String> {
+    new_loader: String,
+    loader_version: Option<String>,
+) -> Result<crate::models::LoaderChangeResult, String> {
     let safe_name = sanitize_instance_name(&instance_name)?;
 
-    if !forge_full_version.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '-') {
-        return Err("Invalid Forge version format".to_string());
-    }
-
     let instance_dir = get_instance_dir(&safe_name);
-
     if !instance_dir.exists() {
         return Err(format!("Instance '{}' does not exist", safe_name));
     }
 
+    if is_running(&safe_name)? {
+        return Err("Close the game before changing the loader".to_string());
+    }
+
     let instance_json_path = instance_dir.join("instance.json");
     let content = std::fs::read_to_string(&instance_json_path)
         .map_err(|e| e.to_string())?;
@@ -998,29 +1555,113 @@ pub async fn update_instance_forge_loader(
     let mut instance: Instance = serde_json::from_str(&content)
         .map_err(|e| e.to_string())?;
 
-    if instance.loader != Some("forge".to_string()) {
-        return Err("This instance is not using Forge loader".to_string());
+    let target = if new_loader == "vanilla" { None } else { Some(new_loader.as_str()) };
+    if instance.loader.as_deref() == target {
+        return Err("The instance already uses this loader, update its version instead".to_string());
     }
 
-    let meta_dir = get_meta_dir();
-    let forge_installer = crate::services::forge::ForgeInstaller::new(meta_dir)
-        .map_err(|e| e.to_string())?;
+    let minecraft_version = instance_minecraft_version(&instance);
 
-    let new_version_id = forge_installer
-        .install_forge(&forge_full_version)
-        .await
-        .map_err(|e| e.to_string())?;
+    let (new_version_id, new_loader_version) = match target {
+        None => {
+            MinecraftInstaller::new(get_meta_dir())
+                .map_err(|e| e.to_string())?
+                .install_version(&minecraft_version)
+                .await
+                .map_err(|e| e.to_string())?;
+            (minecraft_version.clone(), None)
+        }
+        Some(loader) => {
+            let version = loader_version.ok_or("Loader version not specified")?;
+            if version.is_empty() || !version.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '-' || c == '+') {
+                return Err("Invalid loader version format".to_string());
+            }
 
-    instance.version = new_version_id;
-    instance.loader_version = Some(forge_full_version);
+            let version_minecraft = match loader {
+                "forge" => version.split('-').next().map(String::from),
+                "neoforge" => crate::services::neoforge::NeoForgeInstaller::parse_minecraft_version_from_neoforge(&version),
+                _ => None,
+            };
+            if version_minecraft.is_some_and(|mc| mc != minecraft_version) {
+                return Err(format!("{} {} is not for Minecraft {}", loader, version, minecraft_version));
+            }
 
-    let updated_json = serde_json::to_string_pretty(&instance)
-        .map_err(|e| e.to_string())?;
+            let version_id = install_loader_version(loader, &minecraft_version, &version).await?;
+            (version_id, Some(version))
+        }
+    };
+
+    let loadable = loadable_mod_loaders(target);
+    let mods_dir = instance_dir.join("mods");
+    let mut disabled_mods = Vec::new();
+
+    if let Ok(entries) = std::fs::read_dir(&mods_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("jar") {
+                continue;
+            }
 
-    std::fs::write(&instance_json_path, updated_json)
+            let Some(info) = crate::utils::mod_metadata::read_jar_mod_info(&path) else {
+                continue;
+            };
+            if loadable.contains(&info.loader.as_str()) {
+                continue;
+            }
+
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            let disabled_path = mods_dir.join(format!("{}.disabled", file_name));
+            if std::fs::rename(&path, &disabled_path).is_ok() {
+                disabled_mods.push(file_name);
+            }
+        }
+    }
+
+    if !disabled_mods.is_empty() {
+        crate::commands::mods::invalidate_mod_cache(&safe_name);
+    }
+    disabled_mods.sort();
+
+    instance.version = new_version_id.clone();
+    instance.loader = target.map(String::from);
+    instance.loader_version = new_loader_version;
+
+    crate::utils::write_json_atomic(&instance_json_path, &instance)
         .map_err(|e| e.to_string())?;
 
-    Ok(())
+    clear_instance_natives(&instance_dir)?;
+
+    Ok(crate::models::LoaderChangeResult {
+        version_id: new_version_id,
+        disabled_mods,
+    })
+}
+
+#[tauri::command]
+pub async fn update_instance_fabric_loader(
+    instance_name: String,
+    fabric_version: String,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    update_instance_loader(instance_name, "fabric".to_string(), fabric_version, app_handle).await
+}
+
+#[tauri::command]
+pub async fn update_instance_neoforge_loader(
+    instance_name: String,
+    neoforge_version: String,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    update_instance_loader(instance_name, "neoforge".to_string(), neoforge_version, app_handle).await
+}
+
+#[tauri::command]
+pub async fn update_instance_forge_loader(
+    instance_name: String,
+    forge_full_version: String,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    update_instance_loader(instance_name, "forge".to_string(), forge_full_version, app_handle).await
 }
 
 #[tauri::command]
@@ -1030,6 +1671,17 @@ pub async fn update_instance_minecraft_version(
     app_handle: tauri::AppHandle,
 ) -> Result<(), String> {
     let safe_name = sanitize_instance_name(&instance_name)?;
+    let _lock = crate::services::instance_lock::lock(&app_handle, &safe_name, "version update")?;
+    change_minecraft_version(safe_name, new_minecraft_version, app_handle).await
+}
+
+/// Moves an instance to another Minecraft version, for callers already holding its lock.
+pub(crate) async fn change_minecraft_version(
+    safe_name: String,
+    new_minecraft_version: String,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    let operation = crate::services::operations::track(&app_handle, OperationKind::VersionUpdate, Some(&safe_name));
     
     if !new_minecraft_version.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '-') {
         return Err("Invalid Minecraft version format".to_string());
@@ -1053,10 +1705,9 @@ pub async fn update_instance_minecraft_version(
     let is_forge = instance.loader == Some("forge".to_string());
 
     if is_fabric {
-        let _ = app_handle.emit("version-update-progress", serde_json::json!({
-            "instance": safe_name,
-            "stage": format!("Installing Minecraft {}...", new_minecraft_version)
-        }));
+        ProgressEvent::new(OperationKind::VersionUpdate, Some(&safe_name))
+            .stage(format!("Installing Minecraft {}...", new_minecraft_version))
+            .emit(&app_handle);
         
         let meta_dir = get_meta_dir();
         let installer = MinecraftInstaller::new(meta_dir.clone())
@@ -1067,10 +1718,9 @@ pub async fn update_instance_minecraft_version(
             .await
             .map_err(|e| e.to_string())?;
         
-        let _ = app_handle.emit("version-update-progress", serde_json::json!({
-            "instance": safe_name,
-            "stage": "Finding compatible Fabric loader..."
-        }));
+        ProgressEvent::new(OperationKind::VersionUpdate, Some(&safe_name))
+            .stage("Finding compatible Fabric loader...")
+            .emit(&app_handle);
         
         let fabric_installer = FabricInstaller::new(meta_dir.clone())
             .map_err(|e| e.to_string())?;
@@ -1079,10 +1729,9 @@ pub async fn update_instance_minecraft_version(
             .await
             .map_err(|e| e.to_string())?;
         
-        let _ = app_handle.emit("version-update-progress", serde_json::json!({
-            "instance": safe_name,
-            "stage": format!("Installing Fabric loader {}...", compatible_loader)
-        }));
+        ProgressEvent::new(OperationKind::VersionUpdate, Some(&safe_name))
+            .stage(format!("Installing Fabric loader {}...", compatible_loader))
+            .emit(&app_handle);
         
         let new_fabric_version_id = fabric_installer
             .install_fabric(&new_minecraft_version, &compatible_loader)
@@ -1092,10 +1741,9 @@ pub async fn update_instance_minecraft_version(
         instance.version = new_fabric_version_id;
         instance.loader_version = Some(compatible_loader);
     } else if is_neoforge {
-        let _ = app_handle.emit("version-update-progress", serde_json::json!({
-            "instance": safe_name,
-            "stage": format!("Installing Minecraft {}...", new_minecraft_version)
-        }));
+        ProgressEvent::new(OperationKind::VersionUpdate, Some(&safe_name))
+            .stage(format!("Installing Minecraft {}...", new_minecraft_version))
+            .emit(&app_handle);
         
         let meta_dir = get_meta_dir();
         let installer = MinecraftInstaller::new(meta_dir.clone())
@@ -1106,10 +1754,9 @@ pub async fn update_instance_minecraft_version(
             .await
             .map_err(|e| e.to_string())?;
         
-        let _ = app_handle.emit("version-update-progress", serde_json::json!({
-            "instance": safe_name,
-            "stage": "Finding compatible NeoForge loader..."
-        }));
+        ProgressEvent::new(OperationKind::VersionUpdate, Some(&safe_name))
+            .stage("Finding compatible NeoForge loader...")
+            .emit(&app_handle);
         
         let neoforge_installer = crate::services::neoforge::NeoForgeInstaller::new(meta_dir.clone())
             .map_err(|e| e.to_string())?;
@@ -1118,10 +1765,9 @@ pub async fn update_instance_minecraft_version(
             .await
             .map_err(|e| e.to_string())?;
         
-        let _ = app_handle.emit("version-update-progress", serde_json::json!({
-            "instance": safe_name,
-            "stage": format!("Installing NeoForge loader {}...", compatible_loader)
-        }));
+        ProgressEvent::new(OperationKind::VersionUpdate, Some(&safe_name))
+            .stage(format!("Installing NeoForge loader {}...", compatible_loader))
+            .emit(&app_handle);
         
         let new_version_id = neoforge_installer
             .install_neoforge(&compatible_loader)
@@ -1131,10 +1777,9 @@ pub async fn update_instance_minecraft_version(
         instance.version = new_version_id;
         instance.loader_version = Some(compatible_loader);
     } else if is_forge {
-        let _ = app_handle.emit("version-update-progress", serde_json::json!({
-            "instance": safe_name,
-            "stage": format!("Installing Minecraft {}...", new_minecraft_version)
-        }));
+        ProgressEvent::new(OperationKind::VersionUpdate, Some(&safe_name))
+            .stage(format!("Installing Minecraft {}...", new_minecraft_version))
+            .emit(&app_handle);
         
         let meta_dir = get_meta_dir();
         let installer = MinecraftInstaller::new(meta_dir.clone())
@@ -1145,10 +1790,9 @@ pub async fn update_instance_minecraft_version(
             .await
             .map_err(|e| e.to_string())?;
         
-        let _ = app_handle.emit("version-update-progress", serde_json::json!({
-            "instance": safe_name,
-            "stage": "Finding compatible Forge loader..."
-        }));
+        ProgressEvent::new(OperationKind::VersionUpdate, Some(&safe_name))
+            .stage("Finding compatible Forge loader...")
+            .emit(&app_handle);
         
         let forge_installer = crate::services::forge::ForgeInstaller::new(meta_dir.clone())
             .map_err(|e| e.to_string())?;
@@ -1159,10 +1803,9 @@ pub async fn update_instance_minecraft_version(
         
         let full_version = format!("{}-{}", new_minecraft_version, compatible_forge_ver);
         
-        let _ = app_handle.emit("version-update-progress", serde_json::json!({
-            "instance": safe_name,
-            "stage": format!("Installing Forge loader {}...", compatible_forge_ver)
-        }));
+        ProgressEvent::new(OperationKind::VersionUpdate, Some(&safe_name))
+            .stage(format!("Installing Forge loader {}...", compatible_forge_ver))
+            .emit(&app_handle);
         
         let new_version_id = forge_installer
             .install_forge(&full_version)
@@ -1172,10 +1815,9 @@ pub async fn update_instance_minecraft_version(
         instance.version = new_version_id;
         instance.loader_version = Some(full_version);
     } else {
-        let _ = app_handle.emit("version-update-progress", serde_json::json!({
-            "instance": safe_name,
-            "stage": format!("Installing Minecraft {}...", new_minecraft_version)
-        }));
+        ProgressEvent::new(OperationKind::VersionUpdate, Some(&safe_name))
+            .stage(format!("Installing Minecraft {}...", new_minecraft_version))
+            .emit(&app_handle);
         
         let meta_dir = get_meta_dir();
         let installer = MinecraftInstaller::new(meta_dir)
@@ -1189,10 +1831,9 @@ pub async fn update_instance_minecraft_version(
         instance.version = new_minecraft_version.clone();
     }
     
-    let _ = app_handle.emit("version-update-progress", serde_json::json!({
-        "instance": safe_name,
-        "stage": "Updating instance metadata..."
-    }));
+    ProgressEvent::new(OperationKind::VersionUpdate, Some(&safe_name))
+        .stage("Updating instance metadata...")
+        .emit(&app_handle);
     
     let natives_dir = instance_dir.join("natives");
     if natives_dir.exists() {
@@ -1200,17 +1841,14 @@ pub async fn update_instance_minecraft_version(
             .map_err(|e| e.to_string())?;
     }
     
-    let updated_json = serde_json::to_string_pretty(&instance)
-        .map_err(|e| e.to_string())?;
-    
-    std::fs::write(&instance_json_path, updated_json)
+    crate::utils::write_json_atomic(&instance_json_path, &instance)
         .map_err(|e| e.to_string())?;
     
-    let _ = app_handle.emit("version-update-progress", serde_json::json!({
-        "instance": safe_name,
-        "stage": "Complete!"
-    }));
+    ProgressEvent::new(OperationKind::VersionUpdate, Some(&safe_name))
+        .stage("Complete!")
+        .emit(&app_handle);
     
+    operation.complete();
     Ok(())
 }
 
@@ -12,6 +12,18 @@ pub mod friends;
 pub mod screenshots;
 pub mod packs;
 pub mod trash;
+pub mod backup;
+pub mod shortcuts;
+pub mod mod_bisect;
+pub mod fabric_api;
+pub mod instance_data;
+pub mod options;
+pub mod templates;
+pub mod debug_report;
+pub mod profiles;
+pub mod restrictions;
+pub mod worlds;
+pub mod pack_share;
 
 pub use auth::*;
 pub use instances::*;
@@ -25,4 +37,16 @@ pub use skins::*;
 pub use friends::*;
 pub use screenshots::*;
 pub use packs::*;
-pub use trash::*;
\ No newline at end of file
+pub use trash::*;
+pub use backup::*;
+pub use shortcuts::*;
+pub use mod_bisect::*;
+pub use fabric_api::*;
+pub use instance_data::*;
+pub use options::*;
+pub use templates::*;
+pub use debug_report::*;
+pub use profiles::*;
+pub use restrictions::*;
+pub use worlds::*;
+pub use pack_share::*;
@@ -0,0 +1,242 @@
+use crate::commands::mods::invalidate_mod_cache;
+use crate::commands::validation::sanitize_instance_name;
+use crate::utils::get_instance_dir;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+const BISECT_STATE_FILE: &str = ".mod_bisect.json";
+
+/// Persisted between launches so a bisect survives restarting the launcher.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+struct BisectState {
+    /// Mods that were enabled when the bisect started, restored when it ends.
+    original_enabled: Vec<String>,
+    /// Mods that may still contain the culprit.
+    suspects: Vec<String>,
+    /// Suspects enabled for the current test run; the other suspects are disabled.
+    testing: Vec<String>,
+    step: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ModBisectStatus {
+    pub active: bool,
+    pub step: u32,
+    pub remaining_suspects: usize,
+    pub enabled_suspects: Vec<String>,
+    pub disabled_suspects: Vec<String>,
+    /// Set once the bisect has narrowed the problem down to a single mod.
+    pub culprit: Option<String>,
+}
+
+impl ModBisectStatus {
+    fn inactive() -> Self {
+        Self {
+            active: false,
+            step: 0,
+            remaining_suspects: 0,
+            enabled_suspects: Vec::new(),
+            disabled_suspects: Vec::new(),
+            culprit: None,
+        }
+    }
+
+    fn from_state(state: &BisectState) -> Self {
+        Self {
+            active: true,
+            step: state.step,
+            remaining_suspects: state.suspects.len(),
+            enabled_suspects: state.testing.clone(),
+            disabled_suspects: untested(state),
+            culprit: None,
+        }
+    }
+}
+
+#[tauri::command]
+pub async fn start_mod_bisect(instance_name: String) -> Result<ModBisectStatus, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    ensure_not_running(&safe_name)?;
+
+    let instance_dir = get_instance_dir(&safe_name);
+    if load_state(&instance_dir).is_some() {
+        return Err("A mod bisect is already in progress for this instance".to_string());
+    }
+
+    let mods_dir = instance_dir.join("mods");
+    let enabled = list_enabled_mods(&mods_dir)?;
+    if enabled.len() < 2 {
+        return Err("At least two enabled mods are needed to bisect".to_string());
+    }
+
+    let mut state = BisectState {
+        original_enabled: enabled.clone(),
+        suspects: enabled,
+        testing: Vec::new(),
+        step: 1,
+    };
+    state.testing = first_half(&state.suspects);
+
+    apply_state(&mods_dir, &state)?;
+    save_state(&instance_dir, &state)?;
+    invalidate_mod_cache(&safe_name);
+
+    Ok(ModBisectStatus::from_state(&state))
+}
+
+/// Reports whether the crash still happened with the current set of mods and
+/// narrows the suspects down to the half that must contain the culprit.
+#[tauri::command]
+pub async fn bisect_step(instance_name: String, crashed: bool) -> Result<ModBisectStatus, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    ensure_not_running(&safe_name)?;
+
+    let instance_dir = get_instance_dir(&safe_name);
+    let mods_dir = instance_dir.join("mods");
+    let mut state = load_state(&instance_dir)
+        .ok_or("No mod bisect is in progress for this instance")?;
+
+    state.suspects = if crashed {
+        state.testing.clone()
+    } else {
+        untested(&state)
+    };
+
+    if state.suspects.len() <= 1 {
+        let culprit = state.suspects.first().cloned();
+        finish(&safe_name, &instance_dir, &state)?;
+
+        return Ok(ModBisectStatus {
+            culprit,
+            ..ModBisectStatus::inactive()
+        });
+    }
+
+    state.step += 1;
+    state.testing = first_half(&state.suspects);
+
+    apply_state(&mods_dir, &state)?;
+    save_state(&instance_dir, &state)?;
+    invalidate_mod_cache(&safe_name);
+
+    Ok(ModBisectStatus::from_state(&state))
+}
+
+#[tauri::command]
+pub async fn get_mod_bisect_status(instance_name: String) -> Result<ModBisectStatus, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let instance_dir = get_instance_dir(&safe_name);
+
+    Ok(load_state(&instance_dir)
+        .map(|state| ModBisectStatus::from_state(&state))
+        .unwrap_or_else(ModBisectStatus::inactive))
+}
+
+#[tauri::command]
+pub async fn cancel_mod_bisect(instance_name: String) -> Result<(), String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    ensure_not_running(&safe_name)?;
+
+    let instance_dir = get_instance_dir(&safe_name);
+    let state = load_state(&instance_dir)
+        .ok_or("No mod bisect is in progress for this instance")?;
+
+    finish(&safe_name, &instance_dir, &state)
+}
+
+fn ensure_not_running(instance_name: &str) -> Result<(), String> {
+    if crate::commands::instances::is_running(instance_name)? {
+        return Err("Close the game before changing the bisect state".to_string());
+    }
+    Ok(())
+}
+
+fn first_half(mods: &[String]) -> Vec<String> {
+    mods[..mods.len() / 2].to_vec()
+}
+
+fn untested(state: &BisectState) -> Vec<String> {
+    state.suspects
+        .iter()
+        .filter(|m| !state.testing.contains(m))
+        .cloned()
+        .collect()
+}
+
+fn list_enabled_mods(mods_dir: &Path) -> Result<Vec<String>, String> {
+    if !mods_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut mods: Vec<String> = std::fs::read_dir(mods_dir)
+        .map_err(|e| e.to_string())?
+        .flatten()
+        .filter(|e| e.path().is_file())
+        .filter_map(|e| e.file_name().to_str().map(|s| s.to_string()))
+        .filter(|name| name.ends_with(".jar"))
+        .collect();
+
+    mods.sort_by_key(|a| a.to_lowercase());
+    Ok(mods)
+}
+
+/// Disables the suspects not under test; everything else from the original set stays enabled
+/// so cleared mods keep satisfying dependencies.
+fn apply_state(mods_dir: &Path, state: &BisectState) -> Result<(), String> {
+    let disabled = untested(state);
+
+    for name in &state.original_enabled {
+        set_mod_enabled(mods_dir, name, !disabled.contains(name))?;
+    }
+
+    Ok(())
+}
+
+fn set_mod_enabled(mods_dir: &Path, filename: &str, enabled: bool) -> Result<(), String> {
+    let enabled_path = mods_dir.join(filename);
+    let disabled_path = mods_dir.join(format!("{}.disabled", filename));
+
+    let (from, to) = if enabled {
+        (disabled_path, enabled_path)
+    } else {
+        (enabled_path, disabled_path)
+    };
+
+    // Mods removed by the user mid-bisect are simply skipped
+    if !from.exists() {
+        return Ok(());
+    }
+
+    std::fs::rename(&from, &to)
+        .map_err(|e| format!("Failed to toggle {}: {}", filename, e))
+}
+
+fn finish(instance_name: &str, instance_dir: &Path, state: &BisectState) -> Result<(), String> {
+    let mods_dir = instance_dir.join("mods");
+
+    for name in &state.original_enabled {
+        set_mod_enabled(&mods_dir, name, true)?;
+    }
+
+    let state_path = state_path(instance_dir);
+    if state_path.exists() {
+        std::fs::remove_file(&state_path).map_err(|e| e.to_string())?;
+    }
+
+    invalidate_mod_cache(instance_name);
+    Ok(())
+}
+
+fn state_path(instance_dir: &Path) -> PathBuf {
+    instance_dir.join(BISECT_STATE_FILE)
+}
+
+fn load_state(instance_dir: &Path) -> Option<BisectState> {
+    let content = std::fs::read_to_string(state_path(instance_dir)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+fn save_state(instance_dir: &Path, state: &BisectState) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(state).map_err(|e| e.to_string())?;
+    std::fs::write(state_path(instance_dir), json).map_err(|e| e.to_string())
+}
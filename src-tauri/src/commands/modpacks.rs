@@ -1,12 +1,18 @@
-use crate::models::Instance;
+use crate::models::{Instance, ModpackInfo, ModpackUpdateSummary};
 use crate::services::instance::InstanceManager;
 use crate::services::installer::MinecraftInstaller;
 use crate::services::fabric::FabricInstaller;
 use crate::utils::modrinth::{ModrinthClient, ModrinthVersion};
 use crate::utils::*;
-use crate::commands::validation::{sanitize_instance_name, validate_download_url};
+use crate::commands::validation::{instance_folder_name, sanitize_display_name, sanitize_instance_name, validate_download_url};
 use crate::utils::curseforge::CurseforgeClient;
+use crate::services::operations::{OperationKind, ProgressEvent};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use tauri::Emitter;
+use tokio::sync::Semaphore;
+
+const MAX_CONCURRENT_MOD_DOWNLOADS: usize = 8;
 
 #[tauri::command]
 pub async fn get_modpack_versions(
@@ -46,7 +52,11 @@ pub async fn install_modpack(
     preferred_game_version: Option<String>,
     app_handle: tauri::AppHandle,
 ) -> Result<(), String> {
-    let safe_name = sanitize_instance_name(&instance_name)?;
+    let display_name = sanitize_display_name(&instance_name)?;
+    let safe_name = instance_folder_name(&display_name)?;
+    let _lock = crate::services::instance_lock::lock(&app_handle, &safe_name, "modpack install")?;
+    let finalization = PendingFinalization::Install { display_name };
+    let operation = crate::services::operations::track(&app_handle, OperationKind::ModpackInstall, Some(&safe_name));
     
     if !modpack_slug.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
         return Err("Invalid modpack slug format".to_string());
@@ -62,19 +72,17 @@ pub async fn install_modpack(
         }
     }
 
-    let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-        "instance": safe_name,
-        "progress": 0,
-        "stage": "Starting modpack installation..."
-    }));
+    ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+        .percent(0)
+        .stage("Starting modpack installation...")
+        .emit(&app_handle);
     
-    let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-        "instance": safe_name,
-        "progress": 5,
-        "stage": "Fetching modpack information..."
-    }));
+    ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+        .percent(5)
+        .stage("Fetching modpack information...")
+        .emit(&app_handle);
     
-    let client = ModrinthClient::new().map_err(|e| e.to_string())?;
+    let client = ModrinthClient::with_client(crate::utils::http::client_from(&app_handle));
     let versions = client
         .get_project_versions(&modpack_slug, None, None)
         .await
@@ -103,11 +111,10 @@ pub async fn install_modpack(
         .map(|l| l.to_lowercase())
         .unwrap_or_else(|| "vanilla".to_string());
     
-    let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-        "instance": safe_name,
-        "progress": 10,
-        "stage": format!("Installing Minecraft {}...", game_version)
-    }));
+    ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+        .percent(10)
+        .stage(format!("Installing Minecraft {}...", game_version))
+        .emit(&app_handle);
     
     let meta_dir = get_meta_dir();
     let installer = MinecraftInstaller::new(meta_dir.clone())
@@ -118,11 +125,10 @@ pub async fn install_modpack(
         .map_err(|e| e.to_string())?;
     
     let final_version = if loader == "fabric" {
-        let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-            "instance": safe_name,
-            "progress": 20,
-            "stage": "Installing Fabric loader..."
-        }));
+        ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+            .percent(20)
+            .stage("Installing Fabric loader...")
+            .emit(&app_handle);
         
         let fabric_installer = FabricInstaller::new(meta_dir)
             .map_err(|e| e.to_string())?;
@@ -146,11 +152,10 @@ pub async fn install_modpack(
         game_version.clone()
     };
     
-    let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-        "instance": safe_name,
-        "progress": 30,
-        "stage": "Creating instance..."
-    }));
+    ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+        .percent(30)
+        .stage("Creating instance...")
+        .emit(&app_handle);
     
     InstanceManager::create(
         &safe_name,
@@ -160,26 +165,28 @@ pub async fn install_modpack(
     )
     .map_err(|e| e.to_string())?;
     
-    let icon_url_opt = match client.get_project(&modpack_slug).await {
-        Ok(project) => project.icon_url,
-        Err(_) => None,
-    };
+    let project = client.get_project(&modpack_slug).await.ok();
+    let icon_url_opt = project.as_ref().and_then(|p| p.icon_url.clone());
+
+    save_modpack_info(&safe_name, ModpackInfo {
+        source: "modrinth".to_string(),
+        name: project.as_ref().map(|p| p.title.clone()).unwrap_or_else(|| modpack_slug.clone()),
+        summary: project.as_ref().map(|p| p.description.clone()),
+        icon_url: icon_url_opt.clone(),
+        project_url: Some(format!("https://modrinth.com/modpack/{}", modpack_slug)),
+        project_id: Some(version.project_id.clone()),
+        version_id: Some(version.id.clone()),
+        version_name: Some(version.version_number.clone()),
+    });
     
     if let Some(icon_url) = icon_url_opt {
-        let temp_dir = std::env::temp_dir();
-        let icon_extension = icon_url.split('.').last().unwrap_or("png");
-        let icon_path = temp_dir.join(format!("modpack_icon_{}.{}", safe_name, icon_extension));
+        ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+            .percent(35)
+            .stage("Setting modpack icon...")
+            .emit(&app_handle);
         
-        if validate_download_url(&icon_url).is_ok() {
-            if client.download_mod_file(&icon_url, &icon_path).await.is_ok() {
-                if let Ok(icon_bytes) = std::fs::read(&icon_path) {
-                    use base64::{Engine as _, engine::general_purpose};
-                    let icon_base64 = general_purpose::STANDARD.encode(&icon_bytes);
-                    
-                    let _ = crate::commands::set_instance_icon(safe_name.clone(), icon_base64).await;
-                }
-                let _ = std::fs::remove_file(&icon_path);
-            }
+        if let Err(e) = apply_project_icon(&client, &safe_name, &icon_url).await {
+            eprintln!("Failed to set the modpack icon of {}: {}", safe_name, e);
         }
     }
     
@@ -189,11 +196,10 @@ pub async fn install_modpack(
     std::fs::create_dir_all(&mods_dir)
         .map_err(|e| e.to_string())?;
     
-    let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-        "instance": safe_name,
-        "progress": 40,
-        "stage": "Downloading modpack..."
-    }));
+    ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+        .percent(40)
+        .stage("Downloading modpack...")
+        .emit(&app_handle);
     
     let primary_file = version.files.iter()
         .find(|f| f.primary)
@@ -203,18 +209,17 @@ pub async fn install_modpack(
     let temp_dir = std::env::temp_dir();
     let modpack_file = temp_dir.join(&primary_file.filename);
     
-    let _ = validate_download_url(&primary_file.url)?;
+    validate_download_url(&primary_file.url)?;
     
     client
         .download_mod_file(&primary_file.url, &modpack_file)
         .await
         .map_err(|e| e.to_string())?;
     
-    let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-        "instance": safe_name,
-        "progress": 50,
-        "stage": "Extracting modpack..."
-    }));
+    ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+        .percent(50)
+        .stage("Extracting modpack...")
+        .emit(&app_handle);
     
     let extract_dir = temp_dir.join(format!("modpack_extract_{}", safe_name));
     if extract_dir.exists() {
@@ -226,11 +231,10 @@ pub async fn install_modpack(
     extract_modpack(&modpack_file, &extract_dir)
         .map_err(|e| e.to_string())?;
     
-    let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-        "instance": safe_name,
-        "progress": 60,
-        "stage": "Reading modpack manifest..."
-    }));
+    ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+        .percent(60)
+        .stage("Reading modpack manifest...")
+        .emit(&app_handle);
     
     let manifest_path = extract_dir.join("modrinth.index.json");
     if !manifest_path.exists() {
@@ -245,11 +249,10 @@ pub async fn install_modpack(
     
     let overrides_dir = extract_dir.join("overrides");
     if overrides_dir.exists() {
-        let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-            "instance": safe_name,
-            "progress": 65,
-            "stage": "Copying overrides..."
-        }));
+        ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+            .percent(65)
+            .stage("Copying overrides...")
+            .emit(&app_handle);
         
         copy_dir_recursive(&overrides_dir, &instance_dir)
             .map_err(|e| e.to_string())?;
@@ -257,55 +260,767 @@ pub async fn install_modpack(
     
     if let Some(files) = manifest.get("files").and_then(|f| f.as_array()) {
         let total_files = files.len();
-        let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-            "instance": safe_name,
-            "progress": 70,
-            "stage": format!("Downloading {} mods...", total_files)
-        }));
+        ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+            .percent(70)
+            .stage(format!("Downloading {} mods...", total_files))
+            .emit(&app_handle);
         
-        for (idx, file) in files.iter().enumerate() {
-            let downloads = file.get("downloads")
-                .and_then(|d| d.as_array())
-                .ok_or("Invalid file entry in manifest")?;
-            
-            let download_url = downloads.first()
-                .and_then(|u| u.as_str())
-                .ok_or("No download URL found")?;
-            
-            let path = file.get("path")
-                .and_then(|p| p.as_str())
-                .ok_or("No path found in file entry")?;
-            
-            let dest_path = instance_dir.join(path);
-            
-            if let Some(parent) = dest_path.parent() {
-                std::fs::create_dir_all(parent)
-                    .map_err(|e| e.to_string())?;
+        download_mrpack_files(files, files, &instance_dir, &safe_name, Some(&finalization), &app_handle).await?;
+    }
+    
+    let _ = std::fs::remove_file(&modpack_file);
+    let _ = std::fs::remove_dir_all(&extract_dir);
+
+    ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+        .percent(100)
+        .stage("Installation complete!")
+        .emit(&app_handle);
+
+    finalize_modpack_install(&safe_name, &finalization, &app_handle);
+    
+    operation.complete();
+    Ok(())
+}
+
+/// Installs a modpack from a pasted modrinth.com link such as `https://modrinth.com/modpack/<slug>`
+/// or `https://modrinth.com/modpack/<slug>/version/<version>`. Without a version in the link the
+/// newest release is installed. Returns the name of the created instance.
+#[tauri::command]
+pub async fn install_modpack_from_url(
+    url: String,
+    instance_name: Option<String>,
+    preferred_game_version: Option<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<String, String> {
+    let (slug, version_ref) = parse_modrinth_modpack_url(&url)?;
+
+    let client = ModrinthClient::with_client(crate::utils::http::client_from(&app_handle));
+    let project = client
+        .get_project(&slug)
+        .await
+        .map_err(|e| format!("Failed to fetch modpack: {}", e))?;
+
+    if project.project_type != "modpack" {
+        return Err(format!("'{}' is a {}, not a modpack", project.title, project.project_type));
+    }
+
+    let versions = client
+        .get_project_versions(&project.id, None, None)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    // Links may carry either the version id or the human-readable version number
+    let version = match &version_ref {
+        Some(version_ref) => versions
+            .iter()
+            .find(|v| &v.id == version_ref || &v.version_number == version_ref)
+            .ok_or_else(|| format!("Version '{}' not found for {}", version_ref, project.title))?,
+        None => versions
+            .iter()
+            .find(|v| v.version_type == "release")
+            .or_else(|| versions.first())
+            .ok_or_else(|| format!("{} has no versions", project.title))?,
+    };
+
+    let display_name = sanitize_display_name(&instance_name.unwrap_or_else(|| project.title.clone()))?;
+    let safe_name = instance_folder_name(&display_name)?;
+
+    if get_instance_dir(&safe_name).exists() {
+        return Err(format!("An instance named '{}' already exists", safe_name));
+    }
+
+    install_modpack(
+        project.slug.clone(),
+        display_name,
+        version.id.clone(),
+        preferred_game_version,
+        app_handle,
+    )
+    .await?;
+
+    Ok(safe_name)
+}
+
+/// Extracts the project slug and optional version from a modrinth.com project URL.
+fn parse_modrinth_modpack_url(url: &str) -> Result<(String, Option<String>), String> {
+    let parsed = url::Url::parse(url.trim()).map_err(|_| "Invalid URL".to_string())?;
+
+    if !matches!(parsed.scheme(), "http" | "https")
+        || !matches!(parsed.host_str(), Some("modrinth.com") | Some("www.modrinth.com"))
+    {
+        return Err("Only modrinth.com links are supported".to_string());
+    }
+
+    let segments: Vec<&str> = parsed
+        .path_segments()
+        .map(|s| s.filter(|part| !part.is_empty()).collect())
+        .unwrap_or_default();
+
+    let (slug, rest) = match segments.as_slice() {
+        [kind, slug, rest @ ..] if matches!(*kind, "modpack" | "project") => (*slug, rest),
+        _ => return Err("Link does not point to a Modrinth modpack".to_string()),
+    };
+
+    if slug.len() > 100 || !slug.chars().all(|c| c.is_alphanumeric() || c == '-' || c == '_') {
+        return Err("Invalid modpack slug in link".to_string());
+    }
+
+    let version = match rest {
+        ["version", version, ..] => {
+            if !version.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '-' || c == '_' || c == '+') {
+                return Err("Invalid version in link".to_string());
             }
-            
-            let _ = validate_download_url(download_url)?;
-            client.download_mod_file(download_url, &dest_path)
-                .await
-                .map_err(|e| e.to_string())?;
-            
-            let progress = 70 + ((idx + 1) * 25 / total_files) as u32;
-            let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-                "instance": safe_name,
-                "progress": progress,
-                "stage": format!("Downloading mods... ({}/{})", idx + 1, total_files)
-            }));
+            Some(version.to_string())
         }
+        _ => None,
+    };
+
+    Ok((slug.to_string(), version))
+}
+
+/// Pauses the mod downloads of a running modpack install. Downloads already in flight finish.
+#[tauri::command]
+pub async fn pause_install(instance_name: String, app_handle: tauri::AppHandle) -> Result<(), String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let control = crate::services::install_control::get(&safe_name)
+        .ok_or_else(|| format!("No modpack installation is running for '{}'", safe_name))?;
+
+    control.pause();
+
+    let instance_dir = get_instance_dir(&safe_name);
+    if let Some(mut pending) = load_pending_install(&instance_dir) {
+        pending.paused = true;
+        save_pending_install(&instance_dir, &pending);
     }
-    
+
+    ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+        .stage("Paused")
+        .emit(&app_handle);
+
+    Ok(())
+}
+
+/// Resumes a paused install, or continues one interrupted by quitting the launcher.
+#[tauri::command]
+pub async fn resume_install(instance_name: String, app_handle: tauri::AppHandle) -> Result<(), String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let instance_dir = get_instance_dir(&safe_name);
+
+    // A paused install that is still running keeps the instance lock, it only needs waking up
+    if let Some(control) = crate::services::install_control::get(&safe_name) {
+        if let Some(mut pending) = load_pending_install(&instance_dir) {
+            pending.paused = false;
+            save_pending_install(&instance_dir, &pending);
+        }
+        control.resume();
+        return Ok(());
+    }
+
+    let _lock = crate::services::instance_lock::lock(&app_handle, &safe_name, "modpack install")?;
+    let mut pending = load_pending_install(&instance_dir)
+        .ok_or_else(|| format!("No unfinished modpack installation for '{}'", safe_name))?;
+    pending.paused = false;
+    save_pending_install(&instance_dir, &pending);
+    let operation = crate::services::operations::track(&app_handle, OperationKind::ModpackInstall, Some(&safe_name));
+
+    let pack_files = pending.pack_files.as_deref().unwrap_or(&pending.files);
+    download_mrpack_files(&pending.files, pack_files, &instance_dir, &safe_name, pending.finalization.as_ref(), &app_handle).await?;
+
+    ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+        .percent(100)
+        .stage("Installation complete!")
+        .emit(&app_handle);
+
+    // Finishes what the interrupted install would have done, e.g. after a restart
+    if let Some(finalization) = &pending.finalization {
+        finalize_modpack_install(&safe_name, finalization, &app_handle);
+    }
+
+    operation.complete();
+    Ok(())
+}
+
+/// Instances whose modpack install was interrupted and can be continued with `resume_install`.
+#[tauri::command]
+pub async fn get_pending_installs() -> Result<Vec<String>, String> {
+    let Ok(entries) = std::fs::read_dir(get_instances_dir()) else {
+        return Ok(Vec::new());
+    };
+
+    Ok(entries
+        .flatten()
+        .filter(|e| e.path().join(PENDING_INSTALL_FILE).exists())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|name| crate::services::install_control::get(name).is_none())
+        .collect())
+}
+
+/// Updates a Modrinth modpack instance to another version of the same pack. Only files that are
+/// new or changed in the target version are downloaded. Files the old version shipped but the new
+/// one no longer lists are deleted; files the user added themselves are left alone.
+#[tauri::command]
+pub async fn update_modpack(
+    instance_name: String,
+    version_id: String,
+    app_handle: tauri::AppHandle,
+) -> Result<ModpackUpdateSummary, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let _lock = crate::services::instance_lock::lock(&app_handle, &safe_name, "modpack update")?;
+    update_modpack_locked(&safe_name, &version_id, &app_handle).await
+}
+
+/// `update_modpack` for callers already holding the instance lock.
+pub(crate) async fn update_modpack_locked(
+    safe_name: &str,
+    version_id: &str,
+    app_handle: &tauri::AppHandle,
+) -> Result<ModpackUpdateSummary, String> {
+    let instance_dir = get_instance_dir(safe_name);
+
+    let content = std::fs::read_to_string(instance_dir.join("instance.json"))
+        .map_err(|_| format!("Instance '{}' does not exist", safe_name))?;
+    let instance: Instance = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+
+    let modpack = instance.modpack.clone()
+        .filter(|m| m.source == "modrinth")
+        .ok_or("Only instances installed from a Modrinth modpack can be updated")?;
+    let project_id = modpack.project_id.clone()
+        .ok_or("The instance does not record which modpack it was installed from")?;
+
+    if crate::commands::instances::is_running(safe_name)? {
+        return Err("Close the instance before updating its modpack".to_string());
+    }
+
+    if crate::services::install_control::get(safe_name).is_some() || instance_dir.join(PENDING_INSTALL_FILE).exists() {
+        return Err("Finish or cancel the running modpack installation first".to_string());
+    }
+
+    let operation = crate::services::operations::track(app_handle, OperationKind::ModpackInstall, Some(safe_name));
+
+    ProgressEvent::new(OperationKind::ModpackInstall, Some(safe_name))
+        .percent(5)
+        .stage("Fetching modpack information...")
+        .emit(app_handle);
+
+    let client = ModrinthClient::with_client(crate::utils::http::client_from(app_handle));
+    let versions = client
+        .get_project_versions(&project_id, None, None)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let version = versions
+        .iter()
+        .find(|v| v.id == version_id)
+        .ok_or("Version not found")?;
+
+    let primary_file = version.files.iter()
+        .find(|f| f.primary)
+        .or_else(|| version.files.first())
+        .ok_or("No modpack file found")?;
+
+    validate_download_url(&primary_file.url)?;
+
+    ProgressEvent::new(OperationKind::ModpackInstall, Some(safe_name))
+        .percent(15)
+        .stage("Downloading modpack...")
+        .emit(app_handle);
+
+    let temp_dir = std::env::temp_dir();
+    let modpack_file = temp_dir.join(&primary_file.filename);
+    client
+        .download_mod_file(&primary_file.url, &modpack_file)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let finalization = PendingFinalization::Update {
+        version_id: version.id.clone(),
+        version_name: version.version_number.clone(),
+    };
+
+    let extract_dir = temp_dir.join(format!("modpack_update_{}", safe_name));
+    if extract_dir.exists() {
+        let _ = std::fs::remove_dir_all(&extract_dir);
+    }
+    std::fs::create_dir_all(&extract_dir)
+        .map_err(|e| e.to_string())?;
+
+    let result = match extract_modpack(&modpack_file, &extract_dir) {
+        Ok(()) => apply_modpack_update(&instance, safe_name, &extract_dir, &finalization, app_handle).await,
+        Err(e) => Err(e),
+    };
+
     let _ = std::fs::remove_file(&modpack_file);
     let _ = std::fs::remove_dir_all(&extract_dir);
 
-    let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-        "instance": safe_name,
-        "progress": 100,
-        "stage": "Installation complete!"
-    }));
+    let summary = result?;
+
+    finalize_modpack_install(safe_name, &finalization, app_handle);
+
+    ProgressEvent::new(OperationKind::ModpackInstall, Some(safe_name))
+        .percent(100)
+        .stage("Update complete!")
+        .emit(app_handle);
+
+    operation.complete();
+    Ok(ModpackUpdateSummary {
+        version_name: version.version_number.clone(),
+        ..summary
+    })
+}
+
+/// The loader and loader version a Modrinth pack manifest depends on.
+fn manifest_loader(manifest: &serde_json::Value) -> Option<(&'static str, String)> {
+    [("fabric-loader", "fabric"), ("quilt-loader", "quilt"), ("neoforge", "neoforge"), ("forge", "forge")]
+        .into_iter()
+        .find_map(|(key, loader)| {
+            let version = manifest.get("dependencies")?.get(key)?.as_str()?;
+            Some((loader, version.to_string()))
+        })
+}
+
+/// Installs the loader version an updated pack asks for, unless the instance already runs it.
+async fn update_modpack_loader(
+    safe_name: &str,
+    loader: &str,
+    loader_version: &str,
+    app_handle: &tauri::AppHandle,
+) -> Result<(), String> {
+    if loader_version.is_empty() || !loader_version.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '-' || c == '+') {
+        return Err("Invalid modpack: invalid loader version".to_string());
+    }
+
+    let instance_dir = get_instance_dir(safe_name);
+    let instance_json_path = instance_dir.join("instance.json");
+    // Re-read, a Minecraft version change above rewrites it
+    let mut instance: Instance = std::fs::read_to_string(&instance_json_path)
+        .map_err(|e| e.to_string())
+        .and_then(|content| serde_json::from_str(&content).map_err(|e| e.to_string()))?;
+
+    let minecraft_version = crate::commands::instances::instance_minecraft_version(&instance);
+    // Forge is installed and recorded by its full `<minecraft>-<forge>` version
+    let version = if loader == "forge" {
+        format!("{}-{}", minecraft_version, loader_version)
+    } else {
+        loader_version.to_string()
+    };
+
+    if instance.loader.as_deref() == Some(loader) && instance.loader_version.as_deref() == Some(version.as_str()) {
+        return Ok(());
+    }
+
+    ProgressEvent::new(OperationKind::ModpackInstall, Some(safe_name))
+        .percent(40)
+        .stage(format!("Installing {} {}...", loader, loader_version))
+        .emit(app_handle);
+
+    instance.version = crate::commands::instances::install_loader_version(loader, &minecraft_version, &version).await?;
+    instance.loader = Some(loader.to_string());
+    instance.loader_version = Some(version);
+
+    write_json_atomic(&instance_json_path, &instance)
+        .map_err(|e| e.to_string())?;
+
+    crate::commands::instances::clear_instance_natives(&instance_dir)
+}
+
+async fn apply_modpack_update(
+    instance: &Instance,
+    safe_name: &str,
+    extract_dir: &std::path::Path,
+    finalization: &PendingFinalization,
+    app_handle: &tauri::AppHandle,
+) -> Result<ModpackUpdateSummary, String> {
+    let instance_dir = get_instance_dir(safe_name);
+
+    let manifest_content = std::fs::read_to_string(extract_dir.join("modrinth.index.json"))
+        .map_err(|_| "Invalid modpack: modrinth.index.json not found".to_string())?;
+    let manifest: serde_json::Value = serde_json::from_str(&manifest_content)
+        .map_err(|e| e.to_string())?;
+
+    let new_files: Vec<serde_json::Value> = manifest.get("files")
+        .and_then(|f| f.as_array())
+        .cloned()
+        .unwrap_or_default();
+
+    if let Some(game_version) = manifest.pointer("/dependencies/minecraft").and_then(|v| v.as_str()) {
+        if game_version != extract_minecraft_version_from_instance(&instance.version) {
+            ProgressEvent::new(OperationKind::ModpackInstall, Some(safe_name))
+                .percent(25)
+                .stage(format!("Updating to Minecraft {}...", game_version))
+                .emit(app_handle);
+
+            crate::commands::instances::change_minecraft_version(
+                safe_name.to_string(),
+                game_version.to_string(),
+                app_handle.clone(),
+            )
+            .await?;
+        }
+    }
+
+    if let Some((loader, loader_version)) = manifest_loader(&manifest) {
+        update_modpack_loader(safe_name, loader, &loader_version, app_handle).await?;
+    }
+
+    ProgressEvent::new(OperationKind::ModpackInstall, Some(safe_name))
+        .percent(50)
+        .stage("Comparing modpack files...")
+        .emit(app_handle);
+
+    // Instances installed before the file list was recorded can't tell pack files from
+    // user files, so nothing is removed for them
+    let old_hashes: HashMap<String, Option<String>> = load_installed_files(&instance_dir)
+        .unwrap_or_default()
+        .iter()
+        .filter_map(|f| Some((f.get("path")?.as_str()?.to_string(), mrpack_file_sha1(f))))
+        .collect();
+
+    let mut summary = ModpackUpdateSummary::default();
+    let mut to_download = Vec::with_capacity(new_files.len());
+    let mut new_paths = HashSet::new();
+
+    for file in &new_files {
+        let Some(path) = file.get("path").and_then(|p| p.as_str()) else {
+            continue;
+        };
+        new_paths.insert(path.to_string());
+
+        match old_hashes.get(path) {
+            Some(old_sha1) if *old_sha1 == mrpack_file_sha1(file) => {
+                summary.unchanged += 1;
+                // A mod the user disabled stays disabled
+                if instance_dir.join(format!("{}.disabled", path)).exists() {
+                    continue;
+                }
+            }
+            Some(_) => summary.updated += 1,
+            None => summary.added += 1,
+        }
+        to_download.push(file.clone());
+    }
+
+    for path in old_hashes.keys().filter(|p| !new_paths.contains(*p)) {
+        if has_parent_component(path) {
+            continue;
+        }
+        let target = instance_dir.join(path);
+        if !target.starts_with(&instance_dir) {
+            continue;
+        }
+
+        let removed = std::fs::remove_file(&target).is_ok();
+        let removed_disabled = std::fs::remove_file(instance_dir.join(format!("{}.disabled", path))).is_ok();
+        if removed || removed_disabled {
+            summary.removed += 1;
+        }
+    }
+
+    let overrides_dir = extract_dir.join("overrides");
+    if overrides_dir.exists() {
+        ProgressEvent::new(OperationKind::ModpackInstall, Some(safe_name))
+            .percent(65)
+            .stage("Copying overrides...")
+            .emit(app_handle);
+
+        copy_dir_recursive(&overrides_dir, &instance_dir)
+            .map_err(|e| e.to_string())?;
+    }
+
+    ProgressEvent::new(OperationKind::ModpackInstall, Some(safe_name))
+        .percent(70)
+        .stage(format!("Downloading {} changed files...", summary.added + summary.updated))
+        .emit(app_handle);
+
+    download_mrpack_files(&to_download, &new_files, &instance_dir, safe_name, Some(finalization), app_handle).await?;
+
+    Ok(summary)
+}
+
+/// Downloads a Modrinth project icon and sets it as the icon of `instance_name`.
+async fn apply_project_icon(client: &ModrinthClient, instance_name: &str, icon_url: &str) -> Result<(), String> {
+    validate_download_url(icon_url)?;
+    
+    // set_instance_icon detects the format from the contents, so the URL's extension isn't trusted
+    let icon_path = std::env::temp_dir().join(format!("modpack_icon_{}", instance_name));
+    client
+        .download_mod_file(icon_url, &icon_path)
+        .await
+        .map_err(|e| e.to_string())?;
     
+    let icon_bytes = std::fs::read(&icon_path);
+    let _ = std::fs::remove_file(&icon_path);
+    let icon_bytes = icon_bytes.map_err(|e| e.to_string())?;
+    
+    use base64::{Engine as _, engine::general_purpose};
+    crate::commands::set_instance_icon(instance_name.to_string(), general_purpose::STANDARD.encode(&icon_bytes)).await
+}
+
+/// Sets the icon of a pack installed from a local `.mrpack` that was downloaded from Modrinth,
+/// found by the file's hash. Packs Modrinth doesn't know are left without an icon.
+async fn apply_known_pack_icon(mrpack_path: &std::path::Path, instance_name: &str) -> Result<(), String> {
+    let sha1 = MinecraftInstaller::sha1_file(&mrpack_path.to_path_buf())
+        .map_err(|e| e.to_string())?;
+    
+    let client = ModrinthClient::new().map_err(|e| e.to_string())?;
+    let version_files = client
+        .get_version_files_by_hashes(std::slice::from_ref(&sha1))
+        .await
+        .map_err(|e| e.to_string())?;
+    
+    let Some(version) = version_files.get(&sha1) else {
+        return Ok(());
+    };
+    
+    let project = client
+        .get_project(&version.project_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    
+    match project.icon_url {
+        Some(icon_url) => apply_project_icon(&client, instance_name, &icon_url).await,
+        None => Ok(()),
+    }
+}
+
+fn mrpack_file_sha1(file: &serde_json::Value) -> Option<String> {
+    file.pointer("/hashes/sha1").and_then(|h| h.as_str()).map(|h| h.to_lowercase())
+}
+
+/// The mrpack file list of the installed pack version, used to tell pack files from user
+/// files when the pack is updated.
+const INSTALLED_FILES_FILE: &str = ".modpack_files.json";
+
+fn save_installed_files(instance_dir: &std::path::Path, files: &[serde_json::Value]) {
+    if let Err(e) = crate::utils::write_json_atomic(&instance_dir.join(INSTALLED_FILES_FILE), files) {
+        eprintln!("Failed to record the installed modpack files of {}: {}", instance_dir.display(), e);
+    }
+}
+
+fn load_installed_files(instance_dir: &std::path::Path) -> Option<Vec<serde_json::Value>> {
+    let content = std::fs::read_to_string(instance_dir.join(INSTALLED_FILES_FILE)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Written into the instance while its mrpack files download, so an interrupted install
+/// can be resumed after a restart.
+pub(crate) const PENDING_INSTALL_FILE: &str = ".pending_install.json";
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct PendingInstall {
+    files: Vec<serde_json::Value>,
+    /// Every file of the pack version when only some of them are downloaded, as in an update.
+    /// Recorded as the installed files once the download finishes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pack_files: Option<Vec<serde_json::Value>>,
+    #[serde(default)]
+    paused: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    finalization: Option<PendingFinalization>,
+}
+
+/// What is left to do once the downloaded files are in place.
+#[derive(serde::Serialize, serde::Deserialize, Clone)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum PendingFinalization {
+    /// A new modpack instance.
+    Install { display_name: String },
+    /// An update of the instance's modpack to another version.
+    Update { version_id: String, version_name: String },
+}
+
+fn finalize_modpack_install(safe_name: &str, finalization: &PendingFinalization, app_handle: &tauri::AppHandle) {
+    match finalization {
+        PendingFinalization::Install { display_name } => {
+            if let Err(e) = InstanceManager::set_display_name(safe_name, display_name) {
+                eprintln!("Failed to save the display name of {}: {}", safe_name, e);
+            }
+            crate::services::templates::TemplateManager::apply_default_template(safe_name);
+            apply_recommended_memory(safe_name);
+            crate::commands::fabric_api::notify_if_fabric_api_missing(app_handle, safe_name);
+        }
+        PendingFinalization::Update { version_id, version_name } => {
+            let modpack = std::fs::read_to_string(get_instance_dir(safe_name).join("instance.json"))
+                .ok()
+                .and_then(|content| serde_json::from_str::<Instance>(&content).ok())
+                .and_then(|instance| instance.modpack);
+            match modpack {
+                Some(modpack) => save_modpack_info(safe_name, ModpackInfo {
+                    version_id: Some(version_id.clone()),
+                    version_name: Some(version_name.clone()),
+                    ..modpack
+                }),
+                None => eprintln!("Failed to record the modpack version of {}: no modpack info", safe_name),
+            }
+            crate::commands::mods::invalidate_mod_cache(safe_name);
+        }
+    }
+}
+
+fn save_pending_install(instance_dir: &std::path::Path, pending: &PendingInstall) {
+    if let Err(e) = crate::utils::write_json_atomic(&instance_dir.join(PENDING_INSTALL_FILE), pending) {
+        eprintln!("Failed to record the pending modpack install of {}: {}", instance_dir.display(), e);
+    }
+}
+
+fn load_pending_install(instance_dir: &std::path::Path) -> Option<PendingInstall> {
+    let content = std::fs::read_to_string(instance_dir.join(PENDING_INSTALL_FILE)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Whether a manifest path climbs out of the folder it is joined to. File names containing
+/// `..` are fine.
+fn has_parent_component(path: &str) -> bool {
+    std::path::Path::new(path)
+        .components()
+        .any(|component| matches!(component, std::path::Component::ParentDir))
+}
+
+/// Downloads every file listed in an mrpack manifest, several at a time over the shared client.
+///
+/// Files already on disk with the expected SHA-1 are skipped, which is what makes resuming cheap.
+/// The download can be paused with `pause_install`; files already downloading finish first.
+async fn download_mrpack_files(
+    files: &[serde_json::Value],
+    pack_files: &[serde_json::Value],
+    instance_dir: &std::path::Path,
+    safe_name: &str,
+    finalization: Option<&PendingFinalization>,
+    app_handle: &tauri::AppHandle,
+) -> Result<(), String> {
+    let mut tasks = Vec::with_capacity(files.len());
+
+    for file in files {
+        let downloads = file.get("downloads")
+            .and_then(|d| d.as_array())
+            .ok_or("Invalid file entry in manifest")?;
+
+        let download_url = downloads.iter()
+            .filter_map(|u| u.as_str())
+            .find(|u| validate_download_url(u).is_ok())
+            .or_else(|| downloads.first().and_then(|u| u.as_str()))
+            .ok_or("No download URL found")?;
+
+        let path = file.get("path")
+            .and_then(|p| p.as_str())
+            .ok_or("No path found in file entry")?;
+
+        let dest_path = instance_dir.join(path);
+        if !dest_path.starts_with(instance_dir) || has_parent_component(path) {
+            return Err(format!("Invalid file path in manifest: {}", path));
+        }
+
+        if let Some(parent) = dest_path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| e.to_string())?;
+        }
+
+        validate_download_url(download_url)?;
+        let sha1 = file.pointer("/hashes/sha1").and_then(|h| h.as_str()).map(|h| h.to_lowercase());
+        tasks.push((download_url.to_string(), dest_path, sha1));
+    }
+
+    let total_files = tasks.len();
+    if total_files == 0 {
+        save_installed_files(instance_dir, pack_files);
+        return Ok(());
+    }
+
+    let was_paused = load_pending_install(instance_dir).is_some_and(|p| p.paused);
+    save_pending_install(instance_dir, &PendingInstall {
+        files: files.to_vec(),
+        pack_files: (pack_files != files).then(|| pack_files.to_vec()),
+        paused: was_paused,
+        finalization: finalization.cloned(),
+    });
+    let control = crate::services::install_control::register(safe_name, was_paused);
+
+    // Hashing existing jars is blocking work
+    let tasks = tokio::task::spawn_blocking(move || {
+        tasks
+            .into_iter()
+            .filter(|(_, dest_path, sha1)| match sha1 {
+                Some(expected) => MinecraftInstaller::sha1_file(dest_path).ok().as_deref() != Some(expected.as_str()),
+                None => true,
+            })
+            .collect::<Vec<_>>()
+    })
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let result = download_mrpack_tasks(tasks, total_files, &control, safe_name, app_handle).await;
+    crate::services::install_control::unregister(safe_name);
+
+    // A failed install keeps its pending state so it can be resumed
+    if result.is_ok() {
+        let _ = std::fs::remove_file(instance_dir.join(PENDING_INSTALL_FILE));
+        save_installed_files(instance_dir, pack_files);
+    }
+
+    result
+}
+
+async fn download_mrpack_tasks(
+    tasks: Vec<(String, std::path::PathBuf, Option<String>)>,
+    total_files: usize,
+    control: &crate::services::install_control::InstallControl,
+    safe_name: &str,
+    app_handle: &tauri::AppHandle,
+) -> Result<(), String> {
+    let http_client = crate::utils::http::client_from(app_handle);
+    let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_MOD_DOWNLOADS));
+    let already_done = total_files - tasks.len();
+
+    let emit_progress = |completed: usize| {
+        let progress = 70 + (completed * 25 / total_files) as u32;
+        let stage = if control.is_paused() {
+            format!("Paused ({}/{})", completed, total_files)
+        } else {
+            format!("Downloading mods... ({}/{})", completed, total_files)
+        };
+        ProgressEvent::new(OperationKind::ModpackInstall, Some(safe_name))
+            .percent(progress)
+            .stage(stage)
+            .emit(app_handle);
+    };
+
+    let mut downloads = tokio::task::JoinSet::new();
+    let mut completed = already_done;
+    emit_progress(completed);
+
+    // Returning early drops the set, which aborts the downloads still running
+    for (url, dest_path, _) in tasks {
+        if control.is_paused() {
+            control.wait_while_paused().await;
+        }
+
+        // Finished downloads are collected while waiting for a slot, so a failure stops early
+        let permit = loop {
+            tokio::select! {
+                permit = semaphore.clone().acquire_owned() => break permit.map_err(|e| e.to_string())?,
+                Some(result) = downloads.join_next() => {
+                    result.map_err(|e| e.to_string())??;
+                    completed += 1;
+                    emit_progress(completed);
+                }
+            }
+        };
+        let client = ModrinthClient::with_client(http_client.clone());
+
+        downloads.spawn(async move {
+            let result = client.download_mod_file(&url, &dest_path)
+                .await
+                .map_err(|e| format!("Failed to download {}: {}", url, e));
+            drop(permit);
+            result
+        });
+    }
+
+    while let Some(result) = downloads.join_next().await {
+        result.map_err(|e| e.to_string())??;
+        completed += 1;
+        emit_progress(completed);
+    }
+
     Ok(())
 }
 
@@ -328,7 +1043,7 @@ fn copy_dir_recursive(
         if file_type.is_dir() {
             copy_dir_recursive(&src_path, &dst_path)?;
         } else if file_type.is_file() {
-            fs::copy(&src_path, &dst_path)?;
+            crate::utils::copy_file_unlinked(&src_path, &dst_path)?;
         }
     }
     
@@ -368,7 +1083,7 @@ fn extract_modpack(
                         .map_err(|e| e.to_string())?;
                 }
             }
-            let mut outfile = std::fs::File::create(&outpath)
+            let mut outfile = crate::utils::create_file_unlinked(&outpath)
                 .map_err(|e| e.to_string())?;
             std::io::copy(&mut file, &mut outfile)
                 .map_err(|e| e.to_string())?;
@@ -505,7 +1220,11 @@ pub async fn install_modpack_from_file(
 ) -> Result<(), String> {
     use std::path::Path;
     
-    let safe_name = sanitize_instance_name(&instance_name)?;
+    let display_name = sanitize_display_name(&instance_name)?;
+    let safe_name = instance_folder_name(&display_name)?;
+    let _lock = crate::services::instance_lock::lock(&app_handle, &safe_name, "modpack install")?;
+    let finalization = PendingFinalization::Install { display_name };
+    let operation = crate::services::operations::track(&app_handle, OperationKind::ModpackInstall, Some(&safe_name));
     
     let file_path_obj = Path::new(&file_path);
     if !file_path_obj.exists() {
@@ -527,17 +1246,15 @@ pub async fn install_modpack_from_file(
         }
     }
 
-    let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-        "instance": safe_name,
-        "progress": 0,
-        "stage": "Starting modpack installation..."
-    }));
+    ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+        .percent(0)
+        .stage("Starting modpack installation...")
+        .emit(&app_handle);
     
-    let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-        "instance": safe_name,
-        "progress": 5,
-        "stage": "Reading modpack file..."
-    }));
+    ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+        .percent(5)
+        .stage("Reading modpack file...")
+        .emit(&app_handle);
     
     let temp_dir = std::env::temp_dir();
     let extract_dir = temp_dir.join(format!("modpack_extract_{}", safe_name));
@@ -547,20 +1264,18 @@ pub async fn install_modpack_from_file(
     std::fs::create_dir_all(&extract_dir)
         .map_err(|e| e.to_string())?;
     
-    let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-        "instance": safe_name,
-        "progress": 10,
-        "stage": "Extracting modpack..."
-    }));
+    ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+        .percent(10)
+        .stage("Extracting modpack...")
+        .emit(&app_handle);
     
     extract_modpack(file_path_obj, &extract_dir)
         .map_err(|e| e.to_string())?;
     
-    let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-        "instance": safe_name,
-        "progress": 20,
-        "stage": "Reading modpack manifest..."
-    }));
+    ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+        .percent(20)
+        .stage("Reading modpack manifest...")
+        .emit(&app_handle);
     
     let manifest_path = extract_dir.join("modrinth.index.json");
     let is_mrpack = manifest_path.exists();
@@ -574,33 +1289,41 @@ pub async fn install_modpack_from_file(
     if is_mrpack {
         install_from_mrpack(
             extract_dir,
-            safe_name,
+            safe_name.clone(),
             preferred_game_version,
-            app_handle
-        ).await
+            (extension == "mrpack").then_some(file_path_obj),
+            &finalization,
+            app_handle.clone()
+        ).await?;
     } else if is_standard_zip {
         install_from_standard_zip(
             extract_dir,
-            safe_name,
+            safe_name.clone(),
             preferred_game_version,
-            app_handle
-        ).await
+            app_handle.clone()
+        ).await?;
     } else if is_curseforge {
         install_from_curseforge_manifest(
             extract_dir,
-            safe_name,
+            safe_name.clone(),
             preferred_game_version,
-            app_handle
-        ).await
+            app_handle.clone()
+        ).await?;
     } else {
-        Err("Invalid modpack format: missing modrinth.index.json or instance.json or manifest.json".to_string())
+        return Err("Invalid modpack format: missing modrinth.index.json or instance.json or manifest.json".to_string());
     }
+
+    finalize_modpack_install(&safe_name, &finalization, &app_handle);
+    operation.complete();
+    Ok(())
 }
 
 async fn install_from_mrpack(
     extract_dir: std::path::PathBuf,
     safe_name: String,
     preferred_game_version: Option<String>,
+    mrpack_path: Option<&std::path::Path>,
+    finalization: &PendingFinalization,
     app_handle: tauri::AppHandle,
 ) -> Result<(), String> {
     let manifest_path = extract_dir.join("modrinth.index.json");
@@ -633,11 +1356,10 @@ async fn install_from_mrpack(
         "vanilla"
     };
     
-    let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-        "instance": safe_name,
-        "progress": 30,
-        "stage": format!("Installing Minecraft {}...", game_version)
-    }));
+    ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+        .percent(30)
+        .stage(format!("Installing Minecraft {}...", game_version))
+        .emit(&app_handle);
     
     let meta_dir = get_meta_dir();
     let installer = MinecraftInstaller::new(meta_dir.clone())
@@ -648,11 +1370,10 @@ async fn install_from_mrpack(
         .map_err(|e| e.to_string())?;
     
     let final_version = if loader == "fabric" {
-        let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-            "instance": safe_name,
-            "progress": 40,
-            "stage": "Installing Fabric loader..."
-        }));
+        ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+            .percent(40)
+            .stage("Installing Fabric loader...")
+            .emit(&app_handle);
         
         let fabric_installer = FabricInstaller::new(meta_dir)
             .map_err(|e| e.to_string())?;
@@ -673,11 +1394,10 @@ async fn install_from_mrpack(
             .await
             .map_err(|e| e.to_string())?
     } else if loader == "forge" {
-        let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-            "instance": safe_name,
-            "progress": 40,
-            "stage": "Installing Forge loader..."
-        }));
+        ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+            .percent(40)
+            .stage("Installing Forge loader...")
+            .emit(&app_handle);
         
         let forge_installer = crate::services::forge::ForgeInstaller::new(meta_dir)
             .map_err(|e| e.to_string())?;
@@ -700,11 +1420,10 @@ async fn install_from_mrpack(
         game_version.clone()
     };
     
-    let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-        "instance": safe_name,
-        "progress": 50,
-        "stage": "Creating instance..."
-    }));
+    ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+        .percent(50)
+        .stage("Creating instance...")
+        .emit(&app_handle);
     
     InstanceManager::create(
         &safe_name,
@@ -714,11 +1433,18 @@ async fn install_from_mrpack(
     )
     .map_err(|e| e.to_string())?;
 
-    let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-        "instance": safe_name,
-        "progress": 55,
-        "stage": "Setting modpack icon..."
-    }));
+    save_modpack_info(&safe_name, ModpackInfo {
+        source: "file".to_string(),
+        name: manifest_str(&manifest, "name").unwrap_or_else(|| safe_name.clone()),
+        summary: manifest_str(&manifest, "summary"),
+        version_name: manifest_str(&manifest, "versionId"),
+        ..ModpackInfo::default()
+    });
+
+    ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+        .percent(55)
+        .stage("Setting modpack icon...")
+        .emit(&app_handle);
     
     let icon_path = extract_dir.join("icon.png");
     if icon_path.exists() {
@@ -727,15 +1453,18 @@ async fn install_from_mrpack(
             let icon_base64 = general_purpose::STANDARD.encode(&icon_bytes);
             let _ = crate::commands::set_instance_icon(safe_name.clone(), icon_base64).await;
         }
+    } else if let Some(mrpack_path) = mrpack_path {
+        if let Err(e) = apply_known_pack_icon(mrpack_path, &safe_name).await {
+            eprintln!("Failed to look up the modpack icon of {}: {}", safe_name, e);
+        }
     }
     
     let instance_dir = get_instance_dir(&safe_name);
     
-    let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-        "instance": safe_name,
-        "progress": 60,
-        "stage": "Copying overrides..."
-    }));
+    ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+        .percent(60)
+        .stage("Copying overrides...")
+        .emit(&app_handle);
     
     let overrides_dir = extract_dir.join("overrides");
     if overrides_dir.exists() {
@@ -745,55 +1474,20 @@ async fn install_from_mrpack(
     
     if let Some(files) = manifest.get("files").and_then(|f| f.as_array()) {
         let total_files = files.len();
-        let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-            "instance": safe_name,
-            "progress": 70,
-            "stage": format!("Downloading {} mods...", total_files)
-        }));
-        
-        let client = crate::utils::modrinth::ModrinthClient::new().map_err(|e| e.to_string())?;
+        ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+            .percent(70)
+            .stage(format!("Downloading {} mods...", total_files))
+            .emit(&app_handle);
         
-        for (idx, file) in files.iter().enumerate() {
-            let downloads = file.get("downloads")
-                .and_then(|d| d.as_array())
-                .ok_or("Invalid file entry in manifest")?;
-            
-            let download_url = downloads.first()
-                .and_then(|u| u.as_str())
-                .ok_or("No download URL found")?;
-            
-            let path = file.get("path")
-                .and_then(|p| p.as_str())
-                .ok_or("No path found in file entry")?;
-            
-            let dest_path = instance_dir.join(path);
-            
-            if let Some(parent) = dest_path.parent() {
-                std::fs::create_dir_all(parent)
-                    .map_err(|e| e.to_string())?;
-            }
-            
-            let _ = validate_download_url(download_url)?;
-            client.download_mod_file(download_url, &dest_path)
-                .await
-                .map_err(|e| e.to_string())?;
-            
-            let progress = 70 + ((idx + 1) * 25 / total_files) as u32;
-            let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-                "instance": safe_name,
-                "progress": progress,
-                "stage": format!("Downloading mods... ({}/{})", idx + 1, total_files)
-            }));
-        }
+        download_mrpack_files(files, files, &instance_dir, &safe_name, Some(finalization), &app_handle).await?;
     }
     
     let _ = std::fs::remove_dir_all(&extract_dir);
 
-    let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-        "instance": safe_name,
-        "progress": 100,
-        "stage": "Installation complete!"
-    }));
+    ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+        .percent(100)
+        .stage("Installation complete!")
+        .emit(&app_handle);
     
     Ok(())
 }
@@ -820,11 +1514,10 @@ async fn install_from_standard_zip(
     let loader = instance.loader.clone();
     let loader_version = instance.loader_version.clone();
     
-    let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-        "instance": safe_name,
-        "progress": 30,
-        "stage": format!("Installing Minecraft {}...", game_version)
-    }));
+    ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+        .percent(30)
+        .stage(format!("Installing Minecraft {}...", game_version))
+        .emit(&app_handle);
     
     let meta_dir = get_meta_dir();
     let installer = MinecraftInstaller::new(meta_dir.clone())
@@ -835,11 +1528,10 @@ async fn install_from_standard_zip(
         .map_err(|e| e.to_string())?;
     
     let final_version = if loader == Some("fabric".to_string()) {
-        let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-            "instance": safe_name,
-            "progress": 40,
-            "stage": "Installing Fabric loader..."
-        }));
+        ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+            .percent(40)
+            .stage("Installing Fabric loader...")
+            .emit(&app_handle);
         
         let fabric_installer = FabricInstaller::new(meta_dir)
             .map_err(|e| e.to_string())?;
@@ -865,11 +1557,10 @@ async fn install_from_standard_zip(
             .await
             .map_err(|e| e.to_string())?
     } else if loader == Some("forge".to_string()) {
-        let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-            "instance": safe_name,
-            "progress": 40,
-            "stage": "Installing Forge loader..."
-        }));
+        ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+            .percent(40)
+            .stage("Installing Forge loader...")
+            .emit(&app_handle);
         
         let forge_installer = crate::services::forge::ForgeInstaller::new(meta_dir)
             .map_err(|e| e.to_string())?;
@@ -897,11 +1588,10 @@ async fn install_from_standard_zip(
         game_version.clone()
     };
     
-    let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-        "instance": safe_name,
-        "progress": 50,
-        "stage": "Creating instance..."
-    }));
+    ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+        .percent(50)
+        .stage("Creating instance...")
+        .emit(&app_handle);
     
     InstanceManager::create(
         &safe_name,
@@ -913,11 +1603,10 @@ async fn install_from_standard_zip(
     
     let instance_dir = get_instance_dir(&safe_name);
     
-    let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-        "instance": safe_name,
-        "progress": 60,
-        "stage": "Copying instance data..."
-    }));
+    ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+        .percent(60)
+        .stage("Copying instance data...")
+        .emit(&app_handle);
 
     let entries_to_copy = vec!["saves", "resourcepacks", "shaderpacks", "mods", "config"];
     
@@ -951,11 +1640,10 @@ async fn install_from_standard_zip(
     
     let _ = std::fs::remove_dir_all(&extract_dir);
 
-    let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-        "instance": safe_name,
-        "progress": 100,
-        "stage": "Installation complete!"
-    }));
+    ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+        .percent(100)
+        .stage("Installation complete!")
+        .emit(&app_handle);
     
     Ok(())
 }
@@ -1008,11 +1696,10 @@ async fn install_from_curseforge_manifest(
         ("vanilla".to_string(), None)
     };
 
-    let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-        "instance": safe_name,
-        "progress": 30,
-        "stage": format!("Installing Minecraft {}...", game_version)
-    }));
+    ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+        .percent(30)
+        .stage(format!("Installing Minecraft {}...", game_version))
+        .emit(&app_handle);
 
     let meta_dir = get_meta_dir();
     let installer = MinecraftInstaller::new(meta_dir.clone())
@@ -1023,11 +1710,10 @@ async fn install_from_curseforge_manifest(
         .map_err(|e| e.to_string())?;
 
     let final_version = if loader == "fabric" {
-        let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-            "instance": safe_name,
-            "progress": 40,
-            "stage": "Installing Fabric loader..."
-        }));
+        ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+            .percent(40)
+            .stage("Installing Fabric loader...")
+            .emit(&app_handle);
 
         let fabric_installer = FabricInstaller::new(meta_dir)
             .map_err(|e| e.to_string())?;
@@ -1048,11 +1734,10 @@ async fn install_from_curseforge_manifest(
             .await
             .map_err(|e| e.to_string())?
     } else if loader == "forge" {
-        let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-            "instance": safe_name,
-            "progress": 40,
-            "stage": "Installing Forge loader..."
-        }));
+        ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+            .percent(40)
+            .stage("Installing Forge loader...")
+            .emit(&app_handle);
 
         let forge_installer = crate::services::forge::ForgeInstaller::new(meta_dir)
             .map_err(|e| e.to_string())?;
@@ -1080,11 +1765,10 @@ async fn install_from_curseforge_manifest(
         game_version.clone()
     };
 
-    let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-        "instance": safe_name,
-        "progress": 50,
-        "stage": "Creating instance..."
-    }));
+    ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+        .percent(50)
+        .stage("Creating instance...")
+        .emit(&app_handle);
 
     InstanceManager::create(
         &safe_name,
@@ -1094,13 +1778,20 @@ async fn install_from_curseforge_manifest(
     )
     .map_err(|e| e.to_string())?;
 
+    save_modpack_info(&safe_name, ModpackInfo {
+        source: "curseforge".to_string(),
+        name: manifest_str(&manifest, "name").unwrap_or_else(|| safe_name.clone()),
+        summary: manifest_str(&manifest, "author").map(|author| format!("By {}", author)),
+        version_name: manifest_str(&manifest, "version"),
+        ..ModpackInfo::default()
+    });
+
     let instance_dir = get_instance_dir(&safe_name);
 
-    let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-        "instance": safe_name,
-        "progress": 60,
-        "stage": "Copying overrides..."
-    }));
+    ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+        .percent(60)
+        .stage("Copying overrides...")
+        .emit(&app_handle);
 
     let overrides_dir = extract_dir.join("overrides");
     if overrides_dir.exists() {
@@ -1119,11 +1810,10 @@ async fn install_from_curseforge_manifest(
 
         let total_files = curseforge_files.len();
         if total_files > 0 {
-            let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-                "instance": safe_name,
-                "progress": 70,
-                "stage": format!("Downloading {} mods...", total_files)
-            }));
+            ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+                .percent(70)
+                .stage(format!("Downloading {} mods...", total_files))
+                .emit(&app_handle);
 
             let api_key = super::curseforge_api_key(&app_handle)?;
             let cf_client = CurseforgeClient::new(api_key).map_err(|e| e.to_string())?;
@@ -1132,30 +1822,49 @@ async fn install_from_curseforge_manifest(
             std::fs::create_dir_all(&mods_dir)
                 .map_err(|e| e.to_string())?;
 
+            let mut skipped = Vec::new();
             for (idx, &(_file_entry, project_id, file_id)) in curseforge_files.iter().enumerate() {
-                let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-                    "instance": safe_name,
-                    "progress": 70 + ((idx + 1) * 25 / total_files) as u32,
-                    "stage": format!("Downloading mods... ({}/{})", idx + 1, total_files)
-                }));
+                ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+                    .percent(70 + ((idx + 1) * 25 / total_files) as u32)
+                    .stage(format!("Downloading mods... ({}/{})", idx + 1, total_files))
+                    .emit(&app_handle);
 
                 match cf_client.get_single_mod_file(project_id, file_id).await {
                     Ok(cf_file) => {
-                        if let Some(download_url) = cf_file.download_url {
-                            let dest_path = mods_dir.join(&cf_file.file_name);
+                        let Some(download_url) = cf_file.download_url else {
+                            skipped.push(format!("{} (downloads disabled by its author)", cf_file.file_name));
+                            continue;
+                        };
+                        if let Err(e) = validate_download_url(&download_url) {
+                            eprintln!("Skipping mod {} file {}: {}", project_id, file_id, e);
+                            skipped.push(format!("{} ({})", cf_file.file_name, e));
+                            continue;
+                        }
 
-                            if let Some(parent) = dest_path.parent() {
-                                let _ = std::fs::create_dir_all(parent);
-                            }
+                        let dest_path = mods_dir.join(&cf_file.file_name);
 
-                            let _ = cf_client.download_file(&download_url, &dest_path).await;
+                        if let Some(parent) = dest_path.parent() {
+                            let _ = std::fs::create_dir_all(parent);
+                        }
+
+                        if let Err(e) = cf_client.download_file(&download_url, &dest_path).await {
+                            skipped.push(format!("{} ({})", cf_file.file_name, e));
                         }
                     }
                     Err(e) => {
                         eprintln!("Failed to fetch mod {} file {}: {}", project_id, file_id, e);
+                        skipped.push(format!("Mod {} file {} ({})", project_id, file_id, e));
                     }
                 }
             }
+
+            // The instance still works without them, so they are reported instead of failing
+            if !skipped.is_empty() {
+                let _ = app_handle.emit("modpack-files-skipped", serde_json::json!({
+                    "instance": safe_name,
+                    "files": skipped
+                }));
+            }
         }
     }
 
@@ -1170,16 +1879,105 @@ async fn install_from_curseforge_manifest(
 
     let _ = std::fs::remove_dir_all(&extract_dir);
 
-    let _ = app_handle.emit("modpack-install-progress", serde_json::json!({
-        "instance": safe_name,
-        "progress": 100,
-        "stage": "Installation complete!"
-    }));
+    ProgressEvent::new(OperationKind::ModpackInstall, Some(&safe_name))
+        .percent(100)
+        .stage("Installation complete!")
+        .emit(&app_handle);
 
     Ok(())
 }
 
-fn extract_minecraft_version_from_instance(version_string: &str) -> String {
+fn manifest_str(manifest: &serde_json::Value, key: &str) -> Option<String> {
+    manifest
+        .get(key)
+        .and_then(|v| v.as_str())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+/// Records the pack branding in instance.json. Failures are only logged since the pack itself installed fine.
+fn save_modpack_info(instance_name: &str, info: ModpackInfo) {
+    let instance_json = get_instance_dir(instance_name).join("instance.json");
+
+    let result = std::fs::read_to_string(&instance_json)
+        .map_err(|e| e.to_string())
+        .and_then(|content| serde_json::from_str::<Instance>(&content).map_err(|e| e.to_string()))
+        .and_then(|mut instance| {
+            instance.modpack = Some(info);
+            crate::utils::write_json_atomic(&instance_json, &instance).map_err(|e| e.to_string())
+        });
+
+    if let Err(e) = result {
+        eprintln!("Failed to save modpack info for {}: {}", instance_name, e);
+    }
+}
+
+/// Memory for a pack with `mod_count` mods of `mods_bytes` in total, at most half the RAM.
+fn recommended_pack_memory_mb(mod_count: usize, mods_bytes: u64, total_memory_mb: u64) -> u32 {
+    let by_count = match mod_count {
+        0..=99 => 4096,
+        100..=199 => 6144,
+        _ => 8192,
+    };
+    let by_size = match mods_bytes / 1024 / 1024 {
+        0..=299 => 4096,
+        300..=599 => 6144,
+        _ => 8192,
+    };
+
+    let cap = (total_memory_mb / 2 / 512 * 512).max(2048) as u32;
+    by_count.max(by_size).min(cap)
+}
+
+/// Gives a freshly installed pack more memory than the launcher default when its mods need
+/// it, through the instance's launch options. Packs that already set their own memory (e.g.
+/// from the default template) are left alone. Failures are only logged.
+fn apply_recommended_memory(instance_name: &str) {
+    let instance_dir = get_instance_dir(instance_name);
+
+    let (mod_count, mods_bytes) = std::fs::read_dir(instance_dir.join("mods"))
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|e| e.path().extension().is_some_and(|ext| ext.eq_ignore_ascii_case("jar")))
+                .fold((0usize, 0u64), |(count, bytes), e| {
+                    (count + 1, bytes + e.metadata().map(|m| m.len()).unwrap_or(0))
+                })
+        })
+        .unwrap_or((0, 0));
+
+    if mod_count == 0 {
+        return;
+    }
+
+    let mut sys = sysinfo::System::new();
+    sys.refresh_memory();
+    let recommended = recommended_pack_memory_mb(mod_count, mods_bytes, sys.total_memory() / 1024 / 1024);
+
+    let global = crate::services::settings::SettingsManager::load().unwrap_or_default();
+    if recommended <= global.memory_mb {
+        return;
+    }
+
+    let instance_json = instance_dir.join("instance.json");
+    let result = std::fs::read_to_string(&instance_json)
+        .map_err(|e| e.to_string())
+        .and_then(|content| serde_json::from_str::<Instance>(&content).map_err(|e| e.to_string()))
+        .and_then(|mut instance| {
+            let options = instance.launch_options.get_or_insert_with(Default::default);
+            if options.memory_mb.is_some() {
+                return Ok(());
+            }
+            options.memory_mb = Some(recommended);
+            crate::utils::write_json_atomic(&instance_json, &instance).map_err(|e| e.to_string())
+        });
+
+    if let Err(e) = result {
+        eprintln!("Failed to set recommended memory for {}: {}", instance_name, e);
+    }
+}
+
+pub(crate) fn extract_minecraft_version_from_instance(version_string: &str) -> String {
     if version_string.contains("fabric-loader") {
         if let Some(mc_version) = version_string.rsplit('-').next() {
             return mc_version.to_string();
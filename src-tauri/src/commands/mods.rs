@@ -1,7 +1,10 @@
 use crate::commands::validation::{sanitize_instance_name, sanitize_mod_filename, sanitize_filename, sanitize_resourcepack_filename, sanitize_shaderpack_filename, validate_download_url};
+use crate::services::mod_index::{mod_key, ModIndex, ModProvenance, ModSource};
+use crate::services::restrictions::RestrictionManager;
 use crate::utils::{get_instance_dir, open_folder};
 use crate::utils::curseforge::{CurseforgeClient, CurseforgeGetModFilesResult, CurseforgeSearchResult};
-use crate::utils::modrinth::{ModrinthClient, ModrinthProjectDetails, ModrinthSearchResult, ModrinthVersion};
+use crate::utils::github::GithubRelease;
+use crate::utils::modrinth::{ModEnvironment, ModrinthClient, ModrinthProjectDetails, ModrinthSearchResult, ModrinthVersion};
 use tauri::Manager;
 use serde::{Deserialize, Serialize};
 use sha1::{Digest, Sha1};
@@ -12,7 +15,7 @@ fn cache_path(instance_dir: &std::path::Path) -> std::path::PathBuf {
     instance_dir.join(".mod_cache.json")
 }
 
-fn invalidate_mod_cache(instance_name: &str) {
+pub(crate) fn invalidate_mod_cache(instance_name: &str) {
     let instance_dir = get_instance_dir(instance_name);
     let path = cache_path(&instance_dir);
     if path.exists() {
@@ -54,6 +57,8 @@ pub struct ModFileWithMetadata {
     pub downloads: Option<u64>,
     pub disabled: bool,
     pub current_version_id: Option<String>,
+    #[serde(default)]
+    pub pinned: bool,
 }
 
 #[tauri::command]
@@ -103,9 +108,10 @@ pub async fn get_installed_mods(instance_name: String) -> Result<Vec<ModFile>, S
 }
 
 #[tauri::command]
-pub async fn delete_mod(instance_name: String, filename: String) -> Result<(), String> {
+pub async fn delete_mod(instance_name: String, filename: String, app_handle: tauri::AppHandle) -> Result<(), String> {
     let safe_name = sanitize_instance_name(&instance_name)?;
     let safe_filename = sanitize_mod_filename(&filename)?;
+    let _lock = crate::services::instance_lock::lock(&app_handle, &safe_name, "mod deletion")?;
     
     let instance_dir = get_instance_dir(&safe_name);
     let mods_dir = instance_dir.join("mods");
@@ -130,9 +136,31 @@ pub async fn delete_mod(instance_name: String, filename: String) -> Result<(), S
 
     invalidate_mod_cache(&safe_name);
 
+    let mut mod_index = ModIndex::load(&safe_name);
+    if mod_index.mods.contains_key(mod_key(&safe_filename)) {
+        mod_index.remove(&safe_filename);
+        mod_index.save(&safe_name).map_err(|e| e.to_string())?;
+    }
+
     Ok(())
 }
 
+/// Pins a mod so "Update All" skips it, or unpins it.
+#[tauri::command]
+pub async fn set_mod_pinned(instance_name: String, filename: String, pinned: bool) -> Result<(), String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let safe_filename = sanitize_mod_filename(mod_key(&filename))?;
+
+    let mods_dir = get_instance_dir(&safe_name).join("mods");
+    if !mods_dir.join(&safe_filename).is_file() && !mods_dir.join(format!("{}.disabled", safe_filename)).is_file() {
+        return Err(format!("Mod file '{}' not found", safe_filename));
+    }
+
+    let mut mod_index = ModIndex::load(&safe_name);
+    mod_index.set_pinned(&safe_filename, pinned);
+    mod_index.save(&safe_name).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn open_mods_folder(instance_name: String) -> Result<(), String> {
     let safe_name = sanitize_instance_name(&instance_name)?;
@@ -264,8 +292,11 @@ pub async fn get_installed_mods_with_metadata(instance_name: String) -> Result<V
         HashMap::new()
     };
 
+    let mod_index = ModIndex::load(&safe_name);
     let mut mods = Vec::new();
     let mut hashes_needing_metadata: Vec<String> = Vec::new();
+    // Mods the launcher installed from Modrinth need no lookup by hash
+    let mut hash_to_version_and_project: HashMap<String, (String, String)> = HashMap::new();
 
     for entry in std::fs::read_dir(&mods_dir).map_err(|e| e.to_string())? {
         let entry = entry.map_err(|e| e.to_string())?;
@@ -297,11 +328,20 @@ pub async fn get_installed_mods_with_metadata(instance_name: String) -> Result<V
                 (hash, meta)
             }
             _ => {
-                let hash = match std::fs::read(&path) {
-                    Ok(bytes) => format!("{:x}", Sha1::digest(&bytes)),
-                    Err(_) => continue,
+                let recorded = mod_index.provenance(&filename).filter(|p| p.matches(&path));
+                let hash = match recorded {
+                    Some(provenance) => provenance.sha1.clone(),
+                    None => match std::fs::read(&path) {
+                        Ok(bytes) => format!("{:x}", Sha1::digest(&bytes)),
+                        Err(_) => continue,
+                    },
                 };
-                hashes_needing_metadata.push(hash.clone());
+                match recorded.and_then(|p| p.modrinth_ids()) {
+                    Some((project_id, version_id)) => {
+                        hash_to_version_and_project.insert(hash.clone(), (project_id.to_string(), version_id.to_string()));
+                    }
+                    None => hashes_needing_metadata.push(hash.clone()),
+                }
                 let existing_meta = disk_cache.get(&filename).and_then(|e| e.metadata.clone());
                 disk_cache.insert(filename.clone(), CacheEntry {
                     mtime,
@@ -324,15 +364,20 @@ pub async fn get_installed_mods_with_metadata(instance_name: String) -> Result<V
             downloads: metadata.as_ref().and_then(|m| m.downloads),
             disabled,
             current_version_id: metadata.as_ref().and_then(|m| m.current_version_id.clone()),
+            pinned: mod_index.is_pinned(&filename),
         });
 
     }
 
 
-    if !hashes_needing_metadata.is_empty() {
+    if !hashes_needing_metadata.is_empty() || !hash_to_version_and_project.is_empty() {
         let client = ModrinthClient::new().map_err(|e| e.to_string())?;
         let mut project_ids: Vec<String> = Vec::new();
-        let mut hash_to_version_and_project: HashMap<String, (String, String)> = HashMap::new();
+        for (project_id, _) in hash_to_version_and_project.values() {
+            if !project_ids.contains(project_id) {
+                project_ids.push(project_id.clone());
+            }
+        }
 
         for chunk in hashes_needing_metadata.chunks(100) {
             if let Ok(version_files) = client.get_version_files_by_hashes(chunk).await {
@@ -363,6 +408,7 @@ pub async fn get_installed_mods_with_metadata(instance_name: String) -> Result<V
                             downloads: Some(project.downloads),
                             disabled: false,
                             current_version_id: Some(version_id.clone()),
+                            pinned: false,
                         };
 
                         for entry in disk_cache.values_mut() {
@@ -404,8 +450,9 @@ pub async fn get_installed_mods_with_metadata(instance_name: String) -> Result<V
 }
 
 #[tauri::command]
-pub async fn toggle_mod(instance_name: String, filename: String, disable: bool) -> Result<(), String> {
+pub async fn toggle_mod(instance_name: String, filename: String, disable: bool, app_handle: tauri::AppHandle) -> Result<(), String> {
     let safe_name = sanitize_instance_name(&instance_name)?;
+    let _lock = crate::services::instance_lock::lock(&app_handle, &safe_name, "mod toggle")?;
     
     let safe_filename = if filename.ends_with(".disabled") {
         let base = filename.trim_end_matches(".disabled");
@@ -461,7 +508,7 @@ pub async fn search_mods(
     let safe_limit = limit.unwrap_or(20).min(100);
     
     let client = ModrinthClient::new().map_err(|e| e.to_string())?;
-    client
+    let mut result = client
         .search_projects(
             &query,
             facets.as_deref(),
@@ -470,7 +517,26 @@ pub async fn search_mods(
             Some(safe_limit),
         )
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+
+    let restricted = RestrictionManager::get();
+    result.hits.retain(|hit| {
+        let mut texts = vec![hit.title.as_str(), hit.description.as_str()];
+        texts.extend(hit.categories.iter().map(String::as_str));
+        !RestrictionManager::is_flagged(restricted.as_ref(), &texts)
+    });
+
+    Ok(result)
+}
+
+fn ensure_project_not_flagged(project: &ModrinthProjectDetails) -> Result<(), String> {
+    let mut texts = vec![project.title.as_str(), project.description.as_str()];
+    texts.extend(project.categories.iter().map(String::as_str));
+
+    if RestrictionManager::is_flagged(RestrictionManager::get().as_ref(), &texts) {
+        return Err("This project is hidden in restricted mode".to_string());
+    }
+    Ok(())
 }
 
 #[tauri::command]
@@ -484,10 +550,22 @@ pub async fn get_mod_details(id_or_slug: String) -> Result<ModrinthProjectDetail
     }
     
     let client = ModrinthClient::new().map_err(|e| e.to_string())?;
-    client
+    let project = client
         .get_project(&id_or_slug)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+
+    ensure_project_not_flagged(&project)?;
+    Ok(ModrinthProjectDetails {
+        environment: Some(project.environment()),
+        ..project
+    })
+}
+
+#[tauri::command]
+pub async fn get_modrinth_categories() -> Result<Vec<crate::utils::modrinth::ModrinthCategory>, String> {
+    let client = ModrinthClient::new().map_err(|e| e.to_string())?;
+    client.get_categories().await.map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -501,10 +579,13 @@ pub async fn get_project_details(id_or_slug: String) -> Result<ModrinthProjectDe
     }
     
     let client = ModrinthClient::new().map_err(|e| e.to_string())?;
-    client
+    let project = client
         .get_project(&id_or_slug)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+
+    ensure_project_not_flagged(&project)?;
+    Ok(project)
 }
 
 #[tauri::command]
@@ -544,15 +625,73 @@ pub async fn get_mod_versions(
         .map_err(|e| e.to_string())
 }
 
+/// Installs a Modrinth project file. With a `project_id`, resource packs and shaders go to
+/// their own folders and data packs into the `world` save folder; mods are recorded in the mod
+/// index, and other versions of the same project installed by the launcher are removed and
+/// returned. Server-only mods are refused unless `allow_server_only` is set, since instances
+/// run the client.
 #[tauri::command]
 pub async fn download_mod(
     instance_name: String,
     download_url: String,
     filename: String,
-) -> Result<(), String> {
+    project_id: Option<String>,
+    version_id: Option<String>,
+    project_type: Option<String>,
+    world: Option<String>,
+    allow_server_only: Option<bool>,
+    app_handle: tauri::AppHandle,
+) -> Result<Vec<String>, String> {
+    // Without a project type from the caller it's looked up, a failed lookup must not
+    // quietly put a pack into `mods/`
+    let mut project = None;
+    let project_type = match (project_type, &project_id) {
+        (Some(project_type), _) => project_type,
+        (None, Some(project_id)) => {
+            let details = modrinth_project(project_id).await?;
+            let project_type = details.project_type.clone();
+            project = Some(details);
+            project_type
+        }
+        (None, None) => "mod".to_string(),
+    };
+
+    match project_type.as_str() {
+        "resourcepack" => {
+            return crate::commands::packs::download_resourcepack(instance_name, download_url, filename)
+                .await
+                .map(|_| Vec::new());
+        }
+        "shader" => {
+            return crate::commands::packs::download_shaderpack(instance_name, download_url, filename)
+                .await
+                .map(|_| Vec::new());
+        }
+        // Data packs that also ship as a mod jar are installed as mods
+        "datapack" if !filename.ends_with(".jar") => {
+            return install_datapack(&instance_name, world, &download_url, &filename, &app_handle)
+                .await
+                .map(|_| Vec::new());
+        }
+        "modpack" => return Err("Modpacks are installed as a new instance".to_string()),
+        _ => {}
+    }
+
+    if !allow_server_only.unwrap_or(false) {
+        if project.is_none() {
+            if let Some(project_id) = &project_id {
+                project = modrinth_project(project_id).await.ok();
+            }
+        }
+        if let Some(project) = project.filter(|p| p.environment() == ModEnvironment::ServerOnly) {
+            return Err(format!("{} only runs on servers and does nothing in a client instance", project.title));
+        }
+    }
+
     let safe_name = sanitize_instance_name(&instance_name)?;
     let safe_filename = sanitize_mod_filename(&filename)?;
-    let _ = validate_download_url(&download_url)?;
+    let _lock = crate::services::instance_lock::lock(&app_handle, &safe_name, "mod install")?;
+    validate_download_url(&download_url)?;
     
     let instance_dir = get_instance_dir(&safe_name);
     let mods_dir = instance_dir.join("mods");
@@ -572,7 +711,306 @@ pub async fn download_mod(
     client
         .download_mod_file(&download_url, &destination)
         .await
+        .map_err(|e| e.to_string())?;
+
+    invalidate_mod_cache(&safe_name);
+
+    Ok(record_mod_install(&safe_name, &safe_filename, ModSource::Modrinth, project_id, version_id, Some(download_url)))
+}
+
+async fn modrinth_project(project_id: &str) -> Result<ModrinthProjectDetails, String> {
+    let client = ModrinthClient::new().map_err(|e| e.to_string())?;
+    client
+        .get_project(project_id)
+        .await
+        .map_err(|e| format!("Failed to look up the project: {}", e))
+}
+
+/// Data packs belong to a single world, so the caller has to pick one.
+async fn install_datapack(
+    instance_name: &str,
+    world: Option<String>,
+    download_url: &str,
+    filename: &str,
+    app_handle: &tauri::AppHandle,
+) -> Result<(), String> {
+    let safe_name = sanitize_instance_name(instance_name)?;
+    let _lock = crate::services::instance_lock::lock(app_handle, &safe_name, "data pack install")?;
+    let world = world.ok_or("Choose a world to install this data pack into")?;
+    let safe_world = sanitize_filename(&world).map_err(|_| "Invalid world folder name".to_string())?;
+    let safe_filename = sanitize_filename(filename)?;
+    validate_download_url(download_url)?;
+
+    let world_dir = get_instance_dir(&safe_name).join("saves").join(&safe_world);
+    if !world_dir.join("level.dat").exists() {
+        return Err(format!("World '{}' does not exist", safe_world));
+    }
+
+    let datapacks_dir = world_dir.join("datapacks");
+    std::fs::create_dir_all(&datapacks_dir)
+        .map_err(|e| e.to_string())?;
+
+    let client = ModrinthClient::new().map_err(|e| e.to_string())?;
+    client
+        .download_mod_file(download_url, &datapacks_dir.join(&safe_filename))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Adds a just-installed jar to the mod index and removes the jars it replaces, i.e. other
+/// versions of the same project. Pinned versions are kept. Returns the removed file names.
+/// The install itself already succeeded, so failures here are only logged.
+pub(crate) fn record_mod_install(
+    instance_name: &str,
+    filename: &str,
+    source: ModSource,
+    project_id: Option<String>,
+    version_id: Option<String>,
+    download_url: Option<String>,
+) -> Vec<String> {
+    let mods_dir = get_instance_dir(instance_name).join("mods");
+
+    let provenance = match ModProvenance::from_file(&mods_dir.join(filename), source, project_id, version_id, download_url) {
+        Ok(provenance) => provenance,
+        Err(e) => {
+            eprintln!("Failed to hash {}: {}", filename, e);
+            return Vec::new();
+        }
+    };
+
+    let mut mod_index = ModIndex::load(instance_name);
+    let mut replaced = Vec::new();
+
+    for conflict in mod_index.record(filename, provenance) {
+        if mod_index.is_pinned(&conflict) {
+            continue;
+        }
+        for candidate in [conflict.clone(), format!("{}.disabled", conflict)] {
+            let path = mods_dir.join(&candidate);
+            if path.is_file() && std::fs::remove_file(&path).is_ok() {
+                replaced.push(candidate);
+            }
+        }
+        mod_index.remove(&conflict);
+    }
+
+    if let Err(e) = mod_index.save(instance_name) {
+        eprintln!("Failed to save the mod index of {}: {}", instance_name, e);
+    }
+
+    if !replaced.is_empty() {
+        invalidate_mod_cache(instance_name);
+    }
+
+    replaced
+}
+
+const MOD_METADATA_FILES: &[&str] = &[
+    "fabric.mod.json",
+    "quilt.mod.json",
+    "META-INF/mods.toml",
+    "META-INF/neoforge.mods.toml",
+    "mcmod.info",
+];
+
+fn validate_mod_jar(path: &std::path::Path) -> Result<(), String> {
+    use std::io::Read;
+
+    let mut file = std::fs::File::open(path)
+        .map_err(|e| format!("Failed to open mod file: {}", e))?;
+
+    let mut magic = [0u8; 4];
+    file.read_exact(&mut magic)
+        .map_err(|_| "File is too small to be a mod jar".to_string())?;
+
+    if magic != [0x50, 0x4B, 0x03, 0x04] {
+        return Err("File is not a valid jar archive".to_string());
+    }
+
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|e| format!("Failed to read jar archive: {}", e))?;
+
+    for metadata_name in MOD_METADATA_FILES {
+        let mut entry = match archive.by_name(metadata_name) {
+            Ok(entry) => entry,
+            Err(_) => continue,
+        };
+
+        let mut content = String::new();
+        if entry.read_to_string(&mut content).is_err() {
+            continue;
+        }
+
+        let parsable = if metadata_name.ends_with(".json") || metadata_name.ends_with(".info") {
+            serde_json::from_str::<serde_json::Value>(&content).is_ok()
+        } else {
+            content.contains("[[mods]]")
+        };
+
+        if parsable {
+            return Ok(());
+        }
+    }
+
+    Err("Jar does not contain valid Fabric, Quilt, Forge or NeoForge mod metadata".to_string())
+}
+
+fn unique_mod_destination(mods_dir: &std::path::Path, filename: &str) -> std::path::PathBuf {
+    let stem = filename.trim_end_matches(".jar");
+    let mut candidate = filename.to_string();
+    let mut counter = 1;
+
+    while mods_dir.join(&candidate).exists()
+        || mods_dir.join(format!("{}.disabled", candidate)).exists()
+    {
+        candidate = format!("{} ({}).jar", stem, counter);
+        counter += 1;
+    }
+
+    mods_dir.join(candidate)
+}
+
+#[tauri::command]
+pub async fn install_local_mod(instance_name: String, source_path: String, app_handle: tauri::AppHandle) -> Result<String, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let _lock = crate::services::instance_lock::lock(&app_handle, &safe_name, "mod install")?;
+
+    let source = std::path::Path::new(&source_path);
+    if !source.is_file() {
+        return Err("Mod file does not exist".to_string());
+    }
+
+    let filename = source
+        .file_name()
+        .and_then(|n| n.to_str())
+        .ok_or("Invalid mod filename")?;
+    let safe_filename = sanitize_mod_filename(filename)?;
+
+    validate_mod_jar(source)?;
+
+    let instance_dir = get_instance_dir(&safe_name);
+    if !instance_dir.exists() {
+        return Err(format!("Instance '{}' does not exist", safe_name));
+    }
+
+    let mods_dir = instance_dir.join("mods");
+    if !mods_dir.exists() {
+        std::fs::create_dir_all(&mods_dir)
+            .map_err(|e| e.to_string())?;
+    }
+
+    let destination = unique_mod_destination(&mods_dir, &safe_filename);
+    if !destination.starts_with(&mods_dir) {
+        return Err("Invalid destination path".to_string());
+    }
+
+    std::fs::copy(source, &destination)
+        .map_err(|e| format!("Failed to copy mod: {}", e))?;
+
+    invalidate_mod_cache(&safe_name);
+
+    let installed = destination
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(&safe_filename)
+        .to_string();
+    record_mod_install(&safe_name, &installed, ModSource::Local, None, None, None);
+
+    Ok(installed)
+}
+
+async fn fetch_github_release(release_url: &str, app_handle: &tauri::AppHandle) -> Result<GithubRelease, String> {
+    let release_ref = crate::utils::github::parse_release_url(release_url)?;
+    let client = crate::utils::http::client_from(app_handle);
+    crate::utils::github::get_release(&client, &release_ref)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// The release a GitHub URL points to, with only the assets that can be installed as mods.
+#[tauri::command]
+pub async fn get_github_release_jars(release_url: String, app_handle: tauri::AppHandle) -> Result<GithubRelease, String> {
+    let mut release = fetch_github_release(&release_url, &app_handle).await?;
+    release.assets.retain(|asset| asset.is_mod_jar());
+    Ok(release)
+}
+
+/// Installs a jar from a GitHub release. `asset_name` picks the jar; it can be left out when
+/// the release has only one. Returns the installed file name.
+#[tauri::command]
+pub async fn install_mod_from_github(
+    instance_name: String,
+    release_url: String,
+    asset_name: Option<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<String, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let _lock = crate::services::instance_lock::lock(&app_handle, &safe_name, "mod install")?;
+
+    let instance_dir = get_instance_dir(&safe_name);
+    if !instance_dir.exists() {
+        return Err(format!("Instance '{}' does not exist", safe_name));
+    }
+
+    let release_ref = crate::utils::github::parse_release_url(&release_url)?;
+    let release = fetch_github_release(&release_url, &app_handle).await?;
+    let mut jars = release.assets.into_iter().filter(|asset| asset.is_mod_jar());
+
+    let asset = match asset_name {
+        Some(name) => jars
+            .find(|asset| asset.name == name)
+            .ok_or_else(|| format!("Release {} has no jar named '{}'", release.tag_name, name))?,
+        None => match (jars.next(), jars.next()) {
+            (Some(asset), None) => asset,
+            (None, _) => return Err(format!("Release {} has no mod jars", release.tag_name)),
+            (Some(_), Some(_)) => return Err(format!("Release {} has several jars, choose one to install", release.tag_name)),
+        },
+    };
+
+    let safe_filename = sanitize_mod_filename(&asset.name)?;
+    validate_download_url(&asset.browser_download_url)?;
+
+    let mods_dir = instance_dir.join("mods");
+    std::fs::create_dir_all(&mods_dir)
+        .map_err(|e| e.to_string())?;
+
+    let destination = unique_mod_destination(&mods_dir, &safe_filename);
+    if !destination.starts_with(&mods_dir) {
+        return Err("Invalid destination path".to_string());
+    }
+
+    // Downloaded next to the mods so a jar that turns out not to be a mod is never loaded
+    let partial = mods_dir.join(format!("{}.part", safe_filename));
+    let client = ModrinthClient::with_client(crate::utils::http::client_from(&app_handle));
+    let downloaded = client
+        .download_mod_file(&asset.browser_download_url, &partial)
+        .await
         .map_err(|e| e.to_string())
+        .and_then(|_| validate_mod_jar(&partial))
+        .and_then(|_| std::fs::rename(&partial, &destination).map_err(|e| e.to_string()));
+
+    if let Err(e) = downloaded {
+        let _ = std::fs::remove_file(&partial);
+        return Err(e);
+    }
+
+    invalidate_mod_cache(&safe_name);
+
+    let installed = destination
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or(&safe_filename)
+        .to_string();
+    record_mod_install(
+        &safe_name,
+        &installed,
+        ModSource::Github,
+        Some(format!("{}/{}", release_ref.owner, release_ref.repo)),
+        Some(release.tag_name),
+        Some(asset.browser_download_url),
+    );
+
+    Ok(installed)
 }
 
 // CurseForge
@@ -605,7 +1043,7 @@ pub async fn search_curseforge_mods(
 
     let api_key = curseforge_api_key(&app_handle)?;
     let client = CurseforgeClient::new(api_key).map_err(|e| e.to_string())?;
-    client
+    let mut result = client
         .search_mods(
             &query,
             class_id,
@@ -618,7 +1056,16 @@ pub async fn search_curseforge_mods(
             page_size.unwrap_or(20).min(100),
         )
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+
+    let restricted = RestrictionManager::get();
+    result.data.retain(|hit| {
+        let mut texts = vec![hit.name.as_str(), hit.summary.as_str()];
+        texts.extend(hit.categories.iter().map(|c| c.name.as_str()));
+        !RestrictionManager::is_flagged(restricted.as_ref(), &texts)
+    });
+
+    Ok(result)
 }
 
 #[tauri::command]
@@ -644,6 +1091,7 @@ pub async fn download_curseforge_file_temp(
     filename: String,
 ) -> Result<String, String> {
     let safe_filename = sanitize_filename(&filename)?;
+    validate_download_url(&download_url)?;
 
     let temp_dir = std::env::temp_dir().join("octane_curseforge");
     std::fs::create_dir_all(&temp_dir).map_err(|e| e.to_string())?;
@@ -666,6 +1114,8 @@ pub async fn download_curseforge_file(
     download_url: String,
     filename: String,
     target_folder: String,
+    mod_id: Option<u32>,
+    file_id: Option<u32>,
 ) -> Result<(), String> {
     let safe_name = sanitize_instance_name(&instance_name)?;
     let safe_filename = match target_folder.as_str() {
@@ -674,7 +1124,7 @@ pub async fn download_curseforge_file(
         "shaderpacks" => sanitize_shaderpack_filename(&filename)?,
         _ => sanitize_filename(&filename)?,
     };
-    let _ = validate_download_url(&download_url)?;
+    validate_download_url(&download_url)?;
 
     let instance_dir = get_instance_dir(&safe_name);
     let target_dir = instance_dir.join(&target_folder);
@@ -695,5 +1145,19 @@ pub async fn download_curseforge_file(
     client
         .download_file(&download_url, &destination)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+
+    if target_folder == "mods" {
+        invalidate_mod_cache(&safe_name);
+        record_mod_install(
+            &safe_name,
+            &safe_filename,
+            ModSource::Curseforge,
+            mod_id.map(|id| id.to_string()),
+            file_id.map(|id| id.to_string()),
+            Some(download_url),
+        );
+    }
+
+    Ok(())
 }
\ No newline at end of file
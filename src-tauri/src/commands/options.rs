@@ -0,0 +1,131 @@
+use crate::commands::validation::sanitize_instance_name;
+use crate::services::options::{
+    master_options_path, sync_options_into, validate_option_key, validate_option_value, OptionsFile,
+};
+use crate::utils::get_instance_dir;
+use serde::Serialize;
+use std::collections::HashMap;
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct OptionsSyncResult {
+    /// Instances whose options.txt received at least one changed value.
+    pub updated: Vec<String>,
+    pub unchanged: Vec<String>,
+    /// Running instances are skipped since the game rewrites options.txt on exit.
+    pub skipped_running: Vec<String>,
+}
+
+/// Returns every `key:value` pair from the instance's options.txt (empty before the first launch).
+#[tauri::command]
+pub async fn get_instance_options(instance_name: String) -> Result<HashMap<String, String>, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let instance_dir = get_instance_dir(&safe_name);
+
+    if !instance_dir.join("instance.json").exists() {
+        return Err(format!("Instance '{}' does not exist", safe_name));
+    }
+
+    let options = OptionsFile::load(&instance_dir.join("options.txt"))
+        .map_err(|e| format!("Failed to read options.txt: {}", e))?;
+
+    Ok(options
+        .entries()
+        .map(|(key, value)| (key.to_string(), value.to_string()))
+        .collect())
+}
+
+#[tauri::command]
+pub async fn set_instance_option(instance_name: String, key: String, value: String) -> Result<(), String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    validate_option_key(&key)?;
+    validate_option_value(&value)?;
+
+    let instance_dir = get_instance_dir(&safe_name);
+    if !instance_dir.join("instance.json").exists() {
+        return Err(format!("Instance '{}' does not exist", safe_name));
+    }
+
+    // The game rewrites options.txt when it exits, which would discard the change
+    if crate::commands::instances::is_running(&safe_name)? {
+        return Err("Close the game before changing its options".to_string());
+    }
+
+    let options_path = instance_dir.join("options.txt");
+    let mut options = OptionsFile::load(&options_path)
+        .map_err(|e| format!("Failed to read options.txt: {}", e))?;
+
+    options.set(&key, &value);
+    options
+        .save(&options_path)
+        .map_err(|e| format!("Failed to write options.txt: {}", e))
+}
+
+/// Makes the instance's options.txt the master copy that synced options are taken from.
+#[tauri::command]
+pub async fn set_master_options(instance_name: String) -> Result<(), String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let options_path = get_instance_dir(&safe_name).join("options.txt");
+
+    if !options_path.exists() {
+        return Err(format!("'{}' has no options.txt yet, launch it once first", safe_name));
+    }
+
+    std::fs::copy(&options_path, master_options_path())
+        .map(|_| ())
+        .map_err(|e| format!("Failed to save master options: {}", e))
+}
+
+/// Copies the options matching `keys` (a trailing `*` matches a prefix, e.g. `key_*` for all
+/// keybinds) from `source_instance`, or the master options when omitted, to the given instances.
+#[tauri::command]
+pub async fn sync_options_to_instances(
+    keys: Vec<String>,
+    instance_names: Vec<String>,
+    source_instance: Option<String>,
+) -> Result<OptionsSyncResult, String> {
+    if keys.is_empty() {
+        return Err("Select at least one option to sync".to_string());
+    }
+    for key in &keys {
+        validate_option_key(key.strip_suffix('*').unwrap_or(key))?;
+    }
+
+    let source_path = match &source_instance {
+        Some(name) => get_instance_dir(&sanitize_instance_name(name)?).join("options.txt"),
+        None => master_options_path(),
+    };
+    if !source_path.exists() {
+        return Err("No source options found to sync from".to_string());
+    }
+    let source = OptionsFile::load(&source_path)
+        .map_err(|e| format!("Failed to read source options: {}", e))?;
+
+    let mut result = OptionsSyncResult::default();
+
+    for name in &instance_names {
+        let safe_name = sanitize_instance_name(name)?;
+        let instance_dir = get_instance_dir(&safe_name);
+
+        if !instance_dir.join("instance.json").exists() {
+            return Err(format!("Instance '{}' does not exist", safe_name));
+        }
+        if source_instance.as_deref() == Some(safe_name.as_str()) {
+            continue;
+        }
+        if crate::commands::instances::is_running(&safe_name)? {
+            result.skipped_running.push(safe_name);
+            continue;
+        }
+
+        let changed = sync_options_into(&source, &keys, &instance_dir.join("options.txt"))
+            .map_err(|e| format!("Failed to sync options to {}: {}", safe_name, e))?;
+
+        if changed > 0 {
+            result.updated.push(safe_name);
+        } else {
+            result.unchanged.push(safe_name);
+        }
+    }
+
+    Ok(result)
+}
@@ -0,0 +1,297 @@
+use crate::commands::instances::{install_loader_version, instance_minecraft_version};
+use crate::commands::mods::{invalidate_mod_cache, record_mod_install};
+use crate::commands::validation::{instance_folder_name, sanitize_display_name, sanitize_instance_name, sanitize_mod_filename, validate_download_url};
+use crate::models::{Instance, PackShare, PackShareInstallSummary, SharedMod};
+use crate::services::installer::MinecraftInstaller;
+use crate::services::instance::InstanceManager;
+use crate::services::mod_index::{mod_key, ModIndex, ModSource};
+use crate::services::operations::{OperationKind, ProgressEvent};
+use crate::utils::modrinth::{ModrinthClient, VersionFile};
+use crate::utils::*;
+use sha1::{Digest, Sha1};
+
+const PACK_SHARE_FORMAT_VERSION: u32 = 1;
+
+/// options.txt is a few KB, anything much larger isn't worth sharing.
+const MAX_SHARED_OPTIONS_BYTES: u64 = 256 * 1024;
+
+/// Describes an instance as a `PackShare`, written to `output_path` when given. Only mods the
+/// launcher installed from Modrinth can be replicated, the rest are listed in `unlisted_mods`.
+#[tauri::command]
+pub async fn generate_pack_share(instance_name: String, output_path: Option<String>) -> Result<PackShare, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let instance_dir = get_instance_dir(&safe_name);
+
+    let content = std::fs::read_to_string(instance_dir.join("instance.json"))
+        .map_err(|_| format!("Instance '{}' does not exist", safe_name))?;
+    let instance: Instance = serde_json::from_str(&content)
+        .map_err(|e| e.to_string())?;
+
+    let mod_index = ModIndex::load(&safe_name);
+    let mut mods = Vec::new();
+    let mut unlisted_mods = Vec::new();
+
+    if let Ok(entries) = std::fs::read_dir(instance_dir.join("mods")) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let filename = entry.file_name().to_string_lossy().to_string();
+            if !path.is_file() || !mod_key(&filename).ends_with(".jar") {
+                continue;
+            }
+
+            let ids = mod_index
+                .provenance(&filename)
+                .filter(|provenance| provenance.matches(&path))
+                .and_then(|provenance| provenance.modrinth_ids());
+
+            match ids {
+                Some((project_id, version_id)) => mods.push(SharedMod {
+                    project_id: project_id.to_string(),
+                    version_id: version_id.to_string(),
+                    filename: mod_key(&filename).to_string(),
+                    enabled: !filename.ends_with(".disabled"),
+                }),
+                None => unlisted_mods.push(mod_key(&filename).to_string()),
+            }
+        }
+    }
+
+    mods.sort_by(|a, b| a.filename.cmp(&b.filename));
+    unlisted_mods.sort();
+
+    let options_path = instance_dir.join("options.txt");
+    let options = std::fs::metadata(&options_path)
+        .ok()
+        .filter(|meta| meta.len() <= MAX_SHARED_OPTIONS_BYTES)
+        .and_then(|_| std::fs::read_to_string(&options_path).ok());
+
+    let share = PackShare {
+        format_version: PACK_SHARE_FORMAT_VERSION,
+        name: instance.display_name.clone().unwrap_or_else(|| instance.name.clone()),
+        minecraft_version: instance_minecraft_version(&instance),
+        loader: instance.loader.clone().filter(|loader| loader != "vanilla"),
+        loader_version: instance.loader_version.clone(),
+        mods,
+        options,
+        unlisted_mods,
+    };
+
+    if let Some(output_path) = output_path {
+        write_json_atomic(std::path::Path::new(&output_path), &share)
+            .map_err(|e| format!("Failed to write share file: {}", e))?;
+    }
+
+    Ok(share)
+}
+
+/// Creates a new instance from the share file at `manifest_path`, named after the shared
+/// instance unless `instance_name` is given. Mods that fail to download are reported in the
+/// summary instead of failing the install.
+#[tauri::command]
+pub async fn install_from_share(
+    manifest_path: String,
+    instance_name: Option<String>,
+    app_handle: tauri::AppHandle,
+) -> Result<PackShareInstallSummary, String> {
+    let content = std::fs::read_to_string(&manifest_path)
+        .map_err(|e| format!("Failed to read share file: {}", e))?;
+    let share: PackShare = serde_json::from_str(&content)
+        .map_err(|_| "Not a valid pack share file".to_string())?;
+
+    validate_share(&share)?;
+
+    let display_name = sanitize_display_name(instance_name.as_deref().unwrap_or(&share.name))?;
+    let safe_name = instance_folder_name(&display_name)?;
+    let _lock = crate::services::instance_lock::lock(&app_handle, &safe_name, "pack share install")?;
+    let operation = crate::services::operations::track(&app_handle, OperationKind::CreateInstance, Some(&safe_name));
+
+    ProgressEvent::new(OperationKind::CreateInstance, Some(&safe_name))
+        .percent(0)
+        .stage(format!("Installing Minecraft {}...", share.minecraft_version))
+        .emit(&app_handle);
+
+    MinecraftInstaller::new(get_meta_dir())
+        .map_err(|e| e.to_string())?
+        .install_version(&share.minecraft_version)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let final_version = match (&share.loader, &share.loader_version) {
+        (Some(loader), Some(loader_version)) => {
+            ProgressEvent::new(OperationKind::CreateInstance, Some(&safe_name))
+                .percent(25)
+                .stage(format!("Installing {} {}...", loader, loader_version))
+                .emit(&app_handle);
+
+            install_loader_version(loader, &share.minecraft_version, loader_version).await?
+        }
+        _ => share.minecraft_version.clone(),
+    };
+
+    ProgressEvent::new(OperationKind::CreateInstance, Some(&safe_name))
+        .percent(45)
+        .stage("Creating instance...")
+        .emit(&app_handle);
+
+    InstanceManager::create(&safe_name, &final_version, share.loader.clone(), share.loader_version.clone())
+        .map_err(|e| e.to_string())?;
+
+    if let Err(e) = InstanceManager::set_display_name(&safe_name, &display_name) {
+        eprintln!("Failed to save the display name of {}: {}", safe_name, e);
+    }
+
+    if let Some(options) = &share.options {
+        if let Err(e) = std::fs::write(get_instance_dir(&safe_name).join("options.txt"), options) {
+            eprintln!("Failed to write options.txt for {}: {}", safe_name, e);
+        }
+    }
+
+    let (installed, failed) = install_shared_mods(&safe_name, &share.mods, &app_handle).await?;
+
+    ProgressEvent::new(OperationKind::CreateInstance, Some(&safe_name))
+        .percent(100)
+        .stage("Instance created successfully!")
+        .emit(&app_handle);
+
+    operation.complete();
+    Ok(PackShareInstallSummary {
+        instance_name: safe_name,
+        installed,
+        failed,
+        unlisted_mods: share.unlisted_mods,
+    })
+}
+
+/// Share files come from other people, so everything that ends up in a path or URL is checked.
+fn validate_share(share: &PackShare) -> Result<(), String> {
+    if share.format_version > PACK_SHARE_FORMAT_VERSION {
+        return Err("This share file was made by a newer version of the launcher".to_string());
+    }
+
+    let valid_version = |version: &str| {
+        !version.is_empty() && version.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '-' || c == '+')
+    };
+
+    if !valid_version(&share.minecraft_version) {
+        return Err("Invalid Minecraft version in share file".to_string());
+    }
+
+    match (share.loader.as_deref(), share.loader_version.as_deref()) {
+        (None, _) => {}
+        (Some("fabric" | "quilt" | "forge" | "neoforge"), Some(version)) if valid_version(version) => {}
+        (Some("fabric" | "quilt" | "forge" | "neoforge"), _) => {
+            return Err("Invalid loader version in share file".to_string());
+        }
+        (Some(loader), _) => return Err(format!("Unsupported loader '{}' in share file", loader)),
+    }
+
+    let valid_id = |id: &str| !id.is_empty() && id.chars().all(|c| c.is_ascii_alphanumeric());
+    if let Some(invalid) = share.mods.iter().find(|m| !valid_id(&m.project_id) || !valid_id(&m.version_id)) {
+        return Err(format!("Invalid Modrinth ids for '{}' in share file", invalid.filename));
+    }
+
+    Ok(())
+}
+
+/// Downloads the primary file of every shared mod version. Returns how many were installed and
+/// the file names of the ones that couldn't be.
+async fn install_shared_mods(
+    instance_name: &str,
+    mods: &[SharedMod],
+    app_handle: &tauri::AppHandle,
+) -> Result<(usize, Vec<String>), String> {
+    if mods.is_empty() {
+        return Ok((0, Vec::new()));
+    }
+
+    ProgressEvent::new(OperationKind::CreateInstance, Some(instance_name))
+        .percent(50)
+        .stage("Resolving mods...")
+        .emit(app_handle);
+
+    let client = ModrinthClient::new().map_err(|e| e.to_string())?;
+    let version_ids: Vec<String> = mods.iter().map(|m| m.version_id.clone()).collect();
+    let versions = client
+        .get_versions(&version_ids)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let mods_dir = get_instance_dir(instance_name).join("mods");
+    std::fs::create_dir_all(&mods_dir)
+        .map_err(|e| e.to_string())?;
+
+    let mut installed = 0;
+    let mut failed = Vec::new();
+
+    for (idx, shared) in mods.iter().enumerate() {
+        ProgressEvent::new(OperationKind::CreateInstance, Some(instance_name))
+            .percent(50 + (idx * 49 / mods.len()) as u32)
+            .stage(format!("Downloading mods... ({}/{})", idx + 1, mods.len()))
+            .detail(&shared.filename)
+            .emit(app_handle);
+
+        let file = versions
+            .iter()
+            .find(|v| v.id == shared.version_id && v.project_id == shared.project_id)
+            .and_then(|v| v.files.iter().find(|f| f.primary).or_else(|| v.files.first()));
+
+        let Some(file) = file else {
+            eprintln!("Modrinth version {} of {} not found", shared.version_id, shared.filename);
+            failed.push(shared.filename.clone());
+            continue;
+        };
+
+        match download_shared_mod(&client, &mods_dir, file, shared.enabled).await {
+            Ok(filename) => {
+                record_mod_install(
+                    instance_name,
+                    &filename,
+                    ModSource::Modrinth,
+                    Some(shared.project_id.clone()),
+                    Some(shared.version_id.clone()),
+                    Some(file.url.clone()),
+                );
+                installed += 1;
+            }
+            Err(e) => {
+                eprintln!("Failed to install {}: {}", shared.filename, e);
+                failed.push(shared.filename.clone());
+            }
+        }
+    }
+
+    invalidate_mod_cache(instance_name);
+    Ok((installed, failed))
+}
+
+/// Downloads `file` into `mods_dir` and checks it against its published SHA-1. Returns the
+/// installed file name, with `.disabled` appended for mods that were turned off.
+async fn download_shared_mod(
+    client: &ModrinthClient,
+    mods_dir: &std::path::Path,
+    file: &VersionFile,
+    enabled: bool,
+) -> Result<String, String> {
+    validate_download_url(&file.url)?;
+    let safe_filename = sanitize_mod_filename(&file.filename)?;
+    let filename = if enabled {
+        safe_filename
+    } else {
+        format!("{}.disabled", safe_filename)
+    };
+
+    let destination = mods_dir.join(&filename);
+    client
+        .download_mod_file(&file.url, &destination)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let bytes = std::fs::read(&destination)
+        .map_err(|e| e.to_string())?;
+    if !format!("{:x}", Sha1::digest(&bytes)).eq_ignore_ascii_case(&file.hashes.sha1) {
+        let _ = std::fs::remove_file(&destination);
+        return Err("Downloaded file does not match its hash".to_string());
+    }
+
+    Ok(filename)
+}
@@ -1,5 +1,6 @@
 use crate::commands::mods::{CacheEntry, ModFileWithMetadata};
-use crate::commands::validation::{sanitize_instance_name, sanitize_resourcepack_filename, sanitize_shaderpack_filename, validate_download_url};
+use crate::commands::validation::{sanitize_instance_name, sanitize_resourcepack_filename, sanitize_resourcepack_name, sanitize_shaderpack_filename, validate_download_url};
+use crate::models::Instance;
 use crate::utils::{get_instance_dir, open_folder};
 use crate::utils::modrinth::{ModrinthClient, ModrinthProjectDetails};
 use sha1::{Digest, Sha1};
@@ -59,7 +60,7 @@ pub async fn download_resourcepack(
     let safe_name = sanitize_instance_name(&instance_name)?;
     let safe_filename = sanitize_resourcepack_filename(&filename)?;
     
-    let _ = validate_download_url(&download_url)?;
+    validate_download_url(&download_url)?;
     
     let instance_dir = get_instance_dir(&safe_name);
     let resourcepacks_dir = instance_dir.join("resourcepacks");
@@ -113,6 +114,41 @@ pub async fn delete_resourcepack(instance_name: String, filename: String) -> Res
     Ok(())
 }
 
+/// Sets the resource packs enabled in options.txt before every launch of the instance.
+#[tauri::command]
+pub async fn set_enforced_resource_packs(instance_name: String, packs: Vec<String>) -> Result<(), String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let instance_dir = get_instance_dir(&safe_name);
+
+    let mut safe_packs = Vec::with_capacity(packs.len());
+    for pack in &packs {
+        let safe_pack = sanitize_resourcepack_name(pack)?;
+        let pack_path = instance_dir.join("resourcepacks").join(&safe_pack);
+        if !pack_path.exists() {
+            return Err(format!("Resource pack '{}' not found", safe_pack));
+        }
+        // Folder packs have no extension
+        if !pack_path.is_dir() {
+            sanitize_resourcepack_filename(&safe_pack)?;
+        }
+        if !safe_packs.contains(&safe_pack) {
+            safe_packs.push(safe_pack);
+        }
+    }
+
+    let instance_json_path = instance_dir.join("instance.json");
+    let content = std::fs::read_to_string(&instance_json_path)
+        .map_err(|_| format!("Instance '{}' does not exist", safe_name))?;
+
+    let mut instance: Instance = serde_json::from_str(&content)
+        .map_err(|e| e.to_string())?;
+
+    instance.enforced_resource_packs = safe_packs;
+
+    crate::utils::write_json_atomic(&instance_json_path, &instance)
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub fn open_resourcepacks_folder(instance_name: String) -> Result<(), String> {
     let safe_name = sanitize_instance_name(&instance_name)?;
@@ -182,7 +218,7 @@ pub async fn download_shaderpack(
     let safe_name = sanitize_instance_name(&instance_name)?;
     let safe_filename = sanitize_shaderpack_filename(&filename)?;
     
-    let _ = validate_download_url(&download_url)?;
+    validate_download_url(&download_url)?;
     
     let instance_dir = get_instance_dir(&safe_name);
     let shaderpacks_dir = instance_dir.join("shaderpacks");
@@ -367,6 +403,7 @@ pub async fn get_installed_resourcepacks_with_metadata(instance_name: String) ->
             downloads: metadata.as_ref().and_then(|m| m.downloads),
             disabled: false,
             current_version_id: metadata.as_ref().and_then(|m| m.current_version_id.clone()),
+            pinned: false,
         });
     }
 
@@ -404,6 +441,7 @@ pub async fn get_installed_resourcepacks_with_metadata(instance_name: String) ->
                             downloads: Some(project.downloads),
                             disabled: false,
                             current_version_id: Some(version_id.clone()),
+                            pinned: false,
                         };
 
                         for entry in disk_cache.values_mut() {
@@ -514,6 +552,7 @@ pub async fn get_installed_shaderpacks_with_metadata(instance_name: String) -> R
             downloads: metadata.as_ref().and_then(|m| m.downloads),
             disabled: false,
             current_version_id: metadata.as_ref().and_then(|m| m.current_version_id.clone()),
+            pinned: false,
         });
     }
 
@@ -551,6 +590,7 @@ pub async fn get_installed_shaderpacks_with_metadata(instance_name: String) -> R
                             downloads: Some(project.downloads),
                             disabled: false,
                             current_version_id: Some(version_id.clone()),
+                            pinned: false,
                         };
 
                         for entry in disk_cache.values_mut() {
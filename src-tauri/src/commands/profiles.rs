@@ -0,0 +1,57 @@
+use crate::commands::validation::sanitize_profile_name;
+use crate::models::LauncherProfile;
+use crate::services::profiles::ProfileManager;
+use tauri::{Emitter, Manager};
+
+#[tauri::command]
+pub async fn get_profiles() -> Result<Vec<LauncherProfile>, String> {
+    Ok(ProfileManager::get_all())
+}
+
+#[tauri::command]
+pub async fn create_profile(name: String) -> Result<(), String> {
+    // Restricted mode is stored per profile, a new profile would start without it
+    crate::services::restrictions::RestrictionManager::ensure_allowed("Creating profiles")?;
+    let name = sanitize_profile_name(&name)?;
+    ProfileManager::create(&name).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn delete_profile(name: String) -> Result<(), String> {
+    crate::services::restrictions::RestrictionManager::ensure_allowed("Deleting profiles")?;
+    let name = sanitize_profile_name(&name)?;
+    ProfileManager::delete(&name).map_err(|e| e.to_string())
+}
+
+/// Switches to another profile and reloads the state that depends on it. Refused while a game
+/// or a download is running, since those belong to the current profile's instances, and in
+/// restricted mode, which the other profile's settings may not have.
+#[tauri::command]
+pub async fn switch_profile(name: String, app_handle: tauri::AppHandle) -> Result<(), String> {
+    crate::services::restrictions::RestrictionManager::ensure_allowed("Switching profiles")?;
+    let name = sanitize_profile_name(&name)?;
+
+    {
+        let processes = crate::commands::instances::RUNNING_PROCESSES.lock().map_err(|e| e.to_string())?;
+        if !processes.is_empty() {
+            return Err("Close all running instances before switching profiles".to_string());
+        }
+    }
+
+    if let Some(registry) = app_handle.try_state::<crate::services::operations::OperationRegistry>() {
+        if !registry.active().is_empty() {
+            return Err("Wait for running downloads and installs to finish before switching profiles".to_string());
+        }
+    }
+
+    ProfileManager::switch(&name).map_err(|e| e.to_string())?;
+
+    if let Some(http_client) = app_handle.try_state::<crate::utils::http::HttpClient>() {
+        http_client.reload();
+    }
+    crate::services::tray::refresh_tray_menu(&app_handle);
+
+    let _ = app_handle.emit("profile-changed", serde_json::json!({ "profile": name }));
+
+    Ok(())
+}
@@ -0,0 +1,22 @@
+use crate::services::restrictions::RestrictionManager;
+
+#[tauri::command]
+pub async fn is_restricted_mode() -> Result<bool, String> {
+    Ok(RestrictionManager::is_restricted())
+}
+
+/// Turns on restricted mode. Without `blocked_terms` the default list is used.
+#[tauri::command]
+pub async fn enable_restricted_mode(pin: String, blocked_terms: Option<Vec<String>>) -> Result<(), String> {
+    RestrictionManager::enable(&pin, blocked_terms).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn disable_restricted_mode(pin: String) -> Result<(), String> {
+    RestrictionManager::disable(&pin).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_restricted_blocked_terms(pin: String, blocked_terms: Vec<String>) -> Result<(), String> {
+    RestrictionManager::set_blocked_terms(&pin, blocked_terms).map_err(|e| e.to_string())
+}
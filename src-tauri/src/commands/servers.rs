@@ -1,43 +1,31 @@
-use crate::commands::validation::{sanitize_server_name, validate_server_address};
+use crate::commands::validation::{sanitize_instance_name, sanitize_server_name, validate_server_address};
 use crate::services::accounts::AccountManager;
 use crate::services::instance::InstanceManager;
-use crate::models::AppConfig;
-use crate::utils::{get_launcher_dir, get_instance_dir};
+use crate::services::server_ping::query_status;
+use crate::services::servers::ServerManager;
+use crate::models::{AppConfig, Instance, ServerCompatibilityReport, ServerInfo};
+use crate::utils::get_instance_dir;
+use crate::utils::mod_metadata::read_jar_mod_info;
 use serde::{Deserialize, Serialize};
-use tauri::{Emitter, Manager};
-use std::io::Write;
+use tauri::{Emitter, Manager, State};
+use std::io::{Read, Write};
 use std::net::{TcpStream, ToSocketAddrs};
 use std::time::{Duration, Instant};
 
-#[derive(Serialize, Deserialize, Clone)]
-pub struct ServerInfo {
-    pub name: String,
-    pub address: String,
-    pub port: u16,
-    pub status: String,
-    pub players_online: Option<u32>,
-    pub players_max: Option<u32>,
-    pub version: Option<String>,
-    pub motd: Option<String>,
-    pub favicon: Option<String>,
-    pub last_checked: Option<i64>,
+/// Pings of the same server within this window return the previous result.
+const PING_TTL: Duration = Duration::from_secs(60);
+/// Servers queried at once by `refresh_all_servers`.
+const MAX_CONCURRENT_REFRESHES: usize = 8;
+const REFRESH_TIMEOUT: Duration = Duration::from_secs(5);
+
+lazy_static::lazy_static! {
+    static ref PING_CACHE: std::sync::Mutex<std::collections::HashMap<String, (Instant, u32)>> =
+        std::sync::Mutex::new(std::collections::HashMap::new());
 }
 
 #[tauri::command]
-pub async fn get_servers() -> Result<Vec<ServerInfo>, String> {
-    let servers_file = get_launcher_dir().join("servers.json");
-    
-    if !servers_file.exists() {
-        return Ok(Vec::new());
-    }
-    
-    let content = std::fs::read_to_string(&servers_file)
-        .map_err(|e| e.to_string())?;
-    
-    let servers: Vec<ServerInfo> = serde_json::from_str(&content)
-        .map_err(|e| e.to_string())?;
-    
-    Ok(servers)
+pub async fn get_servers(server_manager: State<'_, ServerManager>) -> Result<Vec<ServerInfo>, String> {
+    server_manager.list().await
 }
 
 #[tauri::command]
@@ -45,7 +33,9 @@ pub async fn add_server(
     name: String,
     address: String,
     port: u16,
+    server_manager: State<'_, ServerManager>,
 ) -> Result<(), String> {
+    crate::services::restrictions::RestrictionManager::ensure_allowed("Adding servers")?;
     let safe_name = sanitize_server_name(&name)?;
     validate_server_address(&address)?;
     
@@ -53,126 +43,618 @@ pub async fn add_server(
         return Err("Port cannot be 0".to_string());
     }
     
-    let mut servers = get_servers().await?;
-    
-    if servers.iter().any(|s| s.name.to_lowercase() == safe_name.to_lowercase()) {
-        return Err(format!("Server '{}' already exists", safe_name));
-    }
-    
-    let new_server = ServerInfo {
-        name: safe_name.clone(),
-        address,
-        port,
-        status: "unknown".to_string(),
-        players_online: None,
-        players_max: None,
-        version: None,
-        motd: None,
-        favicon: None,
-        last_checked: None,
-    };
-    
-    servers.push(new_server);
-    
-    let servers_file = get_launcher_dir().join("servers.json");
-    let json = serde_json::to_string_pretty(&servers)
-        .map_err(|e| e.to_string())?;
-    
-    std::fs::write(&servers_file, json)
-        .map_err(|e| e.to_string())
+    server_manager.update(|servers| {
+        if servers.iter().any(|s| s.name.to_lowercase() == safe_name.to_lowercase()) {
+            return Err(format!("Server '{}' already exists", safe_name));
+        }
+
+        servers.push(ServerInfo {
+            name: safe_name.clone(),
+            address,
+            port,
+            status: "unknown".to_string(),
+            players_online: None,
+            players_max: None,
+            version: None,
+            motd: None,
+            favicon: None,
+            last_checked: None,
+            group: None,
+            preferred_instance: None,
+            modpack_project_id: None,
+        });
+        Ok(())
+    })
+    .await
+    .map(|_| ())
 }
 
 #[tauri::command]
-pub async fn delete_server(server_name: String) -> Result<(), String> {
+pub async fn delete_server(server_name: String, server_manager: State<'_, ServerManager>) -> Result<(), String> {
     let safe_name = sanitize_server_name(&server_name)?;
     
-    let mut servers = get_servers().await?;
-    
-    let initial_len = servers.len();
-    servers.retain(|s| s.name != safe_name);
-    
-    if servers.len() == initial_len {
-        return Err(format!("Server '{}' not found", safe_name));
+    let mut removed = None;
+    let servers = server_manager.update(|servers| {
+        let index = servers.iter()
+            .position(|s| s.name == safe_name)
+            .ok_or(format!("Server '{}' not found", safe_name))?;
+        removed = Some(servers.remove(index));
+        Ok(())
+    })
+    .await?;
+
+    if let Some(removed) = removed {
+        if !servers.iter().any(|s| s.address.eq_ignore_ascii_case(&removed.address) && s.port == removed.port) {
+            let _ = std::fs::remove_file(ServerManager::favicon_path(&removed.address, removed.port));
+        }
     }
-    
-    let servers_file = get_launcher_dir().join("servers.json");
-    let json = serde_json::to_string_pretty(&servers)
-        .map_err(|e| e.to_string())?;
-    
-    std::fs::write(&servers_file, json)
-        .map_err(|e| e.to_string())
+
+    Ok(())
 }
 
 #[tauri::command]
 pub async fn update_server_status(
     server_name: String,
     status: ServerInfo,
+    server_manager: State<'_, ServerManager>,
 ) -> Result<(), String> {
     let safe_name = sanitize_server_name(&server_name)?;
     
-    let mut servers = get_servers().await?;
-    
-    let server = servers.iter_mut()
-        .find(|s| s.name == safe_name)
-        .ok_or(format!("Server '{}' not found", safe_name))?;
-    
-    server.status = status.status;
-    server.players_online = status.players_online;
-    server.players_max = status.players_max;
-    server.version = status.version;
-    server.motd = status.motd;
-    server.favicon = status.favicon;
-    server.last_checked = Some(chrono::Utc::now().timestamp());
-    
-    let servers_file = get_launcher_dir().join("servers.json");
-    let json = serde_json::to_string_pretty(&servers)
-        .map_err(|e| e.to_string())?;
-    
-    std::fs::write(&servers_file, json)
-        .map_err(|e| e.to_string())
+    server_manager.update(|servers| {
+        let server = servers.iter_mut()
+            .find(|s| s.name == safe_name)
+            .ok_or(format!("Server '{}' not found", safe_name))?;
+
+        server.status = status.status;
+        server.players_online = status.players_online;
+        server.players_max = status.players_max;
+        server.version = status.version;
+        server.motd = status.motd;
+        server.favicon = status.favicon;
+        server.last_checked = Some(chrono::Utc::now().timestamp());
+        Ok(())
+    })
+    .await
+    .map(|_| ())
 }
 
 #[tauri::command]
-pub async fn reorder_servers(server_names: Vec<String>) -> Result<(), String> {
-    let mut servers = get_servers().await?;
+pub async fn set_server_group(
+    server_name: String,
+    group: Option<String>,
+    server_manager: State<'_, ServerManager>,
+) -> Result<(), String> {
+    let safe_name = sanitize_server_name(&server_name)?;
 
-    let mut reordered: Vec<ServerInfo> = Vec::with_capacity(server_names.len());
-    for name in &server_names {
-        if let Some(idx) = servers.iter().position(|s| s.name == *name) {
-            reordered.push(servers.remove(idx));
+    let group = match group.as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some(group) => {
+            if group.len() > 64 || group.chars().any(|c| c.is_control()) {
+                return Err("Invalid group name".to_string());
+            }
+            Some(group.to_string())
         }
+    };
+
+    server_manager.update(|servers| {
+        let server = servers.iter_mut()
+            .find(|s| s.name == safe_name)
+            .ok_or(format!("Server '{}' not found", safe_name))?;
+
+        server.group = group;
+        Ok(())
+    })
+    .await
+    .map(|_| ())
+}
+
+#[tauri::command]
+pub async fn set_server_preferred_instance(
+    server_name: String,
+    instance_name: Option<String>,
+    server_manager: State<'_, ServerManager>,
+) -> Result<(), String> {
+    let safe_name = sanitize_server_name(&server_name)?;
+
+    let instance_name = match instance_name {
+        Some(name) => {
+            let safe_instance = sanitize_instance_name(&name)?;
+            if !get_instance_dir(&safe_instance).exists() {
+                return Err(format!("Instance '{}' not found", safe_instance));
+            }
+            Some(safe_instance)
+        }
+        None => None,
+    };
+
+    server_manager.update(|servers| {
+        let server = servers.iter_mut()
+            .find(|s| s.name == safe_name)
+            .ok_or(format!("Server '{}' not found", safe_name))?;
+
+        server.preferred_instance = instance_name;
+        Ok(())
+    })
+    .await
+    .map(|_| ())
+}
+
+/// Marks the Modrinth modpack a server runs, so `check_server_compatibility` can tell whether
+/// an instance was installed from it.
+#[tauri::command]
+pub async fn set_server_modpack(
+    server_name: String,
+    project_id: Option<String>,
+    server_manager: State<'_, ServerManager>,
+) -> Result<(), String> {
+    let safe_name = sanitize_server_name(&server_name)?;
+
+    let project_id = match project_id.as_deref().map(str::trim) {
+        None | Some("") => None,
+        Some(id) => {
+            if id.len() > 64 || !id.chars().all(|c| c.is_ascii_alphanumeric()) {
+                return Err("Invalid Modrinth project id".to_string());
+            }
+            Some(id.to_string())
+        }
+    };
+
+    server_manager.update(|servers| {
+        let server = servers.iter_mut()
+            .find(|s| s.name == safe_name)
+            .ok_or(format!("Server '{}' not found", safe_name))?;
+
+        server.modpack_project_id = project_id;
+        Ok(())
+    })
+    .await
+    .map(|_| ())
+}
+
+#[tauri::command]
+pub async fn reorder_servers(server_names: Vec<String>, server_manager: State<'_, ServerManager>) -> Result<(), String> {
+    server_manager.update(|servers| {
+        let mut reordered: Vec<ServerInfo> = Vec::with_capacity(servers.len());
+        for name in &server_names {
+            if let Some(idx) = servers.iter().position(|s| s.name == *name) {
+                reordered.push(servers.remove(idx));
+            }
+        }
+
+        reordered.append(servers);
+        *servers = reordered;
+        Ok(())
+    })
+    .await
+    .map(|_| ())
+}
+
+/// The parts of a vanilla servers.dat entry the launcher keeps.
+#[derive(Deserialize)]
+struct ServersDat {
+    #[serde(default)]
+    servers: Vec<ServersDatEntry>,
+}
+
+#[derive(Deserialize)]
+struct ServersDatEntry {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    ip: String,
+    icon: Option<String>,
+}
+
+/// Splits a servers.dat `ip` into host and port. Bracketed IPv6 addresses aren't supported
+/// by the server list and are skipped.
+fn parse_servers_dat_ip(ip: &str) -> Option<(String, u16)> {
+    let ip = ip.trim();
+
+    let (host, port) = match ip.rsplit_once(':') {
+        Some((host, port)) => (host, port.parse::<u16>().ok()?),
+        None => (ip, 25565),
+    };
+
+    if port == 0 || validate_server_address(host).is_err() || host.contains(':') {
+        return None;
     }
 
-    reordered.extend(servers);
+    Some((host.to_string(), port))
+}
 
-    let servers_file = get_launcher_dir().join("servers.json");
-    let json = serde_json::to_string_pretty(&reordered)
-        .map_err(|e| e.to_string())?;
+fn truncate_on_char_boundary(text: &mut String, max_len: usize) {
+    if text.len() > max_len {
+        let mut end = max_len;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        text.truncate(end);
+    }
+}
+
+/// Adds an imported server unless one with the same address is already listed. The name is
+/// cleaned up and numbered if it clashes with another server. Returns whether it was added.
+pub(crate) fn merge_server(servers: &mut Vec<ServerInfo>, mut server: ServerInfo) -> Result<bool, String> {
+    if servers.iter().any(|s| s.address.eq_ignore_ascii_case(&server.address) && s.port == server.port) {
+        return Ok(false);
+    }
+
+    let mut base_name: String = server.name.trim()
+        .chars()
+        .filter(|c| !c.is_control() && *c != '/' && *c != '\\')
+        .collect();
+    while base_name.contains("..") {
+        base_name = base_name.replace("..", ".");
+    }
+    if base_name.is_empty() {
+        base_name = server.address.clone();
+    }
+    // Names are limited in bytes; leave room for the suffix below
+    truncate_on_char_boundary(&mut base_name, 90);
+
+    // Names are unique in the list, so clashing entries get a numbered suffix
+    let mut name = base_name.clone();
+    let mut suffix = 2;
+    while servers.iter().any(|s| s.name.to_lowercase() == name.to_lowercase()) {
+        name = format!("{} ({})", base_name, suffix);
+        suffix += 1;
+    }
+
+    server.name = sanitize_server_name(&name)?;
+    servers.push(server);
+    Ok(true)
+}
+
+/// Imports the servers of a vanilla servers.dat into the server list. Servers already in the
+/// list (by address) are skipped. Returns the number of servers added.
+#[tauri::command]
+pub async fn import_servers_from_file(
+    path: String,
+    server_manager: State<'_, ServerManager>,
+) -> Result<usize, String> {
+    crate::services::restrictions::RestrictionManager::ensure_allowed("Adding servers")?;
+
+    let file_path = std::path::Path::new(&path);
+    if !file_path.is_file() {
+        return Err("servers.dat file does not exist".to_string());
+    }
+
+    let raw = std::fs::read(file_path)
+        .map_err(|e| format!("Failed to read servers.dat: {}", e))?;
+
+    // The game writes it uncompressed, but accept gzipped copies too
+    let bytes = if raw.starts_with(&[0x1f, 0x8b]) {
+        let mut bytes = Vec::new();
+        flate2::read::GzDecoder::new(raw.as_slice())
+            .read_to_end(&mut bytes)
+            .map_err(|e| format!("Failed to decompress servers.dat: {}", e))?;
+        bytes
+    } else {
+        raw
+    };
+
+    let servers_dat: ServersDat = fastnbt::from_bytes(&bytes)
+        .map_err(|e| format!("Failed to parse servers.dat: {}", e))?;
+
+    let mut imported = 0;
+    server_manager.update(|servers| {
+        for entry in servers_dat.servers {
+            let Some((address, port)) = parse_servers_dat_ip(&entry.ip) else {
+                continue;
+            };
+
+            let server = ServerInfo {
+                name: entry.name,
+                address,
+                port,
+                status: "unknown".to_string(),
+                players_online: None,
+                players_max: None,
+                version: None,
+                motd: None,
+                favicon: entry.icon.as_deref().and_then(ServerManager::favicon_from_base64),
+                last_checked: None,
+                group: None,
+                preferred_instance: None,
+                modpack_project_id: None,
+            };
+
+            if merge_server(servers, server)? {
+                imported += 1;
+            }
+        }
+        Ok(())
+    })
+    .await?;
+
+    Ok(imported)
+}
+
+/// Emitted as `server-status-updated` for each server `refresh_all_servers` finishes.
+#[derive(Serialize, Clone)]
+struct ServerStatusUpdate {
+    #[serde(flatten)]
+    server: ServerInfo,
+    ping: Option<u32>,
+}
 
-    std::fs::write(&servers_file, json)
-        .map_err(|e| e.to_string())
+/// Queries every saved server, a few at a time, emitting each result as it arrives and
+/// saving them all once done.
+#[tauri::command]
+pub async fn refresh_all_servers(
+    server_manager: State<'_, ServerManager>,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    let servers = server_manager.list().await?;
+
+    let semaphore = std::sync::Arc::new(tokio::sync::Semaphore::new(MAX_CONCURRENT_REFRESHES));
+    let mut handles = Vec::new();
+
+    for mut server in servers {
+        let semaphore = semaphore.clone();
+        let app_handle = app_handle.clone();
+
+        handles.push(tauri::async_runtime::spawn(async move {
+            let _permit = semaphore.acquire_owned().await.ok()?;
+
+            let result = query_status(&server.address, server.port, REFRESH_TIMEOUT).await;
+            let ping = match result {
+                Ok(status) => {
+                    server.status = "online".to_string();
+                    server.players_online = status.players_online;
+                    server.players_max = status.players_max;
+                    server.version = status.version;
+                    server.motd = status.motd;
+                    // Keep the cached icon when the server sends none or an unusable one
+                    if let Some(favicon) = status.favicon.as_deref()
+                        .and_then(|f| f.strip_prefix("data:image/png;base64,"))
+                        .and_then(ServerManager::favicon_from_base64)
+                    {
+                        server.favicon = Some(favicon);
+                    }
+
+                    if let Ok(mut cache) = PING_CACHE.lock() {
+                        cache.insert(format!("{}:{}", server.address.to_lowercase(), server.port), (Instant::now(), status.ping));
+                    }
+                    Some(status.ping)
+                }
+                Err(_) => {
+                    server.status = "offline".to_string();
+                    server.players_online = None;
+                    None
+                }
+            };
+            server.last_checked = Some(chrono::Utc::now().timestamp());
+
+            let _ = app_handle.emit("server-status-updated", ServerStatusUpdate { server: server.clone(), ping });
+            Some(server)
+        }));
+    }
+
+    let mut refreshed = Vec::with_capacity(handles.len());
+    for handle in handles {
+        if let Ok(Some(server)) = handle.await {
+            refreshed.push(server);
+        }
+    }
+
+    server_manager.update(|servers| {
+        // Match on address too, in case a server was replaced while the refresh ran
+        for update in refreshed {
+            if let Some(server) = servers.iter_mut().find(|s| {
+                s.name == update.name && s.address == update.address && s.port == update.port
+            }) {
+                server.status = update.status;
+                server.players_online = update.players_online;
+                server.players_max = update.players_max;
+                server.version = update.version;
+                server.motd = update.motd;
+                server.favicon = update.favicon;
+                server.last_checked = update.last_checked;
+            }
+        }
+        Ok(())
+    })
+    .await
+    .map(|_| ())
 }
 
 #[tauri::command]
 pub async fn ping_server(address: String, port: u16) -> Result<u32, String> {
-    tauri::async_runtime::spawn_blocking(move || {
-        let addr_str = format!("{}:{}", address, port);
+    let addr_str = format!("{}:{}", address.to_lowercase(), port);
+
+    if let Ok(cache) = PING_CACHE.lock() {
+        if let Some((pinged_at, ping)) = cache.get(&addr_str) {
+            if pinged_at.elapsed() < PING_TTL {
+                return Ok(*ping);
+            }
+        }
+    }
 
-        let socket_addr = addr_str
-            .to_socket_addrs()
-            .map_err(|e| format!("Failed to resolve address: {}", e))?
-            .next()
-            .ok_or_else(|| "No addresses found for host".to_string())?;
+    let ping = tauri::async_runtime::spawn_blocking({
+        let addr_str = addr_str.clone();
+        move || {
+            let socket_addr = addr_str
+                .to_socket_addrs()
+                .map_err(|e| format!("Failed to resolve address: {}", e))?
+                .next()
+                .ok_or_else(|| "No addresses found for host".to_string())?;
 
-        let start = Instant::now();
+            let start = Instant::now();
 
-        TcpStream::connect_timeout(&socket_addr, Duration::from_secs(5))
-            .map_err(|e| format!("Connection failed: {}", e))?;
+            TcpStream::connect_timeout(&socket_addr, Duration::from_secs(5))
+                .map_err(|e| format!("Connection failed: {}", e))?;
 
-        Ok(start.elapsed().as_millis() as u32)
+            Ok::<_, String>(start.elapsed().as_millis() as u32)
+        }
     })
     .await
-    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())??;
+
+    if let Ok(mut cache) = PING_CACHE.lock() {
+        cache.retain(|_, (pinged_at, _)| pinged_at.elapsed() < PING_TTL);
+        cache.insert(addr_str, (Instant::now(), ping));
+    }
+
+    Ok(ping)
+}
+
+/// Mod ids every Forge server lists for the game and loader themselves.
+const BUILTIN_MOD_IDS: &[&str] = &["minecraft", "mcp", "fml", "forge", "neoforge"];
+
+/// Compares an instance with a saved server before joining: the Minecraft version, the loader,
+/// the mods Forge servers list and the modpack the server was marked as running. Without
+/// `instance_name`, checks the instance `launch_server` would start.
+#[tauri::command]
+pub async fn check_server_compatibility(
+    server_name: String,
+    instance_name: Option<String>,
+    server_manager: State<'_, ServerManager>,
+) -> Result<ServerCompatibilityReport, String> {
+    let safe_name = sanitize_server_name(&server_name)?;
+    let server = server_manager.list().await?
+        .into_iter()
+        .find(|s| s.name == safe_name)
+        .ok_or(format!("Server '{}' not found", safe_name))?;
+
+    let instance_name = match instance_name {
+        Some(name) => sanitize_instance_name(&name)?,
+        None => launch_target_instance(server.preferred_instance.clone())?,
+    };
+
+    let content = std::fs::read_to_string(get_instance_dir(&instance_name).join("instance.json"))
+        .map_err(|_| format!("Instance '{}' not found", instance_name))?;
+    let instance: Instance = serde_json::from_str(&content)
+        .map_err(|e| e.to_string())?;
+    let instance_version = crate::commands::instances::instance_minecraft_version(&instance);
+
+    let status = query_status(&server.address, server.port, REFRESH_TIMEOUT).await.ok();
+    let server_version = status.as_ref()
+        .and_then(|s| s.version.clone())
+        .or(server.version.clone());
+
+    let mut report = ServerCompatibilityReport {
+        instance_name: instance_name.clone(),
+        instance_version: instance_version.clone(),
+        server_version: server_version.clone(),
+        online: status.is_some(),
+        ..Default::default()
+    };
+
+    if let Some(server_version) = &server_version {
+        if version_compatibility(server_version, &instance_version) == Some(false) {
+            report.mismatches.push(format!(
+                "The server reports {}, this instance runs Minecraft {}",
+                server_version, instance_version
+            ));
+        }
+    }
+
+    if let Some(project_id) = &server.modpack_project_id {
+        let instance_pack = instance.modpack.as_ref().and_then(|m| m.project_id.as_ref());
+        if instance_pack != Some(project_id) {
+            report.mismatches.push("The server runs a modpack this instance wasn't installed from".to_string());
+        }
+    }
+
+    if let Some(status) = &status {
+        let loader = instance.loader.as_deref().unwrap_or("vanilla");
+        if status.modded && !matches!(loader, "forge" | "neoforge") {
+            report.mismatches.push(format!("The server runs Forge or NeoForge, this instance uses {}", loader));
+        }
+
+        if let Some(mods) = &status.mods {
+            let installed = installed_mod_ids(&instance_name);
+            report.mods_checked = true;
+            report.missing_mods = mods.iter()
+                .map(|m| m.id.to_lowercase())
+                .filter(|id| !BUILTIN_MOD_IDS.contains(&id.as_str()) && !installed.contains(id))
+                .collect();
+            report.missing_mods.sort();
+            report.missing_mods.dedup();
+        }
+    }
+
+    Ok(report)
+}
+
+/// Lowercased ids of the enabled mods in an instance, read from their jars.
+fn installed_mod_ids(instance_name: &str) -> std::collections::HashSet<String> {
+    let Ok(entries) = std::fs::read_dir(get_instance_dir(instance_name).join("mods")) else {
+        return std::collections::HashSet::new();
+    };
+
+    entries
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().ends_with(".jar"))
+        .filter_map(|entry| read_jar_mod_info(&entry.path()))
+        .map(|info| info.id.to_lowercase())
+        .collect()
+}
+
+/// Whether a server's version string covers `instance_version`. Proxies report ranges such as
+/// "BungeeCord 1.8.x-1.21.x". `None` when the string names no Minecraft release.
+fn version_compatibility(server_version: &str, instance_version: &str) -> Option<bool> {
+    let tokens: Vec<&str> = server_version
+        .split(|c: char| !(c.is_ascii_alphanumeric() || c == '.'))
+        .filter(|t| !t.is_empty())
+        .collect();
+
+    if tokens.contains(&instance_version) {
+        return Some(true);
+    }
+
+    let releases: Vec<(Vec<u32>, bool)> = tokens.iter().filter_map(|t| release_parts(t)).collect();
+    let Some((instance, _)) = release_parts(instance_version) else {
+        // A snapshot, which only a server on that exact snapshot accepts
+        return (!releases.is_empty()).then_some(false);
+    };
+
+    match releases.as_slice() {
+        [] => None,
+        [(single, wildcard)] => Some(if *wildcard { instance.starts_with(single) } else { instance == *single }),
+        [(low, _), .., (high, _)] => {
+            let prefix = &instance[..instance.len().min(high.len())];
+            Some(instance.as_slice() >= low.as_slice() && prefix <= high.as_slice())
+        }
+    }
+}
+
+/// "1.20.1" as [1, 20, 1], with trailing zeros dropped. A trailing ".x" marks a prefix.
+/// Snapshots and non-Minecraft versions give `None`.
+fn release_parts(version: &str) -> Option<(Vec<u32>, bool)> {
+    let (version, wildcard) = match version.strip_suffix(".x") {
+        Some(prefix) => (prefix, true),
+        None => (version, false),
+    };
+
+    let mut parts: Vec<u32> = version.split('.').map(|p| p.parse().ok()).collect::<Option<_>>()?;
+    if parts.len() < 2 || parts[0] != 1 {
+        return None;
+    }
+    while parts.len() > 2 && parts.last() == Some(&0) {
+        parts.pop();
+    }
+
+    Some((parts, wildcard))
+}
+
+/// The instance `launch_server` starts: the server's preferred one, else the most recently
+/// played.
+fn launch_target_instance(preferred_instance: Option<String>) -> Result<String, String> {
+    if let Some(name) = preferred_instance.filter(|name| get_instance_dir(name).exists()) {
+        return Ok(name);
+    }
+
+    let instances = InstanceManager::get_all()
+        .map_err(|e| e.to_string())?;
+
+    instances
+        .iter()
+        .filter(|inst| inst.last_played.is_some())
+        .max_by_key(|inst| inst.last_played.as_ref())
+        .map(|inst| inst.name.clone())
+        .ok_or_else(|| "No instances found. Please create an instance first.".to_string())
 }
 
 #[tauri::command]
@@ -180,6 +662,7 @@ pub async fn launch_server(
     server_address: String,
     server_port: u16,
     server_name: String,
+    server_manager: State<'_, ServerManager>,
     app_handle: tauri::AppHandle,
 ) -> Result<(), String> {
     let safe_name = sanitize_server_name(&server_name)?;
@@ -199,19 +682,12 @@ pub async fn launch_server(
         .await
         .map_err(|e| e.to_string())?;
 
-    let instances = InstanceManager::get_all()
-        .map_err(|e| e.to_string())?;
-    
-    let most_recent_instance = instances
-        .iter()
-        .filter(|inst| inst.last_played.is_some())
-        .max_by_key(|inst| inst.last_played.as_ref());
+    let preferred_instance = server_manager.list().await?
+        .into_iter()
+        .find(|s| s.name == safe_name)
+        .and_then(|s| s.preferred_instance);
 
-    let instance_name = if let Some(recent_inst) = most_recent_instance {
-        recent_inst.name.clone()
-    } else {
-        return Err("No instances found. Please create an instance first.".to_string());
-    };
+    let instance_name = launch_target_instance(preferred_instance)?;
 
     let instance_dir = get_instance_dir(&instance_name);
 
@@ -232,6 +708,8 @@ pub async fn launch_server(
         "server": safe_name
     }));
 
+    crate::commands::instances::update_snapshot_before_launch(&instance_name, &app_handle).await;
+
     InstanceManager::launch_with_server(
         &instance_name,
         &active_account.username,
@@ -243,6 +721,59 @@ pub async fn launch_server(
     .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub fn get_lan_games(app_handle: tauri::AppHandle) -> Vec<crate::services::lan::LanGame> {
+    app_handle
+        .try_state::<crate::services::lan::LanDiscovery>()
+        .map(|discovery| discovery.games())
+        .unwrap_or_default()
+}
+
+/// Launches an instance straight into a LAN world found by the background listener.
+#[tauri::command]
+pub async fn launch_lan_game(
+    instance_name: String,
+    address: String,
+    app_handle: tauri::AppHandle,
+) -> Result<(), String> {
+    let safe_name = crate::commands::validation::sanitize_instance_name(&instance_name)?;
+    validate_server_address(&address)?;
+
+    let known = app_handle
+        .try_state::<crate::services::lan::LanDiscovery>()
+        .is_some_and(|discovery| discovery.is_known(&address));
+
+    if !known {
+        return Err("This LAN game is no longer being announced".to_string());
+    }
+
+    if !get_instance_dir(&safe_name).join("instance.json").exists() {
+        return Err(format!("Instance '{}' not found", safe_name));
+    }
+
+    let config = app_handle.state::<AppConfig>();
+
+    let active_account = AccountManager::get_active_account()
+        .map_err(|e| e.to_string())?
+        .ok_or("No active account")?;
+
+    let access_token = AccountManager::get_valid_token(&active_account.uuid, &config.microsoft_client_id)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    crate::commands::instances::update_snapshot_before_launch(&safe_name, &app_handle).await;
+
+    InstanceManager::launch_with_server(
+        &safe_name,
+        &active_account.username,
+        &active_account.uuid,
+        &access_token,
+        &address,
+        app_handle.clone(),
+    )
+    .map_err(|e| e.to_string())
+}
+
 fn add_server_to_instance(
     instance_dir: &std::path::Path,
     server_name: &str,
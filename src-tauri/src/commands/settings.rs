@@ -1,9 +1,15 @@
 use crate::commands::validation::{
-    sanitize_instance_name, get_java_info, validate_memory_allocation,
+    sanitize_instance_name, get_java_info, java_memory_warning, validate_memory_allocation,
+    validate_automation, validate_env_vars, validate_jvm_args, validate_launch_wrapper, validate_trusted_hosts,
+    validate_window_size,
 };
-use crate::models::{DetectedJava, Instance, LauncherSettings};
+use crate::models::{AutomationSettings, DetectedJava, Instance, InstanceLaunchOptions, LauncherSettings};
+use crate::services::automation::AutomationEvent;
+use crate::services::instance::InstanceManager;
 use crate::services::settings::SettingsManager;
 use crate::utils::get_instance_dir;
+use crate::services::operations::{OperationKind, ProgressEvent};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 fn detect_path(base: &str, exe_name: &str) -> Option<String> {
@@ -21,11 +27,52 @@ fn try_add(java_paths: &mut Vec<String>, path: &str) {
     }
 }
 
-fn cache_jres(jres: &[DetectedJava]) {
-    let cache_path = crate::utils::get_launcher_dir().join("java_cache.json");
-    if let Ok(json) = serde_json::to_string_pretty(jres) {
-        let _ = std::fs::write(&cache_path, json);
+/// Detection results keyed by executable path, reused while the binary's mtime is unchanged.
+#[derive(serde::Serialize, serde::Deserialize)]
+struct JavaCacheEntry {
+    mtime: u64,
+    info: DetectedJava,
+}
+
+fn java_cache_path() -> PathBuf {
+    crate::utils::get_launcher_dir().join("java_cache.json")
+}
+
+fn load_java_cache() -> HashMap<String, JavaCacheEntry> {
+    std::fs::read_to_string(java_cache_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_java_cache(cache: &HashMap<String, JavaCacheEntry>) {
+    if let Ok(json) = serde_json::to_string_pretty(cache) {
+        let _ = std::fs::write(java_cache_path(), json);
+    }
+}
+
+fn java_mtime(path: &str) -> Option<u64> {
+    std::fs::metadata(path)
+        .ok()?
+        .modified()
+        .ok()?
+        .duration_since(std::time::UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_secs())
+}
+
+fn cached_java_info(cache: &mut HashMap<String, JavaCacheEntry>, path: &str) -> Result<DetectedJava, String> {
+    let mtime = java_mtime(path).ok_or("Java path does not exist")?;
+
+    if let Some(entry) = cache.get(path) {
+        if entry.mtime == mtime {
+            return Ok(entry.info.clone());
+        }
     }
+
+    let info = get_java_info(path)?;
+    cache.insert(path.to_string(), JavaCacheEntry { mtime, info: info.clone() });
+    Ok(info)
 }
 
 #[tauri::command]
@@ -34,20 +81,79 @@ pub async fn get_settings() -> Result<LauncherSettings, String> {
         .map_err(|e| e.to_string())
 }
 
+/// Returns warnings about the saved settings for the UI to show.
 #[tauri::command]
-pub async fn save_settings(settings: LauncherSettings) -> Result<(), String> {
+pub async fn save_settings(mut settings: LauncherSettings) -> Result<Vec<String>, String> {
+    let mut warnings = Vec::new();
+
     if let Some(ref java_path) = settings.java_path {
-        get_java_info(java_path)?;
+        let java = get_java_info(java_path)?;
+        warnings.extend(java_memory_warning(&java, settings.memory_mb));
     }
 
     validate_memory_allocation(settings.memory_mb as u64)?;
+    validate_trusted_hosts(&settings.trusted_download_hosts)?;
+    validate_env_vars(&settings.env_vars)?;
+    validate_jvm_args(&settings.jvm_args)?;
+    validate_window_size(settings.window_width, settings.window_height)?;
+    validate_automation(&settings.automation)?;
+
+    if settings.allow_any_https_host {
+        warnings.push("The download host whitelist is disabled, any HTTPS host is allowed".to_string());
+    }
 
-    SettingsManager::save(&settings)
-        .map_err(|e| e.to_string())
+    SettingsManager::update(|current| {
+        // Restricted mode is only turned on or off with its PIN
+        settings.restricted_mode = current.restricted_mode.take();
+        settings.settings_version = current.settings_version;
+        *current = settings;
+        Ok(())
+    })
+    .map_err(|e| e.to_string())?;
+
+    Ok(warnings)
+}
+
+/// Sends a test event to the given automation targets, enabled or not, so they can be checked
+/// before saving.
+#[tauri::command]
+pub async fn test_automation(automation: AutomationSettings, app_handle: tauri::AppHandle) -> Result<(), String> {
+    validate_automation(&automation)?;
+
+    let has_target = [&automation.webhook_url, &automation.socket_address]
+        .iter()
+        .any(|target| target.as_deref().is_some_and(|t| !t.trim().is_empty()));
+    if !has_target {
+        return Err("Set a webhook URL or socket address first".to_string());
+    }
+
+    let http_client = crate::utils::http::client_from(&app_handle);
+    let errors = crate::services::automation::deliver(&http_client, &automation, &AutomationEvent::Test).await;
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors.join(", "))
+    }
+}
+
+/// Replaces every setting with its default and returns the result. Restricted mode stays as
+/// it is, it can only be turned off with its PIN.
+#[tauri::command]
+pub async fn reset_settings_to_defaults() -> Result<LauncherSettings, String> {
+    SettingsManager::update(|current| {
+        let defaults = LauncherSettings {
+            restricted_mode: current.restricted_mode.take(),
+            ..LauncherSettings::default()
+        };
+        *current = defaults.clone();
+        Ok(defaults)
+    })
+    .map_err(|e| e.to_string())
 }
 
+/// The launch settings an instance overrides, `None` when it uses the launcher settings.
 #[tauri::command]
-pub async fn get_instance_settings(instance_name: String) -> Result<Option<LauncherSettings>, String> {
+pub async fn get_instance_launch_options(instance_name: String) -> Result<Option<InstanceLaunchOptions>, String> {
     let safe_name = sanitize_instance_name(&instance_name)?;
 
     let instance_dir = get_instance_dir(&safe_name);
@@ -60,24 +166,47 @@ pub async fn get_instance_settings(instance_name: String) -> Result<Option<Launc
     let content = std::fs::read_to_string(&instance_json)
         .map_err(|e| e.to_string())?;
 
-    let instance: Instance = serde_json::from_str(&content)
+    let mut instance: Instance = serde_json::from_str(&content)
         .map_err(|e| e.to_string())?;
 
-    Ok(instance.settings_override)
+    if instance.settings_override.is_some() {
+        InstanceManager::migrate_launch_options(&mut instance, &SettingsManager::load().unwrap_or_default());
+        crate::utils::write_json_atomic(&instance_json, &instance)
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(instance.launch_options)
 }
 
+/// Replaces the instance's launch overrides; `None` or empty options clear them. Returns
+/// warnings about the options for the UI to show.
 #[tauri::command]
-pub async fn save_instance_settings(
+pub async fn save_instance_launch_options(
     instance_name: String,
-    settings: Option<LauncherSettings>,
-) -> Result<(), String> {
+    options: Option<InstanceLaunchOptions>,
+) -> Result<Vec<String>, String> {
     let safe_name = sanitize_instance_name(&instance_name)?;
+    let mut warnings = Vec::new();
 
-    if let Some(ref s) = settings {
-        if let Some(ref java_path) = s.java_path {
-            get_java_info(java_path)?;
+    if let Some(ref o) = options {
+        if let Some(ref java_path) = o.java_path {
+            let java = get_java_info(java_path)?;
+            let memory_mb = o.memory_mb.unwrap_or_else(|| SettingsManager::load().unwrap_or_default().memory_mb);
+            warnings.extend(java_memory_warning(&java, memory_mb));
+        }
+        if let Some(memory_mb) = o.memory_mb {
+            validate_memory_allocation(memory_mb as u64)?;
+        }
+        if let Some(ref env_vars) = o.env_vars {
+            validate_env_vars(env_vars)?;
+        }
+        if let Some(ref jvm_args) = o.jvm_args {
+            validate_jvm_args(jvm_args)?;
+        }
+        validate_window_size(o.window_width, o.window_height)?;
+        if let Some(ref wrapper) = o.wrapper {
+            validate_launch_wrapper(wrapper)?;
         }
-        validate_memory_allocation(s.memory_mb as u64)?;
     }
 
     let instance_dir = get_instance_dir(&safe_name);
@@ -93,17 +222,16 @@ pub async fn save_instance_settings(
     let mut instance: Instance = serde_json::from_str(&content)
         .map_err(|e| e.to_string())?;
 
-    instance.settings_override = settings;
+    instance.settings_override = None;
+    instance.launch_options = options.filter(|o| !o.is_empty());
 
-    let updated_json = serde_json::to_string_pretty(&instance)
+    crate::utils::write_json_atomic(&instance_json, &instance)
         .map_err(|e| e.to_string())?;
 
-    std::fs::write(&instance_json, updated_json)
-        .map_err(|e| e.to_string())
+    Ok(warnings)
 }
 
-#[tauri::command]
-pub async fn detect_java_installations() -> Result<Vec<String>, String> {
+fn find_java_candidates() -> Vec<String> {
     let mut java_paths: Vec<String> = Vec::new();
 
     let java_bin = if cfg!(windows) { "javaw.exe" } else { "java" };
@@ -126,9 +254,7 @@ pub async fn detect_java_installations() -> Result<Vec<String>, String> {
                 for entry in entries.flatten() {
                     if entry.path().is_dir() {
                         if let Some(p) = detect_path(&entry.path().to_string_lossy(), java_bin) {
-                            if get_java_info(&p).is_ok() {
-                                try_add(&mut java_paths, &p);
-                            }
+                            try_add(&mut java_paths, &p);
                         }
                     }
                 }
@@ -142,9 +268,7 @@ pub async fn detect_java_installations() -> Result<Vec<String>, String> {
                     for entry in entries.flatten() {
                         if entry.path().is_dir() {
                             if let Some(p) = detect_path(&entry.path().to_string_lossy(), java_bin) {
-                                if get_java_info(&p).is_ok() {
-                                    try_add(&mut java_paths, &p);
-                                }
+                                try_add(&mut java_paths, &p);
                             }
                         }
                     }
@@ -159,9 +283,7 @@ pub async fn detect_java_installations() -> Result<Vec<String>, String> {
             let exe_path = PathBuf::from(path).join(java_bin);
             if exe_path.exists() {
                 if let Some(s) = exe_path.to_str() {
-                    if get_java_info(s).is_ok() {
-                        try_add(&mut java_paths, s);
-                    }
+                    try_add(&mut java_paths, s);
                 }
             }
         }
@@ -169,24 +291,36 @@ pub async fn detect_java_installations() -> Result<Vec<String>, String> {
 
     if let Ok(java_home) = std::env::var("JAVA_HOME") {
         if let Some(p) = detect_path(&java_home, java_bin) {
-            if get_java_info(&p).is_ok() {
-                try_add(&mut java_paths, &p);
-            }
+            try_add(&mut java_paths, &p);
         }
     }
 
     java_paths.sort();
     java_paths.dedup();
+    java_paths
+}
+
+#[tauri::command]
+pub async fn detect_java_installations() -> Result<Vec<DetectedJava>, String> {
+    refresh_java_installations(false).await
+}
+
+/// Detects Java installations, reusing cached results unless `force` is set.
+#[tauri::command]
+pub async fn refresh_java_installations(force: bool) -> Result<Vec<DetectedJava>, String> {
+    let mut cache = if force { HashMap::new() } else { load_java_cache() };
 
-    let mut fresh_detected = Vec::new();
-    for p in &java_paths {
-        if let Ok(info) = get_java_info(p) {
-            fresh_detected.push(info);
+    let mut detected = Vec::new();
+    for path in find_java_candidates() {
+        if let Ok(info) = cached_java_info(&mut cache, &path) {
+            detected.push(info);
         }
     }
-    cache_jres(&fresh_detected);
 
-    Ok(java_paths)
+    cache.retain(|path, _| detected.iter().any(|java| &java.path == path));
+    save_java_cache(&cache);
+
+    Ok(detected)
 }
 
 use base64::{engine::general_purpose, Engine as _};
@@ -298,7 +432,13 @@ pub async fn get_storage_usage() -> Result<Vec<StorageCategory>, String> {
     Ok(categories)
 }
 
-fn dir_size(path: &std::path::Path) -> u64 {
+/// Deletes asset objects no installed version references, whatever the cleanup mode.
+#[tauri::command]
+pub async fn run_asset_cleanup(app_handle: tauri::AppHandle) -> Result<crate::models::AssetCleanupResult, String> {
+    crate::services::asset_cleanup::run(&app_handle).await
+}
+
+pub(crate) fn dir_size(path: &std::path::Path) -> u64 {
     let mut total = 0u64;
     if let Ok(entries) = std::fs::read_dir(path) {
         for entry in entries.flatten() {
@@ -333,4 +473,168 @@ pub async fn open_directory(path: String) -> Result<(), String> {
         .map_err(|e| e.to_string())?;
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Shows a file selected in the OS file manager. Only paths inside the launcher directory
+/// can be revealed.
+#[tauri::command]
+pub async fn reveal_in_file_manager(path: String) -> Result<(), String> {
+    let path = std::path::PathBuf::from(&path)
+        .canonicalize()
+        .map_err(|_| format!("File does not exist: {}", path))?;
+
+    let launcher_dir = crate::utils::get_launcher_dir()
+        .canonicalize()
+        .map_err(|e| e.to_string())?;
+
+    if !path.starts_with(&launcher_dir) {
+        return Err("Only files inside the launcher directory can be revealed".to_string());
+    }
+
+    // explorer doesn't understand the verbatim paths canonicalize returns on Windows
+    #[cfg(target_os = "windows")]
+    let path = std::path::PathBuf::from(path.to_string_lossy().trim_start_matches(r"\\?\").to_string());
+
+    crate::utils::reveal_file(&path).map_err(|e| e.to_string())
+}
+
+/// Moves all launcher data (instances, meta, settings, accounts) to `new_path` and
+/// makes it the active data directory.
+#[tauri::command]
+pub async fn migrate_launcher_directory(
+    new_path: String,
+    app_handle: tauri::AppHandle,
+) -> Result<String, String> {
+    use crate::utils::{get_default_launcher_dir, get_launcher_dir, is_portable, DATA_LOCATION_FILE};
+
+    let operation = crate::services::operations::track(&app_handle, OperationKind::DataMigration, None);
+
+    if is_portable() {
+        return Err("The launcher directory cannot be moved in portable mode".to_string());
+    }
+
+    {
+        let processes = crate::commands::instances::RUNNING_PROCESSES.lock().map_err(|e| e.to_string())?;
+        if !processes.is_empty() {
+            return Err("Close all running instances before moving the launcher directory".to_string());
+        }
+    }
+
+    let target = PathBuf::from(new_path.trim());
+    if !target.is_absolute() {
+        return Err("Launcher directory must be an absolute path".to_string());
+    }
+
+    let current = get_launcher_dir();
+    if target == current {
+        return Err("Launcher data is already in this directory".to_string());
+    }
+    if target.starts_with(&current) || current.starts_with(&target) {
+        return Err("New directory cannot be inside the current launcher directory or vice versa".to_string());
+    }
+
+    if target.exists() {
+        let has_data = std::fs::read_dir(&target)
+            .map_err(|e| e.to_string())?
+            .flatten()
+            .any(|e| e.file_name() != DATA_LOCATION_FILE);
+        if has_data {
+            return Err("Target directory must be empty".to_string());
+        }
+    }
+
+    let moving_from_default = current == get_default_launcher_dir();
+
+    let new_dir = tauri::async_runtime::spawn_blocking(move || {
+        let emit = |progress: u32, stage: String| {
+            ProgressEvent::new(OperationKind::DataMigration, None)
+                .percent(progress)
+                .stage(stage)
+                .emit(&app_handle);
+        };
+
+        emit(0, "Preparing migration...".to_string());
+
+        std::fs::create_dir_all(&target)
+            .map_err(|e| format!("Failed to create target directory: {}", e))?;
+
+        // The pointer file stays behind in the default dir
+        let entries: Vec<PathBuf> = std::fs::read_dir(&current)
+            .map_err(|e| e.to_string())?
+            .flatten()
+            .filter(|e| !(moving_from_default && e.file_name() == DATA_LOCATION_FILE))
+            .map(|e| e.path())
+            .collect();
+
+        let total_files: usize = entries.iter().map(|p| count_files(p)).sum::<usize>().max(1);
+        let mut copied_files = 0;
+
+        for entry in &entries {
+            let Some(name) = entry.file_name() else { continue };
+            copy_with_progress(entry, &target.join(name), &mut copied_files, total_files, &emit)
+                .map_err(|e| format!("Failed to copy {}: {}", entry.display(), e))?;
+        }
+
+        crate::utils::set_launcher_dir(&target)
+            .map_err(|e| format!("Failed to save launcher directory: {}", e))?;
+
+        emit(95, "Removing old data...".to_string());
+
+        for entry in &entries {
+            let result = if entry.is_dir() {
+                std::fs::remove_dir_all(entry)
+            } else {
+                std::fs::remove_file(entry)
+            };
+            if let Err(e) = result {
+                eprintln!("Failed to remove old data {}: {}", entry.display(), e);
+            }
+        }
+
+        emit(100, "Migration complete!".to_string());
+
+        Ok::<String, String>(target.to_string_lossy().to_string())
+    })
+    .await
+    .map_err(|e| format!("Migration task failed: {}", e))??;
+
+    operation.complete();
+    Ok(new_dir)
+}
+
+fn count_files(path: &std::path::Path) -> usize {
+    if path.is_dir() {
+        std::fs::read_dir(path)
+            .map(|entries| entries.flatten().map(|e| count_files(&e.path())).sum())
+            .unwrap_or(0)
+    } else {
+        1
+    }
+}
+
+fn copy_with_progress(
+    src: &std::path::Path,
+    dst: &std::path::Path,
+    copied_files: &mut usize,
+    total_files: usize,
+    emit: &dyn Fn(u32, String),
+) -> std::io::Result<()> {
+    if src.is_dir() {
+        std::fs::create_dir_all(dst)?;
+        for entry in std::fs::read_dir(src)? {
+            let entry = entry?;
+            copy_with_progress(&entry.path(), &dst.join(entry.file_name()), copied_files, total_files, emit)?;
+        }
+        return Ok(());
+    }
+
+    std::fs::copy(src, dst)?;
+    *copied_files += 1;
+
+    let progress = (*copied_files * 95 / total_files) as u32;
+    if copied_files.is_multiple_of(50) || *copied_files == total_files {
+        emit(progress, format!("Copying files... ({}/{})", copied_files, total_files));
+    }
+
+    Ok(())
+}
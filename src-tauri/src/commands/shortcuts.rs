@@ -0,0 +1,128 @@
+use crate::commands::validation::sanitize_instance_name;
+use crate::utils::get_instance_dir;
+use std::path::PathBuf;
+
+/// Creates a desktop shortcut that starts the launcher with `--launch <instance>`.
+/// Returns the path of the created shortcut.
+#[tauri::command]
+pub async fn create_desktop_shortcut(instance_name: String) -> Result<String, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let instance_dir = get_instance_dir(&safe_name);
+
+    if !instance_dir.join("instance.json").exists() {
+        return Err(format!("Instance '{}' does not exist", safe_name));
+    }
+
+    let exe_path = std::env::current_exe()
+        .map_err(|e| format!("Failed to locate launcher executable: {}", e))?;
+
+    let desktop_dir = dirs::desktop_dir()
+        .ok_or("Could not find desktop directory")?;
+    std::fs::create_dir_all(&desktop_dir)
+        .map_err(|e| format!("Failed to create desktop directory: {}", e))?;
+
+    let shortcut_path = write_shortcut(&desktop_dir, &exe_path, &safe_name, &instance_dir)?;
+
+    Ok(shortcut_path.to_string_lossy().to_string())
+}
+
+#[cfg(target_os = "windows")]
+fn write_shortcut(
+    desktop_dir: &std::path::Path,
+    exe_path: &std::path::Path,
+    instance_name: &str,
+    _instance_dir: &std::path::Path,
+) -> Result<PathBuf, String> {
+    use std::os::windows::process::CommandExt;
+
+    let shortcut_path = desktop_dir.join(format!("{}.lnk", instance_name));
+
+    // PowerShell single-quoted strings only need embedded quotes doubled
+    let quote = |s: &str| format!("'{}'", s.replace('\'', "''"));
+    let working_dir = exe_path.parent().unwrap_or(exe_path);
+
+    let script = format!(
+        "$s = (New-Object -ComObject WScript.Shell).CreateShortcut({}); \
+         $s.TargetPath = {}; $s.Arguments = {}; $s.WorkingDirectory = {}; \
+         $s.IconLocation = {}; $s.Description = {}; $s.Save()",
+        quote(&shortcut_path.to_string_lossy()),
+        quote(&exe_path.to_string_lossy()),
+        quote(&format!("--launch \"{}\"", instance_name)),
+        quote(&working_dir.to_string_lossy()),
+        quote(&format!("{},0", exe_path.to_string_lossy())),
+        quote(&format!("Launch {} with Octane Launcher", instance_name)),
+    );
+
+    let output = std::process::Command::new("powershell")
+        .args(["-NoProfile", "-NonInteractive", "-Command", &script])
+        .creation_flags(0x08000000)
+        .output()
+        .map_err(|e| format!("Failed to run PowerShell: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to create shortcut: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    Ok(shortcut_path)
+}
+
+#[cfg(target_os = "linux")]
+fn write_shortcut(
+    desktop_dir: &std::path::Path,
+    exe_path: &std::path::Path,
+    instance_name: &str,
+    instance_dir: &std::path::Path,
+) -> Result<PathBuf, String> {
+    use std::os::unix::fs::PermissionsExt;
+
+    // Desktop entry Exec values need quoting for spaces and escaping of `"`, `\`, `$` and `` ` ``
+    let escape = |s: &str| {
+        let mut escaped = String::with_capacity(s.len() + 2);
+        escaped.push('"');
+        for c in s.chars() {
+            if matches!(c, '"' | '\\' | '$' | '`') {
+                escaped.push('\\');
+            }
+            escaped.push(c);
+        }
+        escaped.push('"');
+        escaped
+    };
+
+    let icon_path = instance_dir.join("icon.png");
+    let icon_line = if icon_path.exists() {
+        format!("Icon={}\n", icon_path.to_string_lossy())
+    } else {
+        String::new()
+    };
+
+    let content = format!(
+        "[Desktop Entry]\n\
+         Type=Application\n\
+         Name={name}\n\
+         Comment=Launch {name} with Octane Launcher\n\
+         Exec={exe} --launch {instance}\n\
+         {icon}Terminal=false\n\
+         Categories=Game;\n",
+        name = instance_name,
+        exe = escape(&exe_path.to_string_lossy()),
+        instance = escape(instance_name),
+        icon = icon_line,
+    );
+
+    let file_stem: String = instance_name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' { c } else { '-' })
+        .collect();
+    let shortcut_path = desktop_dir.join(format!("octane-{}.desktop", file_stem));
+
+    std::fs::write(&shortcut_path, content)
+        .map_err(|e| format!("Failed to write shortcut: {}", e))?;
+    std::fs::set_permissions(&shortcut_path, std::fs::Permissions::from_mode(0o755))
+        .map_err(|e| format!("Failed to mark shortcut executable: {}", e))?;
+
+    Ok(shortcut_path)
+}
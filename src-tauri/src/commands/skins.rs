@@ -2,6 +2,7 @@ use crate::services::accounts::AccountManager;
 use crate::models::AppConfig;
 use base64::{engine::general_purpose, Engine as _};
 use serde::{Deserialize, Serialize};
+use sha2::Digest;
 use std::fs;
 use std::path::PathBuf;
 use tauri::Manager;
@@ -38,16 +39,20 @@ pub struct CapeInfo {
     pub alias: String,
 }
 
-#[derive(Serialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct CurrentSkin {
     pub url: String,
     pub variant: String,
     pub cape_url: Option<String>,
+    /// Set when the session server was unreachable and the URLs point at cached textures.
+    #[serde(default)]
+    pub offline: bool,
 }
 
 #[derive(Serialize)]
 pub struct UserCapesResponse {
     pub capes: Vec<CapeInfo>,
+    pub offline: bool,
 }
 
 #[derive(Deserialize, Debug)]
@@ -106,6 +111,7 @@ pub struct RecentSkin {
     pub timestamp: u64,
 }
 
+/// Recent skins live in the launcher dir, so they follow a custom data directory or portable mode.
 fn get_recent_skins_path(account_uuid: &str) -> Result<PathBuf, String> {
     let launcher_dir = crate::utils::get_launcher_dir();
     let skins_dir = launcher_dir.join("recent_skins");
@@ -179,50 +185,99 @@ pub async fn save_recent_skin(
     Ok(())
 }
 
-#[tauri::command]
-pub async fn upload_skin(
-    skin_data: String,
-    variant: String,
-    app_handle: tauri::AppHandle,
-) -> Result<CurrentSkin, String> {
+const MAX_SKIN_BYTES: u64 = 1024 * 1024;
+
+fn validate_skin_variant(variant: &str) -> Result<(), String> {
     if variant != "classic" && variant != "slim" {
         return Err("Invalid skin variant. Must be 'classic' or 'slim'".to_string());
     }
-    
-    let config = app_handle.state::<AppConfig>();
-    
-    let active_account = AccountManager::get_active_account()
-        .map_err(|e| e.to_string())?
-        .ok_or("No active account".to_string())?;
-    
-    let access_token = AccountManager::get_valid_token(&active_account.uuid, &config.microsoft_client_id)
-        .await
-        .map_err(|e| e.to_string())?;
-    
-    let image_bytes = general_purpose::STANDARD
-        .decode(&skin_data)
-        .map_err(|e| e.to_string())?;
-    
-    if image_bytes.len() > 1024 * 1024 {
+    Ok(())
+}
+
+/// Checks that the bytes are a PNG skin texture of a size the game accepts.
+fn validate_skin_png(image_bytes: &[u8]) -> Result<(), String> {
+    if image_bytes.len() as u64 > MAX_SKIN_BYTES {
         return Err("Skin image too large (max 1MB)".to_string());
     }
     
-    let format = image::guess_format(&image_bytes)
+    let format = image::guess_format(image_bytes)
         .map_err(|e| e.to_string())?;
     
     if format != image::ImageFormat::Png {
         return Err("Skin must be a PNG image".to_string());
     }
     
-    let img = image::load_from_memory(&image_bytes)
+    let img = image::load_from_memory(image_bytes)
         .map_err(|e| e.to_string())?;
     
     let (width, height) = (img.width(), img.height());
     if !((width == 64 && height == 64) || (width == 64 && height == 32)) {
         return Err(format!("Invalid skin dimensions ({}x{}). Must be 64x64 or 64x32", width, height));
     }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn upload_skin(
+    skin_data: String,
+    variant: String,
+    app_handle: tauri::AppHandle,
+) -> Result<CurrentSkin, String> {
+    validate_skin_variant(&variant)?;
+
+    let image_bytes = general_purpose::STANDARD
+        .decode(&skin_data)
+        .map_err(|e| e.to_string())?;
+
+    upload_skin_bytes(image_bytes, variant, &app_handle).await
+}
+
+/// Uploads a skin straight from disk, for the file picker and drag and drop, so large files
+/// don't have to pass through the webview as base64.
+#[tauri::command]
+pub async fn upload_skin_from_file(
+    path: String,
+    variant: String,
+    app_handle: tauri::AppHandle,
+) -> Result<CurrentSkin, String> {
+    validate_skin_variant(&variant)?;
+
+    let path = PathBuf::from(path);
+    let metadata = fs::metadata(&path)
+        .map_err(|e| format!("Failed to read skin file: {}", e))?;
+
+    if !metadata.is_file() {
+        return Err("Skin path is not a file".to_string());
+    }
+    if metadata.len() > MAX_SKIN_BYTES {
+        return Err("Skin image too large (max 1MB)".to_string());
+    }
+
+    let image_bytes = fs::read(&path)
+        .map_err(|e| format!("Failed to read skin file: {}", e))?;
+
+    upload_skin_bytes(image_bytes, variant, &app_handle).await
+}
+
+async fn upload_skin_bytes(
+    image_bytes: Vec<u8>,
+    variant: String,
+    app_handle: &tauri::AppHandle,
+) -> Result<CurrentSkin, String> {
+    validate_skin_png(&image_bytes)?;
+
+    let config = app_handle.state::<AppConfig>();
     
-    let client = crate::utils::http::get_client();
+    let active_account = AccountManager::get_active_account()
+        .map_err(|e| e.to_string())?
+        .ok_or("No active account".to_string())?;
+    
+    let access_token = AccountManager::get_valid_token(&active_account.uuid, &config.microsoft_client_id)
+        .await
+        .map_err(|e| e.to_string())?;
+    
+    let client = crate::utils::http::client_from(app_handle);
     
     let part = reqwest::multipart::Part::bytes(image_bytes)
         .file_name("skin.png")
@@ -286,6 +341,7 @@ pub async fn upload_skin(
                     url: skin_texture.url.replace("http://", "https://"),
                     variant: skin_variant.to_lowercase(),
                     cape_url: cape_url.map(|u| u.replace("http://", "https://")),
+                    offline: false,
                 });
             }
         }
@@ -295,9 +351,353 @@ pub async fn upload_skin(
         url: String::new(),
         variant: variant.to_lowercase(),
         cape_url: None,
+        offline: false,
     })
 }
 
+/// Hosts that serve skin textures or resolve to them, accepted on top of the trusted download hosts.
+const SKIN_HOSTS: &[&str] = &[
+    "textures.minecraft.net",
+    "namemc.com",
+    "s.namemc.com",
+    "mineskin.org",
+    "minesk.in",
+    "api.mineskin.org",
+];
+const NAMEMC_TEXTURE_URL: &str = "https://s.namemc.com/i";
+const MINESKIN_API_URL: &str = "https://api.mineskin.org/v2/skins";
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LibrarySkin {
+    pub id: String,
+    pub path: String,
+    pub source_url: String,
+    pub variant: Option<String>,
+    pub added_at: u64,
+}
+
+#[derive(Deserialize)]
+struct MineskinResponse {
+    skin: MineskinSkin,
+}
+
+#[derive(Deserialize)]
+struct MineskinSkin {
+    variant: Option<String>,
+    texture: MineskinTexture,
+}
+
+#[derive(Deserialize)]
+struct MineskinTexture {
+    url: MineskinTextureUrl,
+}
+
+#[derive(Deserialize)]
+struct MineskinTextureUrl {
+    skin: String,
+}
+
+fn get_skin_library_dir() -> Result<PathBuf, String> {
+    let library_dir = crate::utils::get_launcher_dir().join("skin_library");
+
+    if !library_dir.exists() {
+        fs::create_dir_all(&library_dir)
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(library_dir)
+}
+
+fn load_skin_library(library_dir: &std::path::Path) -> Vec<LibrarySkin> {
+    fs::read_to_string(library_dir.join("library.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn parse_skin_url(url: &str) -> Result<url::Url, String> {
+    // Texture URLs from the session server are still handed out as plain http
+    let url = url.trim().replace("http://textures.minecraft.net/", "https://textures.minecraft.net/");
+    let parsed = url::Url::parse(&url).map_err(|_| "Invalid URL format".to_string())?;
+
+    if parsed.scheme() != "https" {
+        return Err("Only HTTPS URLs are allowed".to_string());
+    }
+
+    let host = parsed.host_str().ok_or("URL has no host")?;
+    let host = host.strip_prefix("www.").unwrap_or(host);
+
+    if SKIN_HOSTS.iter().any(|h| h.eq_ignore_ascii_case(host)) {
+        return Ok(parsed);
+    }
+
+    crate::commands::validation::validate_download_url(&url)
+}
+
+/// Turns a skin page link into the URL of its texture, with the variant when the site knows it.
+async fn resolve_skin_texture_url(
+    client: &reqwest::Client,
+    url: &url::Url,
+) -> Result<(String, Option<String>), String> {
+    let host = url.host_str().unwrap_or_default();
+    let host = host.strip_prefix("www.").unwrap_or(host);
+    let segments: Vec<&str> = url.path_segments().map(|s| s.filter(|p| !p.is_empty()).collect()).unwrap_or_default();
+
+    match host {
+        "namemc.com" => match segments.as_slice() {
+            ["skin", id] if id.chars().all(|c| c.is_ascii_hexdigit()) => {
+                Ok((format!("{}/{}.png", NAMEMC_TEXTURE_URL, id), None))
+            }
+            _ => Err("Unsupported NameMC link. Use a skin page like namemc.com/skin/<id>".to_string()),
+        },
+        "mineskin.org" | "minesk.in" => {
+            let id = match segments.as_slice() {
+                ["skins", id] | [id] => *id,
+                _ => return Err("Unsupported MineSkin link".to_string()),
+            };
+
+            if !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+                return Err("Unsupported MineSkin link".to_string());
+            }
+
+            let response = client
+                .get(format!("{}/{}", MINESKIN_API_URL, id))
+                .send()
+                .await
+                .map_err(|e| e.to_string())?;
+
+            if !response.status().is_success() {
+                return Err(format!("MineSkin lookup failed ({})", response.status()));
+            }
+
+            let mineskin: MineskinResponse = response
+                .json()
+                .await
+                .map_err(|e| e.to_string())?;
+
+            let texture_url = parse_skin_url(&mineskin.skin.texture.url.skin)?;
+            Ok((texture_url.to_string(), mineskin.skin.variant.map(|v| v.to_lowercase())))
+        }
+        _ => Ok((url.to_string(), None)),
+    }
+}
+
+async fn download_skin_texture(client: &reqwest::Client, url: &str) -> Result<Vec<u8>, String> {
+    let response = client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if !response.status().is_success() {
+        return Err(format!("Skin download failed ({})", response.status()));
+    }
+
+    if response.content_length().is_some_and(|len| len > MAX_SKIN_BYTES) {
+        return Err("Skin image too large (max 1MB)".to_string());
+    }
+
+    let bytes = response
+        .bytes()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    validate_skin_png(&bytes)?;
+    Ok(bytes.to_vec())
+}
+
+/// Downloads a skin from a NameMC or MineSkin page, or a direct texture URL, into the local
+/// skin library. The returned path can be applied with `upload_skin_from_file`.
+#[tauri::command]
+pub async fn import_skin_from_url(url: String, app_handle: tauri::AppHandle) -> Result<LibrarySkin, String> {
+    let parsed = parse_skin_url(&url)?;
+    let client = crate::utils::http::client_from(&app_handle);
+
+    let (texture_url, variant) = resolve_skin_texture_url(&client, &parsed).await?;
+    let texture_url = parse_skin_url(&texture_url)?;
+    let image_bytes = download_skin_texture(&client, texture_url.as_str()).await?;
+
+    let id = format!("{:x}", sha2::Sha256::digest(&image_bytes));
+    let library_dir = get_skin_library_dir()?;
+    let skin_path = library_dir.join(format!("{}.png", id));
+
+    fs::write(&skin_path, &image_bytes)
+        .map_err(|e| e.to_string())?;
+
+    let skin = LibrarySkin {
+        id: id.clone(),
+        path: skin_path.to_string_lossy().to_string(),
+        source_url: url.trim().to_string(),
+        variant: variant.filter(|v| v == "classic" || v == "slim"),
+        added_at: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_millis() as u64,
+    };
+
+    let mut library = load_skin_library(&library_dir);
+    library.retain(|s| s.id != id);
+    library.insert(0, skin.clone());
+
+    let json = serde_json::to_string_pretty(&library)
+        .map_err(|e| e.to_string())?;
+
+    fs::write(library_dir.join("library.json"), json)
+        .map_err(|e| e.to_string())?;
+
+    Ok(skin)
+}
+
+#[tauri::command]
+pub async fn get_skin_library() -> Result<Vec<LibrarySkin>, String> {
+    let library_dir = get_skin_library_dir()?;
+
+    Ok(load_skin_library(&library_dir)
+        .into_iter()
+        .filter(|s| PathBuf::from(&s.path).exists())
+        .collect())
+}
+
+const PREVIEW_SCALE: u32 = 8;
+const PREVIEW_GAP: u32 = 4;
+
+/// A face of one body part as (x, y, width, height) in the skin texture.
+type SkinRegion = (u32, u32, u32, u32);
+
+struct PreviewPart {
+    base: SkinRegion,
+    overlay: Option<SkinRegion>,
+    /// Legacy 64x32 skins have no left limbs, so they reuse the right ones mirrored.
+    mirror: bool,
+    x: u32,
+    y: u32,
+}
+
+fn preview_parts(back: bool, slim: bool, legacy: bool) -> Vec<PreviewPart> {
+    let arm = if slim { 3 } else { 4 };
+    let face = |front: SkinRegion, back_face: SkinRegion| if back { back_face } else { front };
+    let layer = |region: SkinRegion| if legacy { None } else { Some(region) };
+
+    let head = face((8, 8, 8, 8), (24, 8, 8, 8));
+    let hat = face((40, 8, 8, 8), (56, 8, 8, 8));
+    let body = face((20, 20, 8, 12), (32, 20, 8, 12));
+    let jacket = face((20, 36, 8, 12), (32, 36, 8, 12));
+    let right_arm = face((44, 20, arm, 12), (44 + arm + 4, 20, arm, 12));
+    let right_sleeve = face((44, 36, arm, 12), (44 + arm + 4, 36, arm, 12));
+    let right_leg = face((4, 20, 4, 12), (12, 20, 4, 12));
+    let right_pants = face((4, 36, 4, 12), (12, 36, 4, 12));
+
+    let (left_arm, left_sleeve, left_leg, left_pants) = if legacy {
+        (right_arm, right_sleeve, right_leg, right_pants)
+    } else {
+        (
+            face((36, 52, arm, 12), (36 + arm + 4, 52, arm, 12)),
+            face((52, 52, arm, 12), (52 + arm + 4, 52, arm, 12)),
+            face((20, 52, 4, 12), (28, 52, 4, 12)),
+            face((4, 52, 4, 12), (12, 52, 4, 12)),
+        )
+    };
+
+    // Seen from behind, the player's right side ends up on the viewer's right.
+    let (right_arm_x, left_arm_x, right_leg_x, left_leg_x) = if back {
+        (12, 4 - arm, 8, 4)
+    } else {
+        (4 - arm, 12, 4, 8)
+    };
+
+    vec![
+        PreviewPart { base: head, overlay: Some(hat), mirror: false, x: 4, y: 0 },
+        PreviewPart { base: body, overlay: layer(jacket), mirror: false, x: 4, y: 8 },
+        PreviewPart { base: right_arm, overlay: layer(right_sleeve), mirror: false, x: right_arm_x, y: 8 },
+        PreviewPart { base: left_arm, overlay: layer(left_sleeve), mirror: legacy, x: left_arm_x, y: 8 },
+        PreviewPart { base: right_leg, overlay: layer(right_pants), mirror: false, x: right_leg_x, y: 20 },
+        PreviewPart { base: left_leg, overlay: layer(left_pants), mirror: legacy, x: left_leg_x, y: 20 },
+    ]
+}
+
+fn skin_face(skin: &image::RgbaImage, region: SkinRegion, mirror: bool) -> image::RgbaImage {
+    let (x, y, width, height) = region;
+    let face = image::imageops::crop_imm(skin, x, y, width, height).to_image();
+    if mirror {
+        image::imageops::flip_horizontal(&face)
+    } else {
+        face
+    }
+}
+
+/// Draws one side of the player flat onto a 16x32 canvas, at texture resolution.
+fn render_skin_side(skin: &image::RgbaImage, back: bool, slim: bool) -> image::RgbaImage {
+    let legacy = skin.height() == 32;
+    let mut canvas = image::RgbaImage::new(16, 32);
+
+    for part in preview_parts(back, slim, legacy) {
+        // The game draws the base layer fully opaque, whatever alpha the texture has.
+        let mut base = skin_face(skin, part.base, part.mirror);
+        for pixel in base.pixels_mut() {
+            pixel[3] = 255;
+        }
+        image::imageops::overlay(&mut canvas, &base, part.x as i64, part.y as i64);
+
+        if let Some(region) = part.overlay {
+            let overlay = skin_face(skin, region, part.mirror);
+            image::imageops::overlay(&mut canvas, &overlay, part.x as i64, part.y as i64);
+        }
+    }
+
+    canvas
+}
+
+/// Renders a flat preview of a skin for the wardrobe. `pose` is "front", "back" or "both"
+/// (front and back side by side). Returns the preview as base64 PNG data.
+#[tauri::command]
+pub async fn render_skin_preview(
+    skin_png_base64: String,
+    variant: String,
+    pose: String,
+) -> Result<String, String> {
+    validate_skin_variant(&variant)?;
+
+    let sides: &[bool] = match pose.as_str() {
+        "front" => &[false],
+        "back" => &[true],
+        "both" => &[false, true],
+        _ => return Err("Invalid pose. Must be 'front', 'back' or 'both'".to_string()),
+    };
+
+    let image_bytes = general_purpose::STANDARD
+        .decode(&skin_png_base64)
+        .map_err(|e| e.to_string())?;
+
+    validate_skin_png(&image_bytes)?;
+
+    let skin = image::load_from_memory(&image_bytes)
+        .map_err(|e| e.to_string())?
+        .to_rgba8();
+
+    let slim = variant == "slim";
+    let width = 16 * sides.len() as u32 + PREVIEW_GAP * (sides.len() as u32 - 1);
+    let mut preview = image::RgbaImage::new(width, 32);
+
+    for (i, back) in sides.iter().enumerate() {
+        let side = render_skin_side(&skin, *back, slim);
+        image::imageops::overlay(&mut preview, &side, (i as u32 * (16 + PREVIEW_GAP)) as i64, 0);
+    }
+
+    let scaled = image::imageops::resize(
+        &preview,
+        width * PREVIEW_SCALE,
+        32 * PREVIEW_SCALE,
+        image::imageops::FilterType::Nearest,
+    );
+
+    let mut png = std::io::Cursor::new(Vec::new());
+    scaled
+        .write_to(&mut png, image::ImageFormat::Png)
+        .map_err(|e| e.to_string())?;
+
+    Ok(general_purpose::STANDARD.encode(png.into_inner()))
+}
+
 #[tauri::command]
 pub async fn reset_skin(app_handle: tauri::AppHandle) -> Result<(), String> {
     let config = app_handle.state::<AppConfig>();
@@ -310,7 +710,7 @@ pub async fn reset_skin(app_handle: tauri::AppHandle) -> Result<(), String> {
         .await
         .map_err(|e| e.to_string())?;
     
-    let client = crate::utils::http::get_client();
+    let client = crate::utils::http::client_from(&app_handle);
 
     let response = client
         .delete(MINECRAFT_SKIN_RESET_URL)
@@ -328,19 +728,141 @@ pub async fn reset_skin(app_handle: tauri::AppHandle) -> Result<(), String> {
     Ok(())
 }
 
+/// Last known wardrobe of an account, so the wardrobe screen can be shown offline.
+#[derive(Serialize, Deserialize, Default)]
+struct WardrobeCache {
+    current_skin: Option<CurrentSkin>,
+    capes: Vec<CapeInfo>,
+}
+
+fn get_texture_cache_dir(account_uuid: &str) -> Result<PathBuf, String> {
+    crate::commands::validation::validate_uuid(account_uuid)?;
+
+    let cache_dir = crate::utils::get_launcher_dir()
+        .join("texture_cache")
+        .join(account_uuid);
+
+    if !cache_dir.exists() {
+        fs::create_dir_all(&cache_dir)
+            .map_err(|e| e.to_string())?;
+    }
+
+    Ok(cache_dir)
+}
+
+/// Texture URLs end in the texture's hash, which names the cached file.
+fn texture_hash(url: &str) -> Option<&str> {
+    let hash = url.rsplit('/').next()?;
+    (!hash.is_empty() && hash.chars().all(|c| c.is_ascii_alphanumeric())).then_some(hash)
+}
+
+fn load_wardrobe_cache(account_uuid: &str) -> WardrobeCache {
+    get_texture_cache_dir(account_uuid)
+        .ok()
+        .and_then(|dir| fs::read_to_string(dir.join("wardrobe.json")).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_wardrobe_cache(account_uuid: &str, cache: &WardrobeCache) -> Result<(), String> {
+    let json = serde_json::to_string_pretty(cache)
+        .map_err(|e| e.to_string())?;
+
+    fs::write(get_texture_cache_dir(account_uuid)?.join("wardrobe.json"), json)
+        .map_err(|e| e.to_string())
+}
+
+/// Downloads textures that aren't cached for the account yet. Failures are ignored, the
+/// online wardrobe doesn't depend on the cache.
+async fn cache_textures(client: &reqwest::Client, account_uuid: &str, urls: &[&str]) {
+    let Ok(cache_dir) = get_texture_cache_dir(account_uuid) else {
+        return;
+    };
+
+    for url in urls {
+        let Some(hash) = texture_hash(url) else {
+            continue;
+        };
+
+        let texture_path = cache_dir.join(format!("{}.png", hash));
+        if texture_path.exists() {
+            continue;
+        }
+
+        let Ok(response) = client.get(*url).send().await else {
+            continue;
+        };
+
+        if !response.status().is_success() {
+            continue;
+        }
+
+        if let Ok(bytes) = response.bytes().await {
+            if bytes.len() as u64 <= MAX_SKIN_BYTES {
+                let _ = fs::write(&texture_path, &bytes);
+            }
+        }
+    }
+}
+
+/// Returns the cached texture as a data URL the wardrobe can display without network access.
+fn cached_texture_url(account_uuid: &str, url: &str) -> Option<String> {
+    let texture_path = get_texture_cache_dir(account_uuid)
+        .ok()?
+        .join(format!("{}.png", texture_hash(url)?));
+
+    let bytes = fs::read(texture_path).ok()?;
+    Some(format!("data:image/png;base64,{}", general_purpose::STANDARD.encode(bytes)))
+}
+
 #[tauri::command]
 pub async fn get_current_skin(app_handle: tauri::AppHandle) -> Result<Option<CurrentSkin>, String> {
-    let config = app_handle.state::<AppConfig>();
-    
     let active_account = AccountManager::get_active_account()
         .map_err(|e| e.to_string())?
         .ok_or("No active account".to_string())?;
+
+    let account_uuid = active_account.uuid;
+
+    match fetch_current_skin(&app_handle, &account_uuid).await {
+        Ok(skin) => {
+            if let Some(skin) = &skin {
+                let urls: Vec<&str> = std::iter::once(skin.url.as_str())
+                    .chain(skin.cape_url.as_deref())
+                    .collect();
+                cache_textures(&crate::utils::http::client_from(&app_handle), &account_uuid, &urls).await;
+            }
+
+            let mut cache = load_wardrobe_cache(&account_uuid);
+            cache.current_skin = skin.clone();
+            let _ = save_wardrobe_cache(&account_uuid, &cache);
+
+            Ok(skin)
+        }
+        Err(e) => {
+            let cached = load_wardrobe_cache(&account_uuid)
+                .current_skin
+                .and_then(|skin| {
+                    Some(CurrentSkin {
+                        url: cached_texture_url(&account_uuid, &skin.url)?,
+                        variant: skin.variant,
+                        cape_url: skin.cape_url.and_then(|url| cached_texture_url(&account_uuid, &url)),
+                        offline: true,
+                    })
+                });
+
+            cached.map(Some).ok_or(e)
+        }
+    }
+}
+
+async fn fetch_current_skin(app_handle: &tauri::AppHandle, account_uuid: &str) -> Result<Option<CurrentSkin>, String> {
+    let config = app_handle.state::<AppConfig>();
     
-    let access_token = AccountManager::get_valid_token(&active_account.uuid, &config.microsoft_client_id)
+    let access_token = AccountManager::get_valid_token(account_uuid, &config.microsoft_client_id)
         .await
         .map_err(|e| e.to_string())?;
     
-    let client = crate::utils::http::get_client();
+    let client = crate::utils::http::client_from(app_handle);
     
     let response = client
         .get(MINECRAFT_PROFILE_URL)
@@ -367,6 +889,7 @@ pub async fn get_current_skin(app_handle: tauri::AppHandle) -> Result<Option<Cur
             url: active_skin.url.replace("http://", "https://"),
             variant: active_skin.variant.to_lowercase(),
             cape_url,
+            offline: false,
         }))
     } else {
         Ok(None)
@@ -375,17 +898,47 @@ pub async fn get_current_skin(app_handle: tauri::AppHandle) -> Result<Option<Cur
 
 #[tauri::command]
 pub async fn get_user_capes(app_handle: tauri::AppHandle) -> Result<UserCapesResponse, String> {
-    let config = app_handle.state::<AppConfig>();
-    
     let active_account = AccountManager::get_active_account()
         .map_err(|e| e.to_string())?
         .ok_or("No active account".to_string())?;
+
+    let account_uuid = active_account.uuid;
+
+    match fetch_user_capes(&app_handle, &account_uuid).await {
+        Ok(capes) => {
+            let urls: Vec<&str> = capes.iter().map(|c| c.url.as_str()).collect();
+            cache_textures(&crate::utils::http::client_from(&app_handle), &account_uuid, &urls).await;
+
+            let mut cache = load_wardrobe_cache(&account_uuid);
+            cache.capes = capes.clone();
+            let _ = save_wardrobe_cache(&account_uuid, &cache);
+
+            Ok(UserCapesResponse { capes, offline: false })
+        }
+        Err(e) => {
+            let cache = load_wardrobe_cache(&account_uuid);
+            if cache.capes.is_empty() {
+                return Err(e);
+            }
+
+            let capes = cache.capes.into_iter().map(|c| {
+                let url = cached_texture_url(&account_uuid, &c.url).unwrap_or(c.url);
+                CapeInfo { url, ..c }
+            }).collect();
+
+            Ok(UserCapesResponse { capes, offline: true })
+        }
+    }
+}
+
+async fn fetch_user_capes(app_handle: &tauri::AppHandle, account_uuid: &str) -> Result<Vec<CapeInfo>, String> {
+    let config = app_handle.state::<AppConfig>();
     
-    let access_token = AccountManager::get_valid_token(&active_account.uuid, &config.microsoft_client_id)
+    let access_token = AccountManager::get_valid_token(account_uuid, &config.microsoft_client_id)
         .await
         .map_err(|e| e.to_string())?;
     
-    let client = crate::utils::http::get_client();
+    let client = crate::utils::http::client_from(app_handle);
     
     let response = client
         .get(MINECRAFT_PROFILE_URL)
@@ -405,12 +958,10 @@ pub async fn get_user_capes(app_handle: tauri::AppHandle) -> Result<UserCapesRes
         .await
         .map_err(|e| e.to_string())?;
     
-    let capes = profile.capes.unwrap_or_default().into_iter().map(|c| CapeInfo {
+    Ok(profile.capes.unwrap_or_default().into_iter().map(|c| CapeInfo {
         url: c.url.replace("http://", "https://"),
         ..c
-    }).collect();
-    
-    Ok(UserCapesResponse { capes })
+    }).collect())
 }
 
 async fn get_player_cape(uuid: &str) -> Result<String, String> {
@@ -469,7 +1020,7 @@ pub async fn equip_cape(cape_id: String, app_handle: tauri::AppHandle) -> Result
         .await
         .map_err(|e| e.to_string())?;
     
-    let client = crate::utils::http::get_client();
+    let client = crate::utils::http::client_from(&app_handle);
     
     let url = "https://api.minecraftservices.com/minecraft/profile/capes/active";
     
@@ -506,7 +1057,7 @@ pub async fn remove_cape(app_handle: tauri::AppHandle) -> Result<(), String> {
         .await
         .map_err(|e| e.to_string())?;
     
-    let client = crate::utils::http::get_client();
+    let client = crate::utils::http::client_from(&app_handle);
     
     let url = "https://api.minecraftservices.com/minecraft/profile/capes/active";
     
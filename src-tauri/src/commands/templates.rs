@@ -0,0 +1,116 @@
+use crate::commands::validation::sanitize_instance_name;
+use crate::models::{Instance, InstanceTemplate, KeybindConflict, LauncherSettings};
+use crate::services::instance::InstanceManager;
+use crate::services::settings::SettingsManager;
+use crate::services::templates::TemplateManager;
+use crate::utils::get_instance_dir;
+
+#[tauri::command]
+pub async fn get_templates() -> Result<Vec<InstanceTemplate>, String> {
+    TemplateManager::get_all().map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn save_template(mut template: InstanceTemplate) -> Result<(), String> {
+    TemplateManager::validate(&mut template).map_err(|e| e.to_string())?;
+    TemplateManager::save(&template).map_err(|e| e.to_string())
+}
+
+/// Deletes the template and clears it as the default template if it was one.
+#[tauri::command]
+pub async fn delete_template(template_id: String) -> Result<(), String> {
+    TemplateManager::delete(&template_id).map_err(|e| e.to_string())?;
+
+    crate::services::settings::SettingsManager::update(|settings| {
+        if settings.default_template_id.as_deref() == Some(template_id.as_str()) {
+            settings.default_template_id = None;
+        }
+        Ok(())
+    })
+    .map_err(|e| e.to_string())
+}
+
+/// Captures the instance's settings override and the template-relevant parts of its options.txt.
+#[tauri::command]
+pub async fn create_template_from_instance(instance_name: String, name: String) -> Result<InstanceTemplate, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    if name.trim().is_empty() {
+        return Err("Template name cannot be empty".to_string());
+    }
+
+    let instance_dir = get_instance_dir(&safe_name);
+    let content = std::fs::read_to_string(instance_dir.join("instance.json"))
+        .map_err(|_| format!("Instance '{}' does not exist", safe_name))?;
+    let mut instance: Instance = serde_json::from_str(&content).map_err(|e| e.to_string())?;
+
+    let global = SettingsManager::load().unwrap_or_default();
+    InstanceManager::migrate_launch_options(&mut instance, &global);
+
+    // Only the launch fields are used when the template is applied
+    let launcher_settings = instance.launch_options.map(|options| {
+        let effective = options.apply(global);
+        LauncherSettings {
+            memory_mb: effective.memory_mb,
+            java_path: effective.java_path,
+            jvm_args: effective.jvm_args,
+            env_vars: effective.env_vars,
+            window_width: effective.window_width,
+            window_height: effective.window_height,
+            ..LauncherSettings::default()
+        }
+    });
+
+    let minecraft_options = std::fs::read_to_string(instance_dir.join("options.txt"))
+        .ok()
+        .map(|content| TemplateManager::parse_options_txt(&content));
+
+    let template = InstanceTemplate {
+        id: chrono::Utc::now().timestamp_millis().to_string(),
+        name: name.trim().to_string(),
+        description: None,
+        created_at: chrono::Utc::now().to_rfc3339(),
+        launcher_settings,
+        minecraft_options,
+        icon: None,
+    };
+
+    TemplateManager::save(&template).map_err(|e| e.to_string())?;
+    Ok(template)
+}
+
+/// Applies the template and returns keys it left bound to more than one action.
+#[tauri::command]
+pub async fn apply_template(template_id: String, instance_name: String) -> Result<Vec<KeybindConflict>, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+
+    if crate::commands::instances::is_running(&safe_name)? {
+        return Err("Close the game before applying a template".to_string());
+    }
+
+    let template = TemplateManager::get(&template_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Template '{}' does not exist", template_id))?;
+
+    TemplateManager::apply_to_instance(&template, &safe_name).map_err(|e| e.to_string())
+}
+
+/// Writes the template to a shareable `.octemplate` file.
+#[tauri::command]
+pub async fn export_template(template_id: String, output_path: String) -> Result<(), String> {
+    let template = TemplateManager::get(&template_id)
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("Template '{}' does not exist", template_id))?;
+
+    TemplateManager::export_to(&template, std::path::Path::new(&output_path))
+        .map_err(|e| format!("Failed to export template: {}", e))
+}
+
+/// Imports an `.octemplate` file as a new template and returns it.
+#[tauri::command]
+pub async fn import_template(file_path: String) -> Result<InstanceTemplate, String> {
+    let template = TemplateManager::import_from(std::path::Path::new(&file_path))
+        .map_err(|e| format!("Failed to import template: {}", e))?;
+
+    TemplateManager::save(&template).map_err(|e| e.to_string())?;
+    Ok(template)
+}
@@ -1,5 +1,6 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
-use crate::models::DetectedJava;
+use crate::models::{AutomationSettings, DetectedJava};
 
 /// Validate  Minecraft/Microsoft account UUID
 pub fn validate_uuid(uuid: &str) -> Result<(), String> {
@@ -30,6 +31,81 @@ pub fn sanitize_instance_name(name: &str) -> Result<String, String> {
     Ok(name.to_string())
 }
 
+/// Validate an instance display name; it's only shown, so beyond length and control
+/// characters anything goes.
+pub fn sanitize_display_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+
+    if name.is_empty() {
+        return Err("Instance name cannot be empty".to_string());
+    }
+
+    if name.chars().count() > 100 {
+        return Err("Instance name is too long (max 100 characters)".to_string());
+    }
+
+    if name.chars().any(char::is_control) {
+        return Err("Instance name contains invalid characters".to_string());
+    }
+
+    Ok(name.to_string())
+}
+
+const RESERVED_WINDOWS_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL",
+    "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9",
+    "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Folder name for a new instance called `display_name`. Characters that some file systems
+/// don't allow are replaced, so any valid display name maps to a usable folder.
+pub fn instance_folder_name(display_name: &str) -> Result<String, String> {
+    let display_name = sanitize_display_name(display_name)?;
+
+    let mut folder: String = display_name
+        .chars()
+        .map(|c| if matches!(c, '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*') { '_' } else { c })
+        .take(64)
+        .collect();
+
+    while folder.contains("..") {
+        folder = folder.replace("..", ".");
+    }
+
+    // Windows drops trailing dots and spaces from folder names
+    let mut folder = folder.trim_start_matches('.').trim_end_matches(['.', ' ']).trim().to_string();
+    if folder.is_empty() {
+        folder = "instance".to_string();
+    }
+
+    // `CON` is reserved with any extension too, so the suffix goes on the stem
+    let stem_len = folder.find('.').unwrap_or(folder.len());
+    if RESERVED_WINDOWS_NAMES.contains(&folder[..stem_len].trim_end().to_ascii_uppercase().as_str()) {
+        folder.insert(stem_len, '_');
+    }
+
+    sanitize_instance_name(&folder)
+}
+
+/// Profile names become folder names under `profiles`.
+pub fn sanitize_profile_name(name: &str) -> Result<String, String> {
+    let name = name.trim();
+
+    if name.is_empty() {
+        return Err("Profile name cannot be empty".to_string());
+    }
+
+    if name.len() > 32 {
+        return Err("Profile name is too long".to_string());
+    }
+
+    if !name.chars().all(|c| c.is_alphanumeric() || c == ' ' || c == '-' || c == '_') {
+        return Err("Profile name contains invalid characters".to_string());
+    }
+
+    Ok(name.to_string())
+}
+
 /// Sanitize mod filenames (only .jar files)
 pub fn sanitize_mod_filename(filename: &str) -> Result<String, String> {
     if filename.is_empty() {
@@ -76,8 +152,8 @@ pub fn sanitize_filename(filename: &str) -> Result<String, String> {
     Ok(filename.to_string())
 }
 
-/// Sanitize resource pack filenames (allow .zip and .jar files)
-pub fn sanitize_resourcepack_filename(filename: &str) -> Result<String, String> {
+/// Sanitize resource pack names, which may also be folders
+pub fn sanitize_resourcepack_name(filename: &str) -> Result<String, String> {
     if filename.is_empty() {
         return Err("Filename cannot be empty".to_string());
     }
@@ -94,6 +170,13 @@ pub fn sanitize_resourcepack_filename(filename: &str) -> Result<String, String>
         return Err("Filename contains null bytes".to_string());
     }
 
+    Ok(filename.to_string())
+}
+
+/// Sanitize resource pack filenames (allow .zip and .jar files)
+pub fn sanitize_resourcepack_filename(filename: &str) -> Result<String, String> {
+    sanitize_resourcepack_name(filename)?;
+
     let lower = filename.to_lowercase();
     if !lower.ends_with(".zip") && !lower.ends_with(".jar") {
         return Err("Only .zip or .jar files are allowed for resource packs".to_string());
@@ -220,6 +303,8 @@ pub fn get_java_info(path: &str) -> Result<DetectedJava, String> {
     let mut java_version = String::new();
     let mut full_version = String::new();
     let mut architecture = String::new();
+    let mut vendor = String::new();
+    let mut data_model = String::new();
 
     for line in combined.lines() {
         let line = line.trim();
@@ -237,6 +322,10 @@ pub fn get_java_info(path: &str) -> Result<DetectedJava, String> {
             if architecture.is_empty() {
                 architecture = val.to_string();
             }
+        } else if let Some(val) = line.strip_prefix("java.vendor = ") {
+            vendor = val.to_string();
+        } else if let Some(val) = line.strip_prefix("sun.arch.data.model = ") {
+            data_model = val.to_string();
         }
     }
 
@@ -244,14 +333,37 @@ pub fn get_java_info(path: &str) -> Result<DetectedJava, String> {
         format!("Could not parse Java major version from: {}", full_version)
     })?;
 
+    // sun.arch.data.model is missing on some non-HotSpot VMs, fall back to the arch name
+    let is_64bit = match data_model.as_str() {
+        "64" => true,
+        "32" => false,
+        _ => architecture.contains("64"),
+    };
+
     Ok(DetectedJava {
         major_version,
         full_version,
         architecture,
         path: path_buf.to_string_lossy().to_string(),
+        vendor,
+        is_64bit,
     })
 }
 
+/// 32-bit JVMs cannot reserve much more than ~1.5GB of heap
+const MAX_32BIT_HEAP_MB: u32 = 1536;
+
+/// Returns a warning when the memory allocation cannot work with the selected JVM.
+pub fn java_memory_warning(java: &DetectedJava, memory_mb: u32) -> Option<String> {
+    if !java.is_64bit && memory_mb > MAX_32BIT_HEAP_MB {
+        return Some(format!(
+            "{} is a 32-bit Java and cannot use {} MB of memory (max ~{} MB). Install a 64-bit Java or lower the allocation.",
+            java.path, memory_mb, MAX_32BIT_HEAP_MB
+        ));
+    }
+    None
+}
+
 fn parse_major_version_str(version_str: &str) -> String {
     let parts: Vec<&str> = version_str.split('.').collect();
     if parts.is_empty() {
@@ -266,17 +378,28 @@ fn parse_major_version_str(version_str: &str) -> String {
 
 /// Validate download URL is from trusted sources
 pub fn validate_download_url(url: &str) -> Result<url::Url, String> {
+    let settings = crate::services::settings::SettingsManager::load().unwrap_or_default();
+    validate_download_url_with(url, &settings.trusted_download_hosts, settings.allow_any_https_host)
+}
+
+fn validate_download_url_with(
+    url: &str,
+    allowed_hosts: &[String],
+    allow_any_https_host: bool,
+) -> Result<url::Url, String> {
     let parsed_url = url::Url::parse(url).map_err(|_| "Invalid URL format".to_string())?;
 
     if parsed_url.scheme() != "https" {
         return Err("Only HTTPS URLs are allowed".to_string());
     }
 
-    let allowed_hosts = ["cdn.modrinth.com", "github.com", "raw.githubusercontent.com", "edge.forgecdn.net"];
-
     let host = parsed_url.host_str().ok_or("URL has no host")?;
 
-    if !allowed_hosts.contains(&host) {
+    if allow_any_https_host {
+        return Ok(parsed_url);
+    }
+
+    if !allowed_hosts.iter().any(|h| h.eq_ignore_ascii_case(host)) {
         return Err(format!(
             "Downloads only allowed from: {}",
             allowed_hosts.join(", ")
@@ -286,6 +409,260 @@ pub fn validate_download_url(url: &str) -> Result<url::Url, String> {
     Ok(parsed_url)
 }
 
+/// Validate trusted download host entries (bare hostnames only)
+pub fn validate_trusted_hosts(hosts: &[String]) -> Result<(), String> {
+    for host in hosts {
+        if host.is_empty() || host.len() > 253 {
+            return Err("Trusted host cannot be empty or longer than 253 characters".to_string());
+        }
+
+        if !host.chars().all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-') {
+            return Err(format!("Invalid trusted host '{}'. Use a bare hostname like cdn.modrinth.com", host));
+        }
+    }
+
+    Ok(())
+}
+
+/// Environment variables instances may not override: credentials the launcher passes itself
+/// and loader hooks that would inject code into the game process.
+pub const BLOCKED_ENV_VARS: &[&str] = &[
+    "MINECRAFT_ACCESS_TOKEN",
+    "JAVA_TOOL_OPTIONS",
+    "_JAVA_OPTIONS",
+    "JDK_JAVA_OPTIONS",
+    "LD_PRELOAD",
+    "LD_LIBRARY_PATH",
+    "DYLD_INSERT_LIBRARIES",
+];
+
+pub fn is_blocked_env_var(key: &str) -> bool {
+    BLOCKED_ENV_VARS.iter().any(|blocked| blocked.eq_ignore_ascii_case(key))
+}
+
+/// Validate per-instance environment variable names and values
+pub fn validate_env_vars(env_vars: &HashMap<String, String>) -> Result<(), String> {
+    for (key, value) in env_vars {
+        if key.is_empty() || key.len() > 128 {
+            return Err("Environment variable names must be 1-128 characters".to_string());
+        }
+
+        if key.chars().next().is_some_and(|c| c.is_ascii_digit())
+            || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            return Err(format!(
+                "Invalid environment variable name '{}'. Use letters, digits and underscores",
+                key
+            ));
+        }
+
+        if is_blocked_env_var(key) {
+            return Err(format!("Environment variable '{}' cannot be set per instance", key));
+        }
+
+        if value.contains('\0') {
+            return Err(format!("Environment variable '{}' contains a null byte", key));
+        }
+    }
+
+    Ok(())
+}
+
+/// JVM flags that would load foreign code, run commands, read further options from a file
+/// or replace the launcher's classpath.
+const BLOCKED_JVM_ARG_PREFIXES: &[&str] = &[
+    "-javaagent",
+    "-agentpath",
+    "-agentlib",
+    "-Xbootclasspath",
+    "-cp",
+    "-classpath",
+    "--class-path",
+    "-Djava.library.path",
+    "-XX:OnError",
+    "-XX:OnOutOfMemoryError",
+    "-XX:VMOptionsFile",
+    "-XX:Flags",
+    "-XX:CompileCommandFile",
+    "-XX:+UnlockDiagnosticVMOptions",
+];
+
+/// Validate custom JVM arguments
+pub fn validate_jvm_args(args: &[String]) -> Result<(), String> {
+    if args.len() > 64 {
+        return Err("Too many JVM arguments (max 64)".to_string());
+    }
+
+    for arg in args {
+        if arg.is_empty() || arg.len() > 1024 {
+            return Err("JVM arguments must be 1-1024 characters".to_string());
+        }
+
+        if arg.contains('\0') || arg.contains('\n') || arg.contains('\r') {
+            return Err(format!("JVM argument '{}' contains invalid characters", arg));
+        }
+
+        if !arg.starts_with('-') {
+            return Err(format!("JVM argument '{}' must start with '-'", arg));
+        }
+
+        let is_blocked = BLOCKED_JVM_ARG_PREFIXES.iter().any(|blocked| {
+            arg.strip_prefix(blocked)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with(':') || rest.starts_with('='))
+        });
+        if is_blocked {
+            return Err(format!("JVM argument '{}' is not allowed", arg));
+        }
+    }
+
+    Ok(())
+}
+
+/// `-XX:+<flag>`/`-XX:-<flag>` switches imported templates may carry: garbage collector and
+/// memory tuning.
+const IMPORTED_JVM_SWITCHES: &[&str] = &[
+    "UseG1GC",
+    "UseZGC",
+    "ZGenerational",
+    "UseShenandoahGC",
+    "UseParallelGC",
+    "UseSerialGC",
+    "UnlockExperimentalVMOptions",
+    "ParallelRefProcEnabled",
+    "DisableExplicitGC",
+    "AlwaysPreTouch",
+    "PerfDisableSharedMem",
+    "UseStringDeduplication",
+    "UseCompressedOops",
+    "UseLargePages",
+    "UseTransparentHugePages",
+    "UseNUMA",
+    "AlwaysActAsServerClassMachine",
+];
+
+/// `-XX:<option>=<number>` options imported templates may carry.
+const IMPORTED_JVM_NUMERIC_OPTIONS: &[&str] = &[
+    "MaxGCPauseMillis",
+    "G1NewSizePercent",
+    "G1MaxNewSizePercent",
+    "G1HeapRegionSize",
+    "G1ReservePercent",
+    "G1HeapWastePercent",
+    "G1MixedGCCountTarget",
+    "G1MixedGCLiveThresholdPercent",
+    "G1RSetUpdatingPauseTimePercent",
+    "InitiatingHeapOccupancyPercent",
+    "SurvivorRatio",
+    "MaxTenuringThreshold",
+    "ParallelGCThreads",
+    "ConcGCThreads",
+    "ReservedCodeCacheSize",
+    "MaxMetaspaceSize",
+    "MetaspaceSize",
+    "SoftMaxHeapSize",
+];
+
+/// `-D<key>=<value>` properties imported templates may set.
+const IMPORTED_JVM_PROPERTIES: &[&str] = &[
+    "file.encoding",
+    "stdout.encoding",
+    "stderr.encoding",
+    "sun.stdout.encoding",
+    "sun.stderr.encoding",
+    "java.net.preferIPv4Stack",
+    "log4j2.formatMsgNoLookups",
+    "fml.ignoreInvalidMinecraftCertificates",
+    "fml.ignorePatchDiscrepancies",
+    "user.language",
+    "user.country",
+];
+
+/// A size or count such as `512`, `8M` or `4g`.
+fn is_jvm_size(value: &str) -> bool {
+    let digits = value.strip_suffix(|c: char| matches!(c, 'k' | 'K' | 'm' | 'M' | 'g' | 'G')).unwrap_or(value);
+    !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Validate JVM arguments from a shared file, which only may tune memory, the garbage
+/// collector and a few harmless properties.
+pub fn validate_imported_jvm_args(args: &[String]) -> Result<(), String> {
+    validate_jvm_args(args)?;
+
+    for arg in args {
+        let allowed = if let Some(size) = ["-Xms", "-Xmx", "-Xmn", "-Xss"].iter().find_map(|flag| arg.strip_prefix(flag)) {
+            is_jvm_size(size)
+        } else if let Some(option) = arg.strip_prefix("-XX:") {
+            match option.split_once('=') {
+                Some((name, value)) => IMPORTED_JVM_NUMERIC_OPTIONS.contains(&name) && is_jvm_size(value),
+                None => option
+                    .strip_prefix(['+', '-'])
+                    .is_some_and(|name| IMPORTED_JVM_SWITCHES.contains(&name)),
+            }
+        } else if let Some(property) = arg.strip_prefix("-D") {
+            property.split_once('=').is_some_and(|(key, value)| {
+                IMPORTED_JVM_PROPERTIES.contains(&key)
+                    && value.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '_'))
+            })
+        } else {
+            false
+        };
+
+        if !allowed {
+            return Err(format!("JVM argument '{}' is not allowed in imported files", arg));
+        }
+    }
+
+    Ok(())
+}
+
+/// Validate a custom window size; width and height go together
+pub fn validate_window_size(width: Option<u32>, height: Option<u32>) -> Result<(), String> {
+    match (width, height) {
+        (None, None) => Ok(()),
+        (Some(w), Some(h)) if (320..=7680).contains(&w) && (240..=4320).contains(&h) => Ok(()),
+        (Some(_), Some(_)) => Err("Window size must be between 320x240 and 7680x4320".to_string()),
+        _ => Err("Set both window width and height, or neither".to_string()),
+    }
+}
+
+/// Validate the automation webhook URL and socket address, set or not
+pub fn validate_automation(automation: &AutomationSettings) -> Result<(), String> {
+    if let Some(webhook_url) = automation.webhook_url.as_deref().filter(|u| !u.trim().is_empty()) {
+        let url = url::Url::parse(webhook_url.trim()).map_err(|_| "Invalid webhook URL".to_string())?;
+        if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+            return Err("Webhook URL must be an http:// or https:// URL".to_string());
+        }
+    }
+
+    if let Some(address) = automation.socket_address.as_deref().filter(|a| !a.trim().is_empty()) {
+        crate::services::automation::SocketTarget::parse(address)?;
+    }
+
+    Ok(())
+}
+
+/// Validate a launch wrapper command such as `gamemoderun` or `prime-run --flag`
+pub fn validate_launch_wrapper(wrapper: &str) -> Result<(), String> {
+    if wrapper.trim().is_empty() || wrapper.len() > 256 {
+        return Err("Wrapper command must be 1-256 characters".to_string());
+    }
+
+    if wrapper.chars().any(|c| c.is_control()) {
+        return Err("Wrapper command contains invalid characters".to_string());
+    }
+
+    Ok(())
+}
+
+/// Validate a `#rrggbb` color, returned lowercased
+pub fn validate_hex_color(color: &str) -> Result<String, String> {
+    let color = color.trim();
+    match color.strip_prefix('#') {
+        Some(hex) if hex.len() == 6 && hex.chars().all(|c| c.is_ascii_hexdigit()) => Ok(color.to_ascii_lowercase()),
+        _ => Err("Color must be in #rrggbb format".to_string()),
+    }
+}
+
 /// Validate memory allocation against system memory
 pub fn validate_memory_allocation(memory_mb: u64) -> Result<(), String> {
     use sysinfo::System;
@@ -2,8 +2,46 @@ use crate::services::installer::MinecraftInstaller;
 use crate::services::fabric::FabricInstaller;
 use crate::services::neoforge::NeoForgeInstaller;
 use crate::services::forge::ForgeInstaller;
-use crate::models::{FabricLoaderVersion, NeoForgeVersion, ForgeVersion};
-use crate::utils::get_meta_dir;
+use crate::models::{FabricLoaderVersion, NeoForgeVersion, ForgeVersion, QuiltLoaderVersion};
+use crate::utils::{get_launcher_dir, get_meta_dir, write_json_atomic};
+use serde::Deserialize;
+use std::path::PathBuf;
+use std::time::Duration;
+
+const PATCH_NOTES_BASE_URL: &str = "https://launchercontent.mojang.com/v2";
+/// The list of patch notes is refetched after this; the notes themselves never change.
+const PATCH_NOTES_INDEX_TTL: Duration = Duration::from_secs(6 * 60 * 60);
+
+#[derive(Deserialize)]
+struct PatchNotesIndex {
+    entries: Vec<PatchNotesEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PatchNotesEntry {
+    title: String,
+    version: String,
+    #[serde(default)]
+    r#type: String,
+    #[serde(default)]
+    date: Option<String>,
+    #[serde(default)]
+    short_text: Option<String>,
+    #[serde(default)]
+    image: Option<PatchNotesImage>,
+    content_path: String,
+}
+
+#[derive(Deserialize)]
+struct PatchNotesImage {
+    url: String,
+}
+
+#[derive(Deserialize)]
+struct PatchNotesContent {
+    body: String,
+}
 
 #[tauri::command]
 pub async fn get_minecraft_versions() -> Result<Vec<String>, String> {
@@ -25,6 +63,228 @@ pub async fn get_minecraft_versions_with_metadata() -> Result<Vec<crate::models:
         .map_err(|e| format!("Failed to fetch versions: {}", e))
 }
 
+fn changelog_cache_dir() -> PathBuf {
+    get_launcher_dir().join("changelog_cache")
+}
+
+/// Fetches a JSON document from the launcher content API.
+async fn fetch_patch_notes_json(path: &str) -> Result<String, String> {
+    let response = crate::utils::http::get_client()
+        .get(format!("{}/{}", PATCH_NOTES_BASE_URL, path.trim_start_matches('/')))
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if !response.status().is_success() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+
+    response.text().await.map_err(|e| e.to_string())
+}
+
+/// The patch notes index, from the cache while it is fresh. A stale cache is used when the
+/// index can't be fetched.
+async fn patch_notes_index() -> Result<PatchNotesIndex, String> {
+    let index_path = changelog_cache_dir().join("index.json");
+
+    let is_fresh = std::fs::metadata(&index_path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age < PATCH_NOTES_INDEX_TTL);
+
+    let cached = std::fs::read_to_string(&index_path).ok();
+    if let (true, Some(content)) = (is_fresh, &cached) {
+        if let Ok(index) = serde_json::from_str(content) {
+            return Ok(index);
+        }
+    }
+
+    match fetch_patch_notes_json("javaPatchNotes.json").await {
+        Ok(content) => {
+            let index = serde_json::from_str(&content)
+                .map_err(|e| format!("Invalid patch notes index: {}", e))?;
+            std::fs::create_dir_all(changelog_cache_dir()).map_err(|e| e.to_string())?;
+            let _ = crate::utils::write_file_atomic(&index_path, content);
+            Ok(index)
+        }
+        Err(e) => cached
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .ok_or_else(|| format!("Failed to fetch patch notes: {}", e)),
+    }
+}
+
+/// Official patch notes of a version, cached under the launcher directory. Fails for
+/// versions Mojang published no notes for.
+#[tauri::command]
+pub async fn get_minecraft_changelog(version: String) -> Result<crate::models::MinecraftChangelog, String> {
+    if version.is_empty() || !version.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '-' || c == '_') {
+        return Err("Invalid version format".to_string());
+    }
+
+    let cache_path = changelog_cache_dir().join(format!("{}.json", version));
+    if let Ok(content) = std::fs::read_to_string(&cache_path) {
+        if let Ok(changelog) = serde_json::from_str(&content) {
+            return Ok(changelog);
+        }
+    }
+
+    let index = patch_notes_index().await?;
+    let entry = index.entries
+        .into_iter()
+        .find(|e| e.version == version)
+        .ok_or_else(|| format!("No patch notes found for {}", version))?;
+
+    let content: PatchNotesContent = serde_json::from_str(&fetch_patch_notes_json(&entry.content_path).await?)
+        .map_err(|e| format!("Invalid patch notes: {}", e))?;
+
+    let changelog = crate::models::MinecraftChangelog {
+        version: entry.version,
+        title: entry.title,
+        r#type: entry.r#type,
+        date: entry.date,
+        short_text: entry.short_text,
+        image_url: entry.image.map(|image| {
+            if image.url.starts_with("https://") {
+                image.url
+            } else if image.url.starts_with('/') {
+                format!("https://launchercontent.mojang.com{}", image.url)
+            } else {
+                format!("{}/{}", PATCH_NOTES_BASE_URL, image.url)
+            }
+        }),
+        body: content.body,
+    };
+
+    std::fs::create_dir_all(changelog_cache_dir()).map_err(|e| e.to_string())?;
+    let _ = write_json_atomic(&cache_path, &changelog);
+
+    Ok(changelog)
+}
+
+/// Reads `id`, `type` and `inheritsFrom` of every installed version json.
+fn read_installed_versions() -> Vec<(String, Option<String>, Option<String>)> {
+    let versions_dir = get_meta_dir().join("versions");
+    let Ok(entries) = std::fs::read_dir(&versions_dir) else {
+        return Vec::new();
+    };
+
+    entries
+        .flatten()
+        .filter(|e| e.path().is_dir())
+        .filter_map(|e| {
+            let id = e.file_name().to_string_lossy().to_string();
+            let content = std::fs::read_to_string(e.path().join(format!("{}.json", id))).ok()?;
+            let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+            let version_type = json.get("type").and_then(|v| v.as_str()).map(String::from);
+            let inherits_from = json.get("inheritsFrom").and_then(|v| v.as_str()).map(String::from);
+            Some((id, version_type, inherits_from))
+        })
+        .collect()
+}
+
+/// Version id -> names of the instances in every profile that launch it, directly or through
+/// a loader version inheriting from it.
+fn version_users(installed: &[(String, Option<String>, Option<String>)]) -> std::collections::HashMap<String, Vec<String>> {
+    let mut users: std::collections::HashMap<String, Vec<String>> = std::collections::HashMap::new();
+
+    for instances_dir in crate::services::profiles::ProfileManager::all_instances_dirs() {
+        let Ok(entries) = std::fs::read_dir(&instances_dir) else {
+            continue;
+        };
+
+        for entry in entries.flatten() {
+            let Ok(content) = std::fs::read_to_string(entry.path().join("instance.json")) else {
+                continue;
+            };
+            let Ok(instance) = serde_json::from_str::<crate::models::Instance>(&content) else {
+                continue;
+            };
+
+            let mut version = Some(instance.version.clone());
+            while let Some(id) = version {
+                let names = users.entry(id.clone()).or_default();
+                if names.contains(&instance.name) {
+                    break;
+                }
+                names.push(instance.name.clone());
+
+                version = installed.iter()
+                    .find(|(installed_id, _, _)| *installed_id == id)
+                    .and_then(|(_, _, inherits_from)| inherits_from.clone());
+            }
+        }
+    }
+
+    users
+}
+
+#[tauri::command]
+pub async fn get_installed_versions() -> Result<Vec<crate::models::InstalledVersion>, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let installed = read_installed_versions();
+        let mut users = version_users(&installed);
+        let versions_dir = get_meta_dir().join("versions");
+
+        let mut versions: Vec<crate::models::InstalledVersion> = installed
+            .into_iter()
+            .map(|(id, version_type, inherits_from)| {
+                let mut used_by = users.remove(&id).unwrap_or_default();
+                used_by.sort();
+
+                crate::models::InstalledVersion {
+                    loader: crate::services::instance::InstanceManager::loader_from_version_id(&id).0,
+                    size_bytes: crate::commands::settings::dir_size(&versions_dir.join(&id)),
+                    r#type: version_type,
+                    inherits_from,
+                    used_by,
+                    id,
+                }
+            })
+            .collect();
+
+        versions.sort_by(|a, b| a.id.cmp(&b.id));
+        versions
+    })
+    .await
+    .map_err(|e| e.to_string())
+}
+
+/// Deletes an installed version. Refused while an instance launches it or another installed
+/// version builds on it. Shared libraries are kept, assets no other version uses are swept
+/// afterwards when asset cleanup is set to run after changes.
+#[tauri::command]
+pub async fn uninstall_version(version_id: String, app_handle: tauri::AppHandle) -> Result<(), String> {
+    if version_id.is_empty() || !version_id.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '-' || c == '_' || c == '+') {
+        return Err("Invalid version format".to_string());
+    }
+
+    let version_dir = get_meta_dir().join("versions").join(&version_id);
+    if !version_dir.is_dir() {
+        return Err(format!("Version '{}' is not installed", version_id));
+    }
+
+    let installed = read_installed_versions();
+
+    if let Some(users) = version_users(&installed).get(&version_id) {
+        return Err(format!("Version '{}' is used by: {}", version_id, users.join(", ")));
+    }
+
+    let dependents: Vec<&str> = installed.iter()
+        .filter(|(_, _, inherits_from)| inherits_from.as_deref() == Some(version_id.as_str()))
+        .map(|(id, _, _)| id.as_str())
+        .collect();
+    if !dependents.is_empty() {
+        return Err(format!("Uninstall {} first, they build on '{}'", dependents.join(", "), version_id));
+    }
+
+    std::fs::remove_dir_all(&version_dir)
+        .map_err(|e| format!("Failed to remove version '{}': {}", version_id, e))?;
+
+    crate::services::asset_cleanup::schedule_after_change(&app_handle);
+    Ok(())
+}
+
 #[tauri::command]
 pub async fn get_minecraft_versions_by_type(version_type: String) -> Result<Vec<String>, String> {
     let valid_types = ["release", "snapshot"];
@@ -60,15 +320,17 @@ pub async fn get_neoforge_supported_game_versions() -> Result<Vec<String>, Strin
         .map_err(|e| format!("Failed to fetch NeoForge supported versions: {}", e))
 }
 
+/// `force_verify` re-checks every asset object even if the version's asset index is already installed.
 #[tauri::command]
-pub async fn install_minecraft(version: String) -> Result<String, String> {
+pub async fn install_minecraft(version: String, force_verify: Option<bool>) -> Result<String, String> {
     if !version.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '-') {
         return Err("Invalid version format".to_string());
     }
     
     let meta_dir = get_meta_dir();
     let installer = MinecraftInstaller::new(meta_dir)
-        .map_err(|e| e.to_string())?;
+        .map_err(|e| e.to_string())?
+        .with_force_verify(force_verify.unwrap_or(false));
 
     installer
         .install_version(&version)
@@ -100,6 +362,16 @@ pub async fn get_fabric_versions() -> Result<Vec<FabricLoaderVersion>, String> {
         .map_err(|e| format!("Failed to fetch Fabric versions: {}", e))
 }
 
+#[tauri::command]
+pub async fn get_quilt_versions() -> Result<Vec<QuiltLoaderVersion>, String> {
+    let installer = crate::services::quilt::QuiltInstaller::new(get_meta_dir())
+        .map_err(|e| e.to_string())?;
+    installer
+        .get_loader_versions()
+        .await
+        .map_err(|e| format!("Failed to fetch Quilt versions: {}", e))
+}
+
 #[tauri::command]
 pub async fn get_neoforge_versions() -> Result<Vec<NeoForgeVersion>, String> {
     let installer = NeoForgeInstaller::new(get_meta_dir())
@@ -0,0 +1,505 @@
+use crate::commands::validation::sanitize_instance_name;
+use crate::utils::get_instance_dir;
+use fastnbt::Value as NbtValue;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Serialize, Clone, Default)]
+pub struct PlayerWorldStats {
+    pub uuid: String,
+    pub play_time_ticks: u64,
+    pub blocks_mined: u64,
+    pub deaths: u64,
+    pub mob_kills: u64,
+    pub player_kills: u64,
+    pub jumps: u64,
+    /// Distance travelled by any means, in centimetres.
+    pub distance_cm: u64,
+    pub advancements_done: usize,
+    /// Advancements the player has made progress on; the game only records those.
+    pub advancements_tracked: usize,
+    pub completed_advancements: Vec<String>,
+}
+
+fn get_world_dir(instance_name: &str, world: &str) -> Result<PathBuf, String> {
+    let safe_name = sanitize_instance_name(instance_name)?;
+
+    if world.is_empty() || world.contains("..") || world.contains('/') || world.contains('\\') {
+        return Err("Invalid folder name".to_string());
+    }
+
+    let world_dir = get_instance_dir(&safe_name).join("saves").join(world);
+
+    if !world_dir.is_dir() {
+        return Err(format!("World folder '{}' does not exist", world));
+    }
+
+    Ok(world_dir)
+}
+
+fn read_json_object(path: &Path) -> Option<Map<String, Value>> {
+    let content = fs::read_to_string(path).ok()?;
+    match serde_json::from_str(&content).ok()? {
+        Value::Object(map) => Some(map),
+        _ => None,
+    }
+}
+
+fn sum_values<'a>(values: impl Iterator<Item = &'a Value>) -> u64 {
+    values.filter_map(Value::as_u64).sum()
+}
+
+/// Reads `stats/<uuid>.json`, in the nested format of 1.13+ or the flat `stat.*` keys before it.
+fn apply_stats(stats: &mut PlayerWorldStats, file: &Map<String, Value>) {
+    if let Some(Value::Object(categories)) = file.get("stats") {
+        let category = |name: &str| categories.get(name).and_then(Value::as_object);
+        let custom = |name: &str| {
+            category("minecraft:custom")
+                .and_then(|c| c.get(name))
+                .and_then(Value::as_u64)
+                .unwrap_or(0)
+        };
+
+        stats.blocks_mined = category("minecraft:mined").map(|c| sum_values(c.values())).unwrap_or(0);
+        // Renamed from play_one_minute in 1.17, despite counting ticks in both
+        stats.play_time_ticks = custom("minecraft:play_time").max(custom("minecraft:play_one_minute"));
+        stats.deaths = custom("minecraft:deaths");
+        stats.mob_kills = custom("minecraft:mob_kills");
+        stats.player_kills = custom("minecraft:player_kills");
+        stats.jumps = custom("minecraft:jump");
+        stats.distance_cm = category("minecraft:custom")
+            .map(|c| sum_values(c.iter().filter(|(k, _)| k.ends_with("_one_cm")).map(|(_, v)| v)))
+            .unwrap_or(0);
+        return;
+    }
+
+    let stat = |name: &str| file.get(name).and_then(Value::as_u64).unwrap_or(0);
+
+    stats.blocks_mined = sum_values(file.iter().filter(|(k, _)| k.starts_with("stat.mineBlock.")).map(|(_, v)| v));
+    stats.play_time_ticks = stat("stat.playOneMinute");
+    stats.deaths = stat("stat.deaths");
+    stats.mob_kills = stat("stat.mobKills");
+    stats.player_kills = stat("stat.playerKills");
+    stats.jumps = stat("stat.jump");
+    stats.distance_cm = sum_values(file.iter().filter(|(k, _)| k.starts_with("stat.") && k.ends_with("OneCm")).map(|(_, v)| v));
+}
+
+fn apply_advancements(stats: &mut PlayerWorldStats, file: &Map<String, Value>) {
+    // Unlocked recipes are stored as advancements too, but aren't shown as such in game
+    let advancements = file
+        .iter()
+        .filter(|(id, _)| !id.contains(":recipes/") && id.as_str() != "DataVersion");
+
+    for (id, progress) in advancements {
+        stats.advancements_tracked += 1;
+
+        if progress.get("done").and_then(Value::as_bool).unwrap_or(false) {
+            stats.advancements_done += 1;
+            stats.completed_advancements.push(id.clone());
+        }
+    }
+
+    stats.completed_advancements.sort();
+}
+
+/// Collects the players that have a file in `dir`, by the file stem.
+fn player_files(dir: &Path) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    entries
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().and_then(|e| e.to_str()) == Some("json"))
+        .filter_map(|p| Some((p.file_stem()?.to_str()?.to_string(), p.clone())))
+        .collect()
+}
+
+/// Play statistics and advancement progress for every player that has joined the world.
+#[tauri::command]
+pub async fn get_world_stats(instance_name: String, world: String) -> Result<Vec<PlayerWorldStats>, String> {
+    let world_dir = get_world_dir(&instance_name, &world)?;
+
+    let mut players: BTreeMap<String, PlayerWorldStats> = BTreeMap::new();
+
+    for (uuid, path) in player_files(&world_dir.join("stats")) {
+        if let Some(file) = read_json_object(&path) {
+            let stats = players.entry(uuid.clone()).or_insert_with(|| PlayerWorldStats { uuid, ..Default::default() });
+            apply_stats(stats, &file);
+        }
+    }
+
+    for (uuid, path) in player_files(&world_dir.join("advancements")) {
+        if let Some(file) = read_json_object(&path) {
+            let stats = players.entry(uuid.clone()).or_insert_with(|| PlayerWorldStats { uuid, ..Default::default() });
+            apply_advancements(stats, &file);
+        }
+    }
+
+    let mut players: Vec<PlayerWorldStats> = players.into_values().collect();
+    players.sort_by_key(|p| std::cmp::Reverse(p.play_time_ticks));
+
+    Ok(players)
+}
+
+const DIFFICULTIES: &[&str] = &["peaceful", "easy", "normal", "hard"];
+
+#[derive(Debug, Serialize, Clone)]
+pub struct WorldSettings {
+    pub level_name: Option<String>,
+    pub seed: Option<i64>,
+    pub difficulty: Option<String>,
+    pub difficulty_locked: bool,
+    pub allow_commands: bool,
+    pub hardcore: bool,
+    pub game_rules: BTreeMap<String, String>,
+}
+
+/// Fields left out are kept as they are in level.dat.
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct WorldSettingsUpdate {
+    pub difficulty: Option<String>,
+    pub allow_commands: Option<bool>,
+    pub game_rules: Option<HashMap<String, String>>,
+}
+
+fn read_level_dat(path: &Path) -> Result<NbtValue, String> {
+    let compressed = fs::read(path).map_err(|e| format!("Failed to read level.dat: {}", e))?;
+
+    let mut bytes = Vec::new();
+    GzDecoder::new(compressed.as_slice())
+        .read_to_end(&mut bytes)
+        .map_err(|e| format!("Failed to decompress level.dat: {}", e))?;
+
+    fastnbt::from_bytes(&bytes).map_err(|e| format!("Failed to parse level.dat: {}", e))
+}
+
+fn write_level_dat(path: &Path, level: &NbtValue) -> Result<(), String> {
+    let bytes = fastnbt::to_bytes(level).map_err(|e| e.to_string())?;
+
+    let mut encoder = GzEncoder::new(Vec::new(), flate2::Compression::default());
+    encoder.write_all(&bytes).map_err(|e| e.to_string())?;
+    let compressed = encoder.finish().map_err(|e| e.to_string())?;
+
+    // Write next to the original first, so a failure can't leave a truncated level.dat
+    let tmp_path = path.with_extension("dat.tmp");
+    fs::write(&tmp_path, compressed).map_err(|e| e.to_string())?;
+    fs::rename(&tmp_path, path).map_err(|e| e.to_string())
+}
+
+fn level_data(level: &NbtValue) -> Result<&HashMap<String, NbtValue>, String> {
+    match level {
+        NbtValue::Compound(root) => match root.get("Data") {
+            Some(NbtValue::Compound(data)) => Ok(data),
+            _ => Err("level.dat has no Data compound".to_string()),
+        },
+        _ => Err("level.dat is not a compound".to_string()),
+    }
+}
+
+fn level_data_mut(level: &mut NbtValue) -> Result<&mut HashMap<String, NbtValue>, String> {
+    match level {
+        NbtValue::Compound(root) => match root.get_mut("Data") {
+            Some(NbtValue::Compound(data)) => Ok(data),
+            _ => Err("level.dat has no Data compound".to_string()),
+        },
+        _ => Err("level.dat is not a compound".to_string()),
+    }
+}
+
+fn nbt_int(value: Option<&NbtValue>) -> Option<i64> {
+    match value? {
+        NbtValue::Byte(v) => Some(*v as i64),
+        NbtValue::Short(v) => Some(*v as i64),
+        NbtValue::Int(v) => Some(*v as i64),
+        NbtValue::Long(v) => Some(*v),
+        _ => None,
+    }
+}
+
+fn nbt_string(value: &NbtValue) -> Option<String> {
+    match value {
+        NbtValue::String(s) => Some(s.clone()),
+        NbtValue::Byte(v) => Some((*v != 0).to_string()),
+        NbtValue::Int(v) => Some(v.to_string()),
+        _ => None,
+    }
+}
+
+fn world_seed(data: &HashMap<String, NbtValue>) -> Option<i64> {
+    // Moved into WorldGenSettings in 1.16
+    if let Some(NbtValue::Compound(gen_settings)) = data.get("WorldGenSettings") {
+        return nbt_int(gen_settings.get("seed"));
+    }
+    nbt_int(data.get("RandomSeed"))
+}
+
+/// Keeps the tag type level.dat already uses for a game rule. Rules were strings before they
+/// became typed, so new ones are written as strings.
+fn game_rule_value(existing: Option<&NbtValue>, value: &str) -> Result<NbtValue, String> {
+    match existing {
+        Some(NbtValue::Byte(_)) => match value {
+            "true" => Ok(NbtValue::Byte(1)),
+            "false" => Ok(NbtValue::Byte(0)),
+            _ => Err(format!("Game rule value '{}' must be true or false", value)),
+        },
+        Some(NbtValue::Int(_)) => value
+            .parse()
+            .map(NbtValue::Int)
+            .map_err(|_| format!("Game rule value '{}' must be a number", value)),
+        _ => Ok(NbtValue::String(value.to_string())),
+    }
+}
+
+fn validate_game_rule(name: &str, value: &str) -> Result<(), String> {
+    if name.is_empty() || name.len() > 64 || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == ':' || c == '.') {
+        return Err(format!("Invalid game rule name '{}'", name));
+    }
+
+    if value.is_empty() || value.len() > 64 || value.chars().any(|c| c.is_control()) {
+        return Err(format!("Invalid value for game rule '{}'", name));
+    }
+
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn get_world_settings(instance_name: String, world: String) -> Result<WorldSettings, String> {
+    let world_dir = get_world_dir(&instance_name, &world)?;
+    let level = read_level_dat(&world_dir.join("level.dat"))?;
+    let data = level_data(&level)?;
+
+    let flag = |key: &str| nbt_int(data.get(key)).is_some_and(|v| v != 0);
+
+    let game_rules = match data.get("GameRules") {
+        Some(NbtValue::Compound(rules)) => rules
+            .iter()
+            .filter_map(|(name, value)| Some((name.clone(), nbt_string(value)?)))
+            .collect(),
+        _ => BTreeMap::new(),
+    };
+
+    Ok(WorldSettings {
+        level_name: match data.get("LevelName") {
+            Some(NbtValue::String(name)) => Some(name.clone()),
+            _ => None,
+        },
+        seed: world_seed(data),
+        difficulty: nbt_int(data.get("Difficulty"))
+            .and_then(|d| DIFFICULTIES.get(d as usize))
+            .map(|d| d.to_string()),
+        difficulty_locked: flag("DifficultyLocked"),
+        allow_commands: flag("allowCommands"),
+        hardcore: flag("hardcore"),
+        game_rules,
+    })
+}
+
+/// Writes difficulty, cheats and game rules to level.dat, after backing it up next to the original.
+#[tauri::command]
+pub async fn set_world_settings(
+    instance_name: String,
+    world: String,
+    update: WorldSettingsUpdate,
+) -> Result<WorldSettings, String> {
+    let safe_name = sanitize_instance_name(&instance_name)?;
+    let world_dir = get_world_dir(&safe_name, &world)?;
+
+    // The game saves level.dat while running and on exit, which would discard the change
+    if crate::commands::instances::is_running(&safe_name)? {
+        return Err("Close the game before changing world settings".to_string());
+    }
+
+    let difficulty = update
+        .difficulty
+        .as_deref()
+        .map(|d| {
+            DIFFICULTIES
+                .iter()
+                .position(|known| known.eq_ignore_ascii_case(d))
+                .ok_or_else(|| format!("Invalid difficulty '{}'", d))
+        })
+        .transpose()?;
+
+    for (name, value) in update.game_rules.iter().flatten() {
+        validate_game_rule(name, value)?;
+    }
+
+    let level_path = world_dir.join("level.dat");
+    let mut level = read_level_dat(&level_path)?;
+    let data = level_data_mut(&mut level)?;
+
+    if let Some(difficulty) = difficulty {
+        if nbt_int(data.get("DifficultyLocked")).is_some_and(|v| v != 0) {
+            return Err("The difficulty of this world is locked".to_string());
+        }
+        data.insert("Difficulty".to_string(), NbtValue::Byte(difficulty as i8));
+    }
+
+    if let Some(allow_commands) = update.allow_commands {
+        data.insert("allowCommands".to_string(), NbtValue::Byte(allow_commands as i8));
+    }
+
+    if let Some(rules) = &update.game_rules {
+        let game_rules = match data
+            .entry("GameRules".to_string())
+            .or_insert_with(|| NbtValue::Compound(HashMap::new()))
+        {
+            NbtValue::Compound(game_rules) => game_rules,
+            _ => return Err("level.dat has an invalid GameRules tag".to_string()),
+        };
+
+        for (name, value) in rules {
+            let value = game_rule_value(game_rules.get(name), value)?;
+            game_rules.insert(name.clone(), value);
+        }
+    }
+
+    let backup_path = world_dir.join(format!(
+        "level.dat.{}.bak",
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ));
+    fs::copy(&level_path, &backup_path)
+        .map_err(|e| format!("Failed to back up level.dat: {}", e))?;
+
+    write_level_dat(&level_path, &level)?;
+
+    get_world_settings(safe_name, world).await
+}
+
+const LARGEST_REGIONS: usize = 10;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct RegionInfo {
+    pub dimension: String,
+    pub file_name: String,
+    pub size_bytes: u64,
+    pub chunks: u32,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct DimensionSummary {
+    pub dimension: String,
+    pub region_files: usize,
+    pub chunks: u64,
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Clone)]
+pub struct WorldAnalysis {
+    pub total_chunks: u64,
+    pub total_region_bytes: u64,
+    pub dimensions: Vec<DimensionSummary>,
+    pub largest_regions: Vec<RegionInfo>,
+}
+
+/// Region folders of the world by dimension id. Datapack dimensions live under
+/// `dimensions/<namespace>/<name>` since 1.16.
+fn dimension_region_dirs(world_dir: &Path) -> Vec<(String, PathBuf)> {
+    let mut dirs = vec![
+        ("minecraft:overworld".to_string(), world_dir.join("region")),
+        ("minecraft:the_nether".to_string(), world_dir.join("DIM-1").join("region")),
+        ("minecraft:the_end".to_string(), world_dir.join("DIM1").join("region")),
+    ];
+
+    let namespaces = fs::read_dir(world_dir.join("dimensions")).into_iter().flatten().flatten();
+    for namespace in namespaces.filter(|e| e.path().is_dir()) {
+        for dimension in fs::read_dir(namespace.path()).into_iter().flatten().flatten() {
+            let region_dir = dimension.path().join("region");
+            if region_dir.is_dir() {
+                dirs.push((
+                    format!(
+                        "{}:{}",
+                        namespace.file_name().to_string_lossy(),
+                        dimension.file_name().to_string_lossy()
+                    ),
+                    region_dir,
+                ));
+            }
+        }
+    }
+
+    dirs
+}
+
+/// Counts the chunks a region file holds from its location table, where every non-zero
+/// entry points at a stored chunk.
+fn count_region_chunks(path: &Path) -> std::io::Result<u32> {
+    let mut header = [0u8; 4096];
+    let mut file = fs::File::open(path)?;
+
+    // Empty or truncated files are left behind by the game and hold no chunks
+    if file.read_exact(&mut header).is_err() {
+        return Ok(0);
+    }
+
+    Ok(header.chunks_exact(4).filter(|entry| entry.iter().any(|b| *b != 0)).count() as u32)
+}
+
+fn analyze_world_dir(world_dir: &Path) -> WorldAnalysis {
+    let mut dimensions = Vec::new();
+    let mut regions = Vec::new();
+
+    for (dimension, region_dir) in dimension_region_dirs(world_dir) {
+        let Ok(entries) = fs::read_dir(&region_dir) else {
+            continue;
+        };
+
+        let mut summary = DimensionSummary {
+            dimension: dimension.clone(),
+            region_files: 0,
+            chunks: 0,
+            size_bytes: 0,
+        };
+
+        for path in entries.flatten().map(|e| e.path()) {
+            if path.extension().and_then(|e| e.to_str()) != Some("mca") {
+                continue;
+            }
+
+            let size_bytes = path.metadata().map(|m| m.len()).unwrap_or(0);
+            let chunks = count_region_chunks(&path).unwrap_or(0);
+
+            summary.region_files += 1;
+            summary.chunks += chunks as u64;
+            summary.size_bytes += size_bytes;
+
+            regions.push(RegionInfo {
+                dimension: dimension.clone(),
+                file_name: path.file_name().unwrap_or_default().to_string_lossy().to_string(),
+                size_bytes,
+                chunks,
+            });
+        }
+
+        dimensions.push(summary);
+    }
+
+    regions.sort_by_key(|r| std::cmp::Reverse(r.size_bytes));
+    regions.truncate(LARGEST_REGIONS);
+
+    WorldAnalysis {
+        total_chunks: dimensions.iter().map(|d| d.chunks).sum(),
+        total_region_bytes: dimensions.iter().map(|d| d.size_bytes).sum(),
+        dimensions,
+        largest_regions: regions,
+    }
+}
+
+/// Breaks a world's region files down by dimension and lists the largest ones, to show where
+/// its size comes from.
+#[tauri::command]
+pub async fn analyze_world(instance_name: String, world: String) -> Result<WorldAnalysis, String> {
+    let world_dir = get_world_dir(&instance_name, &world)?;
+
+    tauri::async_runtime::spawn_blocking(move || analyze_world_dir(&world_dir))
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -1,4 +1,5 @@
 mod auth;
+mod cli;
 mod commands;
 mod services;
 mod utils;
@@ -8,7 +9,7 @@ use tauri::Manager;
 use tauri_plugin_updater::UpdaterExt;
 use services::accounts::AccountManager;
 use services::friends::FriendsService;
-use models::{AppConfig, FriendStatus};
+use models::AppConfig;
 use tauri_plugin_store::StoreExt;
 use std::sync::Arc;
 
@@ -21,6 +22,12 @@ fn get_app_version() -> String {
     format!("{}-{}", version, commit_hash)
 }
 
+/// Called by the frontend once its first view is shown; starts warming metadata caches.
+#[tauri::command]
+fn frontend_ready() {
+    services::metadata_cache::warm_up();
+}
+
 #[tauri::command]
 async fn check_for_updates(app: tauri::AppHandle) -> Result<Option<String>, String> {
     let updater = app.updater().map_err(|e| format!("Failed to get updater: {}", e))?;
@@ -51,6 +58,15 @@ async fn install_update(app: tauri::AppHandle) -> Result<(), String> {
         .map_err(|e| format!("Failed to install update: {}", e))
 }
 
+/// In portable mode the secrets store lives with the rest of the launcher data.
+fn secrets_store_path() -> std::path::PathBuf {
+    if utils::is_portable() {
+        utils::get_launcher_dir().join("secrets.json")
+    } else {
+        std::path::PathBuf::from("secrets.json")
+    }
+}
+
 #[tauri::command]
 async fn save_secrets(
     app: tauri::AppHandle,
@@ -58,7 +74,7 @@ async fn save_secrets(
     supabase_url: String,
     supabase_key: String,
 ) -> Result<(), String> {
-    let store = app.store("secrets.json").map_err(|e| e.to_string())?;
+    let store = app.store(secrets_store_path()).map_err(|e| e.to_string())?;
     store.set("microsoft_client_id", serde_json::Value::String(microsoft_client_id));
     store.set("supabase_url", serde_json::Value::String(supabase_url));
     store.set("supabase_key", serde_json::Value::String(supabase_key));
@@ -68,7 +84,7 @@ async fn save_secrets(
 
 #[tauri::command]
 async fn is_secrets_configured(app: tauri::AppHandle) -> Result<bool, String> {
-    let store = app.store("secrets.json").map_err(|e| e.to_string())?;
+    let store = app.store(secrets_store_path()).map_err(|e| e.to_string())?;
 
     let configured = |key: &str| -> bool {
         store.get(key).is_some_and(|v| v.as_str().is_some_and(|s| !s.is_empty()))
@@ -87,13 +103,19 @@ pub fn run() {
         eprintln!("Warning: Could not load .env file: {}", e);
     }
 
+    let cli_args = cli::parse_args();
+    if cli_args.list_instances {
+        cli::print_instances();
+        return;
+    }
+
     tauri::Builder::default()
         .plugin(tauri_plugin_updater::Builder::new().build())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_store::Builder::default().build())
         .setup(move |app| {
-            let store = app.store("secrets.json")?;
+            let store = app.store(secrets_store_path())?;
 
             let microsoft_client_id = store
                 .get("microsoft_client_id")
@@ -122,10 +144,33 @@ pub fn run() {
                 api_key: Arc::from(curseforge_api_key),
             });
 
+            app.manage(utils::http::HttpClient::new());
+
+            app.manage(services::operations::OperationRegistry::new());
+
+            app.manage(services::instance_lock::InstanceLocks::new());
+
+            app.manage(services::world_backup::WorldBackupScheduler::new());
+            app.state::<services::world_backup::WorldBackupScheduler>().start(app.handle().clone());
+
+            app.manage(services::asset_cleanup::AssetCleanupScheduler::new());
+            app.state::<services::asset_cleanup::AssetCleanupScheduler>().start(app.handle().clone());
+
+            app.manage(services::servers::ServerManager::new());
+
+            app.manage(services::lan::LanDiscovery::new());
+            app.state::<services::lan::LanDiscovery>().start(app.handle().clone());
+
+            if let Err(e) = services::tray::setup_tray(app.handle()) {
+                eprintln!("Failed to create tray icon: {}", e);
+            }
+
             if let Some(window) = app.get_webview_window("main") {
                 let _ = window.set_focus();
             }
 
+            cli::run_startup_actions(app.handle(), cli_args.clone());
+
             tauri::async_runtime::spawn(async move {
                 tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
                 let _ = crate::services::trash::TrashManager::clean_old_items(30);
@@ -147,33 +192,26 @@ pub fn run() {
                 api.prevent_close();
 
                 let app_handle = window.app_handle();
+                if window.label() == "main" && services::tray::should_minimize_to_tray() {
+                    services::tray::hide_to_tray(app_handle);
+                    return;
+                }
+
                 let config = app_handle.state::<AppConfig>();
                 let supabase_url = config.supabase_url.clone();
                 let supabase_key = config.supabase_key.clone();
                 let window = window.clone();
 
                 tauri::async_runtime::spawn(async move {
-                    let accounts = AccountManager::get_all_accounts()
-                        .map_err(|e| e.to_string())
-                        .ok();
-                    if let Some(accounts) = accounts {
-                        let service = FriendsService::new(&supabase_url, &supabase_key)
-                            .map_err(|e| e.to_string())
-                            .ok();
-                        if let Some(service) = service {
-                            for account in &accounts {
-                                let _ = service
-                                    .update_status(&account.uuid, FriendStatus::Offline, None)
-                                    .await;
-                            }
-                        }
-                    }
+                    FriendsService::set_all_offline(&supabase_url, &supabase_key).await;
                     let _ = window.destroy();
                 });
             }
         })
         .invoke_handler(tauri::generate_handler![
             get_app_version,
+            frontend_ready,
+            get_modrinth_categories,
             check_for_updates,
             install_update,
             microsoft_login,
@@ -183,6 +221,7 @@ pub fn run() {
             switch_account,
             remove_account,
             launch_instance_with_active_account,
+            warm_instance,
             get_launch_token,
             refresh_account_token,
             send_friend_request,
@@ -214,6 +253,7 @@ pub fn run() {
             get_instances,
             delete_instance,
             rename_instance,
+            get_instance_folder_name,
             duplicate_instance,
             open_worlds_folder,
             open_world_folder,
@@ -222,9 +262,22 @@ pub fn run() {
             update_instance_fabric_loader,
             update_instance_neoforge_loader,
             update_instance_forge_loader,
+            update_instance_loader,
+            change_instance_loader,
+            set_instance_snapshot_tracking,
+            reset_instance,
             update_instance_minecraft_version,
             export_instance,
+            export_instance_plain,
+            generate_pack_share,
+            install_from_share,
+            export_launcher_data,
+            import_launcher_data,
             get_neoforge_versions,
+            get_quilt_versions,
+            get_minecraft_changelog,
+            get_installed_versions,
+            uninstall_version,
             get_neoforge_supported_game_versions,
             install_neoforge,
             get_forge_versions,
@@ -238,40 +291,122 @@ pub fn run() {
             set_instance_icon,
             remove_instance_icon,
             get_instance_icon,
+            set_instance_cover,
+            remove_instance_cover,
+            get_instance_cover,
+            set_instance_accent_color,
             launch_instance,
             launch_world,
             kill_instance,
             get_launcher_directory,
+            migrate_launcher_directory,
+            create_desktop_shortcut,
             open_instance_folder,
+            open_instance_subfolder,
             search_mods,
             get_mod_details,
             get_mod_versions,
             download_mod,
+            install_local_mod,
+            get_github_release_jars,
+            install_mod_from_github,
+            start_mod_bisect,
+            bisect_step,
+            get_mod_bisect_status,
+            cancel_mod_bisect,
+            is_fabric_api_missing,
+            install_fabric_api,
+            copy_instance_data,
+            diff_instances,
+            get_instance_options,
+            set_instance_option,
+            set_master_options,
+            sync_options_to_instances,
+            get_templates,
+            save_template,
+            delete_template,
+            create_template_from_instance,
+            apply_template,
+            export_template,
+            import_template,
+            get_world_backup_policy,
+            set_world_backup_policy,
+            list_world_backups,
+            backup_worlds_now,
+            snapshot_instance_config,
+            list_config_snapshots,
+            delete_config_snapshot,
+            rollback_instance_config,
+            save_debug_report,
+            get_active_operations,
+            get_task_history,
+            pause_install,
+            resume_install,
+            get_pending_installs,
+            update_modpack,
+            get_unregistered_instances,
+            adopt_instance,
+            get_profiles,
+            create_profile,
+            delete_profile,
+            switch_profile,
+            is_restricted_mode,
+            enable_restricted_mode,
+            disable_restricted_mode,
+            set_restricted_blocked_terms,
+            third_party_login_and_store,
+            get_account_status,
+            reauthenticate_account,
+            upload_skin_from_file,
+            render_skin_preview,
+            import_skin_from_url,
+            get_skin_library,
+            get_world_stats,
+            get_world_settings,
+            set_world_settings,
+            analyze_world,
+            get_lan_games,
+            launch_lan_game,
+            set_server_group,
+            set_server_preferred_instance,
+            set_server_modpack,
+            check_server_compatibility,
+            import_servers_from_file,
+            refresh_all_servers,
             get_project_details,
             get_settings,
             save_settings,
-            get_instance_settings,
-            save_instance_settings,
+            reset_settings_to_defaults,
+            test_automation,
+            export_settings,
+            import_settings,
+            get_instance_launch_options,
+            save_instance_launch_options,
             detect_java_installations,
+            refresh_java_installations,
             set_background,
             get_background,
             remove_background,
             open_directory,
+            reveal_in_file_manager,
             get_installed_mods,
             get_installed_mod_hashes,
             get_installed_mods_with_metadata,
             delete_mod,
             open_mods_folder,
             toggle_mod,
+            set_mod_pinned,
             get_modpack_versions,
             install_modpack,
             get_modpack_manifest,
             get_modpack_game_versions,
             install_modpack_from_file,
+            install_modpack_from_url,
             get_modpack_name_from_file,
             get_installed_resourcepacks,
             download_resourcepack,
             delete_resourcepack,
+            set_enforced_resource_packs,
             open_resourcepacks_folder,
             get_installed_shaderpacks,
             download_shaderpack,
@@ -287,6 +422,7 @@ pub fn run() {
             open_url,
             get_system_info,
             get_storage_usage,
+            run_asset_cleanup,
             save_secrets,
             is_secrets_configured,
             search_curseforge_mods,
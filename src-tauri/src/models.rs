@@ -22,21 +22,232 @@ pub struct AuthResponse {
     pub token_expiry: DateTime<Utc>,
     pub username: String,
     pub uuid: String,
+    /// Missing when the Xbox profile lookup failed, which doesn't block signing in.
+    #[serde(default)]
+    pub xbox_profile: Option<XboxProfile>,
+}
+
+/// Xbox Live identity of a Microsoft account.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct XboxProfile {
+    pub gamertag: String,
+    pub xuid: String,
+    pub picture_url: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Instance {
+    /// Assigned once and never changed; running processes, world backups and their policies
+    /// and options sync are keyed by it. Instances written before it existed get one on load.
+    #[serde(default)]
+    pub id: String,
+    /// Folder name, used by commands and events to refer to the instance. Fixed once the
+    /// instance is created; renaming only changes `display_name`.
     pub name: String,
+    /// Name shown to the user when it differs from the folder name. Renaming only changes
+    /// this.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display_name: Option<String>,
     pub version: String,
     pub created_at: String,
     pub last_played: Option<String>,
     pub loader: Option<String>,
     pub loader_version: Option<String>,
+    /// Full settings copy used as the instance override before `launch_options`. Moved into
+    /// `launch_options` by `InstanceManager::migrate_launch_options` and written no more.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub settings_override: Option<LauncherSettings>,
+    /// Launch settings this instance overrides, the rest come from the launcher settings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub launch_options: Option<InstanceLaunchOptions>,
     #[serde(default)]
     pub icon_path: Option<String>,
+    /// `#rrggbb` color the instance card is tinted with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub accent_color: Option<String>,
+    /// Banner image file in the instance folder, next to the icon.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cover_image: Option<String>,
     #[serde(default)]
     pub total_playtime_seconds: u64,
+    /// Set when the instance was installed from a modpack.
+    #[serde(default)]
+    pub modpack: Option<ModpackInfo>,
+    /// "Latest Snapshot" instances move to the newest snapshot in the version manifest
+    /// whenever they are launched.
+    #[serde(default)]
+    pub track_latest_snapshot: bool,
+    /// Resource pack files enabled in options.txt before every launch, so they stay on even
+    /// when the game resets its pack list.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub enforced_resource_packs: Vec<String>,
+    /// Set when the last session crashed, cleared by the next one that exits cleanly.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_crash: Option<LastCrash>,
+}
+
+/// Summary of a session that ended in a crash.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LastCrash {
+    pub crashed_at: String,
+    /// `None` when the process was ended by a signal.
+    pub exit_code: Option<i32>,
+    /// Crash report the game wrote, if any.
+    pub crash_report: Option<String>,
+    /// Known crash cause, or the description line of the crash report.
+    pub reason: Option<String>,
+}
+
+/// Per-instance launch settings. Fields left `None` use the launcher settings.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct InstanceLaunchOptions {
+    pub memory_mb: Option<u32>,
+    pub java_path: Option<String>,
+    pub jvm_args: Option<Vec<String>>,
+    /// Replaces the launcher's environment variables as a whole.
+    pub env_vars: Option<HashMap<String, String>>,
+    /// Window size, both must be set to take effect.
+    pub window_width: Option<u32>,
+    pub window_height: Option<u32>,
+    /// Command the game is started through, e.g. `gamemoderun` or `prime-run`.
+    pub wrapper: Option<String>,
+}
+
+impl InstanceLaunchOptions {
+    /// The launch settings of `settings` that differ from `base`.
+    pub fn diff(settings: &LauncherSettings, base: &LauncherSettings) -> Self {
+        fn changed<T: PartialEq + Clone>(value: &T, base: &T) -> Option<T> {
+            (value != base).then(|| value.clone())
+        }
+
+        let window_changed = (settings.window_width, settings.window_height) != (base.window_width, base.window_height);
+
+        Self {
+            memory_mb: changed(&settings.memory_mb, &base.memory_mb),
+            java_path: changed(&settings.java_path, &base.java_path).flatten(),
+            jvm_args: changed(&settings.jvm_args, &base.jvm_args),
+            env_vars: changed(&settings.env_vars, &base.env_vars),
+            window_width: settings.window_width.filter(|_| window_changed),
+            window_height: settings.window_height.filter(|_| window_changed),
+            wrapper: None,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// `settings` with these overrides applied.
+    pub fn apply(&self, mut settings: LauncherSettings) -> LauncherSettings {
+        if let Some(memory_mb) = self.memory_mb {
+            settings.memory_mb = memory_mb;
+        }
+        if let Some(java_path) = &self.java_path {
+            settings.java_path = Some(java_path.clone());
+        }
+        if let Some(jvm_args) = &self.jvm_args {
+            settings.jvm_args = jvm_args.clone();
+        }
+        if let Some(env_vars) = &self.env_vars {
+            settings.env_vars = env_vars.clone();
+        }
+        if let (Some(width), Some(height)) = (self.window_width, self.window_height) {
+            settings.window_width = Some(width);
+            settings.window_height = Some(height);
+        }
+        settings
+    }
+}
+
+/// Branding of the modpack an instance was installed from.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ModpackInfo {
+    /// `modrinth`, `curseforge` or `file`
+    pub source: String,
+    pub name: String,
+    pub summary: Option<String>,
+    pub icon_url: Option<String>,
+    pub project_url: Option<String>,
+    pub project_id: Option<String>,
+    pub version_id: Option<String>,
+    pub version_name: Option<String>,
+}
+
+/// What `update_modpack` changed, counted in mrpack files.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ModpackUpdateSummary {
+    pub version_name: String,
+    pub added: usize,
+    pub updated: usize,
+    pub removed: usize,
+    pub unchanged: usize,
+}
+
+/// What `reset_instance` keeps; everything else in the instance is reset.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct InstanceResetKeep {
+    #[serde(default)]
+    pub worlds: bool,
+    /// options.txt, shader and OptiFine options and the server list.
+    #[serde(default)]
+    pub options: bool,
+    #[serde(default)]
+    pub mods: bool,
+    #[serde(default)]
+    pub config: bool,
+}
+
+/// What `duplicate_instance` leaves out of the copy.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct DuplicateSkip {
+    #[serde(default)]
+    pub saves: bool,
+    #[serde(default)]
+    pub logs: bool,
+    #[serde(default)]
+    pub screenshots: bool,
+    #[serde(default)]
+    pub crash_reports: bool,
+}
+
+impl DuplicateSkip {
+    /// The top-level instance folders left out.
+    pub fn folders(&self) -> Vec<&'static str> {
+        [
+            (self.saves, "saves"),
+            (self.logs, "logs"),
+            (self.screenshots, "screenshots"),
+            (self.crash_reports, "crash-reports"),
+        ]
+        .into_iter()
+        .filter_map(|(skip, folder)| skip.then_some(folder))
+        .collect()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct InstanceResetSummary {
+    /// Top-level files and folders moved to the trash.
+    pub removed: Vec<String>,
+    /// Whether the instance's Modrinth modpack was installed again.
+    pub modpack_reapplied: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct InstanceWarmup {
+    /// Pre-launch steps that failed without making the instance unlaunchable, e.g. verifying
+    /// files while offline.
+    pub warnings: Vec<String>,
+}
+
+/// A launcher profile with its own settings, accounts and instances.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LauncherProfile {
+    pub name: String,
+    pub is_default: bool,
+    pub active: bool,
+    pub instance_count: usize,
 }
 
 // ===== FRIENDS SYSTEM MODELS =====
@@ -76,10 +287,89 @@ pub enum RequestStatus {
     Rejected,
 }
 
+// ===== TEMPLATE MODELS =====
+
+/// A reusable instance setup: launcher settings plus in-game options.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InstanceTemplate {
+    pub id: String,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    pub created_at: String,
+    #[serde(default)]
+    pub launcher_settings: Option<LauncherSettings>,
+    #[serde(default)]
+    pub minecraft_options: Option<MinecraftOptions>,
+    /// Base64-encoded PNG, embedded so shared templates keep their icon.
+    #[serde(default)]
+    pub icon: Option<String>,
+}
+
+/// Commonly changed options.txt values; `None` leaves the instance's value untouched.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct MinecraftOptions {
+    /// Field of view in degrees (30-110).
+    #[serde(default)]
+    pub fov: Option<u32>,
+    #[serde(default)]
+    pub render_distance: Option<u32>,
+    #[serde(default)]
+    pub max_fps: Option<u32>,
+    #[serde(default)]
+    pub fullscreen: Option<bool>,
+    #[serde(default)]
+    pub vsync: Option<bool>,
+    #[serde(default)]
+    pub gui_scale: Option<u32>,
+    /// 0.0 (moody) to 1.0 (bright).
+    #[serde(default)]
+    pub brightness: Option<f32>,
+    #[serde(default)]
+    pub entity_shadows: Option<bool>,
+    /// `all`, `decreased` or `minimal`
+    #[serde(default)]
+    pub particles: Option<String>,
+    /// `fast`, `fancy` or `fabulous`
+    #[serde(default)]
+    pub graphics: Option<String>,
+    #[serde(default)]
+    pub smooth_lighting: Option<bool>,
+    #[serde(default)]
+    pub biome_blend: Option<u32>,
+    /// Volumes and sensitivity range from 0.0 to 1.0, like in options.txt.
+    #[serde(default)]
+    pub master_volume: Option<f32>,
+    #[serde(default)]
+    pub music_volume: Option<f32>,
+    #[serde(default)]
+    pub mouse_sensitivity: Option<f32>,
+    #[serde(default)]
+    pub auto_jump: Option<bool>,
+    /// Action (e.g. `key.jump`) to key (e.g. `key.keyboard.space`).
+    #[serde(default)]
+    pub keybinds: Option<HashMap<String, String>>,
+}
+
+/// Two or more actions bound to the same key after a template was applied.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct KeybindConflict {
+    /// e.g. `key.keyboard.r`
+    pub key: String,
+    /// Every action bound to `key`, e.g. `key.sprint` and `key.jei.showRecipe`.
+    pub actions: Vec<String>,
+}
+
 // ===== SETTINGS MODELS =====
 
+/// Every field falls back to its default, so files written by older or newer launchers
+/// still load. Fields the launcher doesn't know are ignored.
 #[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(default)]
 pub struct LauncherSettings {
+    /// Format version of settings.json, see `SettingsManager::migrate`. Files from before
+    /// versioning read as 0.
+    pub settings_version: u32,
     pub java_path: Option<String>,
     #[serde(default = "default_memory")]
     pub memory_mb: u32,
@@ -91,22 +381,183 @@ pub struct LauncherSettings {
     pub theme: String,
     #[serde(default = "default_tab")]
     pub default_tab: String,
+    #[serde(default = "default_trusted_download_hosts")]
+    pub trusted_download_hosts: Vec<String>,
+    /// Advanced: skip the host whitelist and accept any HTTPS download URL.
+    #[serde(default)]
+    pub allow_any_https_host: bool,
+    /// Proxy URL for all launcher HTTP traffic (e.g. `http://host:8080`).
+    #[serde(default)]
+    pub http_proxy: Option<String>,
+    /// Hide to the system tray instead of quitting when the main window is closed.
+    #[serde(default)]
+    pub minimize_to_tray: bool,
+    /// Download and use the Java runtime Mojang specifies for each version when no Java path is set.
+    #[serde(default = "default_auto_java_runtime")]
+    pub auto_java_runtime: bool,
+    /// Ask the OS to run the game on the discrete GPU on hybrid-graphics laptops.
+    #[serde(default)]
+    pub prefer_discrete_gpu: bool,
+    /// Extra environment variables for the game process (e.g. `MESA_GL_VERSION_OVERRIDE`).
+    #[serde(default)]
+    pub env_vars: HashMap<String, String>,
+    /// Option keys kept in sync between instances; a trailing `*` matches a prefix (e.g. `key_*`).
+    #[serde(default)]
+    pub options_sync_keys: Vec<String>,
+    /// Ids of the instances taking part in options sync.
+    #[serde(default)]
+    pub options_sync_instances: Vec<String>,
+    /// Push the synced options of an instance to the others whenever it exits.
+    #[serde(default)]
+    pub options_sync_auto: bool,
+    /// Template applied to every newly created or installed instance.
+    #[serde(default)]
+    pub default_template_id: Option<String>,
+    /// Initial game window size; both must be set to take effect.
+    #[serde(default)]
+    pub window_width: Option<u32>,
+    #[serde(default)]
+    pub window_height: Option<u32>,
+    /// Extra JVM arguments passed after the memory flags (e.g. `-XX:+UseZGC`).
+    #[serde(default)]
+    pub jvm_args: Vec<String>,
+    /// Scheduled world backup policies, keyed by instance id.
+    #[serde(default)]
+    pub world_backup_policies: HashMap<String, WorldBackupPolicy>,
+    /// Allows accounts from third-party auth servers, launched through authlib-injector.
+    #[serde(default)]
+    pub authlib_injector_enabled: bool,
+    /// PIN-protected restricted mode, `None` when off. Only changed through the restricted
+    /// mode commands, `save_settings` keeps the stored value.
+    #[serde(default)]
+    pub restricted_mode: Option<RestrictedMode>,
+    /// Where launch, exit and install events are forwarded to, see `services::automation`.
+    #[serde(default)]
+    pub automation: AutomationSettings,
+    /// When unreferenced asset objects are swept, see `services::asset_cleanup`.
+    #[serde(default)]
+    pub asset_cleanup: AssetCleanupMode,
+}
+
+/// Forwards launcher events to the user's own tools, e.g. OBS scene switching or Home
+/// Assistant triggers.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct AutomationSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    /// HTTP(S) URL every event is POSTed to as JSON.
+    #[serde(default)]
+    pub webhook_url: Option<String>,
+    /// Loopback `host:port` or, on Unix, a socket path. Each event is written as one JSON line.
+    #[serde(default)]
+    pub socket_address: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetCleanupMode {
+    #[default]
+    Off,
+    Weekly,
+    /// Shortly after a version is uninstalled.
+    AfterChange,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct AssetCleanupResult {
+    pub removed_files: usize,
+    pub freed_bytes: u64,
+}
+
+/// Parts of the configuration `export_settings` and `import_settings` can carry.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SettingsCategory {
+    /// Launcher settings, without the Java path and restricted mode.
+    Launcher,
+    Templates,
+    Servers,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct SettingsImportSummary {
+    pub launcher_settings: bool,
+    pub templates: usize,
+    pub servers: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RestrictedMode {
+    /// SHA-256 of the salt followed by the PIN, hex encoded.
+    pub pin_hash: String,
+    pub salt: String,
+    /// Search results whose title, description or categories contain one of these are hidden.
+    pub blocked_terms: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WorldBackupPolicy {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Minutes between two backups of the instance's worlds.
+    pub interval_minutes: u32,
+    /// Oldest backups beyond this count are deleted after each run.
+    pub retention_count: u32,
+    /// Wait until the game is closed instead of copying worlds that are being written to.
+    #[serde(default = "default_true")]
+    pub only_when_not_running: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct WorldBackupInfo {
+    pub file_name: String,
+    pub created_at: String,
+    pub size: u64,
 }
 
 fn default_memory() -> u32 { 2048 }
 fn default_auto_navigate_to_console() -> bool { true }
 fn default_theme() -> String { "octane".to_string() }
 fn default_tab() -> String { "home".to_string() }
+fn default_auto_java_runtime() -> bool { true }
+fn default_true() -> bool { true }
+
+pub fn default_trusted_download_hosts() -> Vec<String> {
+    ["cdn.modrinth.com", "github.com", "raw.githubusercontent.com", "edge.forgecdn.net"]
+        .iter()
+        .map(|h| h.to_string())
+        .collect()
+}
 
 impl Default for LauncherSettings {
     fn default() -> Self {
         Self {
+            settings_version: crate::services::settings::CURRENT_SETTINGS_VERSION,
             java_path: None,
             memory_mb: 2048,
             language: None,
             auto_navigate_to_console: true,
             theme: default_theme(),
             default_tab: default_tab(),
+            trusted_download_hosts: default_trusted_download_hosts(),
+            allow_any_https_host: false,
+            http_proxy: None,
+            minimize_to_tray: false,
+            auto_java_runtime: true,
+            prefer_discrete_gpu: false,
+            env_vars: HashMap::new(),
+            options_sync_keys: Vec::new(),
+            options_sync_instances: Vec::new(),
+            options_sync_auto: false,
+            default_template_id: None,
+            window_width: None,
+            window_height: None,
+            jvm_args: Vec::new(),
+            world_backup_policies: HashMap::new(),
+            authlib_injector_enabled: false,
+            restricted_mode: None,
+            automation: AutomationSettings::default(),
+            asset_cleanup: AssetCleanupMode::Off,
         }
     }
 }
@@ -128,6 +579,74 @@ pub struct TrashIndex {
     pub items: Vec<TrashItem>,
 }
 
+// ===== PACK SHARE MODELS =====
+
+/// An instance described by its Modrinth mod versions, loader and options, small enough to
+/// send to friends so they can rebuild it without the files.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct PackShare {
+    pub format_version: u32,
+    pub name: String,
+    pub minecraft_version: String,
+    pub loader: Option<String>,
+    pub loader_version: Option<String>,
+    pub mods: Vec<SharedMod>,
+    /// Contents of options.txt.
+    #[serde(default)]
+    pub options: Option<String>,
+    /// Mods not installed from Modrinth, which have to be added by hand.
+    #[serde(default)]
+    pub unlisted_mods: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SharedMod {
+    pub project_id: String,
+    pub version_id: String,
+    pub filename: String,
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct PackShareInstallSummary {
+    pub instance_name: String,
+    pub installed: usize,
+    /// Mods whose Modrinth version could not be downloaded.
+    pub failed: Vec<String>,
+    pub unlisted_mods: Vec<String>,
+}
+
+// ===== CONFIG SNAPSHOT MODELS =====
+
+/// Copy of an instance's `config/` tree, stored with the SHA-1 of every file.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConfigSnapshot {
+    pub id: String,
+    pub instance: String,
+    pub created_at: String,
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Path relative to `config/` (forward slashes) to SHA-1.
+    pub files: std::collections::BTreeMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConfigSnapshotInfo {
+    pub id: String,
+    pub instance: String,
+    pub created_at: String,
+    pub label: Option<String>,
+    pub file_count: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ConfigRollbackSummary {
+    pub restored: Vec<String>,
+    pub removed: Vec<String>,
+    pub unchanged: usize,
+}
+
 // ===== AUTHENTICATION MODELS =====
 
 #[derive(Serialize, Deserialize)]
@@ -205,6 +724,23 @@ pub struct MinecraftLoginResponse {
     pub expires_in: usize,
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct XboxProfileResponse {
+    pub profile_users: Vec<XboxProfileUser>,
+}
+
+#[derive(Deserialize)]
+pub struct XboxProfileUser {
+    pub settings: Vec<XboxProfileSetting>,
+}
+
+#[derive(Deserialize)]
+pub struct XboxProfileSetting {
+    pub id: String,
+    pub value: String,
+}
+
 #[derive(Deserialize)]
 pub struct MinecraftProfile {
     pub id: Uuid,
@@ -222,6 +758,12 @@ pub struct StoredAccount {
     pub token_expiry: DateTime<Utc>,
     pub added_at: String,
     pub last_used: Option<String>,
+    #[serde(default)]
+    pub xbox_profile: Option<XboxProfile>,
+    /// Yggdrasil API root of a third-party auth server, `None` for Microsoft accounts.
+    /// For these accounts `refresh_token` holds the Yggdrasil client token.
+    #[serde(default)]
+    pub auth_server: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -246,6 +788,66 @@ pub struct AccountInfo {
     pub is_active: bool,
     pub added_at: String,
     pub last_used: Option<String>,
+    #[serde(default)]
+    pub auth_server: Option<String>,
+    #[serde(default)]
+    pub xbox_profile: Option<XboxProfile>,
+}
+
+/// Sign-in state of a stored account as reported by `get_account_status`.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct AccountStatus {
+    pub uuid: String,
+    /// Whether a launch token could be obtained, refreshing it if needed.
+    pub valid: bool,
+    /// Expiry of the stored token, `None` for third-party accounts whose tokens don't expire
+    /// on a schedule.
+    pub token_expiry: Option<String>,
+    pub expires_in_seconds: Option<i64>,
+    /// The refresh failed and the user has to sign in again with `reauthenticate_account`.
+    pub needs_reauth: bool,
+    pub error: Option<String>,
+}
+
+// ===== SERVER MODELS =====
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ServerInfo {
+    pub name: String,
+    pub address: String,
+    pub port: u16,
+    pub status: String,
+    pub players_online: Option<u32>,
+    pub players_max: Option<u32>,
+    pub version: Option<String>,
+    pub motd: Option<String>,
+    pub favicon: Option<String>,
+    pub last_checked: Option<i64>,
+    /// Free-form group the server list is organised by.
+    #[serde(default)]
+    pub group: Option<String>,
+    /// Instance the play button launches, instead of the most recently played one.
+    #[serde(default)]
+    pub preferred_instance: Option<String>,
+    /// Modrinth project id of the modpack the server runs, set by the user.
+    #[serde(default)]
+    pub modpack_project_id: Option<String>,
+}
+
+/// What `check_server_compatibility` found comparing an instance with a server.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ServerCompatibilityReport {
+    pub instance_name: String,
+    pub instance_version: String,
+    /// As reported by the server, or last known when it couldn't be reached.
+    pub server_version: Option<String>,
+    pub online: bool,
+    /// Reasons the server is likely to refuse the instance.
+    pub mismatches: Vec<String>,
+    /// Mods the server listed that the instance doesn't have.
+    pub missing_mods: Vec<String>,
+    /// Whether the server listed its mods; vanilla, Fabric and Forge 1.18+ servers don't.
+    pub mods_checked: bool,
 }
 
 // ===== MINECRAFT VERSION MODELS =====
@@ -258,15 +860,48 @@ pub struct MinecraftVersion {
     pub time: String,
     #[serde(rename = "releaseTime")]
     pub release_time: String,
+    /// Set from the manifest's `latest` block by `get_versions_with_metadata`.
+    #[serde(default)]
+    pub is_latest_release: bool,
+    #[serde(default)]
+    pub is_latest_snapshot: bool,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// A version in `meta/versions`, vanilla or loader.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct InstalledVersion {
+    pub id: String,
+    /// `release`, `snapshot`, `old_beta`, ... as written in the version json.
+    pub r#type: Option<String>,
+    pub loader: Option<String>,
+    /// The vanilla version a loader version builds on.
+    pub inherits_from: Option<String>,
+    pub size_bytes: u64,
+    /// Instances, in any profile, launching this version or a version built on it.
+    pub used_by: Vec<String>,
+}
+
+/// Official patch notes of a release or snapshot, from Mojang's launcher content.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MinecraftChangelog {
+    pub version: String,
+    pub title: String,
+    /// `release` or `snapshot`
+    pub r#type: String,
+    pub date: Option<String>,
+    pub short_text: Option<String>,
+    pub image_url: Option<String>,
+    /// The patch notes as HTML.
+    pub body: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct VersionManifest {
     pub latest: Latest,
     pub versions: Vec<MinecraftVersion>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Latest {
     pub release: String,
     pub snapshot: String,
@@ -302,6 +937,10 @@ pub struct DetectedJava {
     pub full_version: String,
     pub architecture: String,
     pub path: String,
+    #[serde(default)]
+    pub vendor: String,
+    #[serde(default)]
+    pub is_64bit: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -331,6 +970,54 @@ pub struct Library {
     pub downloads: Option<LibraryDownloads>,
     pub name: String,
     pub rules: Option<Vec<Rule>>,
+    /// OS name -> natives classifier in `downloads.classifiers`, e.g.
+    /// `"windows": "natives-windows-${arch}"`. Used by versions before 1.19.
+    pub natives: Option<HashMap<String, String>>,
+    pub extract: Option<LibraryExtract>,
+}
+
+impl Library {
+    /// The classifiers holding this library's natives for `os` and their jars. Uses the
+    /// `natives` mapping when present, otherwise every classifier named `natives-<os>...`.
+    pub fn native_artifacts(&self, os: &str) -> Vec<(String, &Artifact)> {
+        let Some(classifiers) = self.downloads.as_ref().and_then(|d| d.classifiers.as_ref()) else {
+            return Vec::new();
+        };
+
+        match &self.natives {
+            Some(natives) => {
+                let arch = if cfg!(target_pointer_width = "64") { "64" } else { "32" };
+                natives
+                    .get(os)
+                    .map(|classifier| classifier.replace("${arch}", arch))
+                    .and_then(|classifier| classifiers.get(&classifier).map(|artifact| (classifier, artifact)))
+                    .into_iter()
+                    .collect()
+            }
+            None => {
+                let prefix = format!("natives-{}", os);
+                classifiers
+                    .iter()
+                    .filter(|(key, _)| key.starts_with(&prefix))
+                    .map(|(key, artifact)| (key.clone(), artifact))
+                    .collect()
+            }
+        }
+    }
+
+    /// Whether a natives jar entry is left out when extracting. `META-INF` always is.
+    pub fn excludes_from_extraction(&self, entry: &str) -> bool {
+        entry.starts_with("META-INF")
+            || self.extract.as_ref().is_some_and(|extract| {
+                extract.exclude.iter().any(|prefix| entry.starts_with(prefix.as_str()))
+            })
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LibraryExtract {
+    #[serde(default)]
+    pub exclude: Vec<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -351,6 +1038,9 @@ pub struct Artifact {
 pub struct Rule {
     pub action: String,
     pub os: Option<OsRule>,
+    /// Launcher features the rule depends on, e.g. `{"is_demo_user": true}`.
+    #[serde(default)]
+    pub features: Option<HashMap<String, bool>>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -416,6 +1106,26 @@ pub struct FabricLoaderVersion {
     pub stable: bool,
 }
 
+// ===== LOADER CHANGE MODELS =====
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct LoaderChangeResult {
+    /// Version id the instance now launches.
+    pub version_id: String,
+    /// Mod jars that were renamed to `.disabled` because the new loader can't run them.
+    pub disabled_mods: Vec<String>,
+}
+
+// ===== QUILT LOADER MODELS =====
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct QuiltLoaderVersion {
+    pub separator: String,
+    pub build: u32,
+    pub maven: String,
+    pub version: String,
+}
+
 // ===== FORGE LOADER MODELS =====
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
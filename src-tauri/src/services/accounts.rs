@@ -1,13 +1,17 @@
-use crate::models::{AccountInfo, AccountsData, StoredAccount};
+use crate::models::{AccountInfo, AccountsData, StoredAccount, XboxProfile};
 use chrono::{DateTime, Utc};
 use std::fs;
 use std::path::PathBuf;
+use std::sync::Mutex;
+
+/// Serializes read-modify-write cycles on accounts.json, e.g. token refreshes of two launches.
+static ACCOUNTS_LOCK: Mutex<()> = Mutex::new(());
 
 pub struct AccountManager;
 
 impl AccountManager {
     fn get_accounts_file() -> Result<PathBuf, Box<dyn std::error::Error>> {
-        let data_dir = crate::utils::get_launcher_dir();
+        let data_dir = crate::utils::get_profile_dir();
         
         fs::create_dir_all(&data_dir)?;
         Ok(data_dir.join("accounts.json"))
@@ -27,11 +31,22 @@ impl AccountManager {
 
     fn save_accounts(data: &AccountsData) -> Result<(), Box<dyn std::error::Error>> {
         let path = Self::get_accounts_file()?;
-        let json = serde_json::to_string_pretty(data)?;
-        fs::write(path, json)?;
+        crate::utils::write_json_atomic(&path, data)?;
         Ok(())
     }
 
+    /// Loads, changes and saves the accounts without another update slipping in between.
+    fn update_accounts<T>(
+        f: impl FnOnce(&mut AccountsData) -> Result<T, Box<dyn std::error::Error>>,
+    ) -> Result<T, Box<dyn std::error::Error>> {
+        let _guard = ACCOUNTS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+        let mut data = Self::load_accounts()?;
+        let result = f(&mut data)?;
+        Self::save_accounts(&data)?;
+        Ok(result)
+    }
+
     pub fn add_account(
         uuid: String,
         username: String,
@@ -39,26 +54,64 @@ impl AccountManager {
         refresh_token: String,
         token_expiry: DateTime<Utc>,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        let mut data = Self::load_accounts()?;
-        
-        let account = StoredAccount {
-            uuid: uuid.clone(),
-            username,
-            access_token,
-            refresh_token,
-            token_expiry,
-            added_at: Utc::now().to_rfc3339(),
-            last_used: Some(Utc::now().to_rfc3339()),
-        };
+        Self::update_accounts(|data| {
+            let account = StoredAccount {
+                uuid: uuid.clone(),
+                username,
+                access_token,
+                refresh_token,
+                token_expiry,
+                added_at: Utc::now().to_rfc3339(),
+                last_used: Some(Utc::now().to_rfc3339()),
+                xbox_profile: None,
+                auth_server: None,
+            };
 
-        data.accounts.insert(uuid.clone(), account);
-        
-        if data.active_account_uuid.is_none() {
-            data.active_account_uuid = Some(uuid);
-        }
+            data.accounts.insert(uuid.clone(), account);
 
-        Self::save_accounts(&data)?;
-        Ok(())
+            if data.active_account_uuid.is_none() {
+                data.active_account_uuid = Some(uuid);
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Stores an account from a third-party auth server and makes it the active account.
+    pub fn add_third_party_account(
+        session: crate::services::authlib_injector::YggdrasilSession,
+        auth_server: &str,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        Self::update_accounts(|data| {
+            let added_at = data
+                .accounts
+                .get(&session.uuid)
+                .map(|existing| existing.added_at.clone())
+                .unwrap_or_else(|| Utc::now().to_rfc3339());
+
+            let account = StoredAccount {
+                uuid: session.uuid.clone(),
+                username: session.username,
+                access_token: session.access_token,
+                refresh_token: session.client_token,
+                // Yggdrasil tokens have no fixed lifetime, they are checked with /validate instead
+                token_expiry: Utc::now(),
+                added_at,
+                last_used: Some(Utc::now().to_rfc3339()),
+                xbox_profile: None,
+                auth_server: Some(auth_server.to_string()),
+            };
+
+            data.accounts.insert(session.uuid.clone(), account);
+            data.active_account_uuid = Some(session.uuid);
+
+            Ok(())
+        })
+    }
+
+    /// The third-party auth server of a stored account, `None` for Microsoft or unknown accounts.
+    pub fn get_auth_server(uuid: &str) -> Option<String> {
+        Self::load_accounts().ok()?.accounts.get(uuid)?.auth_server.clone()
     }
 
     pub fn account_exists(uuid: &str) -> Result<bool, Box<dyn std::error::Error>> {
@@ -78,6 +131,8 @@ impl AccountManager {
                 is_active: data.active_account_uuid.as_ref() == Some(&acc.uuid),
                 added_at: acc.added_at.clone(),
                 last_used: acc.last_used.clone(),
+                auth_server: acc.auth_server.clone(),
+                xbox_profile: acc.xbox_profile.clone(),
             })
             .collect();
 
@@ -94,39 +149,41 @@ impl AccountManager {
         }
     }
 
+    pub fn get_account(uuid: &str) -> Result<Option<StoredAccount>, Box<dyn std::error::Error>> {
+        Ok(Self::load_accounts()?.accounts.get(uuid).cloned())
+    }
+
     pub fn set_active_account(uuid: &str) -> Result<(), Box<dyn std::error::Error>> {
-        let mut data = Self::load_accounts()?;
-        
-        if !data.accounts.contains_key(uuid) {
-            return Err("Account not found".into());
-        }
+        Self::update_accounts(|data| {
+            if !data.accounts.contains_key(uuid) {
+                return Err("Account not found".into());
+            }
 
-        data.active_account_uuid = Some(uuid.to_string());
-        
-        if let Some(account) = data.accounts.get_mut(uuid) {
-            account.last_used = Some(Utc::now().to_rfc3339());
-        }
+            data.active_account_uuid = Some(uuid.to_string());
 
-        Self::save_accounts(&data)?;
-        Ok(())
+            if let Some(account) = data.accounts.get_mut(uuid) {
+                account.last_used = Some(Utc::now().to_rfc3339());
+            }
+
+            Ok(())
+        })
     }
 
     pub fn remove_account(uuid: &str) -> Result<(), Box<dyn std::error::Error>> {
-        let mut data = Self::load_accounts()?;
-        
-        let was_active = data.active_account_uuid.as_ref() == Some(&uuid.to_string());
-        data.accounts.remove(uuid);
-        
-        if was_active {
-            if let Some(first_remaining) = data.accounts.keys().next().cloned() {
-                data.active_account_uuid = Some(first_remaining);
-            } else {
-                data.active_account_uuid = None;
+        Self::update_accounts(|data| {
+            let was_active = data.active_account_uuid.as_ref() == Some(&uuid.to_string());
+            data.accounts.remove(uuid);
+
+            if was_active {
+                if let Some(first_remaining) = data.accounts.keys().next().cloned() {
+                    data.active_account_uuid = Some(first_remaining);
+                } else {
+                    data.active_account_uuid = None;
+                }
             }
-        }
 
-        Self::save_accounts(&data)?;
-        Ok(())
+            Ok(())
+        })
     }
 
     pub fn update_account_tokens(
@@ -135,20 +192,52 @@ impl AccountManager {
         refresh_token: String,
         token_expiry: DateTime<Utc>,
     ) -> Result<(), Box<dyn std::error::Error>> {
-        let mut data = Self::load_accounts()?;
-        
-        let account = data
-            .accounts
-            .get_mut(uuid)
-            .ok_or("Account not found")?;
+        Self::update_accounts(|data| {
+            let account = data
+                .accounts
+                .get_mut(uuid)
+                .ok_or("Account not found")?;
 
-        account.access_token = access_token;
-        account.refresh_token = refresh_token;
-        account.token_expiry = token_expiry;
-        account.last_used = Some(Utc::now().to_rfc3339());
+            account.access_token = access_token;
+            account.refresh_token = refresh_token;
+            account.token_expiry = token_expiry;
+            account.last_used = Some(Utc::now().to_rfc3339());
 
-        Self::save_accounts(&data)?;
-        Ok(())
+            Ok(())
+        })
+    }
+
+    /// Updates a Microsoft account after signing in to it again. The Minecraft name may have
+    /// changed since the account was added.
+    pub fn reauthenticate(auth_response: &crate::models::AuthResponse) -> Result<(), Box<dyn std::error::Error>> {
+        Self::update_accounts(|data| {
+            let account = data.accounts.get_mut(&auth_response.uuid).ok_or("Account not found")?;
+
+            account.username = auth_response.username.clone();
+            account.access_token = auth_response.access_token.clone();
+            account.refresh_token = auth_response.refresh_token.clone();
+            account.token_expiry = auth_response.token_expiry;
+            account.last_used = Some(Utc::now().to_rfc3339());
+            if auth_response.xbox_profile.is_some() {
+                account.xbox_profile = auth_response.xbox_profile.clone();
+            }
+
+            Ok(())
+        })
+    }
+
+    /// Keeps the last known profile when a refresh couldn't load it.
+    pub fn set_xbox_profile(uuid: &str, xbox_profile: Option<XboxProfile>) -> Result<(), Box<dyn std::error::Error>> {
+        let Some(xbox_profile) = xbox_profile else {
+            return Ok(());
+        };
+
+        Self::update_accounts(|data| {
+            let account = data.accounts.get_mut(uuid).ok_or("Account not found")?;
+            account.xbox_profile = Some(xbox_profile);
+
+            Ok(())
+        })
     }
 
     pub async fn get_valid_token(uuid: &str, client_id: &str) -> Result<String, Box<dyn std::error::Error>> {
@@ -159,6 +248,10 @@ impl AccountManager {
             .ok_or("Account not found")?
             .clone();
 
+        if let Some(api_root) = &account.auth_server {
+            return Self::get_valid_third_party_token(&account, api_root).await;
+        }
+
         let now = Utc::now();
         let buffer = chrono::Duration::minutes(5);
         
@@ -175,7 +268,42 @@ impl AccountManager {
             refreshed.refresh_token,
             refreshed.token_expiry,
         )?;
+        Self::set_xbox_profile(uuid, refreshed.xbox_profile)?;
         
         Ok(refreshed.access_token)
     }
+
+    async fn get_valid_third_party_token(
+        account: &StoredAccount,
+        api_root: &str,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        use crate::services::authlib_injector::AuthlibInjector;
+
+        if !AuthlibInjector::is_enabled() {
+            return Err("Third-party auth servers are disabled, enable them in the advanced settings".into());
+        }
+
+        let client = crate::utils::http::get_client();
+        AuthlibInjector::ensure_installed(&client).await?;
+        if let Err(e) = AuthlibInjector::prefetch_metadata(&client, api_root).await {
+            eprintln!("Failed to prefetch auth server metadata: {}", e);
+        }
+
+        if AuthlibInjector::validate(&client, api_root, &account.access_token, &account.refresh_token).await {
+            return Ok(account.access_token.clone());
+        }
+
+        let session = AuthlibInjector::refresh(&client, api_root, &account.access_token, &account.refresh_token)
+            .await
+            .map_err(|e| format!("Sign in to {} again: {}", api_root, e))?;
+
+        Self::update_account_tokens(
+            &account.uuid,
+            session.access_token.clone(),
+            session.client_token,
+            Utc::now(),
+        )?;
+
+        Ok(session.access_token)
+    }
 }
\ No newline at end of file
@@ -0,0 +1,220 @@
+use crate::models::{AssetCleanupMode, AssetCleanupResult, AssetIndexData};
+use crate::services::installer::INSTALLED_ASSET_INDEXES_FILE;
+use crate::utils::{get_meta_dir, write_json_atomic};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+
+/// How often the scheduler checks whether the weekly sweep is due.
+const CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const WEEKLY_INTERVAL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+/// Touched after every sweep, its modification time is the last run.
+const LAST_RUN_FILE: &str = ".last_cleanup";
+
+static SWEEPING: AtomicBool = AtomicBool::new(false);
+
+pub struct AssetCleanup;
+
+impl AssetCleanup {
+    fn assets_dir() -> PathBuf {
+        get_meta_dir().join("assets")
+    }
+
+    /// Asset index ids of every installed version. Loader versions inherit theirs. Fails
+    /// when a version can't be read, so nothing it may need is deleted.
+    fn referenced_indexes() -> Result<HashSet<String>, String> {
+        let mut indexes = HashSet::new();
+        let Ok(entries) = fs::read_dir(get_meta_dir().join("versions")) else {
+            return Ok(indexes);
+        };
+
+        for entry in entries.flatten().filter(|e| e.path().is_dir()) {
+            let id = entry.file_name().to_string_lossy().to_string();
+            let json: serde_json::Value = fs::read_to_string(entry.path().join(format!("{}.json", id)))
+                .ok()
+                .and_then(|content| serde_json::from_str(&content).ok())
+                .ok_or_else(|| format!("Version '{}' is incomplete, skipping asset cleanup", id))?;
+
+            match json.get("assetIndex").and_then(|index| index.get("id")).and_then(|v| v.as_str()) {
+                Some(index_id) => {
+                    indexes.insert(index_id.to_string());
+                }
+                None if json.get("inheritsFrom").is_some() => {}
+                None => return Err(format!("Version '{}' has no asset index, skipping asset cleanup", id)),
+            }
+        }
+
+        Ok(indexes)
+    }
+
+    /// Hashes of every object the given indexes list.
+    fn referenced_objects(assets_dir: &Path, indexes: &HashSet<String>) -> Result<HashSet<String>, String> {
+        let mut objects = HashSet::new();
+        for index_id in indexes {
+            let index: AssetIndexData = fs::read_to_string(assets_dir.join("indexes").join(format!("{}.json", index_id)))
+                .ok()
+                .and_then(|content| serde_json::from_str(&content).ok())
+                .ok_or_else(|| format!("Asset index '{}' is missing, skipping asset cleanup", index_id))?;
+            objects.extend(index.objects.into_values().map(|object| object.hash));
+        }
+        Ok(objects)
+    }
+
+    /// Deletes asset objects and indexes no installed version references anymore.
+    pub fn sweep() -> Result<AssetCleanupResult, String> {
+        let assets_dir = Self::assets_dir();
+        let indexes = Self::referenced_indexes()?;
+        let objects = Self::referenced_objects(&assets_dir, &indexes)?;
+        let mut result = AssetCleanupResult::default();
+
+        if let Ok(buckets) = fs::read_dir(assets_dir.join("objects")) {
+            for bucket in buckets.flatten().filter(|e| e.path().is_dir()) {
+                let Ok(files) = fs::read_dir(bucket.path()) else {
+                    continue;
+                };
+
+                for file in files.flatten() {
+                    let hash = file.file_name().to_string_lossy().to_string();
+                    if objects.contains(&hash) {
+                        continue;
+                    }
+                    let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+                    if fs::remove_file(file.path()).is_ok() {
+                        result.removed_files += 1;
+                        result.freed_bytes += size;
+                    }
+                }
+
+                // Only succeeds once the bucket is empty
+                let _ = fs::remove_dir(bucket.path());
+            }
+        }
+
+        if let Ok(files) = fs::read_dir(assets_dir.join("indexes")) {
+            for file in files.flatten() {
+                let path = file.path();
+                let unreferenced = path.extension().and_then(|ext| ext.to_str()) == Some("json")
+                    && path.file_stem().is_some_and(|stem| !indexes.contains(stem.to_string_lossy().as_ref()));
+                let size = file.metadata().map(|m| m.len()).unwrap_or(0);
+                if unreferenced && fs::remove_file(&path).is_ok() {
+                    result.removed_files += 1;
+                    result.freed_bytes += size;
+                }
+            }
+        }
+
+        // Otherwise reinstalling a removed version would skip downloading its objects
+        let installed_path = assets_dir.join(INSTALLED_ASSET_INDEXES_FILE);
+        if let Some(mut installed) = fs::read_to_string(&installed_path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<HashMap<String, String>>(&content).ok())
+        {
+            let before = installed.len();
+            installed.retain(|index_id, _| indexes.contains(index_id));
+            if installed.len() != before {
+                write_json_atomic(&installed_path, &installed)
+                    .map_err(|e| format!("Failed to update installed asset indexes: {}", e))?;
+            }
+        }
+
+        let _ = fs::write(assets_dir.join(LAST_RUN_FILE), chrono::Utc::now().to_rfc3339());
+        Ok(result)
+    }
+
+    fn is_weekly_due() -> bool {
+        match fs::metadata(Self::assets_dir().join(LAST_RUN_FILE)).and_then(|m| m.modified()) {
+            Ok(last) => last.elapsed().map(|elapsed| elapsed >= WEEKLY_INTERVAL).unwrap_or(true),
+            Err(_) => true,
+        }
+    }
+}
+
+/// Runs the weekly sweep in the background; registered as Tauri state.
+#[derive(Default)]
+pub struct AssetCleanupScheduler {
+    task: Mutex<Option<tauri::async_runtime::JoinHandle<()>>>,
+}
+
+impl AssetCleanupScheduler {
+    pub fn new() -> Self {
+        Self { task: Mutex::new(None) }
+    }
+
+    /// Starts the scheduler, replacing a previously started one.
+    pub fn start(&self, app_handle: tauri::AppHandle) {
+        let Ok(mut task) = self.task.lock() else {
+            return;
+        };
+
+        if let Some(previous) = task.take() {
+            previous.abort();
+        }
+
+        *task = Some(tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(CHECK_INTERVAL);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                interval.tick().await;
+                let weekly = crate::services::settings::SettingsManager::load()
+                    .map(|settings| settings.asset_cleanup == AssetCleanupMode::Weekly)
+                    .unwrap_or(false);
+                if weekly && AssetCleanup::is_weekly_due() {
+                    let _ = run(&app_handle).await;
+                }
+            }
+        }));
+    }
+}
+
+/// Sweeps in the background when the cleanup mode is `after_change`. Called once a version
+/// has been uninstalled.
+pub fn schedule_after_change(app_handle: &tauri::AppHandle) {
+    let after_change = crate::services::settings::SettingsManager::load()
+        .map(|settings| settings.asset_cleanup == AssetCleanupMode::AfterChange)
+        .unwrap_or(false);
+    if !after_change {
+        return;
+    }
+
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let _ = run(&app_handle).await;
+    });
+}
+
+/// Sweeps unless one is already running or an install or other operation is in progress,
+/// records the outcome and emits `asset-cleanup-completed`.
+pub async fn run(app_handle: &tauri::AppHandle) -> Result<AssetCleanupResult, String> {
+    let busy = app_handle
+        .try_state::<crate::services::operations::OperationRegistry>()
+        .is_some_and(|registry| !registry.active().is_empty());
+    if busy {
+        return Err("Asset cleanup waits until running operations have finished".to_string());
+    }
+    if SWEEPING.swap(true, Ordering::SeqCst) {
+        return Err("Asset cleanup is already running".to_string());
+    }
+
+    let started_at = chrono::Utc::now().to_rfc3339();
+    let result = tauri::async_runtime::spawn_blocking(AssetCleanup::sweep)
+        .await
+        .map_err(|e| e.to_string())
+        .and_then(|r| r);
+    SWEEPING.store(false, Ordering::SeqCst);
+
+    crate::services::task_history::record_result("asset_cleanup", None, started_at, &result);
+
+    match &result {
+        Ok(summary) => {
+            let _ = app_handle.emit("asset-cleanup-completed", summary);
+        }
+        Err(e) => eprintln!("Asset cleanup failed: {}", e),
+    }
+
+    result
+}
@@ -0,0 +1,275 @@
+use crate::utils::get_meta_dir;
+use base64::{engine::general_purpose, Engine as _};
+use serde::Deserialize;
+use std::fs;
+use std::path::PathBuf;
+
+const LATEST_ARTIFACT_URL: &str = "https://authlib-injector.yushi.moe/artifact/latest.json";
+const JAR_NAME: &str = "authlib-injector.jar";
+
+/// Response header an auth server's website uses to point at its Yggdrasil API root.
+const API_LOCATION_HEADER: &str = "x-authlib-injector-api-location";
+
+#[derive(Deserialize)]
+struct LatestArtifact {
+    download_url: String,
+    checksums: ArtifactChecksums,
+}
+
+#[derive(Deserialize)]
+struct ArtifactChecksums {
+    sha256: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct YggdrasilResponse {
+    access_token: String,
+    client_token: String,
+    selected_profile: Option<YggdrasilProfile>,
+    #[serde(default)]
+    available_profiles: Vec<YggdrasilProfile>,
+}
+
+#[derive(Deserialize, serde::Serialize, Clone)]
+struct YggdrasilProfile {
+    id: String,
+    name: String,
+}
+
+/// A signed-in account on a Yggdrasil-compatible auth server.
+pub struct YggdrasilSession {
+    pub uuid: String,
+    pub username: String,
+    pub access_token: String,
+    pub client_token: String,
+}
+
+/// Support for third-party auth servers (ely.by, Blessing Skin, ...) through authlib-injector,
+/// a Java agent that redirects the game's Mojang API calls to the configured server.
+pub struct AuthlibInjector;
+
+impl AuthlibInjector {
+    fn dir() -> PathBuf {
+        get_meta_dir().join("authlib-injector")
+    }
+
+    pub fn jar_path() -> PathBuf {
+        Self::dir().join(JAR_NAME)
+    }
+
+    fn prefetched_path(api_root: &str) -> PathBuf {
+        let key = general_purpose::URL_SAFE_NO_PAD.encode(api_root.as_bytes());
+        Self::dir().join("prefetched").join(format!("{}.txt", key))
+    }
+
+    /// Third-party accounts are an advanced setting and off by default.
+    pub fn is_enabled() -> bool {
+        crate::services::settings::SettingsManager::load()
+            .map(|s| s.authlib_injector_enabled)
+            .unwrap_or(false)
+    }
+
+    /// Passwords and tokens are sent to the API root, so plain http is only accepted for a
+    /// server on this machine.
+    fn ensure_secure(url: &reqwest::Url) -> Result<(), Box<dyn std::error::Error>> {
+        let loopback = match url.host() {
+            Some(url::Host::Domain(domain)) => domain.eq_ignore_ascii_case("localhost"),
+            Some(url::Host::Ipv4(ip)) => ip.is_loopback(),
+            Some(url::Host::Ipv6(ip)) => ip.is_loopback(),
+            None => false,
+        };
+        match url.scheme() {
+            "https" => Ok(()),
+            "http" if loopback => Ok(()),
+            _ => Err("Auth server must use HTTPS".into()),
+        }
+    }
+
+    /// Resolves the address a user typed (often the server's website) to its API root.
+    pub async fn resolve_api_root(client: &reqwest::Client, url: &str) -> Result<String, Box<dyn std::error::Error>> {
+        let url = url.trim().trim_end_matches('/');
+        let url = if url.starts_with("https://") || url.starts_with("http://") {
+            url.to_string()
+        } else {
+            format!("https://{}", url)
+        };
+        let url = reqwest::Url::parse(&url)?;
+        Self::ensure_secure(&url)?;
+
+        let response = client.get(url.clone()).send().await?;
+        let api_root = match response.headers().get(API_LOCATION_HEADER).and_then(|h| h.to_str().ok()) {
+            Some(location) => url.join(location)?,
+            None => url,
+        };
+        // The header comes from the server, it mustn't downgrade the connection
+        Self::ensure_secure(&api_root)?;
+
+        Ok(api_root.as_str().trim_end_matches('/').to_string())
+    }
+
+    /// Downloads the latest authlib-injector if it isn't installed yet.
+    pub async fn ensure_installed(client: &reqwest::Client) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        let jar_path = Self::jar_path();
+        if jar_path.exists() {
+            return Ok(jar_path);
+        }
+
+        let artifact: LatestArtifact = client.get(LATEST_ARTIFACT_URL).send().await?.error_for_status()?.json().await?;
+        let bytes = client.get(&artifact.download_url).send().await?.error_for_status()?.bytes().await?;
+
+        let sha256 = {
+            use sha2::{Digest, Sha256};
+            format!("{:x}", Sha256::digest(&bytes))
+        };
+        if !sha256.eq_ignore_ascii_case(&artifact.checksums.sha256) {
+            return Err("authlib-injector download is corrupted (checksum mismatch)".into());
+        }
+
+        fs::create_dir_all(Self::dir())?;
+        let temp_path = jar_path.with_extension("jar.part");
+        fs::write(&temp_path, &bytes)?;
+        fs::rename(&temp_path, &jar_path)?;
+
+        Ok(jar_path)
+    }
+
+    /// Caches the server's metadata so the agent doesn't have to fetch it during game start.
+    pub async fn prefetch_metadata(client: &reqwest::Client, api_root: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let body = client.get(api_root).send().await?.error_for_status()?.bytes().await?;
+        serde_json::from_slice::<serde_json::Value>(&body)?;
+
+        let path = Self::prefetched_path(api_root);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, general_purpose::STANDARD.encode(&body))?;
+        Ok(())
+    }
+
+    /// JVM arguments that load the agent for `api_root`. The jar must already be installed,
+    /// which `AccountManager::get_valid_token` takes care of for third-party accounts.
+    pub fn launch_args(api_root: &str) -> Result<Vec<String>, String> {
+        if !Self::is_enabled() {
+            return Err("Third-party auth servers are disabled, enable them in the advanced settings".to_string());
+        }
+
+        let jar_path = Self::jar_path();
+        if !jar_path.exists() {
+            return Err("authlib-injector is not installed".to_string());
+        }
+
+        let mut args = vec![
+            format!("-javaagent:{}={}", jar_path.display(), api_root),
+            "-Dauthlibinjector.side=client".to_string(),
+        ];
+        if let Ok(prefetched) = fs::read_to_string(Self::prefetched_path(api_root)) {
+            args.push(format!("-Dauthlibinjector.yggdrasil.prefetched={}", prefetched.trim()));
+        }
+
+        Ok(args)
+    }
+
+    pub async fn authenticate(
+        client: &reqwest::Client,
+        api_root: &str,
+        username: &str,
+        password: &str,
+    ) -> Result<YggdrasilSession, Box<dyn std::error::Error>> {
+        Self::ensure_secure(&reqwest::Url::parse(api_root)?)?;
+
+        let response = client
+            .post(format!("{}/authserver/authenticate", api_root))
+            .json(&serde_json::json!({
+                "agent": { "name": "Minecraft", "version": 1 },
+                "username": username,
+                "password": password,
+                "requestUser": false
+            }))
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::error_message(response).await.into());
+        }
+
+        let auth: YggdrasilResponse = response.json().await?;
+        if let Some(profile) = auth.selected_profile {
+            return Ok(Self::session(auth.access_token, auth.client_token, profile));
+        }
+
+        // Servers with several characters per login leave the choice to the launcher
+        let profile = auth.available_profiles.first().cloned().ok_or("This account has no Minecraft profile")?;
+        Self::refresh_profile(client, api_root, &auth.access_token, &auth.client_token, Some(&profile)).await
+    }
+
+    pub async fn refresh(
+        client: &reqwest::Client,
+        api_root: &str,
+        access_token: &str,
+        client_token: &str,
+    ) -> Result<YggdrasilSession, Box<dyn std::error::Error>> {
+        Self::refresh_profile(client, api_root, access_token, client_token, None).await
+    }
+
+    async fn refresh_profile(
+        client: &reqwest::Client,
+        api_root: &str,
+        access_token: &str,
+        client_token: &str,
+        profile: Option<&YggdrasilProfile>,
+    ) -> Result<YggdrasilSession, Box<dyn std::error::Error>> {
+        let mut body = serde_json::json!({
+            "accessToken": access_token,
+            "clientToken": client_token
+        });
+        if let Some(profile) = profile {
+            body["selectedProfile"] = serde_json::to_value(profile)?;
+        }
+
+        let response = client
+            .post(format!("{}/authserver/refresh", api_root))
+            .json(&body)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            return Err(Self::error_message(response).await.into());
+        }
+
+        let auth: YggdrasilResponse = response.json().await?;
+        let profile = auth.selected_profile.ok_or("Auth server did not return a profile")?;
+        Ok(Self::session(auth.access_token, auth.client_token, profile))
+    }
+
+    pub async fn validate(client: &reqwest::Client, api_root: &str, access_token: &str, client_token: &str) -> bool {
+        client
+            .post(format!("{}/authserver/validate", api_root))
+            .json(&serde_json::json!({
+                "accessToken": access_token,
+                "clientToken": client_token
+            }))
+            .send()
+            .await
+            .is_ok_and(|r| r.status().is_success())
+    }
+
+    fn session(access_token: String, client_token: String, profile: YggdrasilProfile) -> YggdrasilSession {
+        YggdrasilSession {
+            uuid: profile.id,
+            username: profile.name,
+            access_token,
+            client_token,
+        }
+    }
+
+    async fn error_message(response: reqwest::Response) -> String {
+        let status = response.status();
+        response
+            .json::<serde_json::Value>()
+            .await
+            .ok()
+            .and_then(|body| body["errorMessage"].as_str().map(str::to_string))
+            .unwrap_or_else(|| format!("Auth server returned {}", status))
+    }
+}
@@ -0,0 +1,163 @@
+use crate::models::AutomationSettings;
+use crate::services::settings::SettingsManager;
+use serde::Serialize;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::time::Duration;
+
+/// How long one delivery may take, so an endpoint that hangs doesn't pile up tasks.
+const DELIVERY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Something that happened in the launcher, sent as `{"event": "launch", "timestamp": ..., ...}`.
+#[derive(Debug, Serialize, Clone)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum AutomationEvent {
+    /// The game process of an instance started.
+    Launch { instance: String },
+    /// The game process ended. Not a crash when the launcher killed it.
+    Exit {
+        instance: String,
+        exit_code: Option<i32>,
+        crashed: bool,
+        play_seconds: u64,
+    },
+    /// An instance was created or a modpack installed. `operation` is the `OperationKind`.
+    Install { instance: String, operation: String },
+    /// Sent from the settings to check the targets.
+    Test,
+}
+
+#[derive(Serialize)]
+struct Payload<'a> {
+    #[serde(flatten)]
+    event: &'a AutomationEvent,
+    timestamp: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SocketTarget {
+    Tcp(SocketAddr),
+    #[cfg(unix)]
+    Unix(std::path::PathBuf),
+}
+
+impl SocketTarget {
+    /// Parses a loopback `host:port`, `localhost:port` or, on Unix, an absolute socket path.
+    /// Other hosts are refused, remote targets go through the webhook.
+    pub fn parse(address: &str) -> Result<Self, String> {
+        let address = address.trim();
+
+        if let Ok(addr) = address.parse::<SocketAddr>() {
+            if !addr.ip().is_loopback() {
+                return Err("Automation socket must be on this machine (127.0.0.1 or ::1)".to_string());
+            }
+            return Ok(Self::Tcp(addr));
+        }
+
+        if let Some(port) = address.strip_prefix("localhost:") {
+            let port = port.parse::<u16>().map_err(|_| format!("Invalid port in '{}'", address))?;
+            return Ok(Self::Tcp(SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), port)));
+        }
+
+        #[cfg(unix)]
+        if address.starts_with('/') {
+            return Ok(Self::Unix(std::path::PathBuf::from(address)));
+        }
+
+        Err("Automation socket must be 127.0.0.1:<port>, localhost:<port> or an absolute Unix socket path".to_string())
+    }
+}
+
+/// Forwards `event` to the configured webhook and socket in the background. Does nothing when
+/// automation is off, failed deliveries are only logged.
+pub fn emit(app_handle: &tauri::AppHandle, event: AutomationEvent) {
+    let Ok(settings) = SettingsManager::load() else {
+        return;
+    };
+    if !settings.automation.enabled {
+        return;
+    }
+
+    let http_client = crate::utils::http::client_from(app_handle);
+    tauri::async_runtime::spawn(async move {
+        for error in deliver(&http_client, &settings.automation, &event).await {
+            eprintln!("Failed to deliver automation event: {}", error);
+        }
+    });
+}
+
+/// Sends `event` to every configured target, whether automation is enabled or not. Returns
+/// one error per target that couldn't be reached.
+pub async fn deliver(
+    http_client: &reqwest::Client,
+    automation: &AutomationSettings,
+    event: &AutomationEvent,
+) -> Vec<String> {
+    let payload = Payload { event, timestamp: chrono::Utc::now().to_rfc3339() };
+    let body = match serde_json::to_string(&payload) {
+        Ok(body) => body,
+        Err(e) => return vec![e.to_string()],
+    };
+
+    let mut errors = Vec::new();
+
+    if let Some(webhook_url) = automation.webhook_url.as_deref().map(str::trim).filter(|u| !u.is_empty()) {
+        if let Err(e) = post_webhook(http_client, webhook_url, &body).await {
+            errors.push(format!("webhook: {}", e));
+        }
+    }
+
+    if let Some(address) = automation.socket_address.as_deref().filter(|a| !a.trim().is_empty()) {
+        let sent = match SocketTarget::parse(address) {
+            Ok(target) => write_socket(&target, format!("{}\n", body).as_bytes()).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = sent {
+            errors.push(format!("socket: {}", e));
+        }
+    }
+
+    errors
+}
+
+async fn post_webhook(http_client: &reqwest::Client, url: &str, body: &str) -> Result<(), String> {
+    let response = http_client
+        .post(url)
+        .header("Content-Type", "application/json")
+        .body(body.to_string())
+        .timeout(DELIVERY_TIMEOUT)
+        .send()
+        .await
+        .map_err(|e| e.to_string())?;
+
+    if !response.status().is_success() {
+        return Err(format!("HTTP {}", response.status()));
+    }
+
+    Ok(())
+}
+
+/// Connects, writes `line` and closes, so listeners don't need to keep a connection open.
+async fn write_socket(target: &SocketTarget, line: &[u8]) -> Result<(), String> {
+    use tokio::io::AsyncWriteExt;
+
+    let write = async {
+        match target {
+            SocketTarget::Tcp(addr) => {
+                let mut stream = tokio::net::TcpStream::connect(addr).await?;
+                stream.write_all(line).await?;
+                stream.shutdown().await
+            }
+            #[cfg(unix)]
+            SocketTarget::Unix(path) => {
+                let mut stream = tokio::net::UnixStream::connect(path).await?;
+                stream.write_all(line).await?;
+                stream.shutdown().await
+            }
+        }
+    };
+
+    tokio::time::timeout(DELIVERY_TIMEOUT, write)
+        .await
+        .map_err(|_| "timed out".to_string())?
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,174 @@
+use crate::models::{ConfigRollbackSummary, ConfigSnapshot, ConfigSnapshotInfo};
+use crate::services::installer::MinecraftInstaller;
+use crate::utils::{get_instance_dir, get_profile_dir};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const MANIFEST_FILE: &str = "snapshot.json";
+const FILES_DIR: &str = "files";
+
+pub struct ConfigSnapshotManager;
+
+impl ConfigSnapshotManager {
+    fn snapshots_dir() -> PathBuf {
+        get_profile_dir().join("config_snapshots")
+    }
+
+    fn snapshot_dir(id: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            return Err(format!("Invalid snapshot id '{}'", id).into());
+        }
+        Ok(Self::snapshots_dir().join(id))
+    }
+
+    /// Copies the instance's `config/` tree and records the hash of every file.
+    pub fn create(instance_name: &str, label: Option<String>) -> Result<ConfigSnapshot, Box<dyn std::error::Error>> {
+        let config_dir = get_instance_dir(instance_name).join("config");
+        let id = format!("{}-{}", instance_name_slug(instance_name), chrono::Utc::now().timestamp_millis());
+        let snapshot_dir = Self::snapshot_dir(&id)?;
+        let files_dir = snapshot_dir.join(FILES_DIR);
+        fs::create_dir_all(&files_dir)?;
+
+        let mut files = BTreeMap::new();
+        let result = (|| -> Result<(), Box<dyn std::error::Error>> {
+            for relative in collect_files(&config_dir) {
+                let source = config_dir.join(&relative);
+                let target = files_dir.join(&relative);
+                if let Some(parent) = target.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::copy(&source, &target)?;
+                files.insert(relative, MinecraftInstaller::sha1_file(&target)?);
+            }
+            Ok(())
+        })();
+
+        if let Err(e) = result {
+            let _ = fs::remove_dir_all(&snapshot_dir);
+            return Err(e);
+        }
+
+        let snapshot = ConfigSnapshot {
+            id,
+            instance: instance_name.to_string(),
+            created_at: chrono::Utc::now().to_rfc3339(),
+            label,
+            files,
+        };
+
+        fs::write(snapshot_dir.join(MANIFEST_FILE), serde_json::to_string_pretty(&snapshot)?)?;
+        Ok(snapshot)
+    }
+
+    pub fn get(id: &str) -> Result<ConfigSnapshot, Box<dyn std::error::Error>> {
+        let manifest = Self::snapshot_dir(id)?.join(MANIFEST_FILE);
+        let content = fs::read_to_string(&manifest).map_err(|_| format!("Snapshot '{}' does not exist", id))?;
+        Ok(serde_json::from_str(&content)?)
+    }
+
+    /// Snapshots of the instance, newest first.
+    pub fn list(instance_name: &str) -> Vec<ConfigSnapshotInfo> {
+        let Ok(entries) = fs::read_dir(Self::snapshots_dir()) else {
+            return Vec::new();
+        };
+
+        let mut snapshots: Vec<ConfigSnapshotInfo> = entries
+            .flatten()
+            .filter_map(|e| fs::read_to_string(e.path().join(MANIFEST_FILE)).ok())
+            .filter_map(|content| serde_json::from_str::<ConfigSnapshot>(&content).ok())
+            .filter(|snapshot| snapshot.instance == instance_name)
+            .map(|snapshot| ConfigSnapshotInfo {
+                file_count: snapshot.files.len(),
+                id: snapshot.id,
+                instance: snapshot.instance,
+                created_at: snapshot.created_at,
+                label: snapshot.label,
+            })
+            .collect();
+
+        snapshots.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        snapshots
+    }
+
+    pub fn delete(id: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let snapshot_dir = Self::snapshot_dir(id)?;
+        if snapshot_dir.exists() {
+            fs::remove_dir_all(snapshot_dir)?;
+        }
+        Ok(())
+    }
+
+    /// Makes the instance's `config/` tree match the snapshot: changed and missing files are
+    /// restored, files created since the snapshot are removed.
+    pub fn rollback(snapshot: &ConfigSnapshot) -> Result<ConfigRollbackSummary, Box<dyn std::error::Error>> {
+        let files_dir = Self::snapshot_dir(&snapshot.id)?.join(FILES_DIR);
+        let config_dir = get_instance_dir(&snapshot.instance).join("config");
+        let mut summary = ConfigRollbackSummary::default();
+
+        // Verify the stored copies first so a damaged snapshot never half-restores
+        for (relative, sha1) in &snapshot.files {
+            if Path::new(relative).components().any(|c| !matches!(c, std::path::Component::Normal(_))) {
+                return Err(format!("Invalid path in snapshot: {}", relative).into());
+            }
+
+            let stored = files_dir.join(relative);
+            if MinecraftInstaller::sha1_file(&stored).ok().as_deref() != Some(sha1.as_str()) {
+                return Err(format!("Snapshot copy of {} is missing or damaged", relative).into());
+            }
+        }
+
+        for relative in collect_files(&config_dir) {
+            if !snapshot.files.contains_key(&relative) {
+                fs::remove_file(config_dir.join(&relative))?;
+                summary.removed.push(relative);
+            }
+        }
+
+        for (relative, sha1) in &snapshot.files {
+            let target = config_dir.join(relative);
+            if MinecraftInstaller::sha1_file(&target).ok().as_deref() == Some(sha1.as_str()) {
+                summary.unchanged += 1;
+                continue;
+            }
+
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(files_dir.join(relative), &target)?;
+            summary.restored.push(relative.clone());
+        }
+
+        Ok(summary)
+    }
+}
+
+fn instance_name_slug(instance_name: &str) -> String {
+    instance_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c.to_ascii_lowercase() } else { '_' })
+        .take(32)
+        .collect()
+}
+
+/// Relative paths (forward slashes) of every file below `dir`.
+fn collect_files(dir: &Path) -> Vec<String> {
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+
+    while let Some(current) = pending.pop() {
+        let Ok(entries) = fs::read_dir(&current) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                pending.push(path);
+            } else if let Ok(relative) = path.strip_prefix(dir) {
+                files.push(relative.to_string_lossy().replace('\\', "/"));
+            }
+        }
+    }
+
+    files
+}
@@ -0,0 +1,225 @@
+use crate::models::LastCrash;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tauri::Emitter;
+
+struct CrashSignature {
+    id: &'static str,
+    patterns: &'static [&'static str],
+    title: &'static str,
+    explanation: &'static str,
+    suggestion: &'static str,
+}
+
+// Ordered from most to least specific; the first match wins
+const SIGNATURES: &[CrashSignature] = &[
+    CrashSignature {
+        id: "wrong_java_version",
+        patterns: &["UnsupportedClassVersionError", "has been compiled by a more recent version of the Java Runtime"],
+        title: "Wrong Java version",
+        explanation: "The game or one of its mods was compiled for a newer Java version than the one used to launch it.",
+        suggestion: "Select a newer Java installation in Settings (Java 21 for Minecraft 1.20.5+, Java 17 for 1.18-1.20.4).",
+    },
+    CrashSignature {
+        id: "out_of_memory",
+        patterns: &["java.lang.OutOfMemoryError", "Could not reserve enough space for object heap"],
+        title: "Out of memory",
+        explanation: "Java ran out of memory, or could not reserve the amount of memory requested.",
+        suggestion: "Adjust the memory allocation in Settings. Large modpacks usually need 6-8 GB; 32-bit Java cannot use more than ~1.5 GB.",
+    },
+    CrashSignature {
+        id: "missing_natives",
+        patterns: &[
+            "java.lang.UnsatisfiedLinkError",
+            "no lwjgl in java.library.path",
+            "Failed to locate library: lwjgl",
+            "No native libraries found",
+        ],
+        title: "Missing native libraries",
+        explanation: "The native libraries (LWJGL, OpenAL, GLFW) required by the game could not be loaded.",
+        suggestion: "Reinstall the Minecraft version for this instance. If it persists, make sure your Java matches your OS architecture (64-bit).",
+    },
+    CrashSignature {
+        id: "opengl_driver",
+        patterns: &[
+            "Pixel format not accelerated",
+            "GLFW error 65542",
+            "GLFW error 65543",
+            "WGL: The driver does not appear to support OpenGL",
+            "OpenGL 1.1",
+            "Couldn't set pixel format",
+        ],
+        title: "Graphics driver problem",
+        explanation: "The game could not create an OpenGL context. This usually means the graphics driver is missing, outdated, or the game is running on the wrong GPU.",
+        suggestion: "Install the latest driver from your GPU vendor (not Windows Update) and make sure Java runs on your dedicated GPU.",
+    },
+    CrashSignature {
+        id: "mixin_conflict",
+        patterns: &[
+            "MixinApplyError",
+            "Mixin apply failed",
+            "Mixin transformation of",
+            "InvalidInjectionException",
+            "org.spongepowered.asm.mixin.transformer.throwables",
+        ],
+        title: "Mod conflict (mixin)",
+        explanation: "A mod failed to patch the game, usually because two mods modify the same code or a mod is built for a different game version.",
+        suggestion: "Check the log for the mod named in the mixin error, update or remove it, or use mod bisect to find the conflicting mod.",
+    },
+];
+
+/// Problems that don't crash the game but make it unplayable, usually a few FPS. Checked
+/// against the first lines of output, where the game and mods like Sodium report the renderer.
+const RENDER_SIGNATURES: &[CrashSignature] = &[
+    CrashSignature {
+        id: "software_rendering",
+        patterns: &[
+            "llvmpipe",
+            "softpipe",
+            "SwiftShader",
+            "GDI Generic",
+            "Microsoft Basic Render Driver",
+        ],
+        title: "Running on software rendering",
+        explanation: "The game is drawn by the CPU instead of the graphics card, because no working OpenGL driver was found for it. Expect a few FPS.",
+        suggestion: "Install the latest driver from your GPU vendor, and on laptops set Java to use the dedicated GPU in the system graphics settings.",
+    },
+    CrashSignature {
+        id: "opengl_error",
+        patterns: &[
+            "########## GL ERROR ##########",
+            "OpenGL Error:",
+            "GL_INVALID_OPERATION",
+            "GL_OUT_OF_MEMORY",
+        ],
+        title: "OpenGL errors",
+        explanation: "The graphics driver is reporting OpenGL errors, which often comes with rendering glitches or very low FPS.",
+        suggestion: "Update your graphics driver and make sure the game runs on the dedicated GPU. Removing shaders or rendering mods can help narrow it down.",
+    },
+];
+
+/// Starts of the output lines printed for an uncaught exception, which ends the thread it
+/// happened on. Exceptions the game and mods log and recover from are printed differently.
+const FATAL_LINE_PREFIXES: &[&str] = &["Exception in thread "];
+
+/// Lines of game output checked for rendering problems.
+pub const RENDER_CHECK_LINES: usize = 2000;
+
+#[derive(Debug, Serialize, Clone)]
+pub struct CrashHint {
+    pub id: String,
+    pub title: String,
+    pub explanation: String,
+    pub suggestion: String,
+}
+
+fn match_signatures(signatures: &[CrashSignature], text: &str) -> Option<CrashHint> {
+    signatures
+        .iter()
+        .find(|sig| sig.patterns.iter().any(|p| text.contains(p)))
+        .map(|sig| CrashHint {
+            id: sig.id.to_string(),
+            title: sig.title.to_string(),
+            explanation: sig.explanation.to_string(),
+            suggestion: sig.suggestion.to_string(),
+        })
+}
+
+/// Returns a hint for the first known crash signature found in `text`.
+pub fn match_crash_text(text: &str) -> Option<CrashHint> {
+    match_signatures(SIGNATURES, text)
+}
+
+/// Returns a hint when `line` reports an uncaught exception matching a known crash signature.
+/// Used on live output, where ordinary log lines often mention the same class names.
+pub fn match_fatal_line(line: &str) -> Option<CrashHint> {
+    let line = line.trim_start();
+    if !FATAL_LINE_PREFIXES.iter().any(|prefix| line.starts_with(prefix)) {
+        return None;
+    }
+    match_crash_text(line)
+}
+
+/// Returns a warning when `line` shows the game rendering in software or hitting OpenGL errors.
+pub fn match_render_text(line: &str) -> Option<CrashHint> {
+    match_signatures(RENDER_SIGNATURES, line)
+}
+
+/// The newest crash report written after `since`.
+fn newest_crash_report(instance_dir: &Path, since: std::time::SystemTime) -> Option<PathBuf> {
+    std::fs::read_dir(instance_dir.join("crash-reports"))
+        .ok()
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter_map(|e| {
+            let modified = e.metadata().ok()?.modified().ok()?;
+            (modified >= since).then(|| (modified, e.path()))
+        })
+        .max_by_key(|(modified, _)| *modified)
+        .map(|(_, path)| path)
+}
+
+/// Scans the newest crash report written after `since`, falling back to `logs/latest.log`.
+pub fn analyze_instance_crash(instance_dir: &Path, since: std::time::SystemTime) -> Option<CrashHint> {
+    let candidates = newest_crash_report(instance_dir, since)
+        .into_iter()
+        .chain(std::iter::once(instance_dir.join("logs").join("latest.log")));
+
+    for path in candidates {
+        if let Ok(bytes) = std::fs::read(&path) {
+            if let Some(hint) = match_crash_text(&String::from_utf8_lossy(&bytes)) {
+                return Some(hint);
+            }
+        }
+    }
+
+    None
+}
+
+/// Builds the `last_crash` entry for a session that started at `since`.
+pub fn summarize_crash(
+    instance_dir: &Path,
+    since: std::time::SystemTime,
+    exit_code: Option<i32>,
+    hint: Option<&CrashHint>,
+) -> LastCrash {
+    let report = newest_crash_report(instance_dir, since);
+
+    // Crash reports open with a `Description: Rendering overlay` style line
+    let description = report.as_deref().and_then(|path| {
+        let content = std::fs::read_to_string(path).ok()?;
+        content
+            .lines()
+            .find_map(|line| line.strip_prefix("Description:"))
+            .map(|d| d.trim().to_string())
+            .filter(|d| !d.is_empty())
+    });
+
+    LastCrash {
+        crashed_at: chrono::Utc::now().to_rfc3339(),
+        exit_code,
+        crash_report: report.map(|path| path.to_string_lossy().to_string()),
+        reason: hint.map(|h| h.title.clone()).or(description),
+    }
+}
+
+pub fn emit_render_warning(app_handle: &tauri::AppHandle, instance_name: &str, hint: &CrashHint) {
+    let _ = app_handle.emit("render-warning", serde_json::json!({
+        "instance": instance_name,
+        "id": hint.id,
+        "title": hint.title,
+        "explanation": hint.explanation,
+        "suggestion": hint.suggestion
+    }));
+}
+
+pub fn emit_crash_hint(app_handle: &tauri::AppHandle, instance_name: &str, hint: &CrashHint) {
+    let _ = app_handle.emit("crash-hint", serde_json::json!({
+        "instance": instance_name,
+        "id": hint.id,
+        "title": hint.title,
+        "explanation": hint.explanation,
+        "suggestion": hint.suggestion
+    }));
+}
@@ -1,5 +1,5 @@
 use crate::models::*;
-use std::{fs, path::PathBuf};
+use std::{fs, path::{Path, PathBuf}};
 
 const FABRIC_META_URL: &str = "https://meta.fabricmc.net/v2";
 
@@ -17,6 +17,10 @@ impl FabricInstaller {
     }
 
     pub async fn get_loader_versions(&self) -> Result<Vec<FabricLoaderVersion>, Box<dyn std::error::Error>> {
+        if let Some(versions) = crate::services::metadata_cache::FABRIC_LOADERS.get() {
+            return Ok(versions);
+        }
+
         let url = format!("{}/versions/loader", FABRIC_META_URL);
         let response = self.http_client.get(&url).send().await?;
 
@@ -25,6 +29,7 @@ impl FabricInstaller {
         }
 
         let versions: Vec<FabricLoaderVersion> = response.json().await?;
+        crate::services::metadata_cache::FABRIC_LOADERS.set(versions.clone());
         Ok(versions)
     }
 
@@ -121,52 +126,61 @@ impl FabricInstaller {
         loader_version: &str,
     ) -> Result<String, Box<dyn std::error::Error>> {
         let profile = self.get_fabric_profile(minecraft_version, loader_version).await?;
-        
-        let fabric_id = profile.id.clone();
-        let versions_dir = self.launcher_dir.join("versions").join(&fabric_id);
-        let libraries_dir = self.launcher_dir.join("libraries");
+        install_profile(&self.http_client, &self.launcher_dir, &profile).await
+    }
 
-        fs::create_dir_all(&versions_dir)?;
-        fs::create_dir_all(&libraries_dir)?;
+}
 
-        for lib in &profile.libraries {
-            let parts: Vec<&str> = lib.name.split(':').collect();
-            if parts.len() != 3 {
-                continue;
-            }
+/// Downloads the libraries of a Fabric-style launcher profile and saves it as a version,
+/// returning the version id. Quilt publishes profiles in the same format.
+pub(crate) async fn install_profile(
+    http_client: &reqwest::Client,
+    launcher_dir: &Path,
+    profile: &FabricProfileJson,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let profile_id = profile.id.clone();
+    let versions_dir = launcher_dir.join("versions").join(&profile_id);
+    let libraries_dir = launcher_dir.join("libraries");
+
+    fs::create_dir_all(&versions_dir)?;
+    fs::create_dir_all(&libraries_dir)?;
+
+    for lib in &profile.libraries {
+        let parts: Vec<&str> = lib.name.split(':').collect();
+        if parts.len() != 3 {
+            continue;
+        }
 
-            let (group, artifact, version) = (parts[0], parts[1], parts[2]);
-            let group_path = group.replace('.', "/");
-            let jar_name = format!("{}-{}.jar", artifact, version);
-            let lib_path = libraries_dir.join(&group_path).join(artifact).join(version).join(&jar_name);
-
-            let base_url = if lib.url.ends_with('/') {
-                lib.url.trim_end_matches('/')
-            } else {
-                &lib.url
-            };
-            let url = format!("{}/{}/{}/{}/{}", base_url, group_path, artifact, version, jar_name);
-
-            if !lib_path.exists() {
-                if let Some(parent) = lib_path.parent() {
-                    fs::create_dir_all(parent)?;
-                }
+        let (group, artifact, version) = (parts[0], parts[1], parts[2]);
+        let group_path = group.replace('.', "/");
+        let jar_name = format!("{}-{}.jar", artifact, version);
+        let lib_path = libraries_dir.join(&group_path).join(artifact).join(version).join(&jar_name);
+
+        let base_url = if lib.url.ends_with('/') {
+            lib.url.trim_end_matches('/')
+        } else {
+            &lib.url
+        };
+        let url = format!("{}/{}/{}/{}/{}", base_url, group_path, artifact, version, jar_name);
+
+        if !lib_path.exists() {
+            if let Some(parent) = lib_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
 
-                if let Ok(response) = self.http_client.get(&url).send().await {
-                    if response.status().is_success() {
-                        if let Ok(bytes) = response.bytes().await {
-                            let _ = fs::write(&lib_path, bytes);
-                        }
+            if let Ok(response) = http_client.get(&url).send().await {
+                if response.status().is_success() {
+                    if let Ok(bytes) = response.bytes().await {
+                        let _ = fs::write(&lib_path, bytes);
                     }
                 }
             }
         }
-
-        let profile_path = versions_dir.join(format!("{}.json", fabric_id));
-        let profile_json = serde_json::to_string_pretty(&profile)?;
-        fs::write(&profile_path, profile_json)?;
-
-        Ok(fabric_id)
     }
 
-}
\ No newline at end of file
+    let profile_path = versions_dir.join(format!("{}.json", profile_id));
+    let profile_json = serde_json::to_string_pretty(profile)?;
+    fs::write(&profile_path, profile_json)?;
+
+    Ok(profile_id)
+}
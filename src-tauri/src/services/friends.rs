@@ -17,6 +17,22 @@ impl FriendsService {
         })
     }
 
+    /// Marks every stored account offline, used when the launcher shuts down.
+    pub async fn set_all_offline(supabase_url: &str, supabase_key: &str) {
+        let Ok(accounts) = crate::services::accounts::AccountManager::get_all_accounts() else {
+            return;
+        };
+        let Ok(service) = Self::new(supabase_url, supabase_key) else {
+            return;
+        };
+
+        for account in &accounts {
+            let _ = service
+                .update_status(&account.uuid, FriendStatus::Offline, None)
+                .await;
+        }
+    }
+
     pub async fn register_user(&self, uuid: &str, username: &str) -> Result<(), Box<dyn std::error::Error>> {
         let url = format!("{}/rest/v1/users", self.supabase_url);
         
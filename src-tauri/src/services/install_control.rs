@@ -0,0 +1,55 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::watch;
+
+lazy_static::lazy_static! {
+    static ref INSTALL_CONTROLS: Mutex<HashMap<String, Arc<InstallControl>>> = Mutex::new(HashMap::new());
+}
+
+/// Pause switch for a running modpack download, keyed by instance name.
+pub struct InstallControl {
+    paused: watch::Sender<bool>,
+}
+
+impl InstallControl {
+    pub fn pause(&self) {
+        self.paused.send_replace(true);
+    }
+
+    pub fn resume(&self) {
+        self.paused.send_replace(false);
+    }
+
+    pub fn is_paused(&self) -> bool {
+        *self.paused.borrow()
+    }
+
+    /// Returns immediately when not paused, otherwise once `resume` is called.
+    pub async fn wait_while_paused(&self) {
+        let mut rx = self.paused.subscribe();
+        let _ = rx.wait_for(|paused| !*paused).await;
+    }
+}
+
+/// Registers the download of `instance_name`, starting paused if `paused` is set.
+pub fn register(instance_name: &str, paused: bool) -> Arc<InstallControl> {
+    let control = Arc::new(InstallControl {
+        paused: watch::channel(paused).0,
+    });
+
+    if let Ok(mut controls) = INSTALL_CONTROLS.lock() {
+        controls.insert(instance_name.to_string(), control.clone());
+    }
+
+    control
+}
+
+pub fn unregister(instance_name: &str) {
+    if let Ok(mut controls) = INSTALL_CONTROLS.lock() {
+        controls.remove(instance_name);
+    }
+}
+
+pub fn get(instance_name: &str) -> Option<Arc<InstallControl>> {
+    INSTALL_CONTROLS.lock().ok()?.get(instance_name).cloned()
+}
@@ -1,17 +1,21 @@
 use crate::models::*;
 use crate::utils::{get_current_os, library_maven_path, library_maven_url};
 use sha1::{Digest, Sha1};
+use std::collections::HashMap;
 use std::{fs, path::PathBuf, sync::Arc};
 use tokio::sync::Semaphore;
 
 const VERSION_MANIFEST_URL: &str = "https://launchermeta.mojang.com/mc/game/version_manifest.json";
 const MAX_CONCURRENT_DOWNLOADS: usize = 32;
+/// Asset index id -> sha1 of every index whose objects were fully downloaded.
+pub(crate) const INSTALLED_ASSET_INDEXES_FILE: &str = "installed_indexes.json";
 
 type DownloadError = Box<dyn std::error::Error + Send + Sync>;
 
 pub struct MinecraftInstaller {
     http_client: reqwest::Client,
     launcher_dir: PathBuf,
+    force_verify: bool,
 }
 
 impl MinecraftInstaller {
@@ -19,9 +23,34 @@ impl MinecraftInstaller {
         Ok(Self {
             http_client: crate::utils::http::get_client(),
             launcher_dir,
+            force_verify: false,
         })
     }
 
+    /// Re-check every asset object even when the asset index is already recorded as installed.
+    pub fn with_force_verify(mut self, force_verify: bool) -> Self {
+        self.force_verify = force_verify;
+        self
+    }
+
+    fn installed_asset_indexes_path(&self) -> PathBuf {
+        self.launcher_dir.join("assets").join(INSTALLED_ASSET_INDEXES_FILE)
+    }
+
+    fn load_installed_asset_indexes(&self) -> HashMap<String, String> {
+        fs::read_to_string(self.installed_asset_indexes_path())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn mark_asset_index_installed(&self, index_id: &str, sha1: &str) -> Result<(), DownloadError> {
+        let mut installed = self.load_installed_asset_indexes();
+        installed.insert(index_id.to_string(), sha1.to_string());
+        fs::write(self.installed_asset_indexes_path(), serde_json::to_string_pretty(&installed)?)?;
+        Ok(())
+    }
+
     // Ensure launcher_profiles.json exists
     fn ensure_launcher_profile(&self) -> Result<(), DownloadError> {
         let launcher_profiles_path = self.launcher_dir.join("launcher_profiles.json");
@@ -72,27 +101,77 @@ impl MinecraftInstaller {
             return true;
         }
 
-        let Some(expected_sha1) = expected_sha1 else {
+        let Some(expected_sha1) = expected_sha1.filter(|s| !s.is_empty()) else {
             return false;
         };
 
-        if let Ok(contents) = fs::read(path) {
-            let mut hasher = Sha1::new();
-            hasher.update(&contents);
-            let hash = format!("{:x}", hasher.finalize());
-            hash != expected_sha1
-        } else {
-            true
+        match Self::sha1_file(path) {
+            Ok(hash) => hash != expected_sha1,
+            Err(_) => true,
         }
     }
 
+    /// Hashes in chunks so large jars are never held in memory whole.
+    pub(crate) fn sha1_file(path: &PathBuf) -> std::io::Result<String> {
+        use std::io::Read;
+
+        let mut file = fs::File::open(path)?;
+        let mut hasher = Sha1::new();
+        let mut buffer = vec![0u8; 64 * 1024];
+
+        loop {
+            let read = file.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            hasher.update(&buffer[..read]);
+        }
+
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+
+    /// Runs `file_needs_download` on the blocking pool so hashing never stalls the async runtime.
+    async fn file_needs_download_async(path: PathBuf, expected_sha1: String) -> bool {
+        tokio::task::spawn_blocking(move || Self::file_needs_download(&path, Some(&expected_sha1)))
+            .await
+            .unwrap_or(true)
+    }
+
+    /// Verifies existing files concurrently (one hashing task per CPU) and returns only
+    /// the tasks whose file is missing or does not match its checksum.
+    async fn filter_tasks_needing_download(
+        tasks: Vec<(String, PathBuf, String)>,
+    ) -> Result<Vec<(String, PathBuf, String)>, DownloadError> {
+        let hash_workers = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4);
+        let semaphore = Arc::new(Semaphore::new(hash_workers));
+        let mut handles = Vec::with_capacity(tasks.len());
+
+        for task in tasks {
+            let permit = semaphore.clone().acquire_owned().await?;
+            handles.push(tokio::task::spawn_blocking(move || {
+                let needed = Self::file_needs_download(&task.1, Some(&task.2));
+                drop(permit);
+                needed.then_some(task)
+            }));
+        }
+
+        let mut remaining = Vec::new();
+        for handle in handles {
+            if let Some(task) = handle.await? {
+                remaining.push(task);
+            }
+        }
+
+        Ok(remaining)
+    }
+
     async fn download_file_with_sha1(
         &self,
         url: &str,
         path: &PathBuf,
         expected_sha1: &str,
     ) -> Result<bool, DownloadError> {
-        if !Self::file_needs_download(path, Some(expected_sha1)) {
+        if !Self::file_needs_download_async(path.clone(), expected_sha1.to_string()).await {
             return Ok(false);
         }
 
@@ -100,9 +179,23 @@ impl MinecraftInstaller {
         Ok(true)
     }
 
-    pub async fn get_versions(&self) -> Result<Vec<String>, DownloadError> {
+    async fn fetch_manifest(&self) -> Result<VersionManifest, DownloadError> {
         let response = self.http_client.get(VERSION_MANIFEST_URL).send().await?;
         let manifest: VersionManifest = response.json().await?;
+        crate::services::metadata_cache::VERSION_MANIFEST.set(manifest.clone());
+        Ok(manifest)
+    }
+
+    /// The version manifest, from memory when it was fetched recently.
+    async fn manifest(&self) -> Result<VersionManifest, DownloadError> {
+        match crate::services::metadata_cache::VERSION_MANIFEST.get() {
+            Some(manifest) => Ok(manifest),
+            None => self.fetch_manifest().await,
+        }
+    }
+
+    pub async fn get_versions(&self) -> Result<Vec<String>, DownloadError> {
+        let manifest = self.manifest().await?;
 
         let versions: Vec<String> = manifest
             .versions
@@ -116,22 +209,32 @@ impl MinecraftInstaller {
     }
 
     pub async fn get_versions_with_metadata(&self) -> Result<Vec<MinecraftVersion>, DownloadError> {
-        let response = self.http_client.get(VERSION_MANIFEST_URL).send().await?;
-        let manifest: VersionManifest = response.json().await?;
+        let manifest = self.manifest().await?;
 
+        let latest = manifest.latest;
         let versions: Vec<MinecraftVersion> = manifest
             .versions
             .into_iter()
             .filter(|v| v.r#type == "release" || v.r#type == "snapshot")
             .take(500)
+            .map(|v| MinecraftVersion {
+                is_latest_release: v.id == latest.release,
+                is_latest_snapshot: v.id == latest.snapshot,
+                ..v
+            })
             .collect();
 
         Ok(versions)
     }
 
+    /// The newest release and snapshot ids from the manifest's `latest` block, always
+    /// fetched fresh.
+    pub async fn get_latest_versions(&self) -> Result<Latest, DownloadError> {
+        Ok(self.fetch_manifest().await?.latest)
+    }
+
     pub async fn get_versions_by_type(&self, version_type: &str) -> Result<Vec<String>, DownloadError> {
-        let response = self.http_client.get(VERSION_MANIFEST_URL).send().await?;
-        let manifest: VersionManifest = response.json().await?;
+        let manifest = self.manifest().await?;
 
         let versions: Vec<String> = manifest
             .versions
@@ -238,27 +341,13 @@ impl MinecraftInstaller {
             }
             
             if let Some(downloads) = &library.downloads {
-                if let Some(classifiers) = &downloads.classifiers {
-                    for (key, artifact) in classifiers {
-                        let platform_suffix = if key.contains("natives-windows") {
-                            "windows"
-                        } else if key.contains("natives-linux") {
-                            "linux"
-                        } else {
-                            continue;
-                        };
-
-                        if platform_suffix != current_os {
-                            continue;
-                        }
-
-                        native_count += 1;
-                        library_tasks.push((
-                            artifact.url.clone(),
-                            libraries_dir.join(&artifact.path),
-                            artifact.sha1.clone(),
-                        ));
-                    }
+                for (_, artifact) in library.native_artifacts(&current_os) {
+                    native_count += 1;
+                    library_tasks.push((
+                        artifact.url.clone(),
+                        libraries_dir.join(&artifact.path),
+                        artifact.sha1.clone(),
+                    ));
                 }
 
                 if let Some(artifact) = &downloads.artifact {
@@ -267,7 +356,8 @@ impl MinecraftInstaller {
                         libraries_dir.join(&artifact.path),
                         artifact.sha1.clone(),
                     ));
-                } else {
+                } else if library.natives.is_none() {
+                    // Natives-only libraries have no jar of their own, only their classifiers
                     let path = library_maven_path(&libraries_dir, &library.name);
                     if Self::file_needs_download(&path, None) {
                         let url = library_maven_url(&library.name);
@@ -308,22 +398,43 @@ impl MinecraftInstaller {
         )
         .await?;
 
-        let asset_index_data: AssetIndexData =
-            serde_json::from_str(&fs::read_to_string(&asset_index_path)?)?;
+        let index_id = &version_details.asset_index.id;
+        let index_sha1 = &version_details.asset_index.sha1;
+        let index_already_installed = !self.force_verify
+            && self.load_installed_asset_indexes().get(index_id) == Some(index_sha1);
+
+        // Versions sharing an unchanged index already have every object on disk
+        if !index_already_installed {
+            let asset_index_data: AssetIndexData =
+                serde_json::from_str(&fs::read_to_string(&asset_index_path)?)?;
+
+            let mut asset_tasks = Vec::new();
+            for (_, asset) in asset_index_data.objects {
+                let hash_prefix = &asset.hash[0..2];
+                let asset_path = objects_dir.join(hash_prefix).join(&asset.hash);
+                let asset_url = format!(
+                    "https://resources.download.minecraft.net/{}/{}",
+                    hash_prefix, asset.hash
+                );
+
+                asset_tasks.push((asset_url, asset_path, asset.hash));
+            }
 
-        let mut asset_tasks = Vec::new();
-        for (_, asset) in asset_index_data.objects {
-            let hash_prefix = &asset.hash[0..2];
-            let asset_path = objects_dir.join(hash_prefix).join(&asset.hash);
-            let asset_url = format!(
-                "https://resources.download.minecraft.net/{}/{}",
-                hash_prefix, asset.hash
-            );
+            self.download_parallel(asset_tasks).await?;
 
-            asset_tasks.push((asset_url, asset_path, asset.hash));
+            self.mark_asset_index_installed(index_id, index_sha1)?;
         }
 
-        self.download_parallel(asset_tasks).await?;
+        if let Some(java_version) = &version_details.java_version {
+            let settings = crate::services::settings::SettingsManager::load().unwrap_or_default();
+            if settings.auto_java_runtime && settings.java_path.is_none() {
+                // A missing runtime is not fatal, launch falls back to the system Java
+                let runtime_installer = crate::services::java_runtime::JavaRuntimeInstaller::new(self.launcher_dir.clone());
+                if let Err(e) = runtime_installer.install_runtime(&java_version.component).await {
+                    eprintln!("Failed to install Java runtime {}: {}", java_version.component, e);
+                }
+            }
+        }
 
         Ok(())
     }
@@ -332,18 +443,20 @@ impl MinecraftInstaller {
         &self,
         tasks: Vec<(String, PathBuf, String)>,
     ) -> Result<usize, DownloadError> {
+        let tasks = Self::filter_tasks_needing_download(tasks).await?;
+
         let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_DOWNLOADS));
         let client = Arc::new(self.http_client.clone());
         let downloaded_count = Arc::new(std::sync::atomic::AtomicUsize::new(0));
         let mut handles = Vec::new();
 
-        for (url, path, sha1) in tasks {
+        for (url, path, _sha1) in tasks {
             let permit = semaphore.clone().acquire_owned().await?;
             let client = client.clone();
             let downloaded_count = downloaded_count.clone();
 
             let handle = tokio::spawn(async move {
-                let result = Self::download_with_client(&client, &url, &path, &sha1).await;
+                let result = Self::download_with_client(&client, &url, &path).await;
                 drop(permit);
                 
                 if let Ok(true) = result {
@@ -367,12 +480,8 @@ impl MinecraftInstaller {
         client: &reqwest::Client,
         url: &str,
         path: &PathBuf,
-        expected_sha1: &str,
     ) -> Result<bool, DownloadError> {
-        if !Self::file_needs_download(path, Some(expected_sha1)) {
-            return Ok(false);
-        }
-
+        // Existing files were already verified by `filter_tasks_needing_download`
         if let Some(parent) = path.parent() {
             fs::create_dir_all(parent)?;
         }
@@ -400,22 +509,61 @@ impl MinecraftInstaller {
     }
 }
 
+/// Launcher features that `rules[].features` conditions in version arguments are checked against.
+#[derive(Debug, Default, Clone)]
+pub struct LaunchFeatures {
+    pub is_demo_user: bool,
+    pub has_custom_resolution: bool,
+    pub has_quick_plays_support: bool,
+    pub is_quick_play_singleplayer: bool,
+    pub is_quick_play_multiplayer: bool,
+    pub is_quick_play_realms: bool,
+}
+
+impl LaunchFeatures {
+    fn is_active(&self, feature: &str) -> bool {
+        match feature {
+            "is_demo_user" => self.is_demo_user,
+            "has_custom_resolution" => self.has_custom_resolution,
+            "has_quick_plays_support" => self.has_quick_plays_support,
+            "is_quick_play_singleplayer" => self.is_quick_play_singleplayer,
+            "is_quick_play_multiplayer" => self.is_quick_play_multiplayer,
+            "is_quick_play_realms" => self.is_quick_play_realms,
+            // Unknown features are treated as unsupported
+            _ => false,
+        }
+    }
+}
+
 pub fn should_include_library(rules: &[Rule], current_os: &str) -> bool {
+    rules_allow(rules, current_os, &LaunchFeatures::default())
+}
+
+/// A rule applies when its OS and every listed feature match the current launch.
+pub fn rules_allow(rules: &[Rule], current_os: &str, features: &LaunchFeatures) -> bool {
     let mut allowed = false;
 
     for rule in rules {
-        let matches = if let Some(os) = &rule.os {
+        let os_matches = if let Some(os) = &rule.os {
             os.name.as_deref() == Some(current_os)
         } else {
             true
         };
 
-        if rule.action == "allow" && matches {
-            allowed = true;
-        } else if rule.action == "disallow" && matches {
-            return false;
+        let features_match = rule.features.as_ref().is_none_or(|required| {
+            required
+                .iter()
+                .all(|(feature, expected)| features.is_active(feature) == *expected)
+        });
+
+        if os_matches && features_match {
+            if rule.action == "allow" {
+                allowed = true;
+            } else if rule.action == "disallow" {
+                return false;
+            }
         }
     }
 
     allowed || rules.iter().all(|r| r.action != "allow")
-}
\ No newline at end of file
+}
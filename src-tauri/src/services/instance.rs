@@ -1,11 +1,39 @@
-use crate::models::Instance;
+use crate::models::{Instance, InstanceLaunchOptions, InstanceResetKeep, LauncherSettings};
 use crate::utils::*;
 use chrono::Utc;
 use std::fs;
+use std::path::Path;
 
 pub struct InstanceManager;
 
 impl InstanceManager {
+    /// Moves a "Latest Snapshot" instance to the newest snapshot, installing it first.
+    /// Returns the new version, or `None` when the instance is up to date or doesn't track
+    /// snapshots.
+    pub async fn update_tracked_snapshot(
+        instance_name: &str,
+    ) -> Result<Option<String>, Box<dyn std::error::Error + Send + Sync>> {
+        let instance_json = get_instance_dir(instance_name).join("instance.json");
+        let mut instance: Instance = serde_json::from_str(&fs::read_to_string(&instance_json)?)?;
+
+        if !instance.track_latest_snapshot || instance.loader.is_some() {
+            return Ok(None);
+        }
+
+        let installer = crate::services::installer::MinecraftInstaller::new(get_meta_dir())?;
+        let latest = installer.get_latest_versions().await?;
+        if latest.snapshot == instance.version {
+            return Ok(None);
+        }
+
+        installer.install_version(&latest.snapshot).await?;
+
+        instance.version = latest.snapshot.clone();
+        write_json_atomic(&instance_json, &instance)?;
+
+        Ok(Some(latest.snapshot))
+    }
+
     pub fn create(
         instance_name: &str,
         version: &str,
@@ -26,19 +54,27 @@ impl InstanceManager {
         fs::create_dir_all(instance_dir.join("logs"))?;
 
         let instance = Instance {
+            id: uuid::Uuid::new_v4().to_string(),
             name: instance_name.to_string(),
+            display_name: None,
             version: version.to_string(),
             created_at: Utc::now().to_rfc3339(),
             last_played: None,
             loader,
             loader_version,
             settings_override: None,
+            launch_options: None,
             icon_path: None,
+            accent_color: None,
+            cover_image: None,
             total_playtime_seconds: 0,
+            modpack: None,
+            track_latest_snapshot: false,
+            enforced_resource_packs: Vec::new(),
+            last_crash: None,
         };
 
-        let instance_json = serde_json::to_string_pretty(&instance)?;
-        fs::write(instance_dir.join("instance.json"), instance_json)?;
+        write_json_atomic(&instance_dir.join("instance.json"), &instance)?;
 
         Ok(instance)
     }
@@ -51,6 +87,9 @@ impl InstanceManager {
         }
 
         let mut instances = Vec::new();
+        let mut global_settings = None;
+        let mut seen_ids = std::collections::HashSet::new();
+        let mut assigned_ids = Vec::new();
 
         let entries = fs::read_dir(&instances_dir)?;
 
@@ -58,21 +97,102 @@ impl InstanceManager {
             if let Ok(entry) = entry {
                 let path = entry.path();
                 if path.is_dir() {
-                    let instance_json = path.join("instance.json");
-                    if instance_json.exists() {
-                        if let Ok(content) = fs::read_to_string(instance_json) {
-                            if let Ok(instance) = serde_json::from_str::<Instance>(&content) {
-                                instances.push(instance);
+                    match Self::read_instance_json(&path) {
+                        Ok(mut instance) => {
+                            let mut changed = false;
+                            if instance.settings_override.is_some() {
+                                let global = global_settings.get_or_insert_with(|| {
+                                    crate::services::settings::SettingsManager::load().unwrap_or_default()
+                                });
+                                Self::migrate_launch_options(&mut instance, global);
+                                changed = true;
                             }
+                            // Folders copied by hand carry the id of the instance they came from
+                            let legacy = instance.id.is_empty();
+                            if legacy || !seen_ids.insert(instance.id.clone()) {
+                                instance.id = uuid::Uuid::new_v4().to_string();
+                                seen_ids.insert(instance.id.clone());
+                                if legacy {
+                                    assigned_ids.push((instance.name.clone(), instance.id.clone()));
+                                }
+                                changed = true;
+                            }
+                            if changed {
+                                if let Err(e) = write_json_atomic(&path.join("instance.json"), &instance) {
+                                    eprintln!("Failed to migrate {}: {}", path.display(), e);
+                                }
+                            }
+                            instances.push(instance);
+                        }
+                        Err(e) if path.join("instance.json").exists() => {
+                            eprintln!("Skipping instance folder {}: {}", path.display(), e);
                         }
+                        Err(_) => {}
                     }
                 }
             }
         }
 
+        Self::migrate_name_keys(&assigned_ids);
+
         Ok(instances)
     }
 
+    /// Id of the instance, the key of everything the launcher keeps about it outside its
+    /// folder. Instances written before ids existed get one here.
+    pub fn instance_id(instance_name: &str) -> Result<String, Box<dyn std::error::Error>> {
+        let instance_dir = get_instance_dir(instance_name);
+        let mut instance = Self::read_instance_json(&instance_dir)?;
+
+        if instance.id.is_empty() {
+            instance.id = uuid::Uuid::new_v4().to_string();
+            write_json_atomic(&instance_dir.join("instance.json"), &instance)?;
+            Self::migrate_name_keys(&[(instance_name.to_string(), instance.id.clone())]);
+        }
+
+        Ok(instance.id)
+    }
+
+    /// Moves the backup policies, options sync entries and world backups that were kept under
+    /// the folder name of instances from before ids existed over to their new ids.
+    fn migrate_name_keys(assigned: &[(String, String)]) {
+        if assigned.is_empty() {
+            return;
+        }
+
+        let result = crate::services::settings::SettingsManager::update(|settings| {
+            for (name, id) in assigned {
+                if let Some(policy) = settings.world_backup_policies.remove(name) {
+                    settings.world_backup_policies.insert(id.clone(), policy);
+                }
+                for member in settings.options_sync_instances.iter_mut().filter(|member| *member == name) {
+                    *member = id.clone();
+                }
+            }
+            Ok(())
+        });
+        if let Err(e) = result {
+            eprintln!("Failed to move instance settings to instance ids: {}", e);
+        }
+
+        for (name, id) in assigned {
+            crate::services::world_backup::WorldBackupManager::migrate_backups_dir(name, id);
+        }
+    }
+
+    /// Replaces a pre-`launch_options` settings override with launch options holding only
+    /// the values that differ from `global`.
+    pub fn migrate_launch_options(instance: &mut Instance, global: &LauncherSettings) {
+        let Some(legacy) = instance.settings_override.take() else {
+            return;
+        };
+
+        if instance.launch_options.is_none() {
+            let options = InstanceLaunchOptions::diff(&legacy, global);
+            instance.launch_options = (!options.is_empty()).then_some(options);
+        }
+    }
+
     pub fn delete(instance_name: &str, permanent: bool) -> Result<(), Box<dyn std::error::Error>> {
         let instance_dir = get_instance_dir(instance_name);
 
@@ -106,29 +226,222 @@ impl InstanceManager {
         Ok(())
     }
 
-    #[allow(dead_code)]
-    pub fn rename(old_name: &str, new_name: &str) -> Result<(), Box<dyn std::error::Error>> {
-        let old_dir = get_instance_dir(old_name);
-        let new_dir = get_instance_dir(new_name);
+    /// Moves the game files of an instance to the trash, except for the categories in `keep`,
+    /// screenshots and the launcher's own files. Returns the moved entries.
+    pub fn reset_files(instance_name: &str, keep: &InstanceResetKeep) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        let instance_dir = get_instance_dir(instance_name);
+        if !instance_dir.join("instance.json").exists() {
+            return Err(format!("Instance '{}' does not exist", instance_name).into());
+        }
 
-        if !old_dir.exists() {
-            return Err(format!("Instance '{}' does not exist", old_name).into());
+        let mut kept: Vec<&str> = vec!["instance.json", "icon.png", "icon.gif", "screenshots"];
+        if keep.worlds {
+            kept.push("saves");
+        }
+        if keep.options {
+            kept.extend(["options.txt", "optionsof.txt", "optionsshaders.txt", "servers.dat"]);
+        }
+        if keep.mods {
+            kept.push("mods");
         }
+        if keep.config {
+            kept.extend(["config", "defaultconfigs"]);
+        }
+
+        let to_remove: Vec<String> = fs::read_dir(&instance_dir)?
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            // Dot files hold launcher metadata such as the installed modpack file list
+            .filter(|name| !name.starts_with('.') && !kept.contains(&name.as_str()))
+            .collect();
 
-        if new_dir.exists() {
-            return Err(format!("Instance '{}' already exists", new_name).into());
+        if !to_remove.is_empty() {
+            let folder_name = format!("{}_reset_{}", instance_name, Utc::now().format("%Y%m%d%H%M%S"));
+            let trash_path = get_trash_dir().join(&folder_name);
+            fs::create_dir_all(&trash_path)?;
+
+            for name in &to_remove {
+                fs::rename(instance_dir.join(name), trash_path.join(name))?;
+            }
+
+            crate::services::trash::TrashManager::add_item(instance_name, "instance_reset", &folder_name)?;
         }
 
-        fs::rename(&old_dir, &new_dir)?;
+        for dir in ["saves", "resourcepacks", "shaderpacks", "mods", "logs"] {
+            fs::create_dir_all(instance_dir.join(dir))?;
+        }
 
-        let instance_json = new_dir.join("instance.json");
-        let mut instance: Instance = serde_json::from_str(&fs::read_to_string(&instance_json)?)?;
+        let mut removed = to_remove;
+        removed.sort();
+        Ok(removed)
+    }
+
+    fn read_instance_json(instance_dir: &Path) -> Result<Instance, Box<dyn std::error::Error>> {
+        let content = fs::read_to_string(instance_dir.join("instance.json"))?;
+        Ok(serde_json::from_str::<Instance>(&content)?)
+    }
+
+    /// Folders in the instances directory that `get_all` can't load, either because they have
+    /// no `instance.json` or because it doesn't parse.
+    pub fn get_unregistered() -> Vec<String> {
+        let Ok(entries) = fs::read_dir(get_instances_dir()) else {
+            return Vec::new();
+        };
+
+        let mut folders: Vec<String> = entries
+            .flatten()
+            .filter(|e| e.path().is_dir())
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .filter(|name| !name.starts_with('.'))
+            .filter(|name| Self::read_instance_json(&get_instance_dir(name)).is_err())
+            .collect();
 
-        instance.name = new_name.to_string();
+        folders.sort();
+        folders
+    }
+
+    /// Registers a folder that was copied into the instances directory by hand. An existing
+    /// `instance.json` is kept (with its name corrected to the folder name); otherwise one is
+    /// written for the folder's game files. A `.minecraft` folder nested inside is moved up, and
+    /// the version is taken from `version_id` or from the folder's own `versions` directory.
+    /// An `instance.json` that doesn't parse is kept as `instance.json.broken`.
+    pub fn adopt(folder: &str, version_id: Option<&str>) -> Result<Instance, Box<dyn std::error::Error>> {
+        let instance_dir = get_instance_dir(folder);
+
+        if !instance_dir.is_dir() {
+            return Err(format!("Folder '{}' does not exist in the instances directory", folder).into());
+        }
+
+        let instance_json = instance_dir.join("instance.json");
+
+        if let Ok(mut instance) = Self::read_instance_json(&instance_dir) {
+            if instance.name != folder {
+                instance.name = folder.to_string();
+                write_json_atomic(&instance_json, &instance)?;
+            }
+            return Ok(instance);
+        }
+
+        Self::flatten_nested_game_dir(&instance_dir)?;
+
+        let version = match version_id {
+            Some(version) => version.to_string(),
+            None => Self::detect_version(&instance_dir)
+                .ok_or("Could not detect the Minecraft version of this folder, select one to adopt it")?,
+        };
+
+        if !version.chars().all(|c| c.is_alphanumeric() || c == '.' || c == '-') {
+            return Err("Invalid version format".into());
+        }
+
+        let (loader, loader_version) = Self::loader_from_version_id(&version);
+
+        if instance_json.exists() {
+            fs::rename(&instance_json, instance_dir.join("instance.json.broken"))?;
+        }
+
+        for dir in ["saves", "resourcepacks", "shaderpacks", "mods", "logs"] {
+            fs::create_dir_all(instance_dir.join(dir))?;
+        }
+
+        let instance = Instance {
+            id: uuid::Uuid::new_v4().to_string(),
+            name: folder.to_string(),
+            display_name: None,
+            version,
+            created_at: Utc::now().to_rfc3339(),
+            last_played: None,
+            loader,
+            loader_version,
+            settings_override: None,
+            launch_options: None,
+            icon_path: None,
+            accent_color: None,
+            cover_image: None,
+            total_playtime_seconds: 0,
+            modpack: None,
+            track_latest_snapshot: false,
+            enforced_resource_packs: Vec::new(),
+            last_crash: None,
+        };
+
+        write_json_atomic(&instance_json, &instance)?;
+
+        Ok(instance)
+    }
+
+    /// Moves the contents of a `.minecraft` or `minecraft` folder (as exported by other
+    /// launchers) up into the instance folder, when the game files aren't already there.
+    fn flatten_nested_game_dir(instance_dir: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        if ["saves", "mods", "options.txt"].iter().any(|p| instance_dir.join(p).exists()) {
+            return Ok(());
+        }
+
+        let Some(nested) = [".minecraft", "minecraft"]
+            .iter()
+            .map(|name| instance_dir.join(name))
+            .find(|path| path.is_dir())
+        else {
+            return Ok(());
+        };
+
+        for entry in fs::read_dir(&nested)?.flatten() {
+            let target = instance_dir.join(entry.file_name());
+            if !target.exists() {
+                fs::rename(entry.path(), target)?;
+            }
+        }
+
+        // Only succeeds when everything was moved
+        let _ = fs::remove_dir(&nested);
+
+        Ok(())
+    }
+
+    /// The version a bare game directory was last played with, from its `versions` folder.
+    /// Loader versions are preferred over the vanilla version they are based on.
+    fn detect_version(instance_dir: &Path) -> Option<String> {
+        let mut versions: Vec<String> = fs::read_dir(instance_dir.join("versions"))
+            .ok()?
+            .flatten()
+            .map(|e| e.file_name().to_string_lossy().to_string())
+            .filter(|id| instance_dir.join("versions").join(id).join(format!("{}.json", id)).exists())
+            .collect();
+
+        versions.sort_by_key(|id| Self::loader_from_version_id(id).0.is_none());
+        versions.into_iter().next()
+    }
+
+    /// Splits a version id as written by the loader installers into loader name and version.
+    pub fn loader_from_version_id(version_id: &str) -> (Option<String>, Option<String>) {
+        if let Some(rest) = version_id.strip_prefix("fabric-loader-") {
+            // fabric-loader-<loader>-<minecraft>
+            let loader_version = rest.split_once('-').map(|(lv, _)| lv.to_string());
+            return (Some("fabric".to_string()), loader_version);
+        }
+        if let Some(rest) = version_id.strip_prefix("quilt-loader-") {
+            // quilt-loader-<loader>-<minecraft>, same layout as Fabric
+            let loader_version = rest.split_once('-').map(|(lv, _)| lv.to_string());
+            return (Some("quilt".to_string()), loader_version);
+        }
+        if let Some(neoforge_version) = version_id.strip_prefix("neoforge-") {
+            return (Some("neoforge".to_string()), Some(neoforge_version.to_string()));
+        }
+        if let Some((minecraft, forge)) = version_id.split_once("-forge-") {
+            return (Some("forge".to_string()), Some(format!("{}-{}", minecraft, forge)));
+        }
+        (None, None)
+    }
+
+    /// Records the name the user gave the instance in `instance_name`; it's only stored when
+    /// it differs from the folder name.
+    pub fn set_display_name(instance_name: &str, display_name: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let instance_json = get_instance_dir(instance_name).join("instance.json");
+        let mut instance: Instance = serde_json::from_str(&fs::read_to_string(&instance_json)?)?;
 
-        let updated_json = serde_json::to_string_pretty(&instance)?;
-        fs::write(instance_json, updated_json)?;
+        instance.display_name = (display_name != instance_name).then(|| display_name.to_string());
 
+        write_json_atomic(&instance_json, &instance)?;
         Ok(())
     }
 }
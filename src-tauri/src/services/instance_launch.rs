@@ -1,14 +1,30 @@
-use crate::models::{FabricProfileJson, ForgeProfileJson, Instance, LauncherSettings, NeoForgeProfileJson, Rule, VersionDetails};
-use crate::services::installer::should_include_library;
+use crate::models::{Artifact, FabricProfileJson, ForgeProfileJson, Instance, LauncherSettings, Library, NeoForgeProfileJson, Rule, VersionDetails};
+use crate::services::automation::{self, AutomationEvent};
+use crate::services::crash_analysis;
+use crate::services::installer::{rules_allow, should_include_library, LaunchFeatures};
 use crate::utils::*;
 use chrono::Utc;
-use std::collections::HashSet;
+use sha1::{Digest, Sha1};
+use std::collections::{HashMap, HashSet};
 use std::io::{BufRead, BufReader};
 use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
 use std::{fs, path::PathBuf};
 use tauri::{Emitter, Manager};
 use zip::ZipArchive;
 
+/// Left in the natives folder after extraction, holding the version and a hash of the native
+/// jars the files came from.
+const NATIVES_STAMP_FILE: &str = ".extracted";
+
+lazy_static::lazy_static! {
+    /// Major version of each Java executable by its modification time, so launches don't run
+    /// `java -version` every time.
+    static ref JAVA_VERSIONS: Mutex<HashMap<String, (SystemTime, u32)>> = Mutex::new(HashMap::new());
+}
+
 struct ResolvedProfile {
     main_class: String,
     base_version_id: String,
@@ -17,11 +33,12 @@ struct ResolvedProfile {
     assets_id: String,
     is_neoforge: bool,
     is_forge: bool,
-    jvm_arguments: Vec<String>,
-    game_arguments: Vec<String>,
+    /// Loader arguments in the modern format; rules are evaluated at launch time.
+    jvm_arguments: Vec<serde_json::Value>,
+    game_arguments: Vec<serde_json::Value>,
 }
 
-fn process_arguments_args(args: &[serde_json::Value], current_os: &str) -> Vec<String> {
+fn process_arguments_args(args: &[serde_json::Value], current_os: &str, features: &LaunchFeatures) -> Vec<String> {
     let mut result = Vec::new();
     for arg in args {
         match arg {
@@ -33,7 +50,7 @@ fn process_arguments_args(args: &[serde_json::Value], current_os: &str) -> Vec<S
             serde_json::Value::Object(obj) => {
                 let should_include = if let Some(rules_val) = obj.get("rules") {
                     if let Ok(rules) = serde_json::from_value::<Vec<Rule>>(rules_val.clone()) {
-                        should_include_library(&rules, current_os)
+                        rules_allow(&rules, current_os, features)
                     } else {
                         true
                     }
@@ -80,9 +97,34 @@ impl super::instance::InstanceManager {
             "message": format!("ERROR: {}", error_msg),
             "type": "stderr"
         }));
+
+        if let Some(hint) = crash_analysis::match_crash_text(error_msg) {
+            crash_analysis::emit_crash_hint(app_handle, instance_name, &hint);
+        }
     }
 
     fn get_java_version(java_path: &str) -> Result<u32, Box<dyn std::error::Error>> {
+        // Bare `java` from PATH has no metadata and is probed every time
+        let modified = fs::metadata(java_path).and_then(|meta| meta.modified()).ok();
+
+        if let (Some(modified), Ok(versions)) = (modified, JAVA_VERSIONS.lock()) {
+            if let Some((cached_modified, major)) = versions.get(java_path) {
+                if *cached_modified == modified {
+                    return Ok(*major);
+                }
+            }
+        }
+
+        let major = Self::probe_java_version(java_path)?;
+
+        if let (Some(modified), Ok(mut versions)) = (modified, JAVA_VERSIONS.lock()) {
+            versions.insert(java_path.to_string(), (modified, major));
+        }
+
+        Ok(major)
+    }
+
+    fn probe_java_version(java_path: &str) -> Result<u32, Box<dyn std::error::Error>> {
         let mut cmd = Command::new(java_path);
         cmd.arg("-version");
 
@@ -131,7 +173,8 @@ impl super::instance::InstanceManager {
         }
     }
 
-    fn get_required_java_version_from_meta(version: &str) -> Option<u32> {
+    /// Reads the `javaVersion` object of a version, following `inheritsFrom` for loader profiles.
+    fn get_java_version_meta(version: &str) -> Option<serde_json::Value> {
         let meta_dir = get_meta_dir();
         let json_path = meta_dir.join("versions").join(version).join(format!("{}.json", version));
         let content = std::fs::read_to_string(&json_path).ok()?;
@@ -142,18 +185,26 @@ impl super::instance::InstanceManager {
 
         if base_version != version {
             let base_path = meta_dir.join("versions").join(base_version).join(format!("{}.json", base_version));
-            if let Ok(base_content) = std::fs::read_to_string(&base_path) {
-                if let Ok(base_json) = serde_json::from_str::<serde_json::Value>(&base_content) {
-                    if let Some(mv) = base_json.pointer("/javaVersion/majorVersion").and_then(|v| v.as_u64()) {
-                        return Some(mv as u32);
-                    }
-                }
-            }
-        } else if let Some(mv) = json.pointer("/javaVersion/majorVersion").and_then(|v| v.as_u64()) {
-            return Some(mv as u32);
+            let base_content = std::fs::read_to_string(&base_path).ok()?;
+            let base_json: serde_json::Value = serde_json::from_str(&base_content).ok()?;
+            base_json.get("javaVersion").cloned()
+        } else {
+            json.get("javaVersion").cloned()
         }
+    }
+
+    fn get_required_java_version_from_meta(version: &str) -> Option<u32> {
+        Self::get_java_version_meta(version)?
+            .get("majorVersion")
+            .and_then(|v| v.as_u64())
+            .map(|mv| mv as u32)
+    }
 
-        None
+    fn get_java_runtime_component(version: &str) -> Option<String> {
+        Self::get_java_version_meta(version)?
+            .get("component")
+            .and_then(|v| v.as_str())
+            .map(|c| c.to_string())
     }
 
     fn get_required_java_version(minecraft_version: &str) -> u32 {
@@ -249,7 +300,9 @@ impl super::instance::InstanceManager {
             return Err(err_msg.into());
         }
 
+        Self::step_check_parallel_session(instance_name, uuid, server_address, &app_handle)?;
         let (instance, version) = Self::step_load_instance(instance_name, &instance_dir, &app_handle)?;
+        Self::step_enforce_resource_packs(instance_name, &instance, &instance_dir, &app_handle);
         let (java_path, effective_settings) = Self::step_resolve_java(instance_name, &instance, &app_handle)?;
         let required_java = Self::get_required_java_version(&version);
         Self::step_check_java(instance_name, &version, &java_path, required_java, &app_handle)?;
@@ -265,20 +318,114 @@ impl super::instance::InstanceManager {
         Ok(())
     }
 
+    /// Runs the launch steps that don't need an account without starting the game: Java check,
+    /// profile resolution, natives and classpath. The next launch reuses the extracted natives
+    /// and the detected Java version.
+    pub fn warm(instance_name: &str, app_handle: &tauri::AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+        let meta_dir = get_meta_dir();
+        let instance_dir = get_instance_dir(instance_name);
+
+        if !instance_dir.exists() {
+            return Err(format!("Instance '{}' does not exist", instance_name).into());
+        }
+
+        let (instance, version) = Self::step_load_instance(instance_name, &instance_dir, app_handle)?;
+        let (java_path, _) = Self::step_resolve_java(instance_name, &instance, app_handle)?;
+        let required_java = Self::get_required_java_version(&version);
+        Self::step_check_java(instance_name, &version, &java_path, required_java, app_handle)?;
+        let resolved = Self::step_resolve_profile(instance_name, &version, &meta_dir, app_handle)?;
+        Self::step_extract_natives(instance_name, &resolved, &meta_dir, app_handle)?;
+        Self::step_build_classpath(instance_name, &resolved.libraries, &meta_dir, app_handle)?;
+        Ok(())
+    }
+
+    /// Two game sessions with one account conflict on the session servers, joining a server from
+    /// the second disconnects the first. Launching straight into a server is refused in that
+    /// case, other launches only warn. Different accounts can play in parallel.
+    fn step_check_parallel_session(
+        instance_name: &str,
+        uuid: &str,
+        server_address: Option<&str>,
+        app_handle: &tauri::AppHandle,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let mut others: Vec<String> = crate::commands::instances::RUNNING_PROCESSES
+            .lock()
+            .map(|processes| {
+                processes
+                    .values()
+                    .filter(|process| process.instance_name != instance_name && process.account_uuid == uuid)
+                    .map(|process| process.instance_name.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        if others.is_empty() {
+            return Ok(());
+        }
+        others.sort();
+
+        let running = format!("This account is already playing in '{}'", others.join("', '"));
+
+        if server_address.is_some() {
+            let err_msg = format!("{}. Close it or switch accounts before joining a server.", running);
+            Self::emit_error_log(app_handle, instance_name, &err_msg);
+            return Err(err_msg.into());
+        }
+
+        Self::emit_error_log(
+            app_handle,
+            instance_name,
+            &format!("WARNING: {}. Joining an online server from both will disconnect one of them.", running),
+        );
+        Ok(())
+    }
+
     fn step_load_instance(
-        _instance_name: &str,
+        instance_name: &str,
         instance_dir: &PathBuf,
         _app_handle: &tauri::AppHandle,
     ) -> Result<(Instance, String), Box<dyn std::error::Error>> {
         let instance_json = instance_dir.join("instance.json");
         let content = fs::read_to_string(&instance_json)
             .map_err(|e| format!("Failed to read instance.json: {}", e))?;
-        let instance: Instance = serde_json::from_str(&content)
+        let mut instance: Instance = serde_json::from_str(&content)
             .map_err(|e| format!("Failed to parse instance.json: {}", e))?;
+        // The running game is tracked by id
+        if instance.id.is_empty() {
+            instance.id = crate::services::instance::InstanceManager::instance_id(instance_name)?;
+        }
         let version = instance.version.clone();
         Ok((instance, version))
     }
 
+    /// Turns the instance's enforced resource packs back on in options.txt. Packs that were
+    /// deleted are skipped, and a failure only warns since the game runs fine without them.
+    fn step_enforce_resource_packs(
+        instance_name: &str,
+        instance: &Instance,
+        instance_dir: &std::path::Path,
+        app_handle: &tauri::AppHandle,
+    ) {
+        if instance.enforced_resource_packs.is_empty() {
+            return;
+        }
+
+        let resourcepacks_dir = instance_dir.join("resourcepacks");
+        let packs: Vec<String> = instance
+            .enforced_resource_packs
+            .iter()
+            .filter(|pack| resourcepacks_dir.join(pack).exists())
+            .cloned()
+            .collect();
+
+        let minecraft_version = crate::commands::instances::instance_minecraft_version(instance);
+        let legacy_names = crate::services::options::uses_legacy_pack_names(&minecraft_version);
+
+        if let Err(e) = crate::services::options::enforce_resource_packs(&instance_dir.join("options.txt"), &packs, legacy_names) {
+            Self::emit_error_log(app_handle, instance_name, &format!("WARNING: Failed to enable resource packs: {}", e));
+        }
+    }
+
     fn step_resolve_java(
         instance_name: &str,
         instance: &Instance,
@@ -287,14 +434,25 @@ impl super::instance::InstanceManager {
         let global_settings = crate::services::settings::SettingsManager::load()
             .unwrap_or_default();
 
-        let effective_settings = if let Some(override_settings) = &instance.settings_override {
-            override_settings.clone()
+        let effective_settings = match (&instance.launch_options, &instance.settings_override) {
+            (Some(options), _) => options.apply(global_settings),
+            // Not migrated yet, see `InstanceManager::migrate_launch_options`
+            (None, Some(override_settings)) => override_settings.clone(),
+            (None, None) => global_settings,
+        };
+
+        let bundled_java = if effective_settings.auto_java_runtime {
+            Self::get_java_runtime_component(&instance.version).and_then(|component| {
+                crate::services::java_runtime::JavaRuntimeInstaller::installed_java_path(&get_meta_dir(), &component)
+            })
         } else {
-            global_settings
+            None
         };
 
         let java_path = if let Some(custom_java) = &effective_settings.java_path {
             custom_java.clone()
+        } else if let Some(bundled_java) = bundled_java {
+            bundled_java.to_string_lossy().to_string()
         } else {
             match find_java() {
                 Some(path) => path,
@@ -551,8 +709,7 @@ impl super::instance::InstanceManager {
         }
 
         let (jvm_arguments, game_arguments) = if let Some(args) = &neoforge_profile.arguments {
-            (process_arguments_args(&args.jvm, current_os),
-             process_arguments_args(&args.game, current_os))
+            (args.jvm.clone(), args.game.clone())
         } else {
             (Vec::new(), Vec::new())
         };
@@ -650,8 +807,7 @@ impl super::instance::InstanceManager {
         }
 
         let (jvm_arguments, game_arguments) = if let Some(args) = &forge_profile.arguments {
-            (process_arguments_args(&args.jvm, current_os),
-             process_arguments_args(&args.game, current_os))
+            (args.jvm.clone(), args.game.clone())
         } else {
             (Vec::new(), Vec::new())
         };
@@ -722,15 +878,11 @@ impl super::instance::InstanceManager {
     ) -> Result<(), Box<dyn std::error::Error>> {
         let instance_dir = get_instance_dir(instance_name);
         let natives_dir = instance_dir.join("natives");
-        fs::create_dir_all(&natives_dir)
-            .map_err(|e| format!("Failed to create natives directory: {}", e))?;
-
         let current_os = get_current_os();
         let libraries_dir = meta_dir.join("libraries");
 
-        let mut natives_extracted = 0;
-        let mut natives_attempted = 0;
-
+        // Native jars for this OS, with what to call them in errors
+        let mut natives: Vec<(String, &Library, &Artifact)> = Vec::new();
         for library in &resolved.base_version.libraries {
             let is_native_name = library.name.contains(":natives-");
 
@@ -753,117 +905,23 @@ impl super::instance::InstanceManager {
                     }
                 }
 
-                if let Some(downloads) = &library.downloads {
-                    if let Some(artifact) = &downloads.artifact {
-                        natives_attempted += 1;
-                        let native_path = libraries_dir.join(&artifact.path);
-
-                        if native_path.exists() {
-                            if let Ok(file) = fs::File::open(&native_path) {
-                                if let Ok(mut archive) = ZipArchive::new(file) {
-                                    for i in 0..archive.len() {
-                                        if let Ok(mut file) = archive.by_index(i) {
-                                            let file_name = file.name().to_string();
-                                            if file_name.ends_with('/') || file_name.starts_with("META-INF") {
-                                                continue;
-                                            }
-                                            let outpath = natives_dir.join(&file_name);
-                                            if let Some(parent) = outpath.parent() {
-                                                let _ = fs::create_dir_all(parent);
-                                            }
-                                            if let Ok(mut outfile) = fs::File::create(&outpath) {
-                                                if std::io::copy(&mut file, &mut outfile).is_ok() {
-                                                    natives_extracted += 1;
-                                                }
-                                            }
-                                        }
-                                    }
-                                } else {
-                                    Self::emit_error_log(app_handle, instance_name, &format!("Failed to open native archive for {}", library.name));
-                                }
-                            } else {
-                                Self::emit_error_log(app_handle, instance_name, &format!("Failed to open native file for {}", library.name));
-                            }
-                        } else {
-                            Self::emit_error_log(app_handle, instance_name, &format!(
-                                "Native library not found: {}. This will cause LWJGL to fail!",
-                                artifact.path
-                            ));
-                            return Err(format!(
-                                "Native library missing: {}. Please reinstall Minecraft {}",
-                                artifact.path, resolved.base_version_id
-                            ).into());
-                        }
-                    }
+                if let Some(artifact) = library.downloads.as_ref().and_then(|d| d.artifact.as_ref()) {
+                    natives.push((library.name.clone(), library, artifact));
                 }
             }
 
-            if let Some(downloads) = &library.downloads {
-                if let Some(classifiers) = &downloads.classifiers {
-                    for (key, artifact) in classifiers {
-                        let platform_suffix = if key.contains("natives-windows") {
-                            "windows"
-                        } else if key.contains("natives-linux") {
-                            "linux"
-                        } else {
-                            continue;
-                        };
-
-                        if platform_suffix != current_os {
-                            continue;
-                        }
-
-                        if let Some(rules) = &library.rules {
-                            if !should_include_library(rules, &current_os) {
-                                continue;
-                            }
-                        }
-
-                        natives_attempted += 1;
-                        let native_path = libraries_dir.join(&artifact.path);
-
-                        if native_path.exists() {
-                            if let Ok(file) = fs::File::open(&native_path) {
-                                if let Ok(mut archive) = ZipArchive::new(file) {
-                                    for i in 0..archive.len() {
-                                        if let Ok(mut file) = archive.by_index(i) {
-                                            let file_name = file.name().to_string();
-                                            if file_name.ends_with('/') || file_name.starts_with("META-INF") {
-                                                continue;
-                                            }
-                                            let outpath = natives_dir.join(&file_name);
-                                            if let Some(parent) = outpath.parent() {
-                                                let _ = fs::create_dir_all(parent);
-                                            }
-                                            if let Ok(mut outfile) = fs::File::create(&outpath) {
-                                                if std::io::copy(&mut file, &mut outfile).is_ok() {
-                                                    natives_extracted += 1;
-                                                }
-                                            }
-                                        }
-                                    }
-                                } else {
-                                    Self::emit_error_log(app_handle, instance_name, &format!("Failed to open native archive for classifier {}", key));
-                                }
-                            } else {
-                                Self::emit_error_log(app_handle, instance_name, &format!("Failed to open native file for classifier {}", key));
-                            }
-                        } else {
-                            Self::emit_error_log(app_handle, instance_name, &format!(
-                                "Native library not found: {}. This will cause LWJGL to fail!",
-                                artifact.path
-                            ));
-                            return Err(format!(
-                                "Native library missing: {}. Please reinstall Minecraft {}",
-                                artifact.path, resolved.base_version_id
-                            ).into());
-                        }
-                    }
+            if let Some(rules) = &library.rules {
+                if !should_include_library(rules, &current_os) {
+                    continue;
                 }
             }
+
+            for (key, artifact) in library.native_artifacts(&current_os) {
+                natives.push((format!("classifier {}", key), library, artifact));
+            }
         }
 
-        if natives_attempted == 0 {
+        if natives.is_empty() {
             let err_msg = format!(
                 "No native libraries found for OS '{}'. Minecraft cannot start without natives.",
                 current_os
@@ -875,15 +933,82 @@ impl super::instance::InstanceManager {
             ).into());
         }
 
-        if natives_extracted == 0 && natives_attempted > 0 {
+        // Already extracted from exactly these jars by an earlier launch or warm-up
+        let mut hasher = Sha1::new();
+        for (_, _, artifact) in &natives {
+            hasher.update(format!("{}:{}\n", artifact.path, artifact.sha1));
+        }
+        let stamp = format!("{}\n{:x}", resolved.base_version_id, hasher.finalize());
+        let stamp_path = natives_dir.join(NATIVES_STAMP_FILE);
+        if fs::read_to_string(&stamp_path).is_ok_and(|existing| existing == stamp) {
+            return Ok(());
+        }
+
+        // Stale or partially extracted natives would otherwise be mixed with the new ones
+        if natives_dir.exists() {
+            fs::remove_dir_all(&natives_dir)
+                .map_err(|e| format!("Failed to remove old natives: {}", e))?;
+        }
+        fs::create_dir_all(&natives_dir)
+            .map_err(|e| format!("Failed to create natives directory: {}", e))?;
+
+        let mut natives_extracted = 0;
+
+        for (label, library, artifact) in &natives {
+            let native_path = libraries_dir.join(&artifact.path);
+
+            if !native_path.exists() {
+                Self::emit_error_log(app_handle, instance_name, &format!(
+                    "Native library not found: {}. This will cause LWJGL to fail!",
+                    artifact.path
+                ));
+                return Err(format!(
+                    "Native library missing: {}. Please reinstall Minecraft {}",
+                    artifact.path, resolved.base_version_id
+                ).into());
+            }
+
+            let Ok(file) = fs::File::open(&native_path) else {
+                Self::emit_error_log(app_handle, instance_name, &format!("Failed to open native file for {}", label));
+                continue;
+            };
+            let Ok(mut archive) = ZipArchive::new(file) else {
+                Self::emit_error_log(app_handle, instance_name, &format!("Failed to open native archive for {}", label));
+                continue;
+            };
+
+            for i in 0..archive.len() {
+                if let Ok(mut file) = archive.by_index(i) {
+                    let file_name = file.name().to_string();
+                    if file_name.ends_with('/') || library.excludes_from_extraction(&file_name) {
+                        continue;
+                    }
+                    let outpath = natives_dir.join(&file_name);
+                    if let Some(parent) = outpath.parent() {
+                        let _ = fs::create_dir_all(parent);
+                    }
+                    if let Ok(mut outfile) = fs::File::create(&outpath) {
+                        if std::io::copy(&mut file, &mut outfile).is_ok() {
+                            natives_extracted += 1;
+                        }
+                    }
+                }
+            }
+        }
+
+        if natives_extracted == 0 {
             let err_msg = format!(
                 "Found {} native JARs but failed to extract any files. Check file permissions and disk space.",
-                natives_attempted
+                natives.len()
             );
             Self::emit_error_log(app_handle, instance_name, &err_msg);
             return Err(err_msg.into());
         }
 
+        if let Err(e) = fs::write(&stamp_path, &stamp) {
+            eprintln!("Failed to mark natives of {} as extracted: {}", instance_name, e);
+        }
+
         Ok(())
     }
 
@@ -931,6 +1056,56 @@ impl super::instance::InstanceManager {
         Ok(classpath)
     }
 
+    /// Windows stores per-executable GPU preferences in the registry (the same key the
+    /// Graphics settings page writes); Linux drivers pick the GPU from environment variables.
+    fn apply_discrete_gpu_preference(cmd: &mut Command, java_path: &str) {
+        #[cfg(target_os = "windows")]
+        {
+            use std::os::windows::process::CommandExt;
+            let _ = cmd;
+
+            let result = Command::new("reg")
+                .args([
+                    "add",
+                    r"HKCU\Software\Microsoft\DirectX\UserGpuPreferences",
+                    "/v",
+                    java_path,
+                    "/t",
+                    "REG_SZ",
+                    "/d",
+                    "GpuPreference=2;",
+                    "/f",
+                ])
+                .creation_flags(0x08000000)
+                .output();
+
+            match result {
+                Ok(output) if output.status.success() => {}
+                Ok(output) => eprintln!(
+                    "Failed to set GPU preference for {}: {}",
+                    java_path,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ),
+                Err(e) => eprintln!("Failed to set GPU preference for {}: {}", java_path, e),
+            }
+        }
+
+        #[cfg(target_os = "linux")]
+        {
+            let _ = java_path;
+            cmd.env("DRI_PRIME", "1");
+
+            // PRIME render offload for the proprietary NVIDIA driver
+            if std::path::Path::new("/proc/driver/nvidia").exists() {
+                cmd.env("__NV_PRIME_RENDER_OFFLOAD", "1")
+                    .env("__GLX_VENDOR_LIBRARY_NAME", "nvidia");
+            }
+        }
+
+        #[cfg(not(any(target_os = "windows", target_os = "linux")))]
+        let _ = (cmd, java_path);
+    }
+
     fn step_launch(
         instance_name: &str,
         username: &str,
@@ -977,6 +1152,17 @@ impl super::instance::InstanceManager {
         let instance_dir_str = instance_dir.to_string_lossy().into_owned();
         let assets_root = meta_dir.join("assets");
         let assets_root_str = assets_root.to_string_lossy().into_owned();
+        let window_size = effective_settings.window_width.zip(effective_settings.window_height);
+        let window_width_str = window_size.map(|(w, _)| w.to_string()).unwrap_or_default();
+        let window_height_str = window_size.map(|(_, h)| h.to_string()).unwrap_or_default();
+        let auth_server = crate::services::accounts::AccountManager::get_auth_server(uuid);
+        let injector_args = match &auth_server {
+            Some(api_root) => crate::services::authlib_injector::AuthlibInjector::launch_args(api_root).inspect_err(|e| {
+                Self::emit_error_log(app_handle, instance_name, e);
+            })?,
+            None => Vec::new(),
+        };
+        let user_type = if auth_server.is_some() { "mojang" } else { "msa" };
         let subs: &[(&str, &str)] = &[
             ("${natives_directory}", &natives_dir_str),
             ("${library_directory}", &libraries_dir_str),
@@ -991,18 +1177,53 @@ impl super::instance::InstanceManager {
             ("${auth_uuid}", uuid),
             ("${auth_access_token}", access_token),
             ("${user_properties}", "{}"),
-            ("${user_type}", "msa"),
+            ("${user_type}", user_type),
             ("${version_type}", "release"),
+            ("${quickPlayMultiplayer}", server_address.unwrap_or_default()),
+            ("${quickPlaySingleplayer}", world_name.unwrap_or_default()),
+            ("${resolution_width}", &window_width_str),
+            ("${resolution_height}", &window_height_str),
         ];
 
+        let use_quickplay = Self::should_use_quickplay(&resolved.base_version_id);
+        let features = LaunchFeatures {
+            is_quick_play_multiplayer: use_quickplay && server_address.is_some(),
+            is_quick_play_singleplayer: use_quickplay && world_name.is_some(),
+            has_custom_resolution: window_size.is_some(),
+            ..LaunchFeatures::default()
+        };
+        let current_os = get_current_os();
+        let jvm_arguments = process_arguments_args(&resolved.jvm_arguments, &current_os, &features);
+        let game_arguments = process_arguments_args(&resolved.game_arguments, &current_os, &features);
+
         let xms = (effective_settings.memory_mb * 80 / 100).max(512);
 
-        let mut cmd = Command::new(java_path);
+        let wrapper = instance.launch_options.as_ref().and_then(|o| o.wrapper.as_deref());
+        let mut wrapper_parts = wrapper.map(str::split_whitespace).into_iter().flatten();
+
+        let mut cmd = match wrapper_parts.next() {
+            Some(program) => {
+                let mut cmd = Command::new(program);
+                cmd.args(wrapper_parts).arg(java_path);
+                cmd
+            }
+            None => Command::new(java_path),
+        };
         cmd.arg(format!("-Xms{}M", xms))
             .arg(format!("-Xmx{}M", effective_settings.memory_mb));
 
+        for arg in &effective_settings.jvm_args {
+            // Re-checked here since instance.json can be edited by hand
+            if crate::commands::validation::validate_jvm_args(std::slice::from_ref(arg)).is_err() {
+                eprintln!("Ignoring disallowed JVM argument {}", arg);
+                continue;
+            }
+            cmd.arg(arg);
+        }
+        cmd.args(&injector_args);
+
         if resolved.is_neoforge || resolved.is_forge {
-            for arg in &resolved.jvm_arguments {
+            for arg in &jvm_arguments {
                 cmd.arg(substitute_arg(arg, subs));
             }
             cmd.arg("--add-opens").arg("java.base/java.lang=ALL-UNNAMED")
@@ -1039,27 +1260,50 @@ impl super::instance::InstanceManager {
             .arg("--assetIndex").arg(&resolved.assets_id);
 
         if resolved.is_neoforge || resolved.is_forge {
-            for arg in &resolved.game_arguments {
+            for arg in &game_arguments {
                 cmd.arg(substitute_arg(arg, subs));
             }
         }
 
+        // Profiles whose feature-gated arguments already carry the quick play flag get it substituted above
+        let has_game_arg = |flag: &str| game_arguments.iter().any(|a| a == flag);
+
         if let Some(server) = server_address {
-            let use_quickplay = Self::should_use_quickplay(&resolved.base_version_id);
             if use_quickplay {
-                cmd.arg("--quickPlayMultiplayer").arg(server);
+                if !has_game_arg("--quickPlayMultiplayer") {
+                    cmd.arg("--quickPlayMultiplayer").arg(server);
+                }
             } else {
                 cmd.arg("--server").arg(server);
             }
         }
 
         if let Some(world) = world_name {
-            let use_quickplay = Self::should_use_quickplay(&resolved.base_version_id);
-            if use_quickplay {
+            if use_quickplay && !has_game_arg("--quickPlaySingleplayer") {
                 cmd.arg("--quickPlaySingleplayer").arg(world);
             }
         }
 
+        if let Some((width, height)) = window_size {
+            if !has_game_arg("--width") {
+                cmd.arg("--width").arg(width.to_string())
+                    .arg("--height").arg(height.to_string());
+            }
+        }
+
+        // Re-checked here since instance.json can be edited by hand
+        for (key, value) in &effective_settings.env_vars {
+            if crate::commands::validation::is_blocked_env_var(key) {
+                eprintln!("Ignoring blocked environment variable {}", key);
+                continue;
+            }
+            cmd.env(key, value);
+        }
+
+        if effective_settings.prefer_discrete_gpu {
+            Self::apply_discrete_gpu_preference(&mut cmd, java_path);
+        }
+
         cmd.current_dir(instance_dir)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped());
@@ -1084,8 +1328,13 @@ impl super::instance::InstanceManager {
 
         {
             let mut processes = crate::commands::instances::RUNNING_PROCESSES.lock().map_err(|e| e.to_string())?;
-            processes.insert(instance_name.to_string(), child_pid);
+            processes.insert(instance.id.clone(), crate::commands::instances::RunningProcess {
+                instance_name: instance_name.to_string(),
+                pid: child_pid,
+                account_uuid: uuid.to_string(),
+            });
         }
+        automation::emit(app_handle, AutomationEvent::Launch { instance: instance_name.to_string() });
 
         let instance_name_for_status = instance_name.to_string();
         let launching_uuid = uuid.to_string();
@@ -1100,20 +1349,29 @@ impl super::instance::InstanceManager {
             let _ = service.update_status(&launching_uuid, crate::models::FriendStatus::InGame, Some(instance_name_for_status)).await;
         });
 
+        let live_hint: Arc<Mutex<Option<String>>> = Arc::new(Mutex::new(None));
+        let render_warned = Arc::new(AtomicBool::new(false));
+
         if let Some(stdout) = child.stdout.take() {
             let reader = BufReader::new(stdout);
             let instance_name_clone = instance_name.to_string();
             let app_handle_clone = app_handle.clone();
+            let live_hint = live_hint.clone();
+            let render_warned = render_warned.clone();
+            let access_token = access_token.to_string();
             std::thread::spawn(move || {
-                for line in reader.lines() {
+                for (index, line) in reader.lines().enumerate() {
                     if let Ok(line) = line {
-                        if !line.contains("accessToken") && !line.contains("MINECRAFT_ACCESS_TOKEN") {
-                            let _ = app_handle_clone.emit("console-log", serde_json::json!({
-                                "instance": instance_name_clone,
-                                "message": line,
-                                "type": "stdout"
-                            }));
+                        let line = Self::scrub_access_token(&line, &access_token);
+                        Self::check_crash_line(&app_handle_clone, &instance_name_clone, &line, &live_hint);
+                        if index < crash_analysis::RENDER_CHECK_LINES {
+                            Self::check_render_line(&app_handle_clone, &instance_name_clone, &line, &render_warned);
                         }
+                        let _ = app_handle_clone.emit("console-log", serde_json::json!({
+                            "instance": instance_name_clone,
+                            "message": line,
+                            "type": "stdout"
+                        }));
                     }
                 }
             });
@@ -1123,12 +1381,17 @@ impl super::instance::InstanceManager {
             let reader = BufReader::new(stderr);
             let instance_name_clone = instance_name.to_string();
             let app_handle_clone = app_handle.clone();
+            let live_hint = live_hint.clone();
+            let render_warned = render_warned.clone();
+            let access_token = access_token.to_string();
             std::thread::spawn(move || {
                 let mut has_shown_friendly_error = false;
-                for line in reader.lines() {
+                for (index, line) in reader.lines().enumerate() {
                     if let Ok(line) = line {
-                        if line.contains("accessToken") || line.contains("MINECRAFT_ACCESS_TOKEN") {
-                            continue;
+                        let line = Self::scrub_access_token(&line, &access_token);
+                        Self::check_crash_line(&app_handle_clone, &instance_name_clone, &line, &live_hint);
+                        if index < crash_analysis::RENDER_CHECK_LINES {
+                            Self::check_render_line(&app_handle_clone, &instance_name_clone, &line, &render_warned);
                         }
                         if !has_shown_friendly_error {
                             let error_message = if line.contains("UnsupportedClassVersionError") {
@@ -1166,21 +1429,25 @@ impl super::instance::InstanceManager {
         let instance_json = instance_dir.join("instance.json");
         let mut updated_instance = instance.clone();
         updated_instance.last_played = Some(Utc::now().to_rfc3339());
-        let updated_json = serde_json::to_string_pretty(&updated_instance)?;
-        fs::write(instance_json, updated_json)?;
+        crate::utils::write_json_atomic(&instance_json, &updated_instance)?;
 
         let instance_name_clone = instance_name.to_string();
+        let instance_id = instance.id.clone();
         let app_handle_clone = app_handle.clone();
         let launching_uuid = uuid.to_string();
         let launch_time = std::time::Instant::now();
+        let launch_system_time = std::time::SystemTime::now();
 
         std::thread::spawn(move || {
             Self::step_post_launch_process(
                 child,
                 &instance_name_clone,
+                &instance_id,
                 &launching_uuid,
                 &app_handle_clone,
                 launch_time,
+                launch_system_time,
+                live_hint,
             );
         });
 
@@ -1228,34 +1495,130 @@ impl super::instance::InstanceManager {
         false
     }
 
+    /// Masks the session token in game output. Loaders like Forge echo their launch
+    /// arguments, so both the raw token and whatever follows `--accessToken` are replaced.
+    pub(crate) fn scrub_access_token(line: &str, access_token: &str) -> String {
+        const MASK: &str = "[REDACTED]";
+
+        let mut scrubbed = if access_token.len() >= 8 {
+            line.replace(access_token, MASK)
+        } else {
+            line.to_string()
+        };
+
+        let mut search_from = 0;
+        while let Some(pos) = scrubbed[search_from..].find("accessToken") {
+            let flag_end = search_from + pos + "accessToken".len();
+            let rest = &scrubbed[flag_end..];
+            let value_start = flag_end + rest.len() - rest.trim_start_matches([' ', ',', '=', ':', '"', '\'']).len();
+            if scrubbed[value_start..].starts_with(MASK) {
+                search_from = value_start + MASK.len();
+                continue;
+            }
+
+            let value_len = scrubbed[value_start..]
+                .find(|c: char| c.is_whitespace() || matches!(c, ',' | '"' | '\'' | ']' | '}'))
+                .unwrap_or(scrubbed.len() - value_start);
+            if value_len > 0 {
+                scrubbed.replace_range(value_start..value_start + value_len, MASK);
+            }
+            search_from = value_start;
+        }
+
+        scrubbed
+    }
+
+    /// Emits a `crash-hint` for the first fatal line of game output matching a known crash
+    /// signature, and remembers its id in `live_hint`.
+    fn check_crash_line(
+        app_handle: &tauri::AppHandle,
+        instance_name: &str,
+        line: &str,
+        live_hint: &Mutex<Option<String>>,
+    ) {
+        let Some(hint) = crash_analysis::match_fatal_line(line) else {
+            return;
+        };
+        let Ok(mut live_hint) = live_hint.lock() else {
+            return;
+        };
+        if live_hint.is_none() {
+            crash_analysis::emit_crash_hint(app_handle, instance_name, &hint);
+            *live_hint = Some(hint.id);
+        }
+    }
+
+    /// Emits a `render-warning` for the first sign of software rendering or OpenGL errors.
+    fn check_render_line(
+        app_handle: &tauri::AppHandle,
+        instance_name: &str,
+        line: &str,
+        render_warned: &AtomicBool,
+    ) {
+        if render_warned.load(Ordering::Relaxed) {
+            return;
+        }
+        if let Some(warning) = crash_analysis::match_render_text(line) {
+            if !render_warned.swap(true, Ordering::Relaxed) {
+                crash_analysis::emit_render_warning(app_handle, instance_name, &warning);
+                Self::emit_error_log(app_handle, instance_name, &format!("WARNING: {}. {}", warning.title, warning.suggestion));
+            }
+        }
+    }
+
     fn step_post_launch_process(
         mut child: Child,
         instance_name: &str,
+        instance_id: &str,
         uuid: &str,
         app_handle: &tauri::AppHandle,
         launch_time: std::time::Instant,
+        launch_system_time: std::time::SystemTime,
+        live_hint: Arc<Mutex<Option<String>>>,
     ) {
-        let _ = child.wait();
+        let exit_status = child.wait();
         let play_duration = launch_time.elapsed().as_secs();
 
         let instance_dir = get_instance_dir(instance_name);
+
+        // `kill_instance` unregisters the process before ending it, that's no crash
+        let killed = crate::commands::instances::RUNNING_PROCESSES
+            .lock()
+            .map(|processes| !processes.contains_key(instance_id))
+            .unwrap_or(false);
+
+        let crashed = !killed && exit_status.as_ref().map(|s| !s.success()).unwrap_or(false);
+        let last_crash = crashed.then(|| {
+            let hint = crash_analysis::analyze_instance_crash(&instance_dir, launch_system_time);
+            // The exit analysis sees the whole crash report, only skip it when the live
+            // check already showed the same hint
+            if let Some(hint) = &hint {
+                let shown_live = live_hint.lock().is_ok_and(|live| live.as_deref() == Some(hint.id.as_str()));
+                if !shown_live {
+                    crash_analysis::emit_crash_hint(app_handle, instance_name, hint);
+                }
+            }
+            let exit_code = exit_status.as_ref().ok().and_then(|s| s.code());
+            crash_analysis::summarize_crash(&instance_dir, launch_system_time, exit_code, hint.as_ref())
+        });
         let instance_json_path = instance_dir.join("instance.json");
 
         if let Ok(content) = fs::read_to_string(&instance_json_path) {
             if let Ok(mut instance) = serde_json::from_str::<Instance>(&content) {
                 instance.total_playtime_seconds += play_duration;
-                if let Ok(updated_json) = serde_json::to_string_pretty(&instance) {
-                    let _ = fs::write(&instance_json_path, updated_json);
+                if !killed {
+                    instance.last_crash = last_crash;
                 }
+                let _ = crate::utils::write_json_atomic(&instance_json_path, &instance);
             }
         }
 
-        {
-            if let Ok(mut processes) = crate::commands::instances::RUNNING_PROCESSES.lock() {
-                processes.remove(instance_name);
-            }
+        if let Ok(mut processes) = crate::commands::instances::RUNNING_PROCESSES.lock() {
+            processes.remove(instance_id);
         }
 
+        crate::services::options::auto_sync_after_session(instance_name, instance_id);
+
         let uuid_owned = uuid.to_string();
         let config = app_handle.state::<crate::models::AppConfig>();
         let supabase_url = config.supabase_url.clone();
@@ -1268,6 +1631,13 @@ impl super::instance::InstanceManager {
             let _ = service.update_status(&uuid_owned, crate::models::FriendStatus::Online, None).await;
         });
 
+        automation::emit(app_handle, AutomationEvent::Exit {
+            instance: instance_name.to_string(),
+            exit_code: exit_status.as_ref().ok().and_then(|s| s.code()),
+            crashed,
+            play_seconds: play_duration,
+        });
+
         let _ = app_handle.emit("instance-exited", serde_json::json!({
             "instance": instance_name
         }));
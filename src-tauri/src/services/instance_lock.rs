@@ -0,0 +1,64 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::Manager;
+
+/// Instances a mutating operation is running against, registered as Tauri state. Commands that
+/// change an instance's files take its lock first, so a mod deletion can't run while e.g. a
+/// modpack update rewrites the mods folder.
+#[derive(Default)]
+pub struct InstanceLocks {
+    held: Mutex<HashMap<String, &'static str>>,
+}
+
+impl InstanceLocks {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn try_acquire(&self, instance_name: &str, action: &'static str) -> Result<(), String> {
+        let mut held = self.held.lock().map_err(|e| e.to_string())?;
+
+        if let Some(running) = held.get(instance_name) {
+            return Err(format!(
+                "Another operation ({}) is in progress for '{}', try again when it finishes",
+                running, instance_name
+            ));
+        }
+
+        held.insert(instance_name.to_string(), action);
+        Ok(())
+    }
+
+    fn release(&self, instance_name: &str) {
+        if let Ok(mut held) = self.held.lock() {
+            held.remove(instance_name);
+        }
+    }
+}
+
+/// Releases the instance when dropped.
+pub struct InstanceLockGuard {
+    app_handle: tauri::AppHandle,
+    instance_name: String,
+}
+
+impl Drop for InstanceLockGuard {
+    fn drop(&mut self) {
+        if let Some(locks) = self.app_handle.try_state::<InstanceLocks>() {
+            locks.release(&self.instance_name);
+        }
+    }
+}
+
+/// Takes the lock of `instance_name` for `action`, e.g. "modpack update", until the returned
+/// guard is dropped. Fails right away when another operation holds it.
+pub fn lock(app_handle: &tauri::AppHandle, instance_name: &str, action: &'static str) -> Result<InstanceLockGuard, String> {
+    if let Some(locks) = app_handle.try_state::<InstanceLocks>() {
+        locks.try_acquire(instance_name, action)?;
+    }
+
+    Ok(InstanceLockGuard {
+        app_handle: app_handle.clone(),
+        instance_name: instance_name.to_string(),
+    })
+}
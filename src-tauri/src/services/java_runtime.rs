@@ -0,0 +1,252 @@
+use serde::Deserialize;
+use sha1::{Digest, Sha1};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Semaphore;
+
+const JAVA_RUNTIME_MANIFEST_URL: &str = "https://launchermeta.mojang.com/v1/products/java-runtime/2ec0cc96c44e5a76b9c8b7c39df7210883d12871/all.json";
+const MAX_CONCURRENT_DOWNLOADS: usize = 16;
+const VERSION_MARKER_FILE: &str = ".runtime_version";
+
+type DownloadError = Box<dyn std::error::Error + Send + Sync>;
+
+#[derive(Debug, Deserialize)]
+struct RuntimeEntry {
+    manifest: RuntimeManifestRef,
+    version: RuntimeVersion,
+}
+
+#[derive(Debug, Deserialize)]
+struct RuntimeManifestRef {
+    url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RuntimeVersion {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RuntimeFiles {
+    files: HashMap<String, RuntimeFile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RuntimeFile {
+    #[serde(rename = "type")]
+    file_type: String,
+    #[serde(default)]
+    executable: bool,
+    downloads: Option<RuntimeFileDownloads>,
+    #[cfg_attr(not(unix), allow(dead_code))]
+    target: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RuntimeFileDownloads {
+    raw: RuntimeDownload,
+}
+
+#[derive(Debug, Deserialize)]
+struct RuntimeDownload {
+    url: String,
+    sha1: String,
+}
+
+/// Downloads the Java runtimes Mojang publishes per game version (java-runtime-gamma etc.)
+/// into `meta/runtimes/<component>`, like the vanilla launcher does.
+pub struct JavaRuntimeInstaller {
+    http_client: reqwest::Client,
+    meta_dir: PathBuf,
+}
+
+impl JavaRuntimeInstaller {
+    pub fn new(meta_dir: PathBuf) -> Self {
+        Self {
+            http_client: crate::utils::http::get_client(),
+            meta_dir,
+        }
+    }
+
+    /// Mojang's platform key for the running OS and architecture.
+    fn platform_key() -> Option<&'static str> {
+        if cfg!(target_os = "windows") {
+            if cfg!(target_arch = "x86_64") {
+                Some("windows-x64")
+            } else if cfg!(target_arch = "aarch64") {
+                Some("windows-arm64")
+            } else if cfg!(target_arch = "x86") {
+                Some("windows-x86")
+            } else {
+                None
+            }
+        } else if cfg!(target_os = "linux") {
+            if cfg!(target_arch = "x86_64") {
+                Some("linux")
+            } else if cfg!(target_arch = "x86") {
+                Some("linux-i386")
+            } else {
+                None
+            }
+        } else {
+            None
+        }
+    }
+
+    pub fn runtime_dir(meta_dir: &Path, component: &str) -> PathBuf {
+        meta_dir.join("runtimes").join(component)
+    }
+
+    /// Path of the java executable of an installed runtime, if present.
+    pub fn installed_java_path(meta_dir: &Path, component: &str) -> Option<PathBuf> {
+        let java_bin = if cfg!(windows) { "javaw.exe" } else { "java" };
+        let path = Self::runtime_dir(meta_dir, component).join("bin").join(java_bin);
+        path.is_file().then_some(path)
+    }
+
+    pub async fn install_runtime(&self, component: &str) -> Result<PathBuf, DownloadError> {
+        if !component.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            return Err(format!("Invalid Java runtime component: {}", component).into());
+        }
+
+        let platform = Self::platform_key()
+            .ok_or("Mojang does not publish Java runtimes for this platform")?;
+
+        let manifest: HashMap<String, HashMap<String, Vec<RuntimeEntry>>> = self
+            .http_client
+            .get(JAVA_RUNTIME_MANIFEST_URL)
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        let entry = manifest
+            .get(platform)
+            .and_then(|components| components.get(component))
+            .and_then(|entries| entries.first())
+            .ok_or_else(|| format!("Java runtime '{}' is not available for {}", component, platform))?;
+
+        let runtime_dir = Self::runtime_dir(&self.meta_dir, component);
+        let marker_path = runtime_dir.join(VERSION_MARKER_FILE);
+
+        if let Ok(installed_version) = std::fs::read_to_string(&marker_path) {
+            if installed_version.trim() == entry.version.name {
+                if let Some(java_path) = Self::installed_java_path(&self.meta_dir, component) {
+                    return Ok(java_path);
+                }
+            }
+        }
+
+        let files: RuntimeFiles = self
+            .http_client
+            .get(&entry.manifest.url)
+            .send()
+            .await?
+            .json()
+            .await?;
+
+        std::fs::create_dir_all(&runtime_dir)?;
+
+        let mut tasks = Vec::new();
+        let mut executables = Vec::new();
+
+        for (relative, file) in &files.files {
+            if relative.split('/').any(|part| part == "..") {
+                continue;
+            }
+            let path = runtime_dir.join(relative);
+
+            match file.file_type.as_str() {
+                "directory" => std::fs::create_dir_all(&path)?,
+                "file" => {
+                    if let Some(downloads) = &file.downloads {
+                        tasks.push((downloads.raw.url.clone(), path.clone(), downloads.raw.sha1.clone()));
+                    }
+                    if file.executable {
+                        executables.push(path);
+                    }
+                }
+                "link" => {
+                    #[cfg(unix)]
+                    if let Some(target) = &file.target {
+                        if let Some(parent) = path.parent() {
+                            std::fs::create_dir_all(parent)?;
+                        }
+                        let _ = std::fs::remove_file(&path);
+                        std::os::unix::fs::symlink(target, &path)?;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        self.download_parallel(tasks).await?;
+
+        #[cfg(unix)]
+        for path in &executables {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o755))?;
+        }
+        #[cfg(not(unix))]
+        let _ = executables;
+
+        std::fs::write(&marker_path, &entry.version.name)?;
+
+        Self::installed_java_path(&self.meta_dir, component)
+            .ok_or_else(|| format!("Java runtime '{}' is missing its java executable", component).into())
+    }
+
+    async fn download_parallel(&self, tasks: Vec<(String, PathBuf, String)>) -> Result<(), DownloadError> {
+        let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_DOWNLOADS));
+        let mut handles = Vec::new();
+
+        for (url, path, sha1) in tasks {
+            let permit = semaphore.clone().acquire_owned().await?;
+            let client = self.http_client.clone();
+
+            handles.push(tokio::spawn(async move {
+                let result = Self::download_verified(&client, &url, &path, &sha1).await;
+                drop(permit);
+                result
+            }));
+        }
+
+        for handle in handles {
+            handle.await??;
+        }
+
+        Ok(())
+    }
+
+    async fn download_verified(
+        client: &reqwest::Client,
+        url: &str,
+        path: &Path,
+        expected_sha1: &str,
+    ) -> Result<(), DownloadError> {
+        if let Ok(existing) = tokio::fs::read(path).await {
+            if format!("{:x}", Sha1::digest(&existing)) == expected_sha1 {
+                return Ok(());
+            }
+        }
+
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+
+        let response = client.get(url).send().await?;
+        if !response.status().is_success() {
+            return Err(format!("HTTP {} for {}", response.status(), url).into());
+        }
+
+        let bytes = response.bytes().await?;
+        let actual_sha1 = format!("{:x}", Sha1::digest(&bytes));
+        if actual_sha1 != expected_sha1 {
+            return Err(format!("Checksum mismatch for {}", path.display()).into());
+        }
+
+        tokio::fs::write(path, bytes).await?;
+        Ok(())
+    }
+}
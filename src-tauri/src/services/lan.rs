@@ -0,0 +1,142 @@
+use serde::Serialize;
+use socket2::{Domain, Protocol, Socket, Type};
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{Emitter, Manager};
+
+/// Multicast group and port the game announces "Open to LAN" worlds on.
+const LAN_MULTICAST_GROUP: Ipv4Addr = Ipv4Addr::new(224, 0, 2, 60);
+const LAN_PORT: u16 = 4445;
+/// The game announces every 1.5 seconds; games not heard from for this long are dropped.
+const LAN_GAME_TIMEOUT_SECS: i64 = 10;
+const RETRY_INTERVAL: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Serialize, Clone)]
+pub struct LanGame {
+    pub motd: String,
+    /// `host:port`, ready to be passed to Quick Play.
+    pub address: String,
+    pub last_seen: i64,
+}
+
+/// Listens for LAN worlds in the background; registered as Tauri state.
+#[derive(Default)]
+pub struct LanDiscovery {
+    games: Mutex<HashMap<String, LanGame>>,
+    task: Mutex<Option<tauri::async_runtime::JoinHandle<()>>>,
+}
+
+impl LanDiscovery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Starts the listener, replacing a previously started one.
+    pub fn start(&self, app_handle: tauri::AppHandle) {
+        let Ok(mut task) = self.task.lock() else {
+            return;
+        };
+
+        if let Some(previous) = task.take() {
+            previous.abort();
+        }
+
+        *task = Some(tauri::async_runtime::spawn(async move {
+            loop {
+                if let Err(e) = listen(&app_handle).await {
+                    eprintln!("LAN discovery stopped: {}", e);
+                }
+                tokio::time::sleep(RETRY_INTERVAL).await;
+            }
+        }));
+    }
+
+    /// LAN games announced recently, sorted by MOTD.
+    pub fn games(&self) -> Vec<LanGame> {
+        let cutoff = chrono::Utc::now().timestamp() - LAN_GAME_TIMEOUT_SECS;
+
+        let Ok(mut games) = self.games.lock() else {
+            return Vec::new();
+        };
+        games.retain(|_, game| game.last_seen >= cutoff);
+
+        let mut games: Vec<LanGame> = games.values().cloned().collect();
+        games.sort_by(|a, b| a.motd.cmp(&b.motd).then_with(|| a.address.cmp(&b.address)));
+        games
+    }
+
+    pub fn is_known(&self, address: &str) -> bool {
+        self.games().iter().any(|game| game.address == address)
+    }
+
+    /// Records an announcement, returning true for a game that wasn't listed yet.
+    fn record(&self, game: LanGame) -> bool {
+        let Ok(mut games) = self.games.lock() else {
+            return false;
+        };
+        games.insert(game.address.clone(), game).is_none()
+    }
+}
+
+/// Binds the announcement port shared with running game clients, which listen on it too.
+fn bind_socket() -> std::io::Result<tokio::net::UdpSocket> {
+    let socket = Socket::new(Domain::IPV4, Type::DGRAM, Some(Protocol::UDP))?;
+    socket.set_reuse_address(true)?;
+    socket.set_nonblocking(true)?;
+    socket.bind(&SocketAddr::from((Ipv4Addr::UNSPECIFIED, LAN_PORT)).into())?;
+    socket.join_multicast_v4(&LAN_MULTICAST_GROUP, &Ipv4Addr::UNSPECIFIED)?;
+
+    tokio::net::UdpSocket::from_std(socket.into())
+}
+
+async fn listen(app_handle: &tauri::AppHandle) -> std::io::Result<()> {
+    let socket = bind_socket()?;
+    let mut buf = [0u8; 1024];
+
+    loop {
+        let (len, sender) = socket.recv_from(&mut buf).await?;
+        let packet = String::from_utf8_lossy(&buf[..len]);
+
+        let Some(game) = parse_announcement(&packet, sender) else {
+            continue;
+        };
+
+        let Some(discovery) = app_handle.try_state::<LanDiscovery>() else {
+            continue;
+        };
+
+        if discovery.record(game.clone()) {
+            let _ = app_handle.emit("lan-game-discovered", &game);
+        }
+    }
+}
+
+fn tag<'a>(packet: &'a str, name: &str) -> Option<&'a str> {
+    let start = packet.find(&format!("[{}]", name))? + name.len() + 2;
+    let end = packet[start..].find(&format!("[/{}]", name))? + start;
+    Some(&packet[start..end])
+}
+
+/// Parses `[MOTD]<motd>[/MOTD][AD]<port>[/AD]`. The host is the sender of the packet.
+fn parse_announcement(packet: &str, sender: SocketAddr) -> Option<LanGame> {
+    // Some versions announce `host:port`, the host is taken from the sender either way
+    let port: u16 = tag(packet, "AD")?.trim().rsplit(':').next()?.parse().ok()?;
+    if port == 0 {
+        return None;
+    }
+
+    let motd: String = tag(packet, "MOTD")
+        .unwrap_or("")
+        .chars()
+        .filter(|c| !c.is_control())
+        .take(128)
+        .collect();
+
+    Some(LanGame {
+        motd,
+        address: format!("{}:{}", sender.ip(), port),
+        last_seen: chrono::Utc::now().timestamp(),
+    })
+}
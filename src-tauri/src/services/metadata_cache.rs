@@ -0,0 +1,82 @@
+use crate::models::{FabricLoaderVersion, VersionManifest};
+use crate::utils::modrinth::ModrinthCategory;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Metadata lists are refetched after this.
+const METADATA_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// A value fetched from the network, kept in memory for `METADATA_TTL`.
+pub struct Cached<T> {
+    value: Mutex<Option<(Instant, T)>>,
+}
+
+impl<T: Clone> Cached<T> {
+    const fn new() -> Self {
+        Self { value: Mutex::new(None) }
+    }
+
+    pub fn get(&self) -> Option<T> {
+        let value = self.value.lock().ok()?;
+        value.as_ref()
+            .filter(|(fetched_at, _)| fetched_at.elapsed() < METADATA_TTL)
+            .map(|(_, value)| value.clone())
+    }
+
+    pub fn set(&self, value: T) {
+        if let Ok(mut cached) = self.value.lock() {
+            *cached = Some((Instant::now(), value));
+        }
+    }
+}
+
+pub static VERSION_MANIFEST: Cached<VersionManifest> = Cached::new();
+pub static FABRIC_LOADERS: Cached<Vec<FabricLoaderVersion>> = Cached::new();
+pub static MODRINTH_CATEGORIES: Cached<Vec<ModrinthCategory>> = Cached::new();
+
+static WARMED_UP: AtomicBool = AtomicBool::new(false);
+
+/// Fetches the lists the "New instance" and browse views open with, once per app start,
+/// so the first click doesn't wait on the network. Failures are only logged, the views
+/// fetch again on their own.
+pub fn warm_up() {
+    if WARMED_UP.swap(true, Ordering::SeqCst) {
+        return;
+    }
+
+    tauri::async_runtime::spawn(async {
+        let meta_dir = crate::utils::get_meta_dir();
+
+        let manifest = async {
+            let installer = crate::services::installer::MinecraftInstaller::new(meta_dir.clone())?;
+            installer.get_versions().await.map(|_| ())
+        };
+
+        let fabric = async {
+            let installer = crate::services::fabric::FabricInstaller::new(meta_dir.clone())
+                .map_err(|e| e.to_string())?;
+            installer.get_loader_versions().await.map(|_| ()).map_err(|e| e.to_string())
+        };
+
+        let categories = async {
+            crate::utils::modrinth::ModrinthClient::with_client(crate::utils::http::get_client())
+                .get_categories()
+                .await
+                .map(|_| ())
+                .map_err(|e| e.to_string())
+        };
+
+        let (manifest, fabric, categories) = tokio::join!(manifest, fabric, categories);
+
+        if let Err(e) = manifest {
+            eprintln!("Failed to prefetch the version manifest: {}", e);
+        }
+        if let Err(e) = fabric {
+            eprintln!("Failed to prefetch Fabric loaders: {}", e);
+        }
+        if let Err(e) = categories {
+            eprintln!("Failed to prefetch Modrinth categories: {}", e);
+        }
+    });
+}
@@ -1,10 +1,33 @@
 pub mod instance;
 pub mod instance_launch;
 pub mod fabric;
+pub mod quilt;
 pub mod neoforge;
 pub mod forge;
 pub mod installer;
 pub mod settings;
 pub mod accounts;
 pub mod friends;
-pub mod trash;
\ No newline at end of file
+pub mod trash;
+pub mod tray;
+pub mod crash_analysis;
+pub mod java_runtime;
+pub mod options;
+pub mod templates;
+pub mod world_backup;
+pub mod config_snapshot;
+pub mod system_info;
+pub mod operations;
+pub mod install_control;
+pub mod profiles;
+pub mod restrictions;
+pub mod authlib_injector;
+pub mod lan;
+pub mod servers;
+pub mod server_ping;
+pub mod metadata_cache;
+pub mod task_history;
+pub mod mod_index;
+pub mod instance_lock;
+pub mod automation;
+pub mod asset_cleanup;
@@ -0,0 +1,182 @@
+use crate::utils::{get_instance_dir, write_json_atomic};
+use serde::{Deserialize, Serialize};
+use sha1::{Digest, Sha1};
+use sha2::Sha512;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const MOD_INDEX_FILE: &str = "mods.index.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ModSource {
+    Modrinth,
+    Curseforge,
+    Github,
+    Local,
+}
+
+/// Where an installed jar came from, recorded by the launcher when it installs the jar.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ModProvenance {
+    pub source: ModSource,
+    /// Modrinth project id, CurseForge mod id or `owner/repo` on GitHub.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project_id: Option<String>,
+    /// Modrinth version id, CurseForge file id or release tag on GitHub.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub download_url: Option<String>,
+    pub sha1: String,
+    pub sha512: String,
+    pub size: u64,
+    /// Modification time of the file in seconds since the epoch.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified: Option<u64>,
+}
+
+fn modified_secs(meta: &fs::Metadata) -> Option<u64> {
+    meta.modified()
+        .ok()
+        .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+        .map(|duration| duration.as_secs())
+}
+
+impl ModProvenance {
+    /// Hashes the installed file at `path`.
+    pub fn from_file(
+        path: &Path,
+        source: ModSource,
+        project_id: Option<String>,
+        version_id: Option<String>,
+        download_url: Option<String>,
+    ) -> std::io::Result<Self> {
+        let bytes = fs::read(path)?;
+        let modified = fs::metadata(path).ok().as_ref().and_then(modified_secs);
+        Ok(Self {
+            source,
+            project_id,
+            version_id,
+            download_url,
+            sha1: format!("{:x}", Sha1::digest(&bytes)),
+            sha512: format!("{:x}", Sha512::digest(&bytes)),
+            size: bytes.len() as u64,
+            modified,
+        })
+    }
+
+    /// Whether `path` still looks like the recorded file. Only size and modification time
+    /// are compared, so checking doesn't mean reading every jar; entries recorded without a
+    /// modification time never match.
+    pub fn matches(&self, path: &Path) -> bool {
+        fs::metadata(path).is_ok_and(|meta| {
+            meta.len() == self.size && self.modified.is_some() && modified_secs(&meta) == self.modified
+        })
+    }
+
+    /// Whether `path` hashes to the recorded file.
+    pub fn verify(&self, path: &Path) -> bool {
+        fs::read(path).is_ok_and(|bytes| {
+            bytes.len() as u64 == self.size
+                && format!("{:x}", Sha1::digest(&bytes)) == self.sha1
+                && format!("{:x}", Sha512::digest(&bytes)) == self.sha512
+        })
+    }
+
+    /// The Modrinth project and version of the file, when it was installed from Modrinth.
+    pub fn modrinth_ids(&self) -> Option<(&str, &str)> {
+        match (self.source, &self.project_id, &self.version_id) {
+            (ModSource::Modrinth, Some(project_id), Some(version_id)) => Some((project_id, version_id)),
+            _ => None,
+        }
+    }
+}
+
+/// What the launcher knows about one jar in an instance's `mods` folder.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ModIndexEntry {
+    /// Left alone by "Update All".
+    #[serde(default)]
+    pub pinned: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<ModProvenance>,
+}
+
+/// Per-instance mod metadata, keyed by jar file name without the `.disabled` suffix so
+/// entries survive a mod being turned off.
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ModIndex {
+    #[serde(default)]
+    pub mods: HashMap<String, ModIndexEntry>,
+}
+
+fn index_path(instance_name: &str) -> PathBuf {
+    get_instance_dir(instance_name).join(MOD_INDEX_FILE)
+}
+
+/// The key a mod file is stored under.
+pub fn mod_key(filename: &str) -> &str {
+    filename.trim_end_matches(".disabled")
+}
+
+impl ModIndex {
+    pub fn load(instance_name: &str) -> Self {
+        fs::read_to_string(index_path(instance_name))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, instance_name: &str) -> Result<(), Box<dyn std::error::Error>> {
+        write_json_atomic(&index_path(instance_name), self)?;
+        Ok(())
+    }
+
+    pub fn is_pinned(&self, filename: &str) -> bool {
+        self.mods.get(mod_key(filename)).is_some_and(|entry| entry.pinned)
+    }
+
+    pub fn set_pinned(&mut self, filename: &str, pinned: bool) {
+        self.mods.entry(mod_key(filename).to_string()).or_default().pinned = pinned;
+        self.prune_empty();
+    }
+
+    pub fn provenance(&self, filename: &str) -> Option<&ModProvenance> {
+        self.mods.get(mod_key(filename)).and_then(|entry| entry.provenance.as_ref())
+    }
+
+    /// Records where `filename` came from. Returns the other mods installed from the same
+    /// project, which conflict with it.
+    pub fn record(&mut self, filename: &str, provenance: ModProvenance) -> Vec<String> {
+        let key = mod_key(filename);
+
+        let conflicts = match &provenance.project_id {
+            Some(project_id) => self
+                .mods
+                .iter()
+                .filter(|(other, entry)| {
+                    other.as_str() != key
+                        && entry.provenance.as_ref().is_some_and(|p| {
+                            p.source == provenance.source && p.project_id.as_ref() == Some(project_id)
+                        })
+                })
+                .map(|(other, _)| other.clone())
+                .collect(),
+            None => Vec::new(),
+        };
+
+        self.mods.entry(key.to_string()).or_default().provenance = Some(provenance);
+        conflicts
+    }
+
+    /// Forgets a mod that was deleted.
+    pub fn remove(&mut self, filename: &str) {
+        self.mods.remove(mod_key(filename));
+    }
+
+    fn prune_empty(&mut self) {
+        self.mods.retain(|_, entry| entry.pinned || entry.provenance.is_some());
+    }
+}
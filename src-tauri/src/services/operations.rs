@@ -0,0 +1,277 @@
+use crate::services::task_history::TaskOutcome;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use tauri::{Emitter, Manager};
+
+/// Every progress event is also emitted on this channel, for a single progress view.
+pub const PROGRESS_EVENT: &str = "operation-progress";
+
+/// Long-running operations that report progress.
+#[derive(Debug, Serialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OperationKind {
+    CreateInstance,
+    ModpackInstall,
+    Export,
+    CopyData,
+    Duplicate,
+    VersionUpdate,
+    DataMigration,
+}
+
+impl OperationKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::CreateInstance => "create_instance",
+            Self::ModpackInstall => "modpack_install",
+            Self::Export => "export",
+            Self::CopyData => "copy_data",
+            Self::Duplicate => "duplicate",
+            Self::VersionUpdate => "version_update",
+            Self::DataMigration => "data_migration",
+        }
+    }
+
+    /// The kind's own event, kept for listeners that only follow one kind of operation.
+    pub fn event_name(self) -> &'static str {
+        match self {
+            Self::CreateInstance => "creation-progress",
+            Self::ModpackInstall => "modpack-install-progress",
+            Self::Export => "export-progress",
+            Self::CopyData => "copy-data-progress",
+            Self::Duplicate => "duplication-progress",
+            Self::VersionUpdate => "version-update-progress",
+            Self::DataMigration => "data-migration-progress",
+        }
+    }
+}
+
+/// Identifies one run of an operation; at most one runs per kind and instance at a time.
+fn operation_id(kind: OperationKind, instance: Option<&str>) -> String {
+    format!("{}:{}", kind.as_str(), instance.unwrap_or(""))
+}
+
+/// Payload of every progress event. `percent` is `None` for updates that only change the
+/// stage, and an operation is finished once it reports 100 or an `error`.
+#[derive(Debug, Serialize, Clone)]
+pub struct ProgressEvent {
+    pub operation_id: String,
+    pub kind: OperationKind,
+    pub instance: Option<String>,
+    pub percent: Option<u32>,
+    pub stage: Option<String>,
+    /// Secondary text, e.g. the file being copied.
+    pub detail: Option<String>,
+    pub error: Option<String>,
+}
+
+impl ProgressEvent {
+    pub fn new(kind: OperationKind, instance: Option<&str>) -> Self {
+        Self {
+            operation_id: operation_id(kind, instance),
+            kind,
+            instance: instance.map(str::to_string),
+            percent: None,
+            stage: None,
+            detail: None,
+            error: None,
+        }
+    }
+
+    pub fn percent(mut self, percent: u32) -> Self {
+        self.percent = Some(percent.min(100));
+        self
+    }
+
+    pub fn stage(mut self, stage: impl Into<String>) -> Self {
+        self.stage = Some(stage.into());
+        self
+    }
+
+    pub fn detail(mut self, detail: impl Into<String>) -> Self {
+        self.detail = Some(detail.into());
+        self
+    }
+
+    pub fn error(mut self, error: impl Into<String>) -> Self {
+        self.error = Some(error.into());
+        self
+    }
+
+    /// Updates the operation registry and emits the event on the kind's channel and on
+    /// [`PROGRESS_EVENT`].
+    pub fn emit(self, app_handle: &tauri::AppHandle) {
+        if let Some(registry) = app_handle.try_state::<OperationRegistry>() {
+            if self.error.is_some() {
+                registry.finish(self.kind, self.instance.as_deref());
+            } else {
+                registry.update(self.kind, self.instance.as_deref(), self.percent, self.stage.clone());
+            }
+        }
+
+        let _ = app_handle.emit(self.kind.event_name(), &self);
+        let _ = app_handle.emit(PROGRESS_EVENT, &self);
+    }
+}
+
+/// Operations that stop reporting without a guard (e.g. a panicked task) are dropped after this.
+const STALE_AFTER: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ActiveOperation {
+    /// Matches `operation_id` of the operation's progress events.
+    pub id: String,
+    pub kind: OperationKind,
+    pub instance: Option<String>,
+    /// Percent done, `None` for operations that only report stages.
+    pub percent: Option<u32>,
+    pub stage: Option<String>,
+    pub started_at: String,
+    #[serde(skip)]
+    updated: Option<Instant>,
+}
+
+impl ActiveOperation {
+    fn new(kind: OperationKind, instance: Option<&str>) -> Self {
+        Self {
+            id: operation_id(kind, instance),
+            kind,
+            instance: instance.map(str::to_string),
+            percent: None,
+            stage: None,
+            started_at: chrono::Utc::now().to_rfc3339(),
+            updated: Some(Instant::now()),
+        }
+    }
+}
+
+/// In-memory registry of running downloads and installs, registered as Tauri state so the
+/// frontend can resynchronize its progress UI after a reload.
+#[derive(Default)]
+pub struct OperationRegistry {
+    operations: Mutex<HashMap<String, ActiveOperation>>,
+}
+
+impl OperationRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn start(&self, kind: OperationKind, instance: Option<&str>) {
+        let Ok(mut operations) = self.operations.lock() else {
+            return;
+        };
+
+        operations.insert(operation_id(kind, instance), ActiveOperation::new(kind, instance));
+    }
+
+    pub fn update(&self, kind: OperationKind, instance: Option<&str>, percent: Option<u32>, stage: Option<String>) {
+        let Ok(mut operations) = self.operations.lock() else {
+            return;
+        };
+
+        let id = operation_id(kind, instance);
+
+        // The final event of an operation reports 100%
+        if percent.is_some_and(|p| p >= 100) {
+            operations.remove(&id);
+            return;
+        }
+
+        let operation = operations.entry(id).or_insert_with(|| ActiveOperation::new(kind, instance));
+
+        if percent.is_some() {
+            operation.percent = percent;
+        }
+        if stage.is_some() {
+            operation.stage = stage;
+        }
+        operation.updated = Some(Instant::now());
+    }
+
+    pub fn finish(&self, kind: OperationKind, instance: Option<&str>) {
+        if let Ok(mut operations) = self.operations.lock() {
+            operations.remove(&operation_id(kind, instance));
+        }
+    }
+
+    pub fn active(&self) -> Vec<ActiveOperation> {
+        let Ok(mut operations) = self.operations.lock() else {
+            return Vec::new();
+        };
+
+        operations.retain(|_, op| op.updated.is_some_and(|updated| updated.elapsed() < STALE_AFTER));
+
+        let mut active: Vec<ActiveOperation> = operations.values().cloned().collect();
+        active.sort_by(|a, b| a.started_at.cmp(&b.started_at));
+        active
+    }
+}
+
+/// Removes its operation from the registry when dropped, so failed commands don't leave
+/// entries behind, and adds it to the task history. Operations dropped without
+/// [`OperationGuard::complete`] are recorded as failed and report an error event.
+pub struct OperationGuard {
+    app_handle: tauri::AppHandle,
+    kind: OperationKind,
+    instance: Option<String>,
+    started_at: String,
+    completed: bool,
+}
+
+impl OperationGuard {
+    /// Marks the operation as succeeded.
+    pub fn complete(mut self) {
+        self.completed = true;
+    }
+}
+
+impl Drop for OperationGuard {
+    fn drop(&mut self) {
+        if self.completed {
+            if let Some(registry) = self.app_handle.try_state::<OperationRegistry>() {
+                registry.finish(self.kind, self.instance.as_deref());
+            }
+
+            if let (OperationKind::CreateInstance | OperationKind::ModpackInstall, Some(instance)) = (self.kind, &self.instance) {
+                crate::services::automation::emit(
+                    &self.app_handle,
+                    crate::services::automation::AutomationEvent::Install {
+                        instance: instance.clone(),
+                        operation: self.kind.as_str().to_string(),
+                    },
+                );
+            }
+        } else {
+            ProgressEvent::new(self.kind, self.instance.as_deref())
+                .error("Operation failed")
+                .emit(&self.app_handle);
+        }
+
+        let outcome = if self.completed { TaskOutcome::Succeeded } else { TaskOutcome::Failed };
+        crate::services::task_history::record(
+            self.kind.as_str(),
+            self.instance.as_deref(),
+            std::mem::take(&mut self.started_at),
+            outcome,
+            None,
+        );
+    }
+}
+
+/// Registers an operation for the duration of a command.
+pub fn track(app_handle: &tauri::AppHandle, kind: OperationKind, instance: Option<&str>) -> OperationGuard {
+    if let Some(registry) = app_handle.try_state::<OperationRegistry>() {
+        registry.start(kind, instance);
+    }
+
+    OperationGuard {
+        app_handle: app_handle.clone(),
+        kind,
+        instance: instance.map(str::to_string),
+        started_at: chrono::Utc::now().to_rfc3339(),
+        completed: false,
+    }
+}
@@ -0,0 +1,237 @@
+use crate::utils::{get_instance_dir, get_launcher_dir};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Launcher-wide options.txt that synced keys are copied from.
+const MASTER_OPTIONS_FILE: &str = "master_options.txt";
+
+/// Minecraft's `options.txt`: one `key:value` pair per line.
+///
+/// Lines are kept in their original order, and lines that are not `key:value` pairs are kept
+/// as well, so saving after an edit only changes the edited entries.
+#[derive(Debug, Clone, Default)]
+pub struct OptionsFile {
+    lines: Vec<OptionsLine>,
+}
+
+#[derive(Debug, Clone)]
+enum OptionsLine {
+    Entry { key: String, value: String },
+    Other(String),
+}
+
+impl OptionsFile {
+    pub fn parse(content: &str) -> Self {
+        let lines = content
+            .lines()
+            .map(|line| match line.split_once(':') {
+                // Values may contain colons themselves (e.g. `resourcePacks:["file/a:b.zip"]`)
+                Some((key, value)) if !key.is_empty() => OptionsLine::Entry {
+                    key: key.to_string(),
+                    value: value.to_string(),
+                },
+                _ => OptionsLine::Other(line.to_string()),
+            })
+            .collect();
+
+        Self { lines }
+    }
+
+    /// Loads the file, treating a missing file as empty (the game creates it on first launch).
+    pub fn load(path: &Path) -> std::io::Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(content) => Ok(Self::parse(&content)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        fs::write(path, self.to_content())
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries().find(|(k, _)| *k == key).map(|(_, v)| v)
+    }
+
+    pub fn set(&mut self, key: &str, value: &str) {
+        for line in &mut self.lines {
+            if let OptionsLine::Entry { key: existing, value: existing_value } = line {
+                if existing == key {
+                    *existing_value = value.to_string();
+                    return;
+                }
+            }
+        }
+
+        self.lines.push(OptionsLine::Entry {
+            key: key.to_string(),
+            value: value.to_string(),
+        });
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.lines.iter().filter_map(|line| match line {
+            OptionsLine::Entry { key, value } => Some((key.as_str(), value.as_str())),
+            OptionsLine::Other(_) => None,
+        })
+    }
+
+    pub fn to_content(&self) -> String {
+        let mut content = String::new();
+        for line in &self.lines {
+            match line {
+                OptionsLine::Entry { key, value } => {
+                    content.push_str(key);
+                    content.push(':');
+                    content.push_str(value);
+                }
+                OptionsLine::Other(raw) => content.push_str(raw),
+            }
+            content.push('\n');
+        }
+        content
+    }
+}
+
+pub fn master_options_path() -> PathBuf {
+    get_launcher_dir().join(MASTER_OPTIONS_FILE)
+}
+
+/// `key_*` matches every key starting with `key_`; anything else must match exactly.
+pub fn key_matches(patterns: &[String], key: &str) -> bool {
+    patterns.iter().any(|pattern| match pattern.strip_suffix('*') {
+        Some(prefix) => key.starts_with(prefix),
+        None => pattern == key,
+    })
+}
+
+/// Copies the values of every key matching `patterns` from `source` into the options file at
+/// `target_path`. Returns how many values changed.
+pub fn sync_options_into(source: &OptionsFile, patterns: &[String], target_path: &Path) -> std::io::Result<usize> {
+    let mut target = OptionsFile::load(target_path)?;
+    let mut changed = 0;
+
+    for (key, value) in source.entries().filter(|(key, _)| key_matches(patterns, key)) {
+        if target.get(key) != Some(value) {
+            target.set(key, value);
+            changed += 1;
+        }
+    }
+
+    if changed > 0 {
+        target.save(target_path)?;
+    }
+
+    Ok(changed)
+}
+
+/// Runs after a play session: pushes the synced keys of the instance that just exited into the
+/// master options and every other instance taking part in sync.
+pub fn auto_sync_after_session(instance_name: &str, instance_id: &str) {
+    let settings = crate::services::settings::SettingsManager::load().unwrap_or_default();
+
+    if !settings.options_sync_auto
+        || settings.options_sync_keys.is_empty()
+        || !settings.options_sync_instances.iter().any(|id| id == instance_id)
+    {
+        return;
+    }
+
+    let source = match OptionsFile::load(&get_instance_dir(instance_name).join("options.txt")) {
+        Ok(source) => source,
+        Err(e) => {
+            eprintln!("Options sync skipped, failed to read options of {}: {}", instance_name, e);
+            return;
+        }
+    };
+
+    if let Err(e) = sync_options_into(&source, &settings.options_sync_keys, &master_options_path()) {
+        eprintln!("Failed to update master options: {}", e);
+    }
+
+    let running: Vec<String> = crate::commands::instances::RUNNING_PROCESSES
+        .lock()
+        .map(|p| p.keys().cloned().collect())
+        .unwrap_or_default();
+
+    let targets = crate::services::instance::InstanceManager::get_all()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|instance| settings.options_sync_instances.contains(&instance.id));
+
+    for target in targets {
+        // A running game would overwrite the synced file when it exits
+        if target.id == instance_id
+            || running.contains(&target.id)
+            || crate::commands::validation::sanitize_instance_name(&target.name).is_err()
+        {
+            continue;
+        }
+
+        let target_path = get_instance_dir(&target.name).join("options.txt");
+        if let Err(e) = sync_options_into(&source, &settings.options_sync_keys, &target_path) {
+            eprintln!("Failed to sync options to {}: {}", target.name, e);
+        }
+    }
+}
+
+/// Before 1.13 options.txt lists resource packs by file name, later versions as `file/<name>`
+/// next to built-in packs like `vanilla`.
+pub fn uses_legacy_pack_names(minecraft_version: &str) -> bool {
+    let mut parts = minecraft_version.split('.');
+    matches!(
+        (parts.next(), parts.next().and_then(|minor| minor.parse::<u32>().ok())),
+        (Some("1"), Some(minor)) if minor < 13
+    )
+}
+
+/// Adds every pack in `packs` (file names in `resourcepacks/`) that is missing from the enabled
+/// resource packs in the options file at `path`, on top of the others. Returns whether the file
+/// changed.
+pub fn enforce_resource_packs(path: &Path, packs: &[String], legacy_names: bool) -> std::io::Result<bool> {
+    let mut options = OptionsFile::load(path)?;
+
+    let mut enabled: Vec<String> = options
+        .get("resourcePacks")
+        .and_then(|value| serde_json::from_str(value).ok())
+        .unwrap_or_else(|| if legacy_names { Vec::new() } else { vec!["vanilla".to_string()] });
+
+    let mut changed = false;
+    for pack in packs {
+        let entry = if legacy_names { pack.clone() } else { format!("file/{}", pack) };
+        if !enabled.contains(&entry) {
+            enabled.push(entry);
+            changed = true;
+        }
+    }
+
+    if changed {
+        let value = serde_json::to_string(&enabled).map_err(std::io::Error::other)?;
+        options.set("resourcePacks", &value);
+        options.save(path)?;
+    }
+
+    Ok(changed)
+}
+
+/// Option keys are plain identifiers like `renderDistance` or `key_key.attack`.
+pub fn validate_option_key(key: &str) -> Result<(), String> {
+    if key.is_empty() || key.len() > 128 {
+        return Err("Option key must be 1-128 characters".to_string());
+    }
+
+    if !key.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-')) {
+        return Err(format!("Invalid option key '{}'", key));
+    }
+
+    Ok(())
+}
+
+pub fn validate_option_value(value: &str) -> Result<(), String> {
+    if value.contains('\n') || value.contains('\r') || value.contains('\0') {
+        return Err("Option value cannot contain line breaks".to_string());
+    }
+
+    Ok(())
+}
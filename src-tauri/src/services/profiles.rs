@@ -0,0 +1,118 @@
+use crate::models::LauncherProfile;
+use crate::utils::{get_active_profile, get_launcher_dir, get_profiles_dir, set_active_profile};
+use std::fs;
+use std::path::PathBuf;
+
+/// Name the default profile is listed and selected under. Its data lives directly in the
+/// launcher dir instead of under `profiles`.
+pub const DEFAULT_PROFILE: &str = "default";
+
+pub struct ProfileManager;
+
+impl ProfileManager {
+    fn profile_dir(name: &str) -> PathBuf {
+        if name == DEFAULT_PROFILE {
+            get_launcher_dir()
+        } else {
+            get_profiles_dir().join(name)
+        }
+    }
+
+    fn instance_count(name: &str) -> usize {
+        fs::read_dir(Self::profile_dir(name).join("instances"))
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter(|e| e.path().join("instance.json").exists())
+                    .count()
+            })
+            .unwrap_or(0)
+    }
+
+    /// The instances folder of every profile, the active one first.
+    pub fn all_instances_dirs() -> Vec<PathBuf> {
+        let active = Self::active();
+        let mut dirs = vec![Self::profile_dir(&active).join("instances")];
+        dirs.extend(
+            Self::get_all()
+                .into_iter()
+                .filter(|p| p.name != active)
+                .map(|p| Self::profile_dir(&p.name).join("instances")),
+        );
+        dirs
+    }
+
+    pub fn active() -> String {
+        get_active_profile().unwrap_or_else(|| DEFAULT_PROFILE.to_string())
+    }
+
+    pub fn get_all() -> Vec<LauncherProfile> {
+        let active = Self::active();
+
+        let mut names: Vec<String> = fs::read_dir(get_profiles_dir())
+            .map(|entries| {
+                entries
+                    .flatten()
+                    .filter(|e| e.path().is_dir())
+                    .map(|e| e.file_name().to_string_lossy().to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        names.sort();
+        names.insert(0, DEFAULT_PROFILE.to_string());
+
+        names
+            .into_iter()
+            .map(|name| LauncherProfile {
+                active: name == active,
+                is_default: name == DEFAULT_PROFILE,
+                instance_count: Self::instance_count(&name),
+                name,
+            })
+            .collect()
+    }
+
+    pub fn exists(name: &str) -> bool {
+        name == DEFAULT_PROFILE || get_profiles_dir().join(name).is_dir()
+    }
+
+    pub fn create(name: &str) -> Result<(), Box<dyn std::error::Error>> {
+        if Self::exists(name) {
+            return Err(format!("Profile '{}' already exists", name).into());
+        }
+
+        fs::create_dir_all(get_profiles_dir().join(name).join("instances"))?;
+        Ok(())
+    }
+
+    /// Deletes an empty profile. Profiles that still have instances are refused so a typo
+    /// can't wipe someone's worlds.
+    pub fn delete(name: &str) -> Result<(), Box<dyn std::error::Error>> {
+        if name == DEFAULT_PROFILE {
+            return Err("The default profile can't be deleted".into());
+        }
+        if !Self::exists(name) {
+            return Err(format!("Profile '{}' does not exist", name).into());
+        }
+        if Self::active() == name {
+            return Err("Switch to another profile before deleting this one".into());
+        }
+        if Self::instance_count(name) > 0 {
+            return Err(format!("Profile '{}' still has instances, delete or move them first", name).into());
+        }
+
+        fs::remove_dir_all(get_profiles_dir().join(name))?;
+        Ok(())
+    }
+
+    /// Makes `name` the profile whose settings, accounts and instances the launcher uses.
+    pub fn switch(name: &str) -> Result<(), Box<dyn std::error::Error>> {
+        if !Self::exists(name) {
+            return Err(format!("Profile '{}' does not exist", name).into());
+        }
+
+        let profile = (name != DEFAULT_PROFILE).then_some(name);
+        set_active_profile(profile)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use crate::models::*;
+use std::path::PathBuf;
+
+const QUILT_META_URL: &str = "https://meta.quiltmc.org/v3";
+
+pub struct QuiltInstaller {
+    http_client: reqwest::Client,
+    launcher_dir: PathBuf,
+}
+
+impl QuiltInstaller {
+    pub fn new(launcher_dir: PathBuf) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Self {
+            http_client: crate::utils::http::get_client(),
+            launcher_dir,
+        })
+    }
+
+    /// Loader versions, newest first. Quilt marks pre-releases in the version string
+    /// (`-beta.N`) instead of a `stable` flag.
+    pub async fn get_loader_versions(&self) -> Result<Vec<QuiltLoaderVersion>, Box<dyn std::error::Error>> {
+        let url = format!("{}/versions/loader", QUILT_META_URL);
+        let response = self.http_client.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            return Err(format!("HTTP {}", response.status()).into());
+        }
+
+        let versions: Vec<QuiltLoaderVersion> = response.json().await?;
+        Ok(versions)
+    }
+
+    pub async fn get_quilt_profile(
+        &self,
+        minecraft_version: &str,
+        loader_version: &str,
+    ) -> Result<FabricProfileJson, Box<dyn std::error::Error>> {
+        let url = format!(
+            "{}/versions/loader/{}/{}/profile/json",
+            QUILT_META_URL, minecraft_version, loader_version
+        );
+
+        let response = self.http_client.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(format!("HTTP {} - {}", status, error_text).into());
+        }
+
+        let profile: FabricProfileJson = response.json().await?;
+        Ok(profile)
+    }
+
+    pub async fn install_quilt(
+        &self,
+        minecraft_version: &str,
+        loader_version: &str,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        let profile = self.get_quilt_profile(minecraft_version, loader_version).await?;
+        crate::services::fabric::install_profile(&self.http_client, &self.launcher_dir, &profile).await
+    }
+}
@@ -0,0 +1,143 @@
+use crate::models::RestrictedMode;
+use crate::services::settings::SettingsManager;
+use sha2::{Digest, Sha256};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Blocked by default when restricted mode is turned on without a custom list.
+pub const DEFAULT_BLOCKED_TERMS: &[&str] = &["nsfw", "gore", "horror", "gambling"];
+
+/// Incorrect PINs in a row before further entries are refused for a while.
+const PIN_ATTEMPTS_BEFORE_LOCKOUT: u32 = 5;
+/// The first lockout, doubled with every further incorrect PIN up to `PIN_LOCKOUT_MAX`.
+const PIN_LOCKOUT: Duration = Duration::from_secs(30);
+const PIN_LOCKOUT_MAX: Duration = Duration::from_secs(15 * 60);
+
+struct PinAttempts {
+    failures: u32,
+    locked_until: Option<Instant>,
+}
+
+static PIN_ATTEMPTS: Mutex<PinAttempts> = Mutex::new(PinAttempts { failures: 0, locked_until: None });
+
+pub struct RestrictionManager;
+
+impl RestrictionManager {
+    pub fn get() -> Option<RestrictedMode> {
+        SettingsManager::load().ok().and_then(|s| s.restricted_mode)
+    }
+
+    pub fn is_restricted() -> bool {
+        Self::get().is_some()
+    }
+
+    /// Central check for actions restricted mode disables. `action` completes the sentence
+    /// "... is disabled in restricted mode".
+    pub fn ensure_allowed(action: &str) -> Result<(), String> {
+        if Self::is_restricted() {
+            return Err(format!("{} is disabled in restricted mode", action));
+        }
+        Ok(())
+    }
+
+    pub fn validate_pin(pin: &str) -> Result<(), String> {
+        if !(4..=8).contains(&pin.len()) || !pin.chars().all(|c| c.is_ascii_digit()) {
+            return Err("PIN must be 4 to 8 digits".to_string());
+        }
+        Ok(())
+    }
+
+    fn hash_pin(pin: &str, salt: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(salt.as_bytes());
+        hasher.update(pin.as_bytes());
+        format!("{:x}", hasher.finalize())
+    }
+
+    pub fn enable(pin: &str, blocked_terms: Option<Vec<String>>) -> Result<(), Box<dyn std::error::Error>> {
+        Self::validate_pin(pin)?;
+
+        SettingsManager::update(|settings| {
+            if settings.restricted_mode.is_some() {
+                return Err("Restricted mode is already enabled".into());
+            }
+
+            let salt = oauth2::CsrfToken::new_random().secret().clone();
+            settings.restricted_mode = Some(RestrictedMode {
+                pin_hash: Self::hash_pin(pin, &salt),
+                salt,
+                blocked_terms: Self::normalize_terms(blocked_terms.unwrap_or_else(|| {
+                    DEFAULT_BLOCKED_TERMS.iter().map(|t| t.to_string()).collect()
+                })),
+            });
+            Ok(())
+        })
+    }
+
+    pub fn disable(pin: &str) -> Result<(), Box<dyn std::error::Error>> {
+        SettingsManager::update(|settings| {
+            let restricted = settings.restricted_mode.as_ref().ok_or("Restricted mode is not enabled")?;
+            Self::verify_pin(restricted, pin)?;
+
+            settings.restricted_mode = None;
+            Ok(())
+        })
+    }
+
+    pub fn set_blocked_terms(pin: &str, blocked_terms: Vec<String>) -> Result<(), Box<dyn std::error::Error>> {
+        SettingsManager::update(|settings| {
+            let restricted = settings.restricted_mode.as_mut().ok_or("Restricted mode is not enabled")?;
+            Self::verify_pin(restricted, pin)?;
+
+            restricted.blocked_terms = Self::normalize_terms(blocked_terms);
+            Ok(())
+        })
+    }
+
+    /// Checks the PIN, refusing every entry for a growing while after repeated incorrect ones.
+    fn verify_pin(restricted: &RestrictedMode, pin: &str) -> Result<(), String> {
+        let mut attempts = PIN_ATTEMPTS.lock().map_err(|e| e.to_string())?;
+
+        if let Some(remaining) = attempts.locked_until.and_then(|until| until.checked_duration_since(Instant::now())) {
+            return Err(format!("Too many incorrect PINs, try again in {} seconds", remaining.as_secs() + 1));
+        }
+
+        if Self::hash_pin(pin, &restricted.salt) != restricted.pin_hash {
+            attempts.failures += 1;
+            if attempts.failures >= PIN_ATTEMPTS_BEFORE_LOCKOUT {
+                let doublings = (attempts.failures - PIN_ATTEMPTS_BEFORE_LOCKOUT).min(16);
+                let lockout = PIN_LOCKOUT.saturating_mul(1 << doublings).min(PIN_LOCKOUT_MAX);
+                attempts.locked_until = Some(Instant::now() + lockout);
+            }
+            return Err("Incorrect PIN".to_string());
+        }
+
+        attempts.failures = 0;
+        attempts.locked_until = None;
+        Ok(())
+    }
+
+    fn normalize_terms(terms: Vec<String>) -> Vec<String> {
+        let mut terms: Vec<String> = terms
+            .into_iter()
+            .map(|t| t.trim().to_lowercase())
+            .filter(|t| !t.is_empty())
+            .collect();
+        terms.sort();
+        terms.dedup();
+        terms
+    }
+
+    /// Whether content with these texts (title, description, categories) is hidden in
+    /// restricted mode. Always false when restricted mode is off.
+    pub fn is_flagged(restricted: Option<&RestrictedMode>, texts: &[&str]) -> bool {
+        let Some(restricted) = restricted else {
+            return false;
+        };
+
+        texts.iter().any(|text| {
+            let text = text.to_lowercase();
+            restricted.blocked_terms.iter().any(|term| text.contains(term.as_str()))
+        })
+    }
+}
@@ -0,0 +1,212 @@
+use serde_json::Value;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+/// Status responses carry the favicon inline, so they can be large, but not this large.
+const MAX_PACKET_BYTES: usize = 2 * 1024 * 1024;
+
+/// What a server reports through the Server List Ping protocol.
+#[derive(Debug, Clone, Default)]
+pub struct ServerStatus {
+    pub version: Option<String>,
+    pub players_online: Option<u32>,
+    pub players_max: Option<u32>,
+    pub motd: Option<String>,
+    /// `data:image/png;base64,...` as sent by the server.
+    pub favicon: Option<String>,
+    /// Round trip of the ping packet, or of the status request for servers that don't answer it.
+    pub ping: u32,
+    /// Whether the server says it runs Forge or NeoForge.
+    pub modded: bool,
+    /// Mods listed by Forge servers. `None` for vanilla and Fabric servers, which don't report
+    /// theirs, and for Forge 1.18+ which only sends a compressed list.
+    pub mods: Option<Vec<ReportedMod>>,
+}
+
+/// A mod from a server's status response.
+#[derive(Debug, Clone)]
+pub struct ReportedMod {
+    pub id: String,
+}
+
+fn write_varint(buf: &mut Vec<u8>, value: i32) {
+    let mut value = value as u32;
+    loop {
+        if value & !0x7F == 0 {
+            buf.push(value as u8);
+            return;
+        }
+        buf.push((value as u8 & 0x7F) | 0x80);
+        value >>= 7;
+    }
+}
+
+async fn read_varint(stream: &mut TcpStream) -> Result<i32, String> {
+    let mut value = 0u32;
+    for i in 0..5 {
+        let byte = stream.read_u8().await.map_err(|e| e.to_string())?;
+        value |= ((byte & 0x7F) as u32) << (7 * i);
+        if byte & 0x80 == 0 {
+            return Ok(value as i32);
+        }
+    }
+    Err("Malformed VarInt from server".to_string())
+}
+
+async fn write_packet(stream: &mut TcpStream, id: i32, payload: &[u8]) -> Result<(), String> {
+    let mut body = Vec::with_capacity(payload.len() + 5);
+    write_varint(&mut body, id);
+    body.extend_from_slice(payload);
+
+    let mut packet = Vec::with_capacity(body.len() + 5);
+    write_varint(&mut packet, body.len() as i32);
+    packet.extend_from_slice(&body);
+
+    stream.write_all(&packet).await.map_err(|e| e.to_string())
+}
+
+/// Reads one packet and returns its id and payload.
+async fn read_packet(stream: &mut TcpStream) -> Result<(i32, Vec<u8>), String> {
+    let length = read_varint(stream).await?;
+    if length <= 0 || length as usize > MAX_PACKET_BYTES {
+        return Err("Invalid packet length from server".to_string());
+    }
+
+    let mut body = vec![0u8; length as usize];
+    stream.read_exact(&mut body).await.map_err(|e| e.to_string())?;
+
+    let mut id = 0u32;
+    let mut offset = 0;
+    for (i, byte) in body.iter().take(5).enumerate() {
+        id |= ((byte & 0x7F) as u32) << (7 * i);
+        offset = i + 1;
+        if byte & 0x80 == 0 {
+            break;
+        }
+    }
+
+    Ok((id as i32, body.split_off(offset)))
+}
+
+/// Flattens a chat component description into plain text, dropping `§` formatting codes.
+fn plain_text(component: &Value) -> String {
+    let mut text = String::new();
+    match component {
+        Value::String(s) => text.push_str(s),
+        Value::Array(parts) => parts.iter().for_each(|part| text.push_str(&plain_text(part))),
+        Value::Object(obj) => {
+            if let Some(s) = obj.get("text").and_then(|t| t.as_str()) {
+                text.push_str(s);
+            }
+            if let Some(extra) = obj.get("extra") {
+                text.push_str(&plain_text(extra));
+            }
+        }
+        _ => {}
+    }
+
+    let mut clean = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '§' {
+            chars.next();
+        } else {
+            clean.push(c);
+        }
+    }
+    clean
+}
+
+/// Forge 1.7 - 1.12 sends `modinfo.modList`, later versions `forgeData.mods`.
+fn parse_reported_mods(value: &Value) -> Option<Vec<ReportedMod>> {
+    let (list, id_key) = if let Some(list) = value["modinfo"]["modList"].as_array() {
+        (list, "modid")
+    } else if let Some(list) = value["forgeData"]["mods"].as_array() {
+        (list, "modId")
+    } else {
+        return None;
+    };
+
+    let mods: Vec<ReportedMod> = list
+        .iter()
+        .filter_map(|entry| {
+            Some(ReportedMod {
+                id: entry[id_key].as_str()?.to_string(),
+            })
+        })
+        .collect();
+
+    // Forge always lists itself, an empty list means the real one was compressed
+    (!mods.is_empty()).then_some(mods)
+}
+
+fn parse_status(json: &str) -> Result<ServerStatus, String> {
+    let value: Value = serde_json::from_str(json)
+        .map_err(|e| format!("Invalid status response: {}", e))?;
+
+    let motd = value.get("description").map(plain_text).map(|m| m.trim().to_string());
+
+    Ok(ServerStatus {
+        version: value["version"]["name"].as_str().map(String::from),
+        players_online: value["players"]["online"].as_u64().map(|n| n as u32),
+        players_max: value["players"]["max"].as_u64().map(|n| n as u32),
+        motd: motd.filter(|m| !m.is_empty()),
+        // Older servers wrap the base64 data over several lines
+        favicon: value["favicon"].as_str().map(|f| f.replace(['\n', '\r'], "")),
+        ping: 0,
+        modded: value.get("forgeData").is_some() || value.get("modinfo").is_some() || value["isModded"].as_bool() == Some(true),
+        mods: parse_reported_mods(&value),
+    })
+}
+
+async fn query(address: &str, port: u16) -> Result<ServerStatus, String> {
+    let mut stream = TcpStream::connect((address, port))
+        .await
+        .map_err(|e| format!("Connection failed: {}", e))?;
+    stream.set_nodelay(true).ok();
+
+    // Handshake: protocol version -1 ("any"), host, port, next state 1 (status)
+    let mut handshake = Vec::new();
+    write_varint(&mut handshake, -1);
+    write_varint(&mut handshake, address.len() as i32);
+    handshake.extend_from_slice(address.as_bytes());
+    handshake.extend_from_slice(&port.to_be_bytes());
+    write_varint(&mut handshake, 1);
+
+    let requested_at = Instant::now();
+    write_packet(&mut stream, 0x00, &handshake).await?;
+    write_packet(&mut stream, 0x00, &[]).await?;
+
+    let (id, payload) = read_packet(&mut stream).await?;
+    let status_rtt = requested_at.elapsed().as_millis() as u32;
+    if id != 0x00 {
+        return Err(format!("Unexpected status packet id {}", id));
+    }
+
+    // The payload is a single length-prefixed string
+    let json_start = payload.iter().take(5).position(|b| b & 0x80 == 0).ok_or("Malformed status response")? + 1;
+    let mut status = parse_status(&String::from_utf8_lossy(&payload[json_start..]))?;
+
+    let ping_sent = Instant::now();
+    let token = chrono::Utc::now().timestamp_millis();
+    let pong = async {
+        write_packet(&mut stream, 0x01, &token.to_be_bytes()).await?;
+        read_packet(&mut stream).await
+    };
+
+    status.ping = match pong.await {
+        Ok((0x01, payload)) if payload == token.to_be_bytes() => ping_sent.elapsed().as_millis() as u32,
+        _ => status_rtt,
+    };
+
+    Ok(status)
+}
+
+/// Queries a server's status, giving up after `timeout`. SRV records aren't resolved, the
+/// address is connected to as stored.
+pub async fn query_status(address: &str, port: u16, timeout: Duration) -> Result<ServerStatus, String> {
+    tokio::time::timeout(timeout, query(address, port))
+        .await
+        .map_err(|_| "Server did not respond in time".to_string())?
+}
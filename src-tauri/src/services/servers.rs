@@ -0,0 +1,156 @@
+use crate::models::ServerInfo;
+use crate::utils::{get_launcher_dir, write_json_atomic};
+use base64::{engine::general_purpose, Engine as _};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tokio::sync::RwLock;
+
+const MAX_FAVICON_BYTES: usize = 64 * 1024;
+const FAVICON_DATA_PREFIX: &str = "data:image/png;base64,";
+
+/// The server list in servers.json; registered as Tauri state so every change goes
+/// through one lock.
+#[derive(Default)]
+pub struct ServerManager {
+    /// The list with the file it was loaded from, which changes with the data directory.
+    servers: RwLock<Option<(PathBuf, Vec<ServerInfo>)>>,
+}
+
+impl ServerManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn servers_file() -> PathBuf {
+        get_launcher_dir().join("servers.json")
+    }
+
+    fn favicon_dir() -> PathBuf {
+        get_launcher_dir().join("server_icons")
+    }
+
+    /// Favicons are stored per address, so renaming a server keeps its icon.
+    pub fn favicon_path(address: &str, port: u16) -> PathBuf {
+        use sha2::Digest;
+        let hash = format!("{:x}", sha2::Sha256::digest(format!("{}:{}", address.to_lowercase(), port)));
+        Self::favicon_dir().join(format!("{}.png", &hash[..16]))
+    }
+
+    /// Moves an inline base64 favicon into the icon cache, leaving URLs untouched. A favicon
+    /// that isn't a small PNG is dropped, the server is kept.
+    fn store_favicon(server: &mut ServerInfo) -> Result<(), String> {
+        let Some(data) = server.favicon.as_deref().and_then(|f| f.strip_prefix(FAVICON_DATA_PREFIX)) else {
+            return Ok(());
+        };
+
+        let bytes = general_purpose::STANDARD.decode(data).ok().filter(|bytes| {
+            bytes.len() <= MAX_FAVICON_BYTES && image::guess_format(bytes).ok() == Some(image::ImageFormat::Png)
+        });
+        let Some(bytes) = bytes else {
+            eprintln!("Dropping the invalid favicon of {}", server.address);
+            server.favicon = None;
+            return Ok(());
+        };
+
+        fs::create_dir_all(Self::favicon_dir())
+            .map_err(|e| e.to_string())?;
+        fs::write(Self::favicon_path(&server.address, server.port), bytes)
+            .map_err(|e| e.to_string())?;
+
+        server.favicon = None;
+        Ok(())
+    }
+
+    /// Turns a raw base64 icon, as stored in a game's servers.dat, into a favicon the list
+    /// accepts. Returns `None` for anything that isn't a small PNG.
+    pub fn favicon_from_base64(data: &str) -> Option<String> {
+        let bytes = general_purpose::STANDARD.decode(data.trim()).ok()?;
+
+        if bytes.len() > MAX_FAVICON_BYTES || image::guess_format(&bytes).ok() != Some(image::ImageFormat::Png) {
+            return None;
+        }
+
+        Some(format!("{}{}", FAVICON_DATA_PREFIX, general_purpose::STANDARD.encode(bytes)))
+    }
+
+    fn read_file(path: &Path) -> Result<Vec<ServerInfo>, String> {
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(path)
+            .map_err(|e| e.to_string())?;
+
+        serde_json::from_str(&content)
+            .map_err(|e| e.to_string())
+    }
+
+    fn write_file(path: &Path, servers: &mut [ServerInfo]) -> Result<(), String> {
+        for server in servers.iter_mut() {
+            Self::store_favicon(server)?;
+        }
+
+        write_json_atomic(path, servers)
+            .map_err(|e| e.to_string())
+    }
+
+    /// The stored list, with cached favicons filled in as data URLs.
+    pub async fn list(&self) -> Result<Vec<ServerInfo>, String> {
+        let path = Self::servers_file();
+
+        let cached = {
+            let servers = self.servers.read().await;
+            servers.as_ref().filter(|(p, _)| *p == path).map(|(_, list)| list.clone())
+        };
+
+        let mut servers = match cached {
+            Some(servers) => servers,
+            None => {
+                let mut cache = self.servers.write().await;
+                let mut servers = Self::read_file(&path)?;
+
+                // servers.json from before the icon cache keeps favicons inline
+                if servers.iter().any(|s| s.favicon.as_deref().is_some_and(|f| f.starts_with(FAVICON_DATA_PREFIX))) {
+                    Self::write_file(&path, &mut servers)?;
+                }
+
+                *cache = Some((path, servers.clone()));
+                servers
+            }
+        };
+
+        for server in servers.iter_mut().filter(|s| s.favicon.is_none()) {
+            if let Ok(bytes) = fs::read(Self::favicon_path(&server.address, server.port)) {
+                server.favicon = Some(format!("{}{}", FAVICON_DATA_PREFIX, general_purpose::STANDARD.encode(bytes)));
+            }
+        }
+
+        Ok(servers)
+    }
+
+    /// Changes the list and writes it back while holding the lock. Nothing is kept when `f`
+    /// or the write fails. Returns the list as saved.
+    pub async fn update(
+        &self,
+        f: impl FnOnce(&mut Vec<ServerInfo>) -> Result<(), String>,
+    ) -> Result<Vec<ServerInfo>, String> {
+        let path = Self::servers_file();
+        let mut servers = self.servers.write().await;
+
+        let mut list = match servers.as_ref() {
+            Some((p, list)) if *p == path => list.clone(),
+            _ => Self::read_file(&path)?,
+        };
+
+        f(&mut list)?;
+        Self::write_file(&path, &mut list)?;
+
+        *servers = Some((path, list.clone()));
+        Ok(list)
+    }
+
+    /// Drops the cached list after servers.json was replaced on disk.
+    pub async fn reload(&self) {
+        *self.servers.write().await = None;
+    }
+}
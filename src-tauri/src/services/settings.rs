@@ -1,12 +1,20 @@
 use crate::models::LauncherSettings;
-use crate::utils::get_launcher_dir;
+use crate::utils::{get_profile_dir, write_json_atomic};
+use serde_json::{Map, Value};
 use std::fs;
+use std::sync::Mutex;
+
+/// Serializes read-modify-write cycles on settings.json.
+static SETTINGS_LOCK: Mutex<()> = Mutex::new(());
+
+/// Bump together with a new step in `SettingsManager::migrate`.
+pub const CURRENT_SETTINGS_VERSION: u32 = 1;
 
 pub struct SettingsManager;
 
 impl SettingsManager {
     fn get_settings_path() -> std::path::PathBuf {
-        get_launcher_dir().join("settings.json")
+        get_profile_dir().join("settings.json")
     }
 
     pub fn load() -> Result<LauncherSettings, Box<dyn std::error::Error>> {
@@ -19,10 +27,81 @@ impl SettingsManager {
         }
 
         let content = fs::read_to_string(&settings_path)?;
-        let settings: LauncherSettings = serde_json::from_str(&content)?;
+        let stored: Value = match serde_json::from_str(&content) {
+            Ok(value) => value,
+            Err(e) => {
+                // Keep the unreadable file around instead of silently overwriting it
+                let backup = settings_path.with_extension(format!("json.{}.bak", chrono::Utc::now().format("%Y%m%d%H%M%S")));
+                fs::rename(&settings_path, &backup)?;
+                eprintln!("settings.json is not valid JSON ({}), moved to {}", e, backup.display());
+
+                let default_settings = LauncherSettings::default();
+                Self::save(&default_settings)?;
+                return Ok(default_settings);
+            }
+        };
+
+        let Value::Object(mut fields) = stored else {
+            return Err("settings.json does not contain an object".into());
+        };
+
+        let version = fields.get("settings_version").and_then(|v| v.as_u64()).unwrap_or(0) as u32;
+        if version > CURRENT_SETTINGS_VERSION {
+            eprintln!("settings.json is from a newer launcher (version {}), unknown settings are ignored", version);
+        }
+
+        let migrated = version < CURRENT_SETTINGS_VERSION;
+        if migrated {
+            Self::migrate(&mut fields, version);
+        }
+
+        let settings = Self::from_fields(fields);
+        if migrated {
+            Self::save(&settings)?;
+        }
+
         Ok(settings)
     }
 
+    /// Upgrades stored settings from `from_version` to `CURRENT_SETTINGS_VERSION`, one step
+    /// per version.
+    fn migrate(fields: &mut Map<String, Value>, from_version: u32) {
+        for version in from_version..CURRENT_SETTINGS_VERSION {
+            match version {
+                // Unversioned files only lack newer fields, which take their defaults
+                0 => {}
+                _ => unreachable!("no migration from settings version {}", version),
+            }
+        }
+
+        fields.insert("settings_version".to_string(), Value::from(CURRENT_SETTINGS_VERSION));
+    }
+
+    /// Deserializes settings field by field, so one value of the wrong type resets only that
+    /// setting instead of the whole file.
+    fn from_fields(fields: Map<String, Value>) -> LauncherSettings {
+        if let Ok(settings) = serde_json::from_value(Value::Object(fields.clone())) {
+            return settings;
+        }
+
+        let Ok(Value::Object(mut merged)) = serde_json::to_value(LauncherSettings::default()) else {
+            return LauncherSettings::default();
+        };
+
+        for (key, value) in fields {
+            let previous = merged.insert(key.clone(), value);
+            if serde_json::from_value::<LauncherSettings>(Value::Object(merged.clone())).is_err() {
+                eprintln!("Ignoring invalid value for setting '{}'", key);
+                match previous {
+                    Some(previous) => merged.insert(key, previous),
+                    None => merged.remove(&key),
+                };
+            }
+        }
+
+        serde_json::from_value(Value::Object(merged)).unwrap_or_default()
+    }
+
     pub fn save(settings: &LauncherSettings) -> Result<(), Box<dyn std::error::Error>> {
         let settings_path = Self::get_settings_path();
         
@@ -30,9 +109,20 @@ impl SettingsManager {
             fs::create_dir_all(parent)?;
         }
 
-        let json = serde_json::to_string_pretty(settings)?;
-        fs::write(&settings_path, json)?;
+        write_json_atomic(&settings_path, settings)?;
         
         Ok(())
     }
+
+    /// Loads, changes and saves the settings without another update slipping in between.
+    pub fn update<T>(
+        f: impl FnOnce(&mut LauncherSettings) -> Result<T, Box<dyn std::error::Error>>,
+    ) -> Result<T, Box<dyn std::error::Error>> {
+        let _guard = SETTINGS_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+        let mut settings = Self::load()?;
+        let result = f(&mut settings)?;
+        Self::save(&settings)?;
+        Ok(result)
+    }
 }
\ No newline at end of file
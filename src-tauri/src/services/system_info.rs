@@ -0,0 +1,139 @@
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GpuInfo {
+    pub name: String,
+    pub vendor: Option<String>,
+    pub driver_version: Option<String>,
+}
+
+/// Graphics adapters as reported by the OS. Detection spawns external tools, so the
+/// result is cached for the lifetime of the launcher.
+pub fn detect_gpus() -> Vec<GpuInfo> {
+    static GPUS: OnceLock<Vec<GpuInfo>> = OnceLock::new();
+    GPUS.get_or_init(query_gpus).clone()
+}
+
+#[cfg(any(target_os = "windows", target_os = "linux", target_os = "macos"))]
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let mut cmd = std::process::Command::new(program);
+    cmd.args(args);
+
+    #[cfg(target_os = "windows")]
+    {
+        use std::os::windows::process::CommandExt;
+        const CREATE_NO_WINDOW: u32 = 0x08000000;
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let output = cmd.output().ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(target_os = "windows")]
+fn query_gpus() -> Vec<GpuInfo> {
+    let Some(output) = command_output(
+        "powershell",
+        &[
+            "-NoProfile",
+            "-Command",
+            "Get-CimInstance Win32_VideoController | Select-Object Name,AdapterCompatibility,DriverVersion | ConvertTo-Json",
+        ],
+    ) else {
+        return Vec::new();
+    };
+
+    // A single adapter is serialized as an object instead of an array
+    let adapters = match serde_json::from_str::<serde_json::Value>(&output) {
+        Ok(serde_json::Value::Array(items)) => items,
+        Ok(item @ serde_json::Value::Object(_)) => vec![item],
+        _ => return Vec::new(),
+    };
+
+    adapters
+        .iter()
+        .filter_map(|adapter| {
+            Some(GpuInfo {
+                name: adapter["Name"].as_str()?.trim().to_string(),
+                vendor: adapter["AdapterCompatibility"].as_str().map(|s| s.trim().to_string()),
+                driver_version: adapter["DriverVersion"].as_str().map(|s| s.trim().to_string()),
+            })
+        })
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+fn query_gpus() -> Vec<GpuInfo> {
+    let Some(output) = command_output("lspci", &["-mm"]) else {
+        return Vec::new();
+    };
+
+    let nvidia_version = std::fs::read_to_string("/sys/module/nvidia/version")
+        .ok()
+        .map(|v| v.trim().to_string());
+
+    output
+        .lines()
+        .filter_map(|line| {
+            // 00:02.0 "VGA compatible controller" "Intel Corporation" "UHD Graphics 620" ...
+            let fields: Vec<&str> = line.split('"').skip(1).step_by(2).collect();
+            let class = fields.first()?;
+            if !["VGA compatible controller", "3D controller", "Display controller"].contains(class) {
+                return None;
+            }
+
+            let vendor = fields.get(1).map(|s| s.to_string());
+            let is_nvidia = vendor.as_deref().is_some_and(|v| v.contains("NVIDIA"));
+
+            Some(GpuInfo {
+                name: fields.get(2)?.to_string(),
+                vendor,
+                driver_version: if is_nvidia { nvidia_version.clone() } else { mesa_version() },
+            })
+        })
+        .collect()
+}
+
+/// Mesa's version, from `glxinfo` when it is installed.
+#[cfg(target_os = "linux")]
+fn mesa_version() -> Option<String> {
+    static MESA: OnceLock<Option<String>> = OnceLock::new();
+    MESA.get_or_init(|| {
+        command_output("glxinfo", &["-B"])?
+            .lines()
+            .find_map(|line| line.trim().strip_prefix("OpenGL version string:"))
+            .map(|v| v.trim().to_string())
+    })
+    .clone()
+}
+
+#[cfg(target_os = "macos")]
+fn query_gpus() -> Vec<GpuInfo> {
+    let Some(output) = command_output("system_profiler", &["SPDisplaysDataType", "-json"]) else {
+        return Vec::new();
+    };
+
+    let Ok(json) = serde_json::from_str::<serde_json::Value>(&output) else {
+        return Vec::new();
+    };
+
+    json["SPDisplaysDataType"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|display| {
+            Some(GpuInfo {
+                name: display["sppci_model"].as_str()?.to_string(),
+                vendor: display["spdisplays_vendor"].as_str().map(|s| s.trim_start_matches("sppci_vendor_").to_string()),
+                // macOS ships GPU drivers with the OS, the Metal family is the closest equivalent
+                driver_version: display["spdisplays_mtlgpufamilysupport"].as_str().map(|s| s.to_string()),
+            })
+        })
+        .collect()
+}
+
+#[cfg(not(any(target_os = "windows", target_os = "linux", target_os = "macos")))]
+fn query_gpus() -> Vec<GpuInfo> {
+    Vec::new()
+}
@@ -0,0 +1,84 @@
+use crate::utils::get_launcher_dir;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+const HISTORY_FILE: &str = "history.json";
+/// Oldest entries are dropped beyond this.
+const MAX_ENTRIES: usize = 500;
+
+/// Serializes read-modify-write cycles of the history file.
+static HISTORY_LOCK: Mutex<()> = Mutex::new(());
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskOutcome {
+    Succeeded,
+    Failed,
+}
+
+/// A finished operation, as listed in the activity log.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TaskRecord {
+    /// Same kinds as the active operations, e.g. `modpack_install` or `world_backup`.
+    pub kind: String,
+    pub instance: Option<String>,
+    pub started_at: String,
+    pub finished_at: String,
+    pub outcome: TaskOutcome,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+fn history_path() -> PathBuf {
+    get_launcher_dir().join(HISTORY_FILE)
+}
+
+fn read_history() -> Vec<TaskRecord> {
+    std::fs::read_to_string(history_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Appends a finished operation to `history.json`. Failures to write are only logged,
+/// the history must never fail the operation itself.
+pub fn record(kind: &str, instance: Option<&str>, started_at: String, outcome: TaskOutcome, error: Option<String>) {
+    let Ok(_lock) = HISTORY_LOCK.lock() else {
+        return;
+    };
+
+    let mut history = read_history();
+    history.push(TaskRecord {
+        kind: kind.to_string(),
+        instance: instance.map(str::to_string),
+        started_at,
+        finished_at: chrono::Utc::now().to_rfc3339(),
+        outcome,
+        error,
+    });
+
+    if history.len() > MAX_ENTRIES {
+        history.drain(..history.len() - MAX_ENTRIES);
+    }
+
+    if let Err(e) = crate::utils::write_json_atomic(&history_path(), &history) {
+        eprintln!("Failed to write task history: {}", e);
+    }
+}
+
+/// Records the outcome of `result` for an operation started at `started_at`.
+pub fn record_result<T>(kind: &str, instance: Option<&str>, started_at: String, result: &Result<T, String>) {
+    match result {
+        Ok(_) => record(kind, instance, started_at, TaskOutcome::Succeeded, None),
+        Err(e) => record(kind, instance, started_at, TaskOutcome::Failed, Some(e.clone())),
+    }
+}
+
+/// Finished operations, newest first.
+pub fn list() -> Vec<TaskRecord> {
+    let _lock = HISTORY_LOCK.lock();
+    let mut history = read_history();
+    history.reverse();
+    history
+}
@@ -0,0 +1,416 @@
+use crate::models::{Instance, InstanceLaunchOptions, InstanceTemplate, KeybindConflict, MinecraftOptions};
+use crate::services::options::OptionsFile;
+use crate::utils::{get_instance_dir, get_launcher_dir, write_json_atomic};
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Extension of shareable template files.
+pub const TEMPLATE_FILE_EXTENSION: &str = "octemplate";
+
+/// Bumped whenever the shared template layout changes incompatibly.
+const TEMPLATE_FORMAT_VERSION: u32 = 1;
+
+const TEMPLATE_ICON_SIZE: u32 = 64;
+const MAX_ICON_BYTES: usize = 512 * 1024;
+
+/// On-disk layout of an `.octemplate` file.
+#[derive(Serialize, Deserialize)]
+struct TemplateFile {
+    format_version: u32,
+    /// SHA-256 of `template` serialized as compact JSON.
+    checksum: String,
+    template: serde_json::Value,
+}
+
+pub struct TemplateManager;
+
+impl TemplateManager {
+    fn templates_dir() -> PathBuf {
+        get_launcher_dir().join("templates")
+    }
+
+    fn template_path(id: &str) -> Result<PathBuf, Box<dyn std::error::Error>> {
+        if id.is_empty() || !id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+            return Err(format!("Invalid template id '{}'", id).into());
+        }
+        Ok(Self::templates_dir().join(format!("{}.json", id)))
+    }
+
+    pub fn get_all() -> Result<Vec<InstanceTemplate>, Box<dyn std::error::Error>> {
+        let templates_dir = Self::templates_dir();
+        if !templates_dir.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut templates: Vec<InstanceTemplate> = fs::read_dir(&templates_dir)?
+            .flatten()
+            .filter(|e| e.path().extension().and_then(|ext| ext.to_str()) == Some("json"))
+            .filter_map(|e| fs::read_to_string(e.path()).ok())
+            .filter_map(|content| serde_json::from_str(&content).ok())
+            .collect();
+
+        templates.sort_by_key(|t| t.name.to_lowercase());
+        Ok(templates)
+    }
+
+    pub fn get(id: &str) -> Result<Option<InstanceTemplate>, Box<dyn std::error::Error>> {
+        let path = Self::template_path(id)?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        Ok(Some(serde_json::from_str(&fs::read_to_string(path)?)?))
+    }
+
+    pub fn save(template: &InstanceTemplate) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Self::template_path(&template.id)?;
+        fs::create_dir_all(Self::templates_dir())?;
+        write_json_atomic(&path, template)?;
+        Ok(())
+    }
+
+    pub fn delete(id: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Self::template_path(id)?;
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    /// Writes the template to a shareable `.octemplate` file. Machine-specific paths are left out.
+    pub fn export_to(template: &InstanceTemplate, path: &Path) -> Result<(), Box<dyn std::error::Error>> {
+        if path.extension().and_then(|ext| ext.to_str()) != Some(TEMPLATE_FILE_EXTENSION) {
+            return Err(format!("Template files must use the .{} extension", TEMPLATE_FILE_EXTENSION).into());
+        }
+
+        let mut template = template.clone();
+        strip_machine_paths(&mut template);
+
+        let value = serde_json::to_value(&template)?;
+        let file = TemplateFile {
+            format_version: TEMPLATE_FORMAT_VERSION,
+            checksum: checksum(&value)?,
+            template: value,
+        };
+
+        fs::write(path, serde_json::to_string_pretty(&file)?)?;
+        Ok(())
+    }
+
+    /// Reads and validates an `.octemplate` file. The template gets a fresh id so importing
+    /// never overwrites an existing one.
+    pub fn import_from(path: &Path) -> Result<InstanceTemplate, Box<dyn std::error::Error>> {
+        if path.extension().and_then(|ext| ext.to_str()) != Some(TEMPLATE_FILE_EXTENSION) {
+            return Err(format!("Not a .{} file", TEMPLATE_FILE_EXTENSION).into());
+        }
+
+        let file: TemplateFile = serde_json::from_str(&fs::read_to_string(path)?)
+            .map_err(|e| format!("Invalid template file: {}", e))?;
+
+        if file.format_version == 0 || file.format_version > TEMPLATE_FORMAT_VERSION {
+            return Err(format!(
+                "Unsupported template format version {} (this launcher supports up to {})",
+                file.format_version, TEMPLATE_FORMAT_VERSION
+            )
+            .into());
+        }
+
+        if !checksum(&file.template)?.eq_ignore_ascii_case(&file.checksum) {
+            return Err("Template file is corrupted (checksum mismatch)".into());
+        }
+
+        let mut template: InstanceTemplate = serde_json::from_value(file.template)
+            .map_err(|e| format!("Invalid template: {}", e))?;
+
+        // A Java path from the machine that exported the template means nothing here
+        strip_machine_paths(&mut template);
+        Self::validate_imported(&mut template)?;
+
+        template.id = chrono::Utc::now().timestamp_millis().to_string();
+        template.created_at = chrono::Utc::now().to_rfc3339();
+        Ok(template)
+    }
+
+    /// Checks names and option values and normalizes the embedded icon to a small PNG.
+    pub fn validate(template: &mut InstanceTemplate) -> Result<(), Box<dyn std::error::Error>> {
+        template.name = template.name.trim().to_string();
+        if template.name.is_empty() || template.name.len() > 64 {
+            return Err("Template name must be 1-64 characters".into());
+        }
+
+        if template.description.as_ref().is_some_and(|d| d.len() > 1024) {
+            return Err("Template description is too long (max 1024 characters)".into());
+        }
+
+        if let Some(settings) = &template.launcher_settings {
+            crate::commands::validation::validate_env_vars(&settings.env_vars)?;
+            crate::commands::validation::validate_jvm_args(&settings.jvm_args)?;
+            crate::commands::validation::validate_window_size(settings.window_width, settings.window_height)?;
+        }
+
+        if let Some(options) = &template.minecraft_options {
+            validate_minecraft_options(options)?;
+        }
+
+        if let Some(icon) = template.icon.take() {
+            template.icon = Some(normalize_icon(&icon)?);
+        }
+
+        Ok(())
+    }
+
+    /// `validate` for templates from a shared file, whose JVM arguments are held to an allowlist.
+    pub fn validate_imported(template: &mut InstanceTemplate) -> Result<(), Box<dyn std::error::Error>> {
+        Self::validate(template)?;
+        if let Some(settings) = &template.launcher_settings {
+            crate::commands::validation::validate_imported_jvm_args(&settings.jvm_args)?;
+        }
+        Ok(())
+    }
+
+    /// Reads the options a template can carry from the contents of an options.txt.
+    pub fn parse_options_txt(content: &str) -> MinecraftOptions {
+        let file = OptionsFile::parse(content);
+        let get = |key: &str| file.get(key).map(|v| v.trim());
+        let get_bool = |key: &str| get(key).and_then(|v| v.parse::<bool>().ok());
+        let get_u32 = |key: &str| get(key).and_then(|v| v.parse::<u32>().ok());
+        let get_f32 = |key: &str| get(key).and_then(|v| v.parse::<f32>().ok());
+
+        let keybinds: HashMap<String, String> = file
+            .entries()
+            .filter_map(|(key, value)| key.strip_prefix("key_").map(|action| (action.to_string(), value.to_string())))
+            .collect();
+
+        MinecraftOptions {
+            // options.txt stores the FOV as -1.0..1.0 around the default of 70 degrees
+            fov: get_f32("fov").map(|v| (70.0 + v * 40.0).round() as u32),
+            render_distance: get_u32("renderDistance"),
+            max_fps: get_u32("maxFps"),
+            fullscreen: get_bool("fullscreen"),
+            vsync: get_bool("enableVsync"),
+            gui_scale: get_u32("guiScale"),
+            brightness: get_f32("gamma"),
+            entity_shadows: get_bool("entityShadows"),
+            particles: get_u32("particles").and_then(|v| PARTICLES.get(v as usize)).map(|s| s.to_string()),
+            graphics: get_u32("graphicsMode")
+                .and_then(|v| GRAPHICS_MODES.get(v as usize))
+                .map(|s| s.to_string())
+                .or_else(|| get_bool("fancyGraphics").map(|fancy| if fancy { "fancy" } else { "fast" }.to_string())),
+            smooth_lighting: get("ao").map(|v| v == "true" || v == "1" || v == "2"),
+            biome_blend: get_u32("biomeBlendRadius"),
+            master_volume: get_f32("soundCategory_master"),
+            music_volume: get_f32("soundCategory_music"),
+            mouse_sensitivity: get_f32("mouseSensitivity"),
+            auto_jump: get_bool("autoJump"),
+            keybinds: (!keybinds.is_empty()).then_some(keybinds),
+        }
+    }
+
+    /// Writes the options set in `options` into the options.txt at `path`, leaving every other line as is.
+    ///
+    /// Returns the keys that end up bound to more than one action where at least one of those
+    /// bindings came from `options`, so the caller can warn instead of leaving broken controls.
+    pub fn merge_options_txt(path: &Path, options: &MinecraftOptions) -> std::io::Result<Vec<KeybindConflict>> {
+        let mut file = OptionsFile::load(path)?;
+
+        for (key, value) in options_entries(options) {
+            file.set(&key, &value);
+        }
+
+        file.save(path)?;
+        Ok(find_keybind_conflicts(&file, options.keybinds.as_ref()))
+    }
+
+    /// Applies the template's launcher settings (the values that differ from the current
+    /// launcher settings become the instance's launch options) and game options.
+    /// Returns any keybind conflicts the template's keybinds introduced.
+    pub fn apply_to_instance(
+        template: &InstanceTemplate,
+        instance_name: &str,
+    ) -> Result<Vec<KeybindConflict>, Box<dyn std::error::Error>> {
+        let instance_dir = get_instance_dir(instance_name);
+        let instance_json = instance_dir.join("instance.json");
+
+        if let Some(settings) = &template.launcher_settings {
+            let global = crate::services::settings::SettingsManager::load().unwrap_or_default();
+            let options = InstanceLaunchOptions::diff(settings, &global);
+
+            let mut instance: Instance = serde_json::from_str(&fs::read_to_string(&instance_json)?)?;
+            instance.settings_override = None;
+            instance.launch_options = (!options.is_empty()).then_some(options);
+            write_json_atomic(&instance_json, &instance)?;
+        }
+
+        match &template.minecraft_options {
+            Some(options) => Ok(Self::merge_options_txt(&instance_dir.join("options.txt"), options)?),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Applies the template chosen in `default_template_id` to a freshly created instance.
+    /// Problems are only logged, the instance is usable either way.
+    pub fn apply_default_template(instance_name: &str) {
+        let settings = crate::services::settings::SettingsManager::load().unwrap_or_default();
+        let Some(template_id) = settings.default_template_id else {
+            return;
+        };
+
+        match Self::get(&template_id) {
+            Ok(Some(template)) => {
+                match Self::apply_to_instance(&template, instance_name) {
+                    Ok(conflicts) => {
+                        for conflict in conflicts {
+                            eprintln!(
+                                "Default template binds {} to several actions in {}: {}",
+                                conflict.key,
+                                instance_name,
+                                conflict.actions.join(", ")
+                            );
+                        }
+                    }
+                    Err(e) => eprintln!("Failed to apply default template to {}: {}", instance_name, e),
+                }
+            }
+            Ok(None) => eprintln!("Default template '{}' no longer exists", template_id),
+            Err(e) => eprintln!("Failed to load default template: {}", e),
+        }
+    }
+}
+
+fn checksum(value: &serde_json::Value) -> Result<String, serde_json::Error> {
+    Ok(format!("{:x}", Sha256::digest(serde_json::to_string(value)?.as_bytes())))
+}
+
+/// Absolute Java paths only exist on the machine that created the template, fall back to auto-detection.
+fn strip_machine_paths(template: &mut InstanceTemplate) {
+    if let Some(settings) = &mut template.launcher_settings {
+        settings.java_path = None;
+    }
+}
+
+fn validate_minecraft_options(options: &MinecraftOptions) -> Result<(), String> {
+    if options.fov.is_some_and(|fov| !(30..=110).contains(&fov)) {
+        return Err("FOV must be between 30 and 110".to_string());
+    }
+    if options.render_distance.is_some_and(|d| !(2..=64).contains(&d)) {
+        return Err("Render distance must be between 2 and 64 chunks".to_string());
+    }
+    if options.max_fps.is_some_and(|fps| !(10..=260).contains(&fps)) {
+        return Err("Max FPS must be between 10 and 260".to_string());
+    }
+    if options.biome_blend.is_some_and(|radius| radius > 7) {
+        return Err("Biome blend radius must be between 0 and 7".to_string());
+    }
+    if options.particles.as_deref().is_some_and(|p| !PARTICLES.contains(&p)) {
+        return Err("Particles must be all, decreased or minimal".to_string());
+    }
+    if options.graphics.as_deref().is_some_and(|g| !GRAPHICS_MODES.contains(&g)) {
+        return Err("Graphics must be fast, fancy or fabulous".to_string());
+    }
+
+    for (action, key) in options.keybinds.iter().flatten() {
+        crate::services::options::validate_option_key(&format!("key_{}", action))?;
+        crate::services::options::validate_option_value(key)?;
+    }
+
+    Ok(())
+}
+
+fn normalize_icon(icon: &str) -> Result<String, String> {
+    let bytes = general_purpose::STANDARD
+        .decode(icon)
+        .map_err(|_| "Template icon is not valid base64".to_string())?;
+
+    if bytes.len() > MAX_ICON_BYTES {
+        return Err("Template icon is too large (max 512KB)".to_string());
+    }
+
+    match image::guess_format(&bytes).map_err(|e| e.to_string())? {
+        image::ImageFormat::Png | image::ImageFormat::Jpeg | image::ImageFormat::WebP => {}
+        _ => return Err("Unsupported icon format. Use PNG, JPEG, or WebP".to_string()),
+    }
+
+    let resized = image::load_from_memory(&bytes)
+        .map_err(|e| e.to_string())?
+        .resize_exact(TEMPLATE_ICON_SIZE, TEMPLATE_ICON_SIZE, image::imageops::FilterType::Lanczos3);
+
+    let mut png = std::io::Cursor::new(Vec::new());
+    resized
+        .write_to(&mut png, image::ImageFormat::Png)
+        .map_err(|e| e.to_string())?;
+
+    Ok(general_purpose::STANDARD.encode(png.into_inner()))
+}
+
+/// Unbound actions all share this key, which is not a conflict.
+const UNBOUND_KEY: &str = "key.keyboard.unknown";
+
+fn find_keybind_conflicts(file: &OptionsFile, applied: Option<&HashMap<String, String>>) -> Vec<KeybindConflict> {
+    let Some(applied) = applied.filter(|keybinds| !keybinds.is_empty()) else {
+        return Vec::new();
+    };
+
+    let mut by_key: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for (option, key) in file.entries() {
+        if let Some(action) = option.strip_prefix("key_") {
+            if key != UNBOUND_KEY {
+                by_key.entry(key).or_default().push(action.to_string());
+            }
+        }
+    }
+
+    by_key
+        .into_iter()
+        .filter(|(_, actions)| actions.len() > 1 && actions.iter().any(|action| applied.contains_key(action)))
+        .map(|(key, mut actions)| {
+            actions.sort();
+            KeybindConflict { key: key.to_string(), actions }
+        })
+        .collect()
+}
+
+const PARTICLES: &[&str] = &["all", "decreased", "minimal"];
+const GRAPHICS_MODES: &[&str] = &["fast", "fancy", "fabulous"];
+
+fn options_entries(options: &MinecraftOptions) -> Vec<(String, String)> {
+    let mut entries = Vec::new();
+    let mut push = |key: &str, value: Option<String>| {
+        if let Some(value) = value {
+            entries.push((key.to_string(), value));
+        }
+    };
+
+    push("fov", options.fov.map(|deg| ((deg.clamp(30, 110) as f32 - 70.0) / 40.0).to_string()));
+    push("renderDistance", options.render_distance.map(|v| v.to_string()));
+    push("maxFps", options.max_fps.map(|v| v.to_string()));
+    push("fullscreen", options.fullscreen.map(|v| v.to_string()));
+    push("enableVsync", options.vsync.map(|v| v.to_string()));
+    push("guiScale", options.gui_scale.map(|v| v.to_string()));
+    push("gamma", options.brightness.map(|v| v.clamp(0.0, 1.0).to_string()));
+    push("entityShadows", options.entity_shadows.map(|v| v.to_string()));
+    push(
+        "particles",
+        options.particles.as_deref().and_then(|p| PARTICLES.iter().position(|name| *name == p)).map(|i| i.to_string()),
+    );
+    push(
+        "graphicsMode",
+        options.graphics.as_deref().and_then(|g| GRAPHICS_MODES.iter().position(|name| *name == g)).map(|i| i.to_string()),
+    );
+    push("ao", options.smooth_lighting.map(|v| v.to_string()));
+    push("biomeBlendRadius", options.biome_blend.map(|v| v.to_string()));
+    push("soundCategory_master", options.master_volume.map(|v| v.clamp(0.0, 1.0).to_string()));
+    push("soundCategory_music", options.music_volume.map(|v| v.clamp(0.0, 1.0).to_string()));
+    push("mouseSensitivity", options.mouse_sensitivity.map(|v| v.clamp(0.0, 1.0).to_string()));
+    push("autoJump", options.auto_jump.map(|v| v.to_string()));
+
+    if let Some(keybinds) = &options.keybinds {
+        for (action, key) in keybinds {
+            push(&format!("key_{}", action), Some(key.clone()));
+        }
+    }
+
+    entries
+}
@@ -0,0 +1,144 @@
+use crate::models::AppConfig;
+use crate::services::friends::FriendsService;
+use crate::services::instance::InstanceManager;
+use crate::services::settings::SettingsManager;
+use tauri::menu::{Menu, MenuEvent, MenuItem, PredefinedMenuItem};
+use tauri::tray::{MouseButton, MouseButtonState, TrayIcon, TrayIconBuilder, TrayIconEvent};
+use tauri::{AppHandle, Manager, Wry};
+
+const TRAY_ID: &str = "main";
+const RECENT_INSTANCE_LIMIT: usize = 5;
+const LAUNCH_PREFIX: &str = "launch:";
+const MENU_SHOW: &str = "show";
+const MENU_QUIT: &str = "quit";
+
+pub fn setup_tray(app: &AppHandle) -> tauri::Result<()> {
+    let menu = build_menu(app)?;
+
+    let mut builder = TrayIconBuilder::with_id(TRAY_ID)
+        .tooltip("Octane Launcher")
+        .menu(&menu)
+        .show_menu_on_left_click(false)
+        .on_menu_event(handle_menu_event)
+        .on_tray_icon_event(|tray: &TrayIcon, event| {
+            if let TrayIconEvent::Click {
+                button: MouseButton::Left,
+                button_state: MouseButtonState::Up,
+                ..
+            } = event
+            {
+                show_main_window(tray.app_handle());
+            }
+        });
+
+    if let Some(icon) = app.default_window_icon() {
+        builder = builder.icon(icon.clone());
+    }
+
+    builder.build(app)?;
+    Ok(())
+}
+
+/// Rebuilds the tray menu so the recent instance list reflects the latest launches.
+pub fn refresh_tray_menu(app: &AppHandle) {
+    let Some(tray) = app.tray_by_id(TRAY_ID) else {
+        return;
+    };
+
+    match build_menu(app) {
+        Ok(menu) => {
+            let _ = tray.set_menu(Some(menu));
+        }
+        Err(e) => eprintln!("Failed to rebuild tray menu: {}", e),
+    }
+}
+
+pub fn should_minimize_to_tray() -> bool {
+    SettingsManager::load()
+        .map(|s| s.minimize_to_tray)
+        .unwrap_or(false)
+}
+
+pub fn hide_to_tray(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.hide();
+    }
+    refresh_tray_menu(app);
+}
+
+fn build_menu(app: &AppHandle) -> tauri::Result<Menu<Wry>> {
+    let menu = Menu::new(app)?;
+
+    let mut instances = InstanceManager::get_all().unwrap_or_default();
+    instances.sort_by(|a, b| b.last_played.cmp(&a.last_played));
+
+    for instance in instances.iter().take(RECENT_INSTANCE_LIMIT) {
+        let item = MenuItem::with_id(
+            app,
+            format!("{}{}", LAUNCH_PREFIX, instance.name),
+            format!("Launch {}", instance.name),
+            true,
+            None::<&str>,
+        )?;
+        menu.append(&item)?;
+    }
+
+    if !instances.is_empty() {
+        menu.append(&PredefinedMenuItem::separator(app)?)?;
+    }
+
+    menu.append(&MenuItem::with_id(app, MENU_SHOW, "Show launcher", true, None::<&str>)?)?;
+    menu.append(&MenuItem::with_id(app, MENU_QUIT, "Quit", true, None::<&str>)?)?;
+
+    Ok(menu)
+}
+
+fn handle_menu_event(app: &AppHandle, event: MenuEvent) {
+    let id = event.id.as_ref();
+
+    if let Some(instance_name) = id.strip_prefix(LAUNCH_PREFIX) {
+        let app_handle = app.clone();
+        let instance_name = instance_name.to_string();
+
+        tauri::async_runtime::spawn(async move {
+            let result = crate::commands::launch_instance_with_active_account(
+                instance_name.clone(),
+                app_handle.clone(),
+            )
+            .await;
+
+            if let Err(e) = result {
+                eprintln!("Failed to launch {} from tray: {}", instance_name, e);
+                show_main_window(&app_handle);
+            }
+            refresh_tray_menu(&app_handle);
+        });
+        return;
+    }
+
+    match id {
+        MENU_SHOW => show_main_window(app),
+        MENU_QUIT => quit(app),
+        _ => {}
+    }
+}
+
+fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+}
+
+fn quit(app: &AppHandle) {
+    let config = app.state::<AppConfig>();
+    let supabase_url = config.supabase_url.clone();
+    let supabase_key = config.supabase_key.clone();
+    let app_handle = app.clone();
+
+    tauri::async_runtime::spawn(async move {
+        FriendsService::set_all_offline(&supabase_url, &supabase_key).await;
+        app_handle.exit(0);
+    });
+}
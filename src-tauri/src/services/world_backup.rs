@@ -0,0 +1,275 @@
+use crate::models::{WorldBackupInfo, WorldBackupPolicy};
+use crate::utils::{get_instance_dir, get_profile_dir};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime};
+use tauri::Emitter;
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipWriter};
+
+/// How often the scheduler checks whether a policy is due.
+const CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
+lazy_static::lazy_static! {
+    /// Instance id -> when its last scheduled backup failed.
+    static ref FAILED_ATTEMPTS: Mutex<HashMap<String, SystemTime>> = Mutex::new(HashMap::new());
+}
+
+pub struct WorldBackupManager;
+
+impl WorldBackupManager {
+    fn backups_root() -> PathBuf {
+        get_profile_dir().join("world_backups")
+    }
+
+    /// Backups are kept under the instance id, so an instance created later under the same
+    /// folder name starts without them.
+    fn backups_dir(instance_name: &str) -> Option<PathBuf> {
+        let instance_id = crate::services::instance::InstanceManager::instance_id(instance_name).ok()?;
+        Some(Self::backups_root().join(instance_id))
+    }
+
+    /// Moves the backups of an instance from before ids existed to its id.
+    pub fn migrate_backups_dir(instance_name: &str, instance_id: &str) {
+        let legacy_dir = Self::backups_root().join(instance_name);
+        let backups_dir = Self::backups_root().join(instance_id);
+        if legacy_dir.is_dir() && !backups_dir.exists() {
+            if let Err(e) = fs::rename(&legacy_dir, &backups_dir) {
+                eprintln!("Failed to move world backups of {}: {}", instance_name, e);
+            }
+        }
+    }
+
+    /// Zips the instance's `saves` folder into a new timestamped backup and returns its file name.
+    pub fn create_backup(instance_name: &str) -> Result<String, String> {
+        let saves_dir = get_instance_dir(instance_name).join("saves");
+        if !saves_dir.is_dir() {
+            return Err(format!("'{}' has no worlds to back up", instance_name));
+        }
+
+        let backups_dir = Self::backups_dir(instance_name)
+            .ok_or_else(|| format!("Instance '{}' could not be loaded", instance_name))?;
+        fs::create_dir_all(&backups_dir)
+            .map_err(|e| format!("Failed to create backup directory: {}", e))?;
+
+        // Backups made within the same second get a numbered suffix, which still sorts after
+        // the plain name
+        let timestamp = chrono::Utc::now().format("%Y-%m-%d_%H-%M-%S").to_string();
+        let mut suffix = 1;
+        let (file_name, backup_path, file) = loop {
+            let file_name = if suffix == 1 {
+                format!("{}.zip", timestamp)
+            } else {
+                format!("{}_{}.zip", timestamp, suffix)
+            };
+            let backup_path = backups_dir.join(&file_name);
+            match fs::OpenOptions::new().write(true).create_new(true).open(&backup_path) {
+                Ok(file) => break (file_name, backup_path, file),
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => suffix += 1,
+                Err(e) => return Err(format!("Failed to create backup file: {}", e)),
+            }
+        };
+        let mut zip = ZipWriter::new(file);
+        let options = SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Deflated)
+            .large_file(true);
+
+        let result = crate::commands::backup::add_dir(&mut zip, &saves_dir, "saves", options)
+            .and_then(|_| zip.finish().map(|_| ()).map_err(|e| format!("Failed to finalize backup: {}", e)));
+
+        if let Err(e) = result {
+            let _ = fs::remove_file(&backup_path);
+            return Err(e);
+        }
+
+        Ok(file_name)
+    }
+
+    /// Backups of the instance, newest first.
+    pub fn list_backups(instance_name: &str) -> Vec<WorldBackupInfo> {
+        let Some(Ok(entries)) = Self::backups_dir(instance_name).map(fs::read_dir) else {
+            return Vec::new();
+        };
+
+        let mut backups: Vec<WorldBackupInfo> = entries
+            .flatten()
+            .filter(|e| e.path().extension().and_then(|ext| ext.to_str()) == Some("zip"))
+            .filter_map(|e| {
+                let metadata = e.metadata().ok()?;
+                let created_at: chrono::DateTime<chrono::Utc> = metadata.modified().ok()?.into();
+                Some(WorldBackupInfo {
+                    file_name: e.file_name().to_string_lossy().to_string(),
+                    created_at: created_at.to_rfc3339(),
+                    size: metadata.len(),
+                })
+            })
+            .collect();
+
+        // Timestamped names sort chronologically
+        backups.sort_by(|a, b| b.file_name.cmp(&a.file_name));
+        backups
+    }
+
+    /// Deletes the oldest backups so at most `keep` remain. Returns how many were removed.
+    pub fn prune(instance_name: &str, keep: u32) -> usize {
+        let Some(backups_dir) = Self::backups_dir(instance_name) else {
+            return 0;
+        };
+        Self::list_backups(instance_name)
+            .into_iter()
+            .skip(keep.max(1) as usize)
+            .filter(|backup| fs::remove_file(backups_dir.join(&backup.file_name)).is_ok())
+            .count()
+    }
+
+    fn last_backup_time(instance_name: &str) -> Option<SystemTime> {
+        fs::read_dir(Self::backups_dir(instance_name)?)
+            .ok()?
+            .flatten()
+            .filter(|e| e.path().extension().and_then(|ext| ext.to_str()) == Some("zip"))
+            .filter_map(|e| e.metadata().ok()?.modified().ok())
+            .max()
+    }
+
+    /// Whether the instance has a world to back up.
+    fn has_worlds(instance_name: &str) -> bool {
+        fs::read_dir(get_instance_dir(instance_name).join("saves"))
+            .is_ok_and(|mut entries| entries.any(|e| e.is_ok_and(|e| e.path().is_dir())))
+    }
+
+    /// Due once the interval has passed since the last backup or failed scheduled attempt,
+    /// so a failing backup is retried at the policy's pace rather than every check.
+    fn is_due(instance_name: &str, instance_id: &str, policy: &WorldBackupPolicy) -> bool {
+        let interval = Duration::from_secs(policy.interval_minutes as u64 * 60);
+        let last_failure = FAILED_ATTEMPTS
+            .lock()
+            .ok()
+            .and_then(|attempts| attempts.get(instance_id).copied());
+        match Self::last_backup_time(instance_name).max(last_failure) {
+            Some(last) => last.elapsed().map(|elapsed| elapsed >= interval).unwrap_or(true),
+            None => true,
+        }
+    }
+}
+
+/// Runs due world backups in the background; registered as Tauri state.
+#[derive(Default)]
+pub struct WorldBackupScheduler {
+    task: Mutex<Option<tauri::async_runtime::JoinHandle<()>>>,
+}
+
+impl WorldBackupScheduler {
+    pub fn new() -> Self {
+        Self { task: Mutex::new(None) }
+    }
+
+    /// Starts the scheduler, replacing a previously started one.
+    pub fn start(&self, app_handle: tauri::AppHandle) {
+        let Ok(mut task) = self.task.lock() else {
+            return;
+        };
+
+        if let Some(previous) = task.take() {
+            previous.abort();
+        }
+
+        *task = Some(tauri::async_runtime::spawn(async move {
+            let mut interval = tokio::time::interval(CHECK_INTERVAL);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+            loop {
+                interval.tick().await;
+                run_due_backups(&app_handle).await;
+            }
+        }));
+    }
+}
+
+async fn run_due_backups(app_handle: &tauri::AppHandle) {
+    // Listed first, so policies of instances from before ids existed are already moved over
+    let Ok(instances) = crate::services::instance::InstanceManager::get_all() else {
+        return;
+    };
+    let Ok(settings) = crate::services::settings::SettingsManager::load() else {
+        return;
+    };
+
+    for instance in instances {
+        let Some(policy) = settings.world_backup_policies.get(&instance.id) else {
+            continue;
+        };
+        let instance_name = instance.name;
+        if !policy.enabled
+            || policy.interval_minutes == 0
+            || crate::commands::validation::sanitize_instance_name(&instance_name).is_err()
+        {
+            continue;
+        }
+
+        if policy.only_when_not_running {
+            let running = crate::commands::instances::RUNNING_PROCESSES
+                .lock()
+                .map(|p| p.contains_key(&instance.id))
+                .unwrap_or(true);
+            if running {
+                continue;
+            }
+        }
+
+        // Nothing to back up yet, checked again once a world exists
+        if !WorldBackupManager::has_worlds(&instance_name)
+            || !WorldBackupManager::is_due(&instance_name, &instance.id, policy)
+        {
+            continue;
+        }
+
+        let result = run_backup(app_handle, instance_name, Some(policy.retention_count)).await;
+        if let Ok(mut attempts) = FAILED_ATTEMPTS.lock() {
+            match result {
+                Ok(_) => attempts.remove(&instance.id),
+                Err(_) => attempts.insert(instance.id, SystemTime::now()),
+            };
+        }
+    }
+}
+
+/// Creates a backup, applies retention and emits `world-backup-completed` or `world-backup-failed`.
+pub async fn run_backup(
+    app_handle: &tauri::AppHandle,
+    instance_name: String,
+    retention_count: Option<u32>,
+) -> Result<String, String> {
+    let _lock = crate::services::instance_lock::lock(app_handle, &instance_name, "world backup")?;
+    let started_at = chrono::Utc::now().to_rfc3339();
+    let name = instance_name.clone();
+    let result = tauri::async_runtime::spawn_blocking(move || {
+        let file_name = WorldBackupManager::create_backup(&name)?;
+        let pruned = retention_count.map(|keep| WorldBackupManager::prune(&name, keep)).unwrap_or(0);
+        Ok::<_, String>((file_name, pruned))
+    })
+    .await
+    .map_err(|e| e.to_string())
+    .and_then(|r| r);
+
+    crate::services::task_history::record_result("world_backup", Some(&instance_name), started_at, &result);
+
+    match result {
+        Ok((file_name, pruned)) => {
+            let _ = app_handle.emit("world-backup-completed", serde_json::json!({
+                "instance": instance_name,
+                "file_name": file_name,
+                "pruned": pruned
+            }));
+            Ok(file_name)
+        }
+        Err(e) => {
+            eprintln!("World backup of {} failed: {}", instance_name, e);
+            let _ = app_handle.emit("world-backup-failed", serde_json::json!({
+                "instance": instance_name,
+                "error": e
+            }));
+            Err(e)
+        }
+    }
+}
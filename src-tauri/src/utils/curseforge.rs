@@ -1,3 +1,4 @@
+use std::sync::OnceLock;
 use std::time::Duration;
 use serde::{Deserialize, Serialize};
 
@@ -13,6 +14,11 @@ fn build_client() -> reqwest::Client {
         .expect("Failed to build CurseForge HTTP client")
 }
 
+fn get_client() -> reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(build_client).clone()
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct CurseforgeSearchResult {
@@ -117,7 +123,7 @@ pub struct CurseforgeClient {
 impl CurseforgeClient {
     pub fn new(api_key: String) -> Result<Self, Box<dyn std::error::Error>> {
         Ok(Self {
-            http_client: build_client(),
+            http_client: get_client(),
             api_key,
         })
     }
@@ -258,7 +264,8 @@ impl CurseforgeClient {
         }
 
         let bytes = response.bytes().await?;
-        std::fs::write(destination, bytes)?;
+        // Replaces rather than overwrites, the file may be hardlinked into a linked duplicate
+        crate::utils::write_file_atomic(destination, bytes)?;
         Ok(())
     }
 }
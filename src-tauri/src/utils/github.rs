@@ -0,0 +1,93 @@
+use serde::{Deserialize, Serialize};
+
+const GITHUB_API_BASE: &str = "https://api.github.com";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GithubRelease {
+    pub tag_name: String,
+    pub name: Option<String>,
+    pub html_url: String,
+    pub assets: Vec<GithubReleaseAsset>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GithubReleaseAsset {
+    pub name: String,
+    pub size: u64,
+    pub browser_download_url: String,
+}
+
+impl GithubReleaseAsset {
+    /// Sources and javadoc jars are published next to mods but are never mods themselves.
+    pub fn is_mod_jar(&self) -> bool {
+        let name = self.name.to_ascii_lowercase();
+        name.ends_with(".jar") && !name.ends_with("-sources.jar") && !name.ends_with("-javadoc.jar")
+    }
+}
+
+/// A release a GitHub URL points to: a tagged release, or the latest one for links to the
+/// repository or its releases page.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ReleaseRef {
+    pub owner: String,
+    pub repo: String,
+    pub tag: Option<String>,
+}
+
+/// Parses `https://github.com/{owner}/{repo}[/releases[/latest|/tag/{tag}]]`.
+pub fn parse_release_url(release_url: &str) -> Result<ReleaseRef, String> {
+    let url = url::Url::parse(release_url.trim()).map_err(|_| "Invalid URL format".to_string())?;
+
+    if url.scheme() != "https" || !url.host_str().is_some_and(|h| h.eq_ignore_ascii_case("github.com")) {
+        return Err("Not a GitHub repository URL".to_string());
+    }
+
+    let segments: Vec<&str> = url.path_segments().map(|s| s.filter(|s| !s.is_empty()).collect()).unwrap_or_default();
+
+    let valid_part = |part: &str| {
+        !part.is_empty() && part != "." && part != ".." && part.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    };
+
+    let (owner, repo, rest) = match segments.as_slice() {
+        [owner, repo, rest @ ..] if valid_part(owner) && valid_part(repo) => (*owner, repo.trim_end_matches(".git"), rest),
+        _ => return Err("URL does not point to a GitHub repository".to_string()),
+    };
+
+    let tag = match rest {
+        [] | ["releases"] | ["releases", "latest"] => None,
+        ["releases", "tag", tag] => Some(tag.to_string()),
+        _ => return Err("URL does not point to a GitHub release".to_string()),
+    };
+
+    Ok(ReleaseRef { owner: owner.to_string(), repo: repo.to_string(), tag })
+}
+
+pub async fn get_release(
+    http_client: &reqwest::Client,
+    release: &ReleaseRef,
+) -> Result<GithubRelease, Box<dyn std::error::Error>> {
+    let mut url = url::Url::parse(GITHUB_API_BASE)?;
+    {
+        let mut segments = url.path_segments_mut().map_err(|_| "Invalid GitHub API URL")?;
+        segments.pop_if_empty().extend(["repos", &release.owner, &release.repo, "releases"]);
+        match &release.tag {
+            Some(tag) => segments.extend(["tags", tag]),
+            None => segments.push("latest"),
+        };
+    }
+
+    let response = http_client
+        .get(url)
+        .header("Accept", "application/vnd.github+json")
+        .send()
+        .await?;
+
+    match response.status() {
+        status if status.is_success() => Ok(response.json().await?),
+        reqwest::StatusCode::NOT_FOUND => Err(format!("No release found for {}/{}", release.owner, release.repo).into()),
+        reqwest::StatusCode::FORBIDDEN | reqwest::StatusCode::TOO_MANY_REQUESTS => {
+            Err("GitHub API rate limit reached, try again later".into())
+        }
+        status => Err(format!("GitHub API error: HTTP {}", status).into()),
+    }
+}
@@ -1,20 +1,63 @@
-use std::sync::OnceLock;
+use std::sync::{OnceLock, RwLock};
 use std::time::Duration;
+use tauri::Manager;
+
+/// Launcher-wide HTTP client, registered as Tauri state so commands share one connection pool.
+pub struct HttpClient(RwLock<reqwest::Client>);
+
+impl HttpClient {
+    pub fn new() -> Self {
+        Self(RwLock::new(get_client()))
+    }
+
+    pub fn get(&self) -> reqwest::Client {
+        self.0.read().map(|client| client.clone()).unwrap_or_else(|_| get_client())
+    }
+
+    /// Rebuilds the client so it picks up the proxy of the active launcher profile.
+    pub fn reload(&self) {
+        if let Ok(mut client) = self.0.write() {
+            *client = build_client();
+        }
+    }
+}
 
 fn build_client() -> reqwest::Client {
-    reqwest::Client::builder()
+    let mut builder = reqwest::Client::builder()
         .timeout(Duration::from_secs(300))
+        .connect_timeout(Duration::from_secs(15))
         .pool_max_idle_per_host(32)
         .pool_idle_timeout(Duration::from_secs(90))
         .tcp_keepalive(Duration::from_secs(60))
         .user_agent("OctaneLauncher (https://github.com/karlsson1000/OctaneLauncher)")
         .http2_keep_alive_interval(Duration::from_secs(30))
-        .http2_keep_alive_timeout(Duration::from_secs(10))
-        .build()
-        .expect("Failed to build HTTP client")
+        .http2_keep_alive_timeout(Duration::from_secs(10));
+
+    // Proxy is read when the client is built; changing it requires a restart or profile switch
+    let proxy_url = crate::services::settings::SettingsManager::load()
+        .ok()
+        .and_then(|s| s.http_proxy)
+        .filter(|p| !p.trim().is_empty());
+
+    if let Some(proxy_url) = proxy_url {
+        match reqwest::Proxy::all(proxy_url.trim()) {
+            Ok(proxy) => builder = builder.proxy(proxy),
+            Err(e) => eprintln!("Ignoring invalid proxy '{}': {}", proxy_url, e),
+        }
+    }
+
+    builder.build().expect("Failed to build HTTP client")
 }
 
 pub fn get_client() -> reqwest::Client {
     static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
     CLIENT.get_or_init(build_client).clone()
 }
+
+/// Returns the client managed in Tauri state, falling back to the global one.
+pub fn client_from(app_handle: &tauri::AppHandle) -> reqwest::Client {
+    app_handle
+        .try_state::<HttpClient>()
+        .map(|state| state.get())
+        .unwrap_or_else(get_client)
+}
@@ -1,6 +1,8 @@
 pub mod http;
 pub mod modrinth;
 pub mod curseforge;
+pub mod github;
+pub mod mod_metadata;
 pub mod utils;
 
 pub use utils::*;
\ No newline at end of file
@@ -0,0 +1,121 @@
+use serde::Serialize;
+use std::io::Read;
+use std::path::Path;
+
+/// Identity of a mod as declared inside its jar.
+#[derive(Debug, Serialize, Clone)]
+pub struct JarModInfo {
+    pub id: String,
+    pub name: Option<String>,
+    pub version: Option<String>,
+    /// `fabric`, `quilt`, `forge` or `neoforge`
+    pub loader: String,
+}
+
+/// Reads the mod id and version from Fabric, Quilt, Forge or NeoForge metadata in a jar.
+pub fn read_jar_mod_info(jar_path: &Path) -> Option<JarModInfo> {
+    let file = std::fs::File::open(jar_path).ok()?;
+    let mut archive = zip::ZipArchive::new(file).ok()?;
+
+    if let Some(json) = read_entry(&mut archive, "fabric.mod.json").and_then(|c| parse_json(&c)) {
+        return Some(JarModInfo {
+            id: json_str(&json, "id")?,
+            name: json_str(&json, "name"),
+            version: json_str(&json, "version"),
+            loader: "fabric".to_string(),
+        });
+    }
+
+    if let Some(json) = read_entry(&mut archive, "quilt.mod.json").and_then(|c| parse_json(&c)) {
+        let loader = json.get("quilt_loader")?;
+        return Some(JarModInfo {
+            id: json_str(loader, "id")?,
+            name: loader.get("metadata").and_then(|m| json_str(m, "name")),
+            version: json_str(loader, "version"),
+            loader: "quilt".to_string(),
+        });
+    }
+
+    for (entry_name, loader) in [("META-INF/neoforge.mods.toml", "neoforge"), ("META-INF/mods.toml", "forge")] {
+        let Some(content) = read_entry(&mut archive, entry_name) else {
+            continue;
+        };
+        let Some(id) = toml_mods_value(&content, "modId") else {
+            continue;
+        };
+
+        // Forge jars usually take their version from the manifest at build time
+        let version = toml_mods_value(&content, "version")
+            .filter(|v| v != "${file.jarVersion}")
+            .or_else(|| {
+                read_entry(&mut archive, "META-INF/MANIFEST.MF")
+                    .and_then(|manifest| manifest_value(&manifest, "Implementation-Version"))
+            });
+
+        return Some(JarModInfo {
+            id,
+            name: toml_mods_value(&content, "displayName"),
+            version,
+            loader: loader.to_string(),
+        });
+    }
+
+    None
+}
+
+fn read_entry(archive: &mut zip::ZipArchive<std::fs::File>, name: &str) -> Option<String> {
+    let mut entry = archive.by_name(name).ok()?;
+    let mut content = String::new();
+    entry.read_to_string(&mut content).ok()?;
+    Some(content)
+}
+
+fn parse_json(content: &str) -> Option<serde_json::Value> {
+    serde_json::from_str(content).ok()
+}
+
+fn json_str(value: &serde_json::Value, key: &str) -> Option<String> {
+    value.get(key).and_then(|v| v.as_str()).map(|s| s.to_string())
+}
+
+/// Minimal lookup of `key = "value"` in the first `[[mods]]` table of a mods.toml.
+fn toml_mods_value(content: &str, key: &str) -> Option<String> {
+    let mut in_mods = false;
+
+    for line in content.lines() {
+        let line = line.trim();
+
+        if line.starts_with('[') {
+            if in_mods {
+                break;
+            }
+            in_mods = line == "[[mods]]";
+            continue;
+        }
+
+        if !in_mods {
+            continue;
+        }
+
+        let Some((name, value)) = line.split_once('=') else {
+            continue;
+        };
+        if name.trim() != key {
+            continue;
+        }
+
+        let value = value.trim();
+        let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+        let end = value[1..].find(quote)?;
+        return Some(value[1..1 + end].to_string());
+    }
+
+    None
+}
+
+fn manifest_value(manifest: &str, key: &str) -> Option<String> {
+    manifest.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        (name.trim() == key).then(|| value.trim().to_string())
+    })
+}
@@ -62,6 +62,36 @@ pub struct ModrinthProjectDetails {
     pub source_url: Option<String>,
     pub wiki_url: Option<String>,
     pub discord_url: Option<String>,
+    /// Derived from `client_side` and `server_side` by `get_mod_details`.
+    #[serde(default)]
+    pub environment: Option<ModEnvironment>,
+}
+
+/// Where a project has to be installed to work.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ModEnvironment {
+    ClientOnly,
+    ServerOnly,
+    Both,
+}
+
+impl ModEnvironment {
+    /// From Modrinth's `required`/`optional`/`unsupported` side support values.
+    pub fn from_sides(client_side: &str, server_side: &str) -> Self {
+        match (client_side, server_side) {
+            ("unsupported", "unsupported") => ModEnvironment::Both,
+            ("unsupported", _) => ModEnvironment::ServerOnly,
+            (_, "unsupported") => ModEnvironment::ClientOnly,
+            _ => ModEnvironment::Both,
+        }
+    }
+}
+
+impl ModrinthProjectDetails {
+    pub fn environment(&self) -> ModEnvironment {
+        ModEnvironment::from_sides(&self.client_side, &self.server_side)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -129,6 +159,15 @@ pub struct VersionFileResponse {
     pub files: Vec<VersionFile>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ModrinthCategory {
+    pub name: String,
+    pub project_type: String,
+    pub header: String,
+    /// SVG markup.
+    pub icon: String,
+}
+
 pub struct ModrinthClient {
     http_client: reqwest::Client,
 }
@@ -138,6 +177,10 @@ impl ModrinthClient {
         Ok(Self { http_client: crate::utils::http::get_client() })
     }
 
+    pub fn with_client(http_client: reqwest::Client) -> Self {
+        Self { http_client }
+    }
+
     pub async fn get_version_files_by_hashes(
         &self,
         hashes: &[String],
@@ -158,6 +201,23 @@ impl ModrinthClient {
         Ok(result)
     }
 
+    /// Search categories of every project type, cached for a few minutes.
+    pub async fn get_categories(&self) -> Result<Vec<ModrinthCategory>, Box<dyn std::error::Error>> {
+        if let Some(categories) = crate::services::metadata_cache::MODRINTH_CATEGORIES.get() {
+            return Ok(categories);
+        }
+
+        let url = format!("{}/tag/category", MODRINTH_API_BASE);
+        let response = self.http_client.get(&url).send().await?;
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(format!("Modrinth API error: {}", error_text).into());
+        }
+        let categories: Vec<ModrinthCategory> = response.json().await?;
+        crate::services::metadata_cache::MODRINTH_CATEGORIES.set(categories.clone());
+        Ok(categories)
+    }
+
     pub async fn get_projects_batch(
         &self,
         project_ids: &[String],
@@ -173,6 +233,21 @@ impl ModrinthClient {
         Ok(result)
     }
 
+    pub async fn get_versions(
+        &self,
+        version_ids: &[String],
+    ) -> Result<Vec<ModrinthVersion>, Box<dyn std::error::Error>> {
+        let url = format!("{}/versions", MODRINTH_API_BASE);
+        let ids_json = serde_json::to_string(version_ids)?;
+        let response = self.http_client.get(&url).query(&[("ids", &ids_json)]).send().await?;
+        if !response.status().is_success() {
+            let error_text = response.text().await?;
+            return Err(format!("Modrinth API error: {}", error_text).into());
+        }
+        let result: Vec<ModrinthVersion> = response.json().await?;
+        Ok(result)
+    }
+
     pub async fn search_projects(
         &self,
         query: &str,
@@ -278,7 +353,16 @@ impl ModrinthClient {
         }
 
         let bytes = response.bytes().await?;
-        std::fs::write(destination, bytes)?;
+
+        // Replaced through a rename rather than rewritten, so an instance duplicated with
+        // hardlinks keeps its own copy of a file that is downloaded again here
+        let mut part_name = destination.file_name().ok_or("Invalid destination")?.to_os_string();
+        part_name.push(".part");
+        let part_path = destination.with_file_name(part_name);
+        std::fs::write(&part_path, bytes)?;
+        std::fs::rename(&part_path, destination).inspect_err(|_| {
+            let _ = std::fs::remove_file(&part_path);
+        })?;
 
         Ok(())
     }
@@ -1,6 +1,8 @@
+use serde::{Deserialize, Serialize};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::fs;
+use std::sync::RwLock;
 
 pub const LIBRARY_BASE_URL: &str = "https://libraries.minecraft.net/";
 
@@ -44,7 +46,17 @@ pub fn get_current_os() -> String {
     return "linux".to_string();
 }
 
-pub fn get_launcher_dir() -> PathBuf {
+/// Pointer file in the default launcher dir naming a custom data directory.
+pub const DATA_LOCATION_FILE: &str = "data_location.json";
+
+static LAUNCHER_DIR: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+#[derive(Serialize, Deserialize)]
+struct DataLocation {
+    path: PathBuf,
+}
+
+pub fn get_default_launcher_dir() -> PathBuf {
     let home = dirs::home_dir().expect("Could not find home directory");
 
     #[cfg(target_os = "windows")]
@@ -56,12 +68,150 @@ pub fn get_launcher_dir() -> PathBuf {
     launcher_dir
 }
 
+/// Marker file next to the executable that enables portable mode.
+pub const PORTABLE_MARKER_FILE: &str = "portable.txt";
+
+/// Data directory used in portable mode, next to the executable.
+fn portable_launcher_dir() -> Option<PathBuf> {
+    let exe_dir = std::env::current_exe().ok()?.parent()?.to_path_buf();
+
+    let enabled = exe_dir.join(PORTABLE_MARKER_FILE).exists()
+        || std::env::args().any(|arg| arg == "--portable");
+
+    enabled.then(|| exe_dir.join("data"))
+}
+
+pub fn is_portable() -> bool {
+    portable_launcher_dir().is_some()
+}
+
+fn resolve_launcher_dir() -> PathBuf {
+    if let Some(portable_dir) = portable_launcher_dir() {
+        return portable_dir;
+    }
+
+    let default_dir = get_default_launcher_dir();
+
+    let custom_dir = fs::read_to_string(default_dir.join(DATA_LOCATION_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str::<DataLocation>(&content).ok())
+        .map(|location| location.path)
+        .filter(|path| path.is_absolute());
+
+    custom_dir.unwrap_or(default_dir)
+}
+
+pub fn get_launcher_dir() -> PathBuf {
+    if let Ok(cached) = LAUNCHER_DIR.read() {
+        if let Some(dir) = cached.as_ref() {
+            return dir.clone();
+        }
+    }
+
+    let dir = resolve_launcher_dir();
+    if let Ok(mut cached) = LAUNCHER_DIR.write() {
+        *cached = Some(dir.clone());
+    }
+    dir
+}
+
+/// Points the launcher at a new data directory. Passing the default dir clears the override.
+pub fn set_launcher_dir(path: &Path) -> std::io::Result<()> {
+    let default_dir = get_default_launcher_dir();
+    let pointer_path = default_dir.join(DATA_LOCATION_FILE);
+
+    if path == default_dir {
+        if pointer_path.exists() {
+            fs::remove_file(&pointer_path)?;
+        }
+    } else {
+        fs::create_dir_all(&default_dir)?;
+        let json = serde_json::to_string_pretty(&DataLocation { path: path.to_path_buf() })?;
+        fs::write(&pointer_path, json)?;
+    }
+
+    if let Ok(mut cached) = LAUNCHER_DIR.write() {
+        *cached = Some(path.to_path_buf());
+    }
+    if let Ok(mut cached) = PROFILE_DIR.write() {
+        *cached = None;
+    }
+
+    Ok(())
+}
+
+/// Names the active launcher profile, in the launcher dir.
+pub const ACTIVE_PROFILE_FILE: &str = "active_profile.json";
+
+static PROFILE_DIR: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+#[derive(Serialize, Deserialize)]
+struct ActiveProfile {
+    name: String,
+}
+
+pub fn get_profiles_dir() -> PathBuf {
+    get_launcher_dir().join("profiles")
+}
+
+/// Name of the active launcher profile, `None` for the default profile.
+pub fn get_active_profile() -> Option<String> {
+    fs::read_to_string(get_launcher_dir().join(ACTIVE_PROFILE_FILE))
+        .ok()
+        .and_then(|content| serde_json::from_str::<ActiveProfile>(&content).ok())
+        .map(|profile| profile.name)
+        .filter(|name| get_profiles_dir().join(name).is_dir())
+}
+
+/// Folder with the settings, accounts and instances of the active profile. The default profile
+/// uses the launcher dir itself, so data from before profiles existed stays where it was.
+/// Game files in `meta` are shared by all profiles.
+pub fn get_profile_dir() -> PathBuf {
+    if let Ok(cached) = PROFILE_DIR.read() {
+        if let Some(dir) = cached.as_ref() {
+            return dir.clone();
+        }
+    }
+
+    let dir = match get_active_profile() {
+        Some(name) => get_profiles_dir().join(name),
+        None => get_launcher_dir(),
+    };
+    if let Ok(mut cached) = PROFILE_DIR.write() {
+        *cached = Some(dir.clone());
+    }
+    dir
+}
+
+/// Makes `name` the active profile, or the default profile for `None`.
+pub fn set_active_profile(name: Option<&str>) -> std::io::Result<()> {
+    let pointer_path = get_launcher_dir().join(ACTIVE_PROFILE_FILE);
+
+    match name {
+        Some(name) => {
+            let json = serde_json::to_string_pretty(&ActiveProfile { name: name.to_string() })?;
+            fs::write(&pointer_path, json)?;
+        }
+        None => {
+            if pointer_path.exists() {
+                fs::remove_file(&pointer_path)?;
+            }
+        }
+    }
+
+    if let Ok(mut cached) = PROFILE_DIR.write() {
+        *cached = None;
+    }
+
+    Ok(())
+}
+
 pub fn get_meta_dir() -> PathBuf {
     get_launcher_dir().join("meta")
 }
 
 pub fn get_instances_dir() -> PathBuf {
-    get_launcher_dir().join("instances")
+    get_profile_dir().join("instances")
 }
 
 pub fn get_instance_dir(instance_name: &str) -> PathBuf {
@@ -69,7 +219,7 @@ pub fn get_instance_dir(instance_name: &str) -> PathBuf {
 }
 
 pub fn get_trash_dir() -> PathBuf {
-    get_launcher_dir().join("trash")
+    get_profile_dir().join("trash")
 }
 
 pub fn get_trash_index_path() -> PathBuf {
@@ -228,4 +378,94 @@ pub fn open_folder(path: PathBuf) -> Result<(), std::io::Error> {
     Command::new("xdg-open").arg(path).spawn()?;
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Opens the file manager with `path` selected. On Linux this asks the desktop's file manager
+/// over D-Bus and falls back to opening the parent folder when none answers.
+pub fn reveal_file(path: &Path) -> Result<(), std::io::Error> {
+    #[cfg(target_os = "windows")]
+    {
+        let mut select = std::ffi::OsString::from("/select,");
+        select.push(path);
+        Command::new("explorer").arg(select).spawn()?;
+    }
+
+    #[cfg(target_os = "macos")]
+    Command::new("open").arg("-R").arg(path).spawn()?;
+
+    #[cfg(target_os = "linux")]
+    {
+        let uri = url::Url::from_file_path(path)
+            .map_err(|_| std::io::Error::new(std::io::ErrorKind::InvalidInput, "Path is not absolute"))?;
+
+        let shown = Command::new("dbus-send")
+            .args([
+                "--session",
+                "--print-reply",
+                "--dest=org.freedesktop.FileManager1",
+                "--type=method_call",
+                "/org/freedesktop/FileManager1",
+                "org.freedesktop.FileManager1.ShowItems",
+            ])
+            .arg(format!("array:string:{}", uri))
+            .arg("string:")
+            .output()
+            .is_ok_and(|output| output.status.success());
+
+        if !shown {
+            let parent = path.parent().unwrap_or(path);
+            Command::new("xdg-open").arg(parent).spawn()?;
+        }
+    }
+
+    Ok(())
+}
+/// Writes to a temporary file next to `path`, flushes it to disk and renames it over the
+/// original, so a crash mid-write leaves either the old or the new file and never a torn one.
+pub fn write_file_atomic(path: &Path, contents: impl AsRef<[u8]>) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let file_name = path
+        .file_name()
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "Path has no file name"))?;
+
+    let mut tmp_name = file_name.to_os_string();
+    tmp_name.push(".tmp");
+    let tmp_path = path.with_file_name(tmp_name);
+
+    let result = fs::File::create(&tmp_path).and_then(|mut file| {
+        file.write_all(contents.as_ref())?;
+        file.sync_all()
+    });
+
+    result.and_then(|_| fs::rename(&tmp_path, path)).inspect_err(|_| {
+        let _ = fs::remove_file(&tmp_path);
+    })
+}
+
+/// Serializes `value` as pretty JSON and writes it with [`write_file_atomic`].
+pub fn write_json_atomic<T: Serialize + ?Sized>(path: &Path, value: &T) -> std::io::Result<()> {
+    let json = serde_json::to_string_pretty(value)?;
+    write_file_atomic(path, json)
+}
+
+/// Creates `path` as a new file, removing an existing one first. Linked duplicates share
+/// mod and pack files through hardlinks, so truncating the file in place would change
+/// every instance sharing it.
+pub fn create_file_unlinked(path: &Path) -> std::io::Result<fs::File> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e),
+        _ => {}
+    }
+    fs::File::create(path)
+}
+
+/// Copies `src` over `dst` like `fs::copy`, without writing through a hardlink at `dst`.
+/// See [`create_file_unlinked`].
+pub fn copy_file_unlinked(src: &Path, dst: &Path) -> std::io::Result<u64> {
+    match fs::remove_file(dst) {
+        Err(e) if e.kind() != std::io::ErrorKind::NotFound => return Err(e),
+        _ => {}
+    }
+    fs::copy(src, dst)
+}